@@ -1,12 +1,29 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use crate::autosave::{AutosaveConfig, recovery_is_newer};
 use crate::cache::PreviewFrameCache;
+use crate::cut_list;
+use crate::edl;
 use crate::error::{EngineError, Result};
 use crate::export::build_video_export_plan;
-use crate::preview::{FfmpegMediaBackend, MediaBackend, PreviewFrame};
-use crate::project::{PreviewRequest, Project, normalize_playhead};
-use crate::time::{TIMELINE_TIME_BASE, rescale};
-use tracing::{debug, info};
+use crate::fcp_xml;
+use crate::otio;
+use crate::playback::{
+    AudioSink, FfplayAudioSink, NullAudioSink, PcmChunk, PcmFormat, audio_spans,
+};
+use crate::preview::{FfmpegMediaBackend, MediaBackend, PreviewFrame, PreviewQuality};
+use crate::project::{
+    MediaAsset, MediaPathStyle, PreviewRequest, Project, ProjectCheckpoint, normalize_playhead,
+    write_file_atomically,
+};
+use crate::time::{FrameGrid, Rational, TIMELINE_TIME_BASE, Timecode, rescale};
+use crate::timeline::Segment;
+use crate::worker::{PreviewJob, PreviewJobKind, PreviewResult, PreviewWorkerPool};
+use tracing::{debug, info, warn};
 
 const PREVIEW_CACHE_CAPACITY: usize = 96;
 /// Fallback preview-cache bucket size in timeline ticks.
@@ -14,8 +31,40 @@ const PREVIEW_CACHE_CAPACITY: usize = 96;
 /// This default is used when stream metadata is insufficient to derive a
 /// source-specific bucket size from frame rate/time base.
 pub const DEFAULT_PREVIEW_CACHE_BUCKET_TL: i64 = 33_333;
-const PREFETCH_RADIUS_IDLE: i64 = 120;
+/// Neighbors warmed on each side of a resting playhead; they fit the preview
+/// cache together with the frame itself, so warming never evicts its own work.
+const PREFETCH_RADIUS_IDLE: i64 = (PREVIEW_CACHE_CAPACITY as i64 - 1) / 2;
 const PREFETCH_MAX_DECODES_PER_REQUEST: usize = 1;
+/// Number of background threads decoding preview frames.
+const PREVIEW_WORKER_COUNT: usize = 2;
+/// How far ahead of the audio clock playback keeps the sink filled.
+const PLAYBACK_AUDIO_LEAD_TL: i64 = 300_000;
+/// Maximum timeline span rendered per audio decode during playback.
+const PLAYBACK_AUDIO_CHUNK_TL: i64 = 100_000;
+/// Length of the audio snippet played for each scrubbed playhead.
+const SCRUB_AUDIO_SNIPPET_TL: i64 = 80_000;
+/// Fastest shuttle speed reached by repeated `ShuttleForward`/`ShuttleReverse`.
+const MAX_SHUTTLE_RATE: i32 = 8;
+
+/// Client-assigned identifier correlating a command with its events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CommandId(pub u64);
+
+/// A command tagged with the id its resulting events echo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaggedCommand {
+    pub id: CommandId,
+    pub command: Command,
+}
+
+/// An event tagged with the id of the command that caused it.
+///
+/// `id` is `None` for unsolicited events such as playback clock ticks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaggedEvent {
+    pub id: Option<CommandId>,
+    pub event: Event,
+}
 
 /// Commands accepted by the engine.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -23,9 +72,125 @@ pub enum Command {
     Import {
         path: PathBuf,
     },
+    /// Probes `path` and adds it to the media bin without touching the
+    /// timeline.
+    ///
+    /// Starts an empty project when none is loaded. Use `InsertFromAsset` or
+    /// `OverwriteFromAsset` to edit the asset into the timeline.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::{Command, Engine, FfmpegMediaBackend};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let _ = engine.handle_command(Command::AddAsset {
+    ///     path: "broll.mp4".into(),
+    /// });
+    /// ```
+    AddAsset {
+        path: PathBuf,
+    },
+    /// Loads a saved project, replacing the current one.
+    ///
+    /// Assets whose file is missing are reported as `offline` in the
+    /// snapshot instead of failing the load.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::{Command, Engine, FfmpegMediaBackend};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let _ = engine.handle_command(Command::OpenProject {
+    ///     path: "cut.nle.json".into(),
+    /// });
+    /// ```
+    OpenProject {
+        path: PathBuf,
+    },
+    /// Saves the project to `path`, writing asset paths in `paths` style.
+    ///
+    /// Paths are recomputed for `path`, so saving to a new folder (save-as)
+    /// rewrites every relative path.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::{Command, Engine, FfmpegMediaBackend, MediaPathStyle};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let _ = engine.handle_command(Command::SaveProject {
+    ///     path: "cut.nle.json".into(),
+    ///     paths: MediaPathStyle::Relative,
+    /// });
+    /// ```
+    SaveProject {
+        path: PathBuf,
+        paths: MediaPathStyle,
+    },
+    /// Offers the untitled project's recovery copy, if one exists.
+    ///
+    /// Sent once at startup; answered with `RecoveryAvailable` or nothing.
+    /// `OpenProject` checks the opened project's recovery copy by itself.
+    CheckForRecovery,
+    /// Loads the offered recovery copy or backup as an unsaved project.
+    RestoreRecovery,
+    /// Deletes the offered recovery copy; an offered backup is kept.
+    DiscardRecovery,
+    /// Points an asset at `new_path` after probing it.
+    ///
+    /// The new file must match the asset's streams (time bases, start
+    /// timestamps, video size) and duration; otherwise the command fails with
+    /// `IncompatibleMedia` and the asset is unchanged.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::{Command, Engine, FfmpegMediaBackend};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let _ = engine.handle_command(Command::RelinkAsset {
+    ///     asset_id: 1,
+    ///     new_path: "/media/moved/shot.mov".into(),
+    /// });
+    /// ```
+    RelinkAsset {
+        asset_id: u64,
+        new_path: PathBuf,
+    },
+    /// Relinks every offline asset to a matching file found under `dir`.
+    ///
+    /// Files match by name and recorded size; candidates that fail to probe
+    /// or do not match the asset's streams are skipped.
+    RelinkFromDirectory {
+        dir: PathBuf,
+    },
     SetPlayhead {
         t_tl: i64,
     },
+    /// Shows a bin asset in the source monitor at `t_src`, in timeline ticks
+    /// from the asset start.
+    ///
+    /// Answered with `SourcePlayheadChanged` and `SourcePreviewFrameReady`.
+    /// Source frames share the preview cache and decode workers with the
+    /// timeline, but scrubbing one monitor never cancels the other's decodes.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::{Command, Engine, FfmpegMediaBackend};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let _ = engine.handle_command(Command::SetSourcePlayhead {
+    ///     asset_id: 2,
+    ///     t_src: 1_500_000,
+    /// });
+    /// ```
+    SetSourcePlayhead {
+        asset_id: u64,
+        t_src: i64,
+    },
+    /// Loads the source frame under the timeline playhead into the source
+    /// monitor.
+    ///
+    /// Returns `SegmentNotFound` when the playhead rests in a gap.
+    MatchFrame,
     /// Splits the segment at `at_tl` in timeline ticks.
     ///
     /// # Example
@@ -135,42 +300,539 @@ pub enum Command {
         settings: ExportSettings,
     },
     CancelExport,
+    /// Writes the enabled segments to `path` as a CMX3600 EDL; answered with
+    /// `ExportFinished`. See `edl::export_edl`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::{Command, Engine, FfmpegMediaBackend};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let _ = engine.handle_command(Command::ExportEdl {
+    ///     path: "cut.edl".into(),
+    /// });
+    /// ```
+    ExportEdl {
+        path: PathBuf,
+    },
+    /// Replaces the timeline with the events of the CMX3600 EDL at `path`,
+    /// matched against assets already in the bin. See `edl::import_edl`.
+    ImportEdl {
+        path: PathBuf,
+    },
+    /// Writes the timeline to `path` as OpenTimelineIO JSON; answered with
+    /// `ExportFinished`. See `otio::export_otio`.
+    ExportOtio {
+        path: PathBuf,
+    },
+    /// Replaces the timeline with the cuts of the CSV or JSON cut list at
+    /// `path`, laid out back-to-back. See `cut_list`.
+    ///
+    /// Relative media paths resolve against the cut list's directory; media
+    /// missing from the bin is probed and added first. Fails with
+    /// `InvalidCutList` listing every bad row, leaving the project unchanged.
+    ImportCutList {
+        path: PathBuf,
+    },
+    /// Writes the timeline to `path` as Final Cut Pro 7 XML (xmeml) for
+    /// finishing in Premiere Pro or Resolve; answered with `ExportFinished`.
+    /// See `fcp_xml::export_fcp_xml`.
+    ExportFcpXml {
+        path: PathBuf,
+    },
+    /// Replaces the timeline with the OpenTimelineIO document at `path`.
+    ///
+    /// Referenced media missing from the bin is probed and added first; with
+    /// no project loaded, a new one is started.
+    ImportOtio {
+        path: PathBuf,
+    },
+    /// Starts playback from the current playhead.
+    ///
+    /// The audio sink is the master clock: call [`Engine::tick_playback`]
+    /// periodically while [`Engine::is_playing`] returns `true` to queue audio
+    /// and receive `PlayheadChanged`/`PreviewFrameReady` for the audio position.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use std::path::PathBuf;
+    /// use engine::{Command, Engine, FfmpegMediaBackend};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let _ = engine.handle_command(Command::Import {
+    ///     path: PathBuf::from("demo.mp4"),
+    /// });
+    /// let _ = engine.handle_command(Command::Play);
+    /// while engine.is_playing() {
+    ///     let _ = engine.tick_playback();
+    /// }
+    /// ```
+    Play,
+    /// Stops playback and leaves the playhead at the last audible position.
+    Pause,
+    /// Shuttles forward (`L`): starts at 1x, then doubles up to 8x.
+    ///
+    /// Speeds other than 1x play silence while the audio sink keeps clocking
+    /// the playhead.
+    ShuttleForward,
+    /// Shuttles in reverse (`J`): starts at -1x, then doubles up to -8x.
+    ShuttleReverse,
+    /// Moves the playhead by `delta` video frames and pauses playback.
+    ///
+    /// Frame boundaries come from the frame rate (or time base) of the asset
+    /// under the playhead, so steps are frame-accurate for any source rate.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::{Command, Engine, FfmpegMediaBackend};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let _ = engine.handle_command(Command::StepFrame { delta: -1 });
+    /// ```
+    StepFrame {
+        delta: i64,
+    },
+    /// Moves the playhead to the previous or next segment boundary.
+    JumpToEditPoint {
+        direction: EditPointDirection,
+    },
+    /// Enables or disables short audio snippets on `SetPlayhead` while paused.
+    SetScrubAudio {
+        enabled: bool,
+    },
+    /// Sets the inclusive in mark at `at_tl` in timeline ticks.
+    ///
+    /// The mark is clamped to the timeline; an out mark at or before it is
+    /// cleared. Emits `ProjectChanged` with the new marks.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::{Command, Engine, FfmpegMediaBackend};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let _ = engine.handle_command(Command::SetInPoint { at_tl: 250_000 });
+    /// let _ = engine.handle_command(Command::SetOutPoint { at_tl: 750_000 });
+    /// ```
+    SetInPoint {
+        at_tl: i64,
+    },
+    /// Sets the exclusive out mark at `at_tl` in timeline ticks.
+    ///
+    /// The mark is clamped to the timeline; an in mark at or after it is
+    /// cleared.
+    SetOutPoint {
+        at_tl: i64,
+    },
+    /// Clears both in and out marks.
+    ClearInOut,
+    /// Loops playback inside the in/out range, or the whole timeline when no
+    /// mark is set, instead of stopping at its edge.
+    SetLoopPlayback {
+        enabled: bool,
+    },
+    /// Snaps edit points (split, cut, move, trim, in/out marks, paste and
+    /// insert/overwrite points) to the nearest start on the project frame
+    /// grid, so cuts stay frame-exact in preview and export.
+    ///
+    /// The setting is saved with the project.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::{Command, Engine, FfmpegMediaBackend};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let _ = engine.handle_command(Command::SetSnapToFrames { enabled: true });
+    /// // Lands on the frame start nearest to 500 ms.
+    /// let _ = engine.handle_command(Command::Split { at_tl: 500_000 });
+    /// ```
+    SetSnapToFrames {
+        enabled: bool,
+    },
+    /// Sets the project frame rate used for snapping, record timecode and
+    /// interchange exports; `None` follows the first video asset.
+    SetProjectFrameRate {
+        frame_rate: Option<Rational>,
+    },
+    /// Removes the in/out range from the timeline.
+    ///
+    /// With `ripple` (extract) later segments shift left to close the gap and
+    /// the marks are cleared; without it (lift) a gap is left behind. Returns
+    /// `InOutRangeNotSet` when no range is marked.
+    DeleteInOutRange {
+        ripple: bool,
+    },
+    /// Replaces the selection with `segment_ids`.
+    ///
+    /// Returns `SegmentIdNotFound` when an id does not exist.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::{Command, Engine, FfmpegMediaBackend};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let _ = engine.handle_command(Command::Select {
+    ///     segment_ids: vec![2, 3],
+    /// });
+    /// let _ = engine.handle_command(Command::DeleteSelection { ripple: true });
+    /// ```
+    Select {
+        segment_ids: Vec<u64>,
+    },
+    /// Adds `segment_ids` to the selection.
+    AddToSelection {
+        segment_ids: Vec<u64>,
+    },
+    /// Adds `segment_id` to the selection, or removes it when already
+    /// selected.
+    ///
+    /// The engine reads its own selection, so toggles sent back to back
+    /// never overwrite each other. Returns `SegmentIdNotFound` when the id
+    /// does not exist.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::{Command, Engine, FfmpegMediaBackend};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let _ = engine.handle_command(Command::SelectAll);
+    /// // Deselects segment 2 and keeps the rest.
+    /// let _ = engine.handle_command(Command::ToggleSelection { segment_id: 2 });
+    /// ```
+    ToggleSelection {
+        segment_id: u64,
+    },
+    /// Selects every segment overlapping `[start_tl, end_tl)`, replacing the
+    /// selection unless `extend` is set.
+    SelectRange {
+        start_tl: i64,
+        end_tl: i64,
+        extend: bool,
+    },
+    /// Selects every segment on the timeline.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::{Command, Engine, FfmpegMediaBackend};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let _ = engine.handle_command(Command::SelectAll);
+    /// let _ = engine.handle_command(Command::SetSelectionEnabled { enabled: false });
+    /// ```
+    SelectAll,
+    /// Empties the selection; the segments themselves are untouched.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::{Command, Engine, FfmpegMediaBackend};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let _ = engine.handle_command(Command::ClearSelection);
+    /// ```
+    ClearSelection,
+    /// Removes the selected segments; `ripple` closes the resulting gaps.
+    ///
+    /// Returns `SelectionEmpty` when nothing is selected.
+    DeleteSelection {
+        ripple: bool,
+    },
+    /// Moves the selected segments together by `delta_tl` timeline ticks.
+    ///
+    /// The delta is clamped so no selected segment overlaps an unselected one
+    /// or starts before zero.
+    MoveSelection {
+        delta_tl: i64,
+    },
+    /// Enables or disables the selected segments.
+    SetSelectionEnabled {
+        enabled: bool,
+    },
+    /// Enables or disables one segment.
+    ///
+    /// Disabled segments keep their place and source range but play and
+    /// export as black and silence.
+    SetSegmentEnabled {
+        segment_id: u64,
+        enabled: bool,
+    },
+    /// Copies the selected segments, with their source ranges and flags, to
+    /// the engine clipboard.
+    ///
+    /// Returns `SelectionEmpty` when nothing is selected. The clipboard is
+    /// cleared by `Import`.
+    CopySelection,
+    /// Pastes the clipboard at the playhead with fresh segment ids.
+    ///
+    /// The pasted segments become the selection and the playhead moves to
+    /// the end of the pasted span. Returns `ClipboardEmpty` when nothing was
+    /// copied.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::{Command, Engine, FfmpegMediaBackend, PasteMode};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let _ = engine.handle_command(Command::SelectAll);
+    /// let _ = engine.handle_command(Command::CopySelection);
+    /// let _ = engine.handle_command(Command::Paste {
+    ///     mode: PasteMode::Insert,
+    /// });
+    /// ```
+    Paste {
+        mode: PasteMode,
+    },
+    /// Inserts a copy of one segment right after it, shifting later segments
+    /// right by its duration.
+    DuplicateSegment {
+        segment_id: u64,
+    },
+    /// Sets the source in mark of a bin asset, in timeline ticks from the
+    /// asset start.
+    ///
+    /// A source out mark at or before the new in mark is cleared.
+    SetSourceInPoint {
+        asset_id: u64,
+        at_src: i64,
+    },
+    /// Sets the exclusive source out mark of a bin asset.
+    ///
+    /// A source in mark at or after the new out mark is cleared.
+    SetSourceOutPoint {
+        asset_id: u64,
+        at_src: i64,
+    },
+    /// Clears both source marks of a bin asset.
+    ClearSourceInOut {
+        asset_id: u64,
+    },
+    /// Inserts the marked source range of a bin asset at `at_tl`, rippling
+    /// later segments right.
+    ///
+    /// Without source marks the whole asset is used. The new segment becomes
+    /// the selection and the playhead moves to the end of the edit.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::{Command, Engine, FfmpegMediaBackend};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let _ = engine.handle_command(Command::SetSourceInPoint {
+    ///     asset_id: 2,
+    ///     at_src: 1_000_000,
+    /// });
+    /// let _ = engine.handle_command(Command::SetSourceOutPoint {
+    ///     asset_id: 2,
+    ///     at_src: 3_000_000,
+    /// });
+    /// let _ = engine.handle_command(Command::InsertFromAsset {
+    ///     asset_id: 2,
+    ///     at_tl: 500_000,
+    /// });
+    /// ```
+    InsertFromAsset {
+        asset_id: u64,
+        at_tl: i64,
+    },
+    /// Overwrites the timeline from `at_tl` with the marked source range of a
+    /// bin asset.
+    ///
+    /// Segments under the edit are split at its edges and replaced; the rest
+    /// of the timeline stays in place.
+    OverwriteFromAsset {
+        asset_id: u64,
+        at_tl: i64,
+    },
+    /// Points a segment at another bin asset, keeping its timeline position
+    /// and duration.
+    ///
+    /// `align` picks the new source in point. `Playhead` lines up the source
+    /// monitor position (when it shows `asset_id`, otherwise the asset's
+    /// source in mark) with the timeline playhead. Returns
+    /// `InsufficientMedia` when the new asset is too short.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::{Command, Engine, FfmpegMediaBackend, ReplaceAlignment};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let _ = engine.handle_command(Command::ReplaceSegmentMedia {
+    ///     segment_id: 3,
+    ///     asset_id: 2,
+    ///     align: ReplaceAlignment::SourceTimecode,
+    /// });
+    /// ```
+    ReplaceSegmentMedia {
+        segment_id: u64,
+        asset_id: u64,
+        align: ReplaceAlignment,
+    },
+    /// Applies project edits atomically.
+    ///
+    /// Only edits (split, cut, move, trim, marks, loop flag, range delete,
+    /// selection commands, paste, duplicate, source marks, insert and
+    /// overwrite from the bin, media replacement and nested batches) are
+    /// accepted.
+    /// When any command fails the project is rolled back and
+    /// `BatchCommandFailed` names the failing index; otherwise a single
+    /// `ProjectUpdated` covers every change.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::{Command, Engine, FfmpegMediaBackend};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let _ = engine.handle_command(Command::Batch(vec![
+    ///     Command::Split { at_tl: 250_000 },
+    ///     Command::Split { at_tl: 500_000 },
+    ///     Command::Cut { at_tl: 250_000 },
+    /// ]));
+    /// ```
+    Batch(Vec<Command>),
+    /// Emits the full project as `ProjectChanged`.
+    ///
+    /// Edits are reported as `ProjectUpdated` deltas; clients request a
+    /// snapshot on startup or when a delta does not apply to their revision.
+    RequestProjectSnapshot,
 }
 
-/// Events emitted by the engine.
-#[derive(Debug, Clone, PartialEq)]
-pub enum Event {
-    ProjectChanged(ProjectSnapshot),
-    PlayheadChanged { t_tl: i64 },
-    PreviewFrameReady { t_tl: i64, frame: PreviewFrame },
-    ExportProgress { done: u64, total: u64 },
-    ExportFinished { path: PathBuf },
-    Error(EngineErrorEvent),
+/// How `Command::Paste` makes room for the pasted segments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasteMode {
+    /// Splits at the playhead and shifts later segments right.
+    Insert,
+    /// Replaces whatever lies under the pasted span.
+    Overwrite,
 }
 
-/// User-facing error payload emitted as an event.
+/// How `Command::ReplaceSegmentMedia` picks the new source in point.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum EngineErrorKind {
-    SplitPointAtBoundary,
-    SegmentNotFound,
-    Other,
+pub enum ReplaceAlignment {
+    /// Finds the segment's source in point timecode in the new asset.
+    SourceTimecode,
+    /// Starts at the first frame of the new asset.
+    StartOfFile,
+    /// Lines up the source monitor position with the timeline playhead.
+    Playhead,
 }
 
-impl From<&EngineError> for EngineErrorKind {
-    fn from(value: &EngineError) -> Self {
-        match value {
-            EngineError::SplitPointAtBoundary { .. } => Self::SplitPointAtBoundary,
-            EngineError::SegmentNotFound { .. } => Self::SegmentNotFound,
-            EngineError::SegmentIdNotFound { .. } => Self::Other,
-            _ => Self::Other,
-        }
-    }
+/// Direction used by `Command::JumpToEditPoint`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditPointDirection {
+    Previous,
+    Next,
 }
 
-/// User-facing error payload emitted as an event.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct EngineErrorEvent {
-    pub kind: EngineErrorKind,
+/// Events emitted by the engine.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// Full project state, emitted on import and `RequestProjectSnapshot`.
+    ProjectChanged(ProjectSnapshot),
+    /// Changes of one edit, applicable with [`ProjectSnapshot::apply_delta`].
+    ProjectUpdated(ProjectDelta),
+    PlayheadChanged {
+        t_tl: i64,
+    },
+    /// Decoded frame for the playhead request identified by `generation`.
+    ///
+    /// Generations increase with every preview request. A request that misses
+    /// the cache is answered twice: first with a `Draft` frame, then with the
+    /// exact `Full` frame unless a newer request superseded it. Frames may
+    /// arrive after later `PlayheadChanged` events; a frame is never emitted
+    /// after a frame with a newer generation, nor a draft after the full
+    /// frame of the same generation.
+    PreviewFrameReady {
+        t_tl: i64,
+        generation: u64,
+        quality: PreviewQuality,
+        frame: PreviewFrame,
+    },
+    /// Source monitor position after `SetSourcePlayhead` or `MatchFrame`.
+    SourcePlayheadChanged {
+        asset_id: u64,
+        t_src: i64,
+    },
+    /// Decoded source monitor frame; ordered like `PreviewFrameReady` but
+    /// with generations of its own.
+    SourcePreviewFrameReady {
+        asset_id: u64,
+        t_src: i64,
+        generation: u64,
+        quality: PreviewQuality,
+        frame: PreviewFrame,
+    },
+    ExportProgress {
+        done: u64,
+        total: u64,
+    },
+    ExportFinished {
+        path: PathBuf,
+    },
+    ProjectSaved {
+        path: PathBuf,
+    },
+    /// A recovery copy newer than the saved project exists; answer with
+    /// `RestoreRecovery` or `DiscardRecovery`.
+    RecoveryAvailable {
+        recovery_path: PathBuf,
+        /// Project the copy belongs to; `None` for an untitled project.
+        project_path: Option<PathBuf>,
+    },
+    /// `OpenProject` found the project file damaged (`reason`) but a valid
+    /// backup; answer with `RestoreRecovery` to open the backup in its place
+    /// or `DiscardRecovery` to ignore it.
+    BackupAvailable {
+        backup_path: PathBuf,
+        project_path: PathBuf,
+        reason: String,
+    },
+    /// Autosave wrote a recovery copy.
+    RecoverySaved {
+        path: PathBuf,
+    },
+    /// Playback started, stopped or changed speed; `rate` is 0 when stopped.
+    PlaybackStateChanged {
+        playing: bool,
+        rate: i32,
+    },
+    /// `SetLoopPlayback` changed the session's loop setting; the project
+    /// revision is unchanged, so set `ProjectSnapshot::loop_playback` directly.
+    LoopPlaybackChanged {
+        enabled: bool,
+    },
+    Error(EngineErrorEvent),
+    /// Terminates the synchronous events of the tagged command `id`.
+    ///
+    /// A failed command reports its error here instead of as `Error`.
+    /// Background preview frames started by the command may still follow.
+    CommandCompleted {
+        id: CommandId,
+        result: std::result::Result<(), EngineErrorEvent>,
+    },
+}
+
+/// User-facing error payload emitted as an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineErrorKind {
+    SplitPointAtBoundary,
+    SegmentNotFound,
+    Other,
+}
+
+impl From<&EngineError> for EngineErrorKind {
+    fn from(value: &EngineError) -> Self {
+        match value {
+            EngineError::SplitPointAtBoundary { .. } => Self::SplitPointAtBoundary,
+            EngineError::SegmentNotFound { .. } => Self::SegmentNotFound,
+            EngineError::SegmentIdNotFound { .. } => Self::Other,
+            EngineError::BatchCommandFailed { source, .. } => Self::from(source.as_ref()),
+            _ => Self::Other,
+        }
+    }
+}
+
+/// User-facing error payload emitted as an event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineErrorEvent {
+    pub kind: EngineErrorKind,
     pub message: String,
 }
 
@@ -185,15 +847,175 @@ impl EngineErrorEvent {
 
 /// Export settings for video-only MVP export.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
-pub struct ExportSettings {}
+pub struct ExportSettings {
+    pub range: ExportRange,
+}
+
+/// Timeline span written by `Command::Export`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportRange {
+    /// The whole timeline.
+    #[default]
+    Timeline,
+    /// Only the in/out range; export fails with `InOutRangeNotSet` without one.
+    InOut,
+}
 
 /// Immutable project snapshot consumed by the UI.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProjectSnapshot {
+    /// Project revision; increases with every change.
+    pub revision: u64,
     pub assets: Vec<MediaAssetSummary>,
     pub segments: Vec<SegmentSummary>,
     pub duration_tl: i64,
     pub preview_bucket_tl: i64,
+    /// Inclusive in mark, if set.
+    pub in_point_tl: Option<i64>,
+    /// Exclusive out mark, if set.
+    pub out_point_tl: Option<i64>,
+    pub loop_playback: bool,
+    /// Project frame grid; see `Project::frame_grid`.
+    pub frame_grid: FrameGrid,
+    /// Record timecode is drop-frame; see `Project::timecode_format`.
+    pub drop_frame: bool,
+    /// Edit points snap to `frame_grid`.
+    pub snap_to_frames: bool,
+    /// Selected segment ids in ascending order.
+    pub selection: Vec<u64>,
+}
+
+impl ProjectSnapshot {
+    /// Returns the record timecode rate and count; see
+    /// `Project::timecode_format`.
+    pub fn timecode_format(&self) -> (FrameGrid, bool) {
+        (self.frame_grid, self.drop_frame)
+    }
+
+    /// Returns the record timecode at `t_tl`; see `Project::timecode_at`.
+    pub fn timecode_at(&self, t_tl: i64) -> Timecode {
+        let (grid, drop_frame) = self.timecode_format();
+        Timecode::from_tl(t_tl, grid, drop_frame)
+    }
+
+    /// Applies `delta` on top of this snapshot.
+    ///
+    /// Returns `false` and leaves the snapshot untouched when `delta` was not
+    /// made against this revision; request a fresh snapshot in that case.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::{Command, Event};
+    ///
+    /// if let Event::ProjectUpdated(delta) = event
+    ///     && !snapshot.apply_delta(&delta)
+    /// {
+    ///     send(Command::RequestProjectSnapshot);
+    /// }
+    /// ```
+    pub fn apply_delta(&mut self, delta: &ProjectDelta) -> bool {
+        if delta.base_revision != self.revision {
+            return false;
+        }
+
+        if !delta.segments_removed.is_empty() {
+            let removed: HashSet<u64> = delta.segments_removed.iter().copied().collect();
+            self.segments
+                .retain(|segment| !removed.contains(&segment.id));
+        }
+        if !delta.segments_updated.is_empty() {
+            let updated: HashMap<u64, &SegmentSummary> = delta
+                .segments_updated
+                .iter()
+                .map(|segment| (segment.id, segment))
+                .collect();
+            for segment in &mut self.segments {
+                if let Some(update) = updated.get(&segment.id) {
+                    *segment = (*update).clone();
+                }
+            }
+            // Only edits that move segments past each other need a re-sort.
+            if !self
+                .segments
+                .is_sorted_by_key(|segment| segment.timeline_start)
+            {
+                self.segments.sort_by_key(|segment| segment.timeline_start);
+            }
+        }
+        for added in &delta.segments_added {
+            let index = self
+                .segments
+                .partition_point(|segment| segment.timeline_start <= added.timeline_start);
+            self.segments.insert(index, added.clone());
+        }
+        if !delta.assets_updated.is_empty() {
+            let updated: HashMap<u64, &MediaAssetSummary> = delta
+                .assets_updated
+                .iter()
+                .map(|asset| (asset.id, asset))
+                .collect();
+            for asset in &mut self.assets {
+                if let Some(update) = updated.get(&asset.id) {
+                    *asset = (*update).clone();
+                }
+            }
+        }
+        self.assets.extend(delta.assets_added.iter().cloned());
+
+        self.revision = delta.revision;
+        self.duration_tl = delta.duration_tl;
+        self.preview_bucket_tl = delta.preview_bucket_tl;
+        self.in_point_tl = delta.in_point_tl;
+        self.out_point_tl = delta.out_point_tl;
+        self.loop_playback = delta.loop_playback;
+        self.frame_grid = delta.frame_grid;
+        self.drop_frame = delta.drop_frame;
+        self.snap_to_frames = delta.snap_to_frames;
+        if let Some(selection) = &delta.selection {
+            self.selection.clone_from(selection);
+        }
+        true
+    }
+}
+
+/// Changes between two project revisions.
+///
+/// Segments and assets are listed only when they changed; the scalar fields
+/// always carry the values at `revision`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectDelta {
+    /// Revision the delta applies to.
+    pub base_revision: u64,
+    /// Revision after applying the delta.
+    pub revision: u64,
+    pub assets_added: Vec<MediaAssetSummary>,
+    /// Bin assets that changed, e.g. source marks, a relink or offline state.
+    pub assets_updated: Vec<MediaAssetSummary>,
+    pub segments_added: Vec<SegmentSummary>,
+    pub segments_updated: Vec<SegmentSummary>,
+    pub segments_removed: Vec<u64>,
+    pub duration_tl: i64,
+    pub preview_bucket_tl: i64,
+    pub in_point_tl: Option<i64>,
+    pub out_point_tl: Option<i64>,
+    pub loop_playback: bool,
+    pub frame_grid: FrameGrid,
+    pub drop_frame: bool,
+    pub snap_to_frames: bool,
+    /// New selection, or `None` when it did not change.
+    pub selection: Option<Vec<u64>>,
+}
+
+impl ProjectDelta {
+    /// Returns whether segments or assets changed, not just marks or flags.
+    ///
+    /// Source mark changes in `assets_updated` do not count.
+    pub fn changes_timeline(&self) -> bool {
+        !self.assets_added.is_empty()
+            || !self.segments_added.is_empty()
+            || !self.segments_updated.is_empty()
+            || !self.segments_removed.is_empty()
+    }
 }
 
 /// Snapshot representation of one media asset.
@@ -204,6 +1026,27 @@ pub struct MediaAssetSummary {
     pub has_video: bool,
     pub has_audio: bool,
     pub duration_tl: i64,
+    /// Inclusive source in mark, in timeline ticks from the asset start.
+    pub source_in_tl: Option<i64>,
+    /// Exclusive source out mark, in timeline ticks from the asset start.
+    pub source_out_tl: Option<i64>,
+    /// The asset's file is missing; relink it before previewing or exporting.
+    pub offline: bool,
+    /// Video frame grid; `None` for audio-only assets.
+    pub frame_grid: Option<FrameGrid>,
+    /// Source timecode of the first frame, when the file carries one.
+    pub start_timecode: Option<Timecode>,
+}
+
+impl MediaAssetSummary {
+    /// Returns the source timecode `t_src` ticks after the asset start; see
+    /// `MediaAsset::source_timecode_at`.
+    pub fn source_timecode_at(&self, t_src: i64) -> Result<Timecode> {
+        let grid = self.frame_grid.unwrap_or(FrameGrid::DEFAULT);
+        self.start_timecode
+            .unwrap_or(Timecode::ZERO)
+            .offset_tl(t_src, grid)
+    }
 }
 
 /// Snapshot representation of one timeline segment.
@@ -217,18 +1060,76 @@ pub struct SegmentSummary {
     pub src_out_video: Option<i64>,
     pub src_in_audio: Option<i64>,
     pub src_out_audio: Option<i64>,
+    pub enabled: bool,
 }
 
 /// Engine implementation for import/scrub/split/export commands.
 #[derive(Debug)]
 pub struct Engine<M> {
-    media: M,
+    media: Arc<M>,
     project: Option<Project>,
+    /// Revision of `project`, bumped by every change.
+    revision: u64,
     playhead_tl: i64,
     next_asset_id: u64,
     next_segment_id: u64,
     preview_cache: PreviewFrameCache,
     last_preview: Option<LastPreviewTarget>,
+    preview_workers: PreviewWorkerPool,
+    /// Generation of the most recent preview request.
+    preview_generation: u64,
+    /// Generation and quality of the most recent `PreviewFrameReady`.
+    shown_preview: (u64, PreviewQuality),
+    /// Generation of the most recent source monitor request.
+    source_generation: u64,
+    /// Generation and quality of the most recent `SourcePreviewFrameReady`.
+    shown_source_preview: (u64, PreviewQuality),
+    /// Asset and position shown in the source monitor.
+    source_playhead: Option<(u64, i64)>,
+    /// Id of the tagged command being handled, recorded on preview jobs.
+    current_command: Option<CommandId>,
+    audio_sink: Box<dyn AudioSink>,
+    audio_format: PcmFormat,
+    scrub_audio: bool,
+    playback: Option<PlaybackSession>,
+    /// Segments from the last `CopySelection`, rebased to start at zero.
+    clipboard: Vec<Segment>,
+    /// File the project was opened from or last saved to.
+    project_path: Option<PathBuf>,
+    /// Revision last written to `project_path`.
+    saved_revision: u64,
+    autosave: Option<AutosaveConfig>,
+    /// When the next recovery copy is written; pushed back by every edit.
+    autosave_due: Option<Instant>,
+    /// Recovery copy or backup offered through `RecoveryAvailable` or
+    /// `BackupAvailable`.
+    offered_recovery: Option<OfferedRecovery>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct OfferedRecovery {
+    recovery_path: PathBuf,
+    project_path: Option<PathBuf>,
+    /// Backups are kept when the offer is discarded.
+    is_backup: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PlaybackSession {
+    start_tl: i64,
+    /// Timeline ticks advanced per tick of audio clock; negative in reverse.
+    rate: i32,
+    /// Audio queued to the sink since `start_tl`, in timeline ticks.
+    queued_sink_tl: i64,
+}
+
+/// Engine state restored when a batch fails part-way.
+#[derive(Debug)]
+struct BatchRollback {
+    project: Project,
+    revision: u64,
+    playhead_tl: i64,
+    next_segment_id: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -246,7 +1147,7 @@ enum ScrubDirection {
 
 impl<M> Engine<M>
 where
-    M: MediaBackend,
+    M: MediaBackend + 'static,
 {
     /// Creates a new engine with the provided media backend.
     ///
@@ -257,9 +1158,18 @@ where
     /// let _engine = Engine::new(FfmpegMediaBackend);
     /// ```
     pub fn new(media: M) -> Self {
+        let media = Arc::new(media);
         Self {
+            preview_workers: PreviewWorkerPool::spawn(Arc::clone(&media), PREVIEW_WORKER_COUNT),
+            preview_generation: 0,
+            shown_preview: (0, PreviewQuality::Full),
+            source_generation: 0,
+            shown_source_preview: (0, PreviewQuality::Full),
+            source_playhead: None,
+            current_command: None,
             media,
             project: None,
+            revision: 0,
             playhead_tl: 0,
             next_asset_id: 1,
             next_segment_id: 1,
@@ -268,14 +1178,287 @@ where
                 DEFAULT_PREVIEW_CACHE_BUCKET_TL,
             ),
             last_preview: None,
+            audio_sink: Box::new(NullAudioSink::new()),
+            audio_format: PcmFormat::DEFAULT,
+            scrub_audio: false,
+            playback: None,
+            clipboard: Vec::new(),
+            project_path: None,
+            saved_revision: 0,
+            autosave: None,
+            autosave_due: None,
+            offered_recovery: None,
+        }
+    }
+
+    /// Enables autosave of recovery copies after edits.
+    ///
+    /// Call `tick_autosave` once `autosave_due` has passed.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use engine::{AutosaveConfig, Engine, FfmpegMediaBackend};
+    ///
+    /// let _engine = Engine::new(FfmpegMediaBackend).with_autosave(AutosaveConfig::from_env());
+    /// ```
+    pub fn with_autosave(mut self, config: AutosaveConfig) -> Self {
+        self.autosave = Some(config);
+        self
+    }
+
+    /// Returns when the pending recovery copy should be written, if any.
+    pub fn autosave_due(&self) -> Option<Instant> {
+        self.autosave_due
+    }
+
+    /// Writes the recovery copy once `autosave_due` has passed.
+    ///
+    /// Emits `RecoverySaved`; a failed write is retried after another
+    /// debounce period.
+    pub fn tick_autosave(&mut self) -> Result<Vec<Event>> {
+        let (Some(due), Some(config)) = (self.autosave_due, self.autosave.as_ref()) else {
+            return Ok(Vec::new());
+        };
+        let now = Instant::now();
+        if now < due {
+            return Ok(Vec::new());
+        }
+        self.autosave_due = None;
+        let (Some(project), Some(path)) = (
+            self.project.as_ref(),
+            config.recovery_path(self.project_path.as_deref()),
+        ) else {
+            return Ok(Vec::new());
+        };
+        if let Err(error) = project.save_recovery_file(&path) {
+            warn!(%error, path = %path.display(), "autosave failed");
+            self.autosave_due = Some(now + config.debounce);
+            return Err(error);
+        }
+        debug!(path = %path.display(), revision = self.revision, "recovery copy written");
+        Ok(vec![Event::RecoverySaved { path }])
+    }
+
+    /// Replaces the audio sink used for playback and scrub audio.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use engine::playback::WavFileAudioSink;
+    /// use engine::{Engine, FfmpegMediaBackend, PcmFormat};
+    ///
+    /// let sink = WavFileAudioSink::create("playback.wav", PcmFormat::DEFAULT)
+    ///     .expect("wav sink should be created");
+    /// let _engine = Engine::new(FfmpegMediaBackend).with_audio_sink(sink);
+    /// ```
+    pub fn with_audio_sink(mut self, sink: impl AudioSink + 'static) -> Self {
+        self.audio_sink = Box::new(sink);
+        self
+    }
+
+    /// Returns whether playback is running and expects `tick_playback` calls.
+    pub fn is_playing(&self) -> bool {
+        self.playback.is_some()
+    }
+
+    /// Advances playback to the audio clock.
+    ///
+    /// Keeps the audio sink filled ahead of the clock and emits
+    /// `PlayheadChanged` plus a preview frame whenever the audible position
+    /// moved. Playback stops at the end of the timeline. Failures stop playback
+    /// and are reported as `Error` followed by `PlaybackStateChanged`.
+    pub fn tick_playback(&mut self) -> Result<Vec<Event>> {
+        if self.playback.is_none() {
+            return Ok(Vec::new());
+        }
+        match self.advance_playback() {
+            Ok(events) => Ok(events),
+            Err(error) => {
+                warn!(%error, "playback stopped after failure");
+                self.stop_playback();
+                Ok(vec![
+                    Event::Error(EngineErrorEvent::from_error(&error)),
+                    Event::PlaybackStateChanged {
+                        playing: false,
+                        rate: 0,
+                    },
+                ])
+            }
+        }
+    }
+
+    /// Returns whether preview decodes are still running in the background.
+    ///
+    /// While this is `true`, call [`Engine::poll_events`] to receive their
+    /// `PreviewFrameReady` events.
+    pub fn has_pending_previews(&self) -> bool {
+        self.preview_workers.has_outstanding()
+    }
+
+    /// Runs `waker` on a decode thread after each background preview
+    /// finishes, so a host loop can sleep until [`Engine::poll_events`] has
+    /// something to return instead of polling.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use std::sync::mpsc;
+    /// use engine::{Engine, FfmpegMediaBackend};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let (wake_tx, wake_rx) = mpsc::channel();
+    /// engine.set_preview_waker(move || {
+    ///     let _ = wake_tx.send(());
+    /// });
+    /// wake_rx.recv()?;
+    /// let events = engine.poll_events();
+    /// ```
+    pub fn set_preview_waker(&mut self, waker: impl Fn() + Send + Sync + 'static) {
+        self.preview_workers.set_waker(Arc::new(waker));
+    }
+
+    /// Collects finished background preview decodes without blocking.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::{Command, Engine, FfmpegMediaBackend};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let mut events = engine.handle_command(Command::SetPlayhead { t_tl: 500_000 })?;
+    /// while engine.has_pending_previews() {
+    ///     events.extend(engine.poll_events());
+    /// }
+    /// ```
+    pub fn poll_events(&mut self) -> Vec<Event> {
+        self.poll_tagged_events()
+            .into_iter()
+            .map(|tagged| tagged.event)
+            .collect()
+    }
+
+    /// Like [`Engine::poll_events`], tagging each event with the id of the
+    /// command that requested the preview.
+    pub fn poll_tagged_events(&mut self) -> Vec<TaggedEvent> {
+        let mut events = Vec::new();
+        while let Some(result) = self.preview_workers.try_collect() {
+            let id = result.job.command_id;
+            events.extend(
+                self.accept_preview_result(result)
+                    .map(|event| TaggedEvent { id, event }),
+            );
+        }
+        events
+    }
+
+    /// Blocks until every background preview decode finished or `timeout`
+    /// elapsed, returning the resulting events.
+    pub fn wait_for_previews(&mut self, timeout: Duration) -> Vec<Event> {
+        let deadline = Instant::now() + timeout;
+        let mut events = Vec::new();
+        while self.has_pending_previews() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let Some(result) = self.preview_workers.collect_timeout(remaining) else {
+                warn!("timed out waiting for preview decodes");
+                break;
+            };
+            events.extend(self.accept_preview_result(result));
         }
+        events
+    }
+
+    /// Applies one tagged command and returns its events, each echoing `id`.
+    ///
+    /// The last event is always `CommandCompleted`; failures are reported
+    /// there rather than returned.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::{Command, CommandId, Engine, Event, FfmpegMediaBackend, TaggedCommand};
+    ///
+    /// let mut engine = Engine::new(FfmpegMediaBackend);
+    /// let events = engine.handle_tagged_command(TaggedCommand {
+    ///     id: CommandId(7),
+    ///     command: Command::Split { at_tl: 500_000 },
+    /// });
+    /// assert!(matches!(
+    ///     events.last().map(|tagged| &tagged.event),
+    ///     Some(Event::CommandCompleted { id: CommandId(7), .. })
+    /// ));
+    /// ```
+    pub fn handle_tagged_command(&mut self, tagged: TaggedCommand) -> Vec<TaggedEvent> {
+        let TaggedCommand { id, command } = tagged;
+        self.current_command = Some(id);
+        let result = self.handle_command(command);
+        self.current_command = None;
+
+        let (mut events, result) = match result {
+            Ok(events) => (events, Ok(())),
+            Err(error) => {
+                debug!(?id, %error, "tagged command failed");
+                (Vec::new(), Err(EngineErrorEvent::from_error(&error)))
+            }
+        };
+        events.push(Event::CommandCompleted { id, result });
+        events
+            .into_iter()
+            .map(|event| TaggedEvent {
+                id: Some(id),
+                event,
+            })
+            .collect()
     }
 
     /// Applies one command and returns emitted events.
     pub fn handle_command(&mut self, command: Command) -> Result<Vec<Event>> {
+        let edits_timeline = is_timeline_edit(&command);
+        let revision = self.revision;
+        let events = self.apply_command(command);
+        if let Some(project) = &mut self.project {
+            // The command's deltas are out; the next command records afresh.
+            project.clear_changes();
+        }
+        self.schedule_autosave(revision);
+        let events = events?;
+        if edits_timeline && let Some(session) = self.playback {
+            self.restart_playback_at(self.playhead_tl, session.rate)?;
+        }
+        Ok(events)
+    }
+
+    /// Pushes the recovery copy back after a change; a saved project needs none.
+    fn schedule_autosave(&mut self, revision_before: u64) {
+        let Some(config) = &self.autosave else {
+            return;
+        };
+        if self.revision == self.saved_revision || self.project.is_none() {
+            self.autosave_due = None;
+        } else if self.revision != revision_before {
+            self.autosave_due = Some(Instant::now() + config.debounce);
+        }
+    }
+
+    fn apply_command(&mut self, command: Command) -> Result<Vec<Event>> {
         match command {
             Command::Import { path } => self.import(path),
+            Command::AddAsset { path } => self.add_asset(path),
+            Command::OpenProject { path } => self.open_project(path),
+            Command::SaveProject { path, paths } => self.save_project(path, paths),
+            Command::CheckForRecovery => self.check_for_recovery(),
+            Command::RestoreRecovery => self.restore_recovery(),
+            Command::DiscardRecovery => self.discard_recovery(),
+            Command::RelinkAsset { asset_id, new_path } => self.relink_asset(asset_id, new_path),
+            Command::RelinkFromDirectory { dir } => self.relink_from_directory(dir),
             Command::SetPlayhead { t_tl } => self.set_playhead(t_tl),
+            Command::SetSourcePlayhead { asset_id, t_src } => {
+                self.set_source_playhead(asset_id, t_src)
+            }
+            Command::MatchFrame => {
+                let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+                let (asset_id, t_src) = project.match_frame(self.playhead_tl)?;
+                debug!(
+                    playhead_tl = self.playhead_tl,
+                    asset_id, t_src, "match frame"
+                );
+                self.set_source_playhead(asset_id, t_src)
+            }
             Command::Split { at_tl } => self.split(at_tl),
             Command::Cut { at_tl } => self.cut(at_tl),
             Command::MoveSegment {
@@ -292,385 +1475,3233 @@ where
             } => self.trim_segment_end(segment_id, new_end_tl),
             Command::Export { path, settings } => self.export(path, settings),
             Command::CancelExport => Ok(Vec::new()),
+            Command::ExportEdl { path } => self.export_edl(path),
+            Command::ImportEdl { path } => self.import_edl(path),
+            Command::ExportOtio { path } => self.export_otio(path),
+            Command::ImportOtio { path } => self.import_otio(path),
+            Command::ExportFcpXml { path } => self.export_fcp_xml(path),
+            Command::ImportCutList { path } => self.import_cut_list(path),
+            Command::Play => self.shuttle(1),
+            Command::Pause => self.pause(),
+            Command::ShuttleForward => self.shuttle_forward(),
+            Command::ShuttleReverse => self.shuttle_reverse(),
+            Command::StepFrame { delta } => self.step_frame(delta),
+            Command::JumpToEditPoint { direction } => self.jump_to_edit_point(direction),
+            Command::SetScrubAudio { enabled } => {
+                self.scrub_audio = enabled;
+                Ok(Vec::new())
+            }
+            Command::SetInPoint { at_tl } => {
+                self.update_marks(|project| project.set_in_point(at_tl))
+            }
+            Command::SetOutPoint { at_tl } => {
+                self.update_marks(|project| project.set_out_point(at_tl))
+            }
+            Command::ClearInOut => self.update_marks(Project::clear_in_out),
+            Command::SetLoopPlayback { enabled } => self.set_loop_playback(enabled),
+            Command::SetSnapToFrames { enabled } => self.set_snap_to_frames(enabled),
+            Command::SetProjectFrameRate { frame_rate } => self.set_project_frame_rate(frame_rate),
+            Command::DeleteInOutRange { ripple } => self.delete_in_out_range(ripple),
+            Command::Select { segment_ids } => {
+                self.update_selection(|project| project.select(&segment_ids, false))
+            }
+            Command::AddToSelection { segment_ids } => {
+                self.update_selection(|project| project.select(&segment_ids, true))
+            }
+            Command::ToggleSelection { segment_id } => {
+                self.update_selection(|project| project.toggle_selection(segment_id))
+            }
+            Command::SelectRange {
+                start_tl,
+                end_tl,
+                extend,
+            } => self.update_selection(|project| {
+                project.select_range(start_tl, end_tl, extend);
+                Ok(())
+            }),
+            Command::SelectAll => self.update_selection(|project| {
+                project.select_all();
+                Ok(())
+            }),
+            Command::ClearSelection => self.update_selection(|project| {
+                project.selection.clear();
+                Ok(())
+            }),
+            Command::DeleteSelection { ripple } => {
+                self.edit_segments(|project| project.delete_selection(ripple))
+            }
+            Command::MoveSelection { delta_tl } => self.edit_segments(|project| {
+                let applied = project.move_selection(delta_tl)?;
+                debug!(delta_tl, applied, "selection moved");
+                Ok(())
+            }),
+            Command::SetSelectionEnabled { enabled } => {
+                self.edit_segments(|project| project.set_selection_enabled(enabled))
+            }
+            Command::SetSegmentEnabled {
+                segment_id,
+                enabled,
+            } => self.set_segment_enabled(segment_id, enabled),
+            Command::CopySelection => self.copy_selection(),
+            Command::Paste { mode } => self.paste(mode),
+            Command::DuplicateSegment { segment_id } => self.duplicate_segment(segment_id),
+            Command::SetSourceInPoint { asset_id, at_src } => self
+                .update_source_marks(asset_id, |project| {
+                    project.set_source_in_point(asset_id, at_src)
+                }),
+            Command::SetSourceOutPoint { asset_id, at_src } => self
+                .update_source_marks(asset_id, |project| {
+                    project.set_source_out_point(asset_id, at_src)
+                }),
+            Command::ClearSourceInOut { asset_id } => {
+                self.update_source_marks(asset_id, |project| project.clear_source_in_out(asset_id))
+            }
+            Command::InsertFromAsset { asset_id, at_tl } => {
+                self.edit_from_asset(asset_id, at_tl, PasteMode::Insert)
+            }
+            Command::OverwriteFromAsset { asset_id, at_tl } => {
+                self.edit_from_asset(asset_id, at_tl, PasteMode::Overwrite)
+            }
+            Command::ReplaceSegmentMedia {
+                segment_id,
+                asset_id,
+                align,
+            } => self.replace_segment_media(segment_id, asset_id, align),
+            Command::Batch(commands) => self.batch(commands),
+            Command::RequestProjectSnapshot => {
+                let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+                Ok(vec![Event::ProjectChanged(self.snapshot_of(project))])
+            }
+        }
+    }
+
+    fn batch(&mut self, commands: Vec<Command>) -> Result<Vec<Event>> {
+        if let Some(index) = commands.iter().position(|command| !is_batchable(command)) {
+            return Err(EngineError::CommandNotBatchable { index });
+        }
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        if commands.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let checkpoint = project.checkpoint();
+        let rollback = BatchRollback {
+            project: project.clone(),
+            revision: self.revision,
+            playhead_tl: self.playhead_tl,
+            next_segment_id: self.next_segment_id,
+        };
+        let count = commands.len();
+        for (index, command) in commands.into_iter().enumerate() {
+            // Per-command events are folded into the single batch delta.
+            if let Err(source) = self.apply_command(command) {
+                warn!(index, %source, "batch failed; rolling back");
+                self.project = Some(rollback.project);
+                self.revision = rollback.revision;
+                self.playhead_tl = rollback.playhead_tl;
+                self.next_segment_id = rollback.next_segment_id;
+                self.invalidate_preview_cache();
+                return Err(EngineError::BatchCommandFailed {
+                    index,
+                    source: Box::new(source),
+                });
+            }
+        }
+
+        let delta = self.publish_delta(checkpoint, rollback.revision)?;
+        info!(count, revision = self.revision, "batch applied");
+        let mut events = vec![Event::ProjectUpdated(delta)];
+        if self.playhead_tl != rollback.playhead_tl {
+            events.push(Event::PlayheadChanged {
+                t_tl: self.playhead_tl,
+            });
         }
+        Ok(events)
     }
 
     fn import(&mut self, path: PathBuf) -> Result<Vec<Event>> {
         let probed = self.media.probe(&path)?;
+        let was_playing = self.is_playing();
+        self.stop_playback();
         let asset_id = self.allocate_asset_id();
         let segment_id = self.allocate_segment_id();
 
         let project = Project::from_single_asset(asset_id, segment_id, probed)?;
+        self.clipboard.clear();
+        self.project_path = None;
+        self.offered_recovery = None;
         let preview_bucket_tl = preview_bucket_tl_for_project(&project);
         self.preview_cache
             .reconfigure_bucket_size(preview_bucket_tl);
-        let snapshot = project.snapshot(preview_bucket_tl);
+        self.revision += 1;
+        let snapshot = self.snapshot_of(&project);
         self.playhead_tl = 0;
         self.project = Some(project);
         self.invalidate_preview_cache();
 
-        Ok(vec![
-            Event::ProjectChanged(snapshot),
-            Event::PlayheadChanged { t_tl: 0 },
-        ])
+        let mut events = Vec::with_capacity(3);
+        if was_playing {
+            events.push(Event::PlaybackStateChanged {
+                playing: false,
+                rate: 0,
+            });
+        }
+        events.push(Event::ProjectChanged(snapshot));
+        events.push(Event::PlayheadChanged { t_tl: 0 });
+        Ok(events)
     }
 
-    fn set_playhead(&mut self, t_tl: i64) -> Result<Vec<Event>> {
-        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
-        let clamped = normalize_playhead(t_tl, project.duration_tl());
-        self.playhead_tl = clamped;
+    fn add_asset(&mut self, path: PathBuf) -> Result<Vec<Event>> {
+        let probed = self.media.probe(&path)?;
+        let asset = MediaAsset::from_probed(self.allocate_asset_id(), probed);
+        let asset_id = asset.id;
+
+        let events = if self.project.is_some() {
+            let ((), delta) = self.edit_project(|project| {
+                project.add_asset(asset);
+                Ok(())
+            })?;
+            vec![Event::ProjectUpdated(delta)]
+        } else {
+            let mut project = Project::default();
+            project.add_asset(asset);
+            self.preview_cache
+                .reconfigure_bucket_size(preview_bucket_tl_for_project(&project));
+            self.project_path = None;
+            self.offered_recovery = None;
+            self.revision += 1;
+            self.playhead_tl = 0;
+            let snapshot = self.snapshot_of(&project);
+            self.project = Some(project);
+            vec![
+                Event::ProjectChanged(snapshot),
+                Event::PlayheadChanged { t_tl: 0 },
+            ]
+        };
 
-        let mut events = vec![Event::PlayheadChanged { t_tl: clamped }];
-        let request = match project.preview_request_at(clamped) {
-            Ok(request) => Some(request),
-            Err(EngineError::SegmentNotFound { .. }) => None,
+        info!(asset_id, path = %path.display(), "asset added to bin");
+        Ok(events)
+    }
+
+    fn open_project(&mut self, path: PathBuf) -> Result<Vec<Event>> {
+        let (project, migrations) = match Project::load_from_file_with_migrations(&path) {
+            Ok(loaded) => loaded,
+            Err(
+                error @ (EngineError::InvalidProjectFile { .. }
+                | EngineError::ProjectSerialization { .. }),
+            ) => return self.offer_backup(path, error),
             Err(error) => return Err(error),
         };
-        if let Some(request) = request {
-            let direction = self.scrub_direction(&request);
-            let (frame, cache_hit) =
-                self.decode_preview_frame_cached(&request.path, request.source_tl)?;
-            events.push(Event::PreviewFrameReady {
-                t_tl: clamped,
-                frame,
-            });
-            if cache_hit && direction == ScrubDirection::Unknown {
-                self.prefetch_neighbors(&request);
-            }
-            self.last_preview = Some(LastPreviewTarget {
-                path: request.path,
-                source_tl: request.source_tl,
-            });
+        for step in &migrations {
+            info!(
+                from_version = step.from_version,
+                to_version = step.to_version,
+                description = step.description,
+                "project file migrated"
+            );
         }
-
+        info!(path = %path.display(), "project opened");
+        let mut events = self.replace_project(project, Some(path));
+        self.saved_revision = self.revision;
+        events.extend(self.offer_recovery());
         Ok(events)
     }
 
-    fn split(&mut self, at_tl: i64) -> Result<Vec<Event>> {
-        let next_segment_id = self.next_segment_id;
-        {
-            let project = self.project.as_mut().ok_or(EngineError::ProjectNotLoaded)?;
-            project.split(at_tl, next_segment_id)?;
+    /// Installs a loaded project and resets per-project engine state.
+    fn replace_project(&mut self, project: Project, path: Option<PathBuf>) -> Vec<Event> {
+        let was_playing = self.is_playing();
+        self.stop_playback();
+        self.next_asset_id = project
+            .assets
+            .iter()
+            .map(|asset| asset.id)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        self.next_segment_id = project
+            .timeline
+            .segments
+            .iter()
+            .map(|segment| segment.id)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        for asset in project.assets.iter().filter(|asset| asset.offline) {
+            warn!(asset_id = asset.id, path = %asset.path.display(), "asset offline");
         }
-        let allocated_segment_id = self.allocate_segment_id();
-        debug_assert_eq!(
-            allocated_segment_id, next_segment_id,
-            "allocated segment id diverged from the split request id"
-        );
-        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
 
-        info!(
-            at_tl,
-            next_segment_id,
-            segment_count = project.timeline.segments.len(),
-            "split applied"
-        );
-        let snapshot = project.snapshot(self.preview_cache.bucket_size_tl());
+        self.clipboard.clear();
+        self.source_playhead = None;
+        self.offered_recovery = None;
+        self.project_path = path;
+        self.preview_cache
+            .reconfigure_bucket_size(preview_bucket_tl_for_project(&project));
+        self.revision += 1;
+        let snapshot = self.snapshot_of(&project);
+        self.playhead_tl = 0;
+        self.project = Some(project);
         self.invalidate_preview_cache();
 
-        Ok(vec![Event::ProjectChanged(snapshot)])
+        let mut events = Vec::with_capacity(3);
+        if was_playing {
+            events.push(Event::PlaybackStateChanged {
+                playing: false,
+                rate: 0,
+            });
+        }
+        events.push(Event::ProjectChanged(snapshot));
+        events.push(Event::PlayheadChanged { t_tl: 0 });
+        events
     }
 
-    fn cut(&mut self, at_tl: i64) -> Result<Vec<Event>> {
-        {
-            let project = self.project.as_mut().ok_or(EngineError::ProjectNotLoaded)?;
-            project.cut(at_tl)?;
-        }
+    fn save_project(&mut self, path: PathBuf, paths: MediaPathStyle) -> Result<Vec<Event>> {
         let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
-        self.playhead_tl = normalize_playhead(self.playhead_tl, project.duration_tl());
-
-        info!(
-            at_tl,
-            segment_count = project.timeline.segments.len(),
-            playhead_tl = self.playhead_tl,
-            "cut applied"
-        );
-        let snapshot = project.snapshot(self.preview_cache.bucket_size_tl());
-        self.invalidate_preview_cache();
+        project.save_to_file_with(&path, paths)?;
+        info!(path = %path.display(), ?paths, "project saved");
+
+        // The saved file supersedes recovery copies from before the save.
+        let stale_recovery = self
+            .autosave
+            .as_ref()
+            .and_then(|config| config.recovery_path(self.project_path.as_deref()));
+        self.project_path = Some(path.clone());
+        self.saved_revision = self.revision;
+        self.autosave_due = None;
+        self.offered_recovery = None;
+        for recovery in stale_recovery.into_iter().chain(self.recovery_path()) {
+            if let Err(error) = fs::remove_file(&recovery)
+                && error.kind() != std::io::ErrorKind::NotFound
+            {
+                warn!(%error, path = %recovery.display(), "stale recovery copy not removed");
+            }
+        }
+        Ok(vec![Event::ProjectSaved { path }])
+    }
 
-        Ok(vec![Event::ProjectChanged(snapshot)])
+    /// Recovery copy location for the current project.
+    fn recovery_path(&self) -> Option<PathBuf> {
+        self.autosave
+            .as_ref()?
+            .recovery_path(self.project_path.as_deref())
     }
 
-    fn move_segment(&mut self, segment_id: u64, new_start_tl: i64) -> Result<Vec<Event>> {
-        {
-            let project = self.project.as_mut().ok_or(EngineError::ProjectNotLoaded)?;
-            project.move_segment(segment_id, new_start_tl)?;
+    /// Offers the current project's recovery copy when it is newer than the
+    /// project file.
+    fn offer_recovery(&mut self) -> Option<Event> {
+        let recovery_path = self.recovery_path()?;
+        if !recovery_is_newer(&recovery_path, self.project_path.as_deref()) {
+            return None;
         }
-        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
-        self.playhead_tl = normalize_playhead(self.playhead_tl, project.duration_tl());
-        let snapshot = project.snapshot(self.preview_cache.bucket_size_tl());
-        self.invalidate_preview_cache();
-        Ok(vec![Event::ProjectChanged(snapshot)])
+        info!(path = %recovery_path.display(), "recovery copy available");
+        let offer = OfferedRecovery {
+            recovery_path,
+            project_path: self.project_path.clone(),
+            is_backup: false,
+        };
+        self.offered_recovery = Some(offer.clone());
+        Some(Event::RecoveryAvailable {
+            recovery_path: offer.recovery_path,
+            project_path: offer.project_path,
+        })
     }
 
-    fn trim_segment_start(&mut self, segment_id: u64, new_start_tl: i64) -> Result<Vec<Event>> {
-        {
-            let project = self.project.as_mut().ok_or(EngineError::ProjectNotLoaded)?;
-            project.trim_segment_start(segment_id, new_start_tl)?;
+    /// Offers the newest valid backup of the damaged project file `path`, or
+    /// fails with `error` when there is none.
+    fn offer_backup(&mut self, path: PathBuf, error: EngineError) -> Result<Vec<Event>> {
+        let Some(backup_path) = Project::newest_valid_backup(&path) else {
+            return Err(error);
+        };
+        warn!(%error, path = %path.display(), backup = %backup_path.display(), "project file damaged, backup available");
+        self.offered_recovery = Some(OfferedRecovery {
+            recovery_path: backup_path.clone(),
+            project_path: Some(path.clone()),
+            is_backup: true,
+        });
+        Ok(vec![Event::BackupAvailable {
+            backup_path,
+            project_path: path,
+            reason: error.to_string(),
+        }])
+    }
+
+    fn check_for_recovery(&mut self) -> Result<Vec<Event>> {
+        if self.project.is_some() {
+            return Ok(Vec::new());
         }
-        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
-        self.playhead_tl = normalize_playhead(self.playhead_tl, project.duration_tl());
-        let snapshot = project.snapshot(self.preview_cache.bucket_size_tl());
-        self.invalidate_preview_cache();
-        Ok(vec![Event::ProjectChanged(snapshot)])
+        Ok(self.offer_recovery().into_iter().collect())
     }
 
-    fn trim_segment_end(&mut self, segment_id: u64, new_end_tl: i64) -> Result<Vec<Event>> {
-        {
-            let project = self.project.as_mut().ok_or(EngineError::ProjectNotLoaded)?;
-            project.trim_segment_end(segment_id, new_end_tl)?;
+    fn restore_recovery(&mut self) -> Result<Vec<Event>> {
+        let offer = self
+            .offered_recovery
+            .take()
+            .ok_or(EngineError::RecoveryNotAvailable)?;
+        let project = match Project::load_from_file(&offer.recovery_path) {
+            Ok(project) => project,
+            Err(error) => {
+                self.offered_recovery = Some(offer);
+                return Err(error);
+            }
+        };
+        info!(path = %offer.recovery_path.display(), "recovery copy restored");
+        // Restored work is unsaved until the next SaveProject.
+        let events = self.replace_project(project, offer.project_path);
+        self.saved_revision = self.revision - 1;
+        Ok(events)
+    }
+
+    fn discard_recovery(&mut self) -> Result<Vec<Event>> {
+        let offer = self
+            .offered_recovery
+            .take()
+            .ok_or(EngineError::RecoveryNotAvailable)?;
+        if offer.is_backup {
+            return Ok(Vec::new());
         }
+        fs::remove_file(&offer.recovery_path).map_err(|source| EngineError::ProjectIo {
+            context: "remove recovery copy",
+            path: offer.recovery_path.clone(),
+            source,
+        })?;
+        info!(path = %offer.recovery_path.display(), "recovery copy discarded");
+        Ok(Vec::new())
+    }
+
+    fn relink_asset(&mut self, asset_id: u64, new_path: PathBuf) -> Result<Vec<Event>> {
+        let probed = self.media.probe(&new_path)?;
+        let candidate = MediaAsset::from_probed(asset_id, probed);
+        let ((), delta) = self.edit_project(|project| project.relink_asset(asset_id, candidate))?;
+        info!(asset_id, path = %new_path.display(), "asset relinked");
+        Ok(self.relinked_events(delta))
+    }
+
+    fn relink_from_directory(&mut self, dir: PathBuf) -> Result<Vec<Event>> {
         let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
-        self.playhead_tl = normalize_playhead(self.playhead_tl, project.duration_tl());
-        let snapshot = project.snapshot(self.preview_cache.bucket_size_tl());
+        let mut relinked = Vec::new();
+        for (asset_id, path) in project.find_relink_candidates(&dir)? {
+            match self.media.probe(&path) {
+                Ok(probed) => relinked.push(MediaAsset::from_probed(asset_id, probed)),
+                Err(error) => {
+                    warn!(asset_id, path = %path.display(), %error, "relink candidate skipped")
+                }
+            }
+        }
+
+        let (count, delta) = self.edit_project(|project| {
+            let mut count = 0;
+            for candidate in relinked {
+                let asset_id = candidate.id;
+                match project.relink_asset(asset_id, candidate) {
+                    Ok(()) => count += 1,
+                    Err(error) => warn!(asset_id, %error, "relink candidate skipped"),
+                }
+            }
+            Ok(count)
+        })?;
+        info!(dir = %dir.display(), count, "assets relinked from directory");
+        Ok(self.relinked_events(delta))
+    }
+
+    /// Drops frames decoded from the old paths and refreshes the preview.
+    fn relinked_events(&mut self, delta: ProjectDelta) -> Vec<Event> {
         self.invalidate_preview_cache();
-        Ok(vec![Event::ProjectChanged(snapshot)])
+        let mut events = vec![Event::ProjectUpdated(delta)];
+        match self.request_preview_at(self.playhead_tl) {
+            Ok(preview) => events.extend(preview),
+            Err(error) => debug!(%error, "preview after relink unavailable"),
+        }
+        events
     }
 
-    fn export(&mut self, path: PathBuf, _settings: ExportSettings) -> Result<Vec<Event>> {
+    fn set_playhead(&mut self, t_tl: i64) -> Result<Vec<Event>> {
         let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
-        let plan = build_video_export_plan(project, path.clone())?;
-        let total = plan.segments.len() as u64;
+        let clamped = normalize_playhead(t_tl, project.duration_tl());
+        self.playhead_tl = clamped;
 
-        self.media.export_video(&plan)?;
+        let mut events = vec![Event::PlayheadChanged { t_tl: clamped }];
+        events.extend(self.request_preview_at(clamped)?);
 
-        Ok(vec![
-            Event::ExportProgress { done: total, total },
-            Event::ExportFinished { path },
-        ])
+        if let Some(session) = self.playback {
+            self.restart_playback_at(clamped, session.rate)?;
+        } else if self.scrub_audio {
+            self.play_scrub_snippet(clamped);
+        }
+
+        Ok(events)
     }
 
-    fn decode_preview_frame_cached(
-        &mut self,
-        path: &Path,
-        source_tl: i64,
-    ) -> Result<(PreviewFrame, bool)> {
-        if let Some(frame) = self.preview_cache.get(path, source_tl) {
-            debug!(source_tl, path = ?path, "preview cache hit");
-            return Ok((frame, true));
+    /// Requests the preview frame at `t_tl`.
+    ///
+    /// A cached full frame is returned immediately. Otherwise a cached draft
+    /// is returned immediately (or a draft decode is queued) and the exact
+    /// frame is queued behind it; both are reported through
+    /// [`Engine::poll_events`]. While the playhead keeps moving, the queued
+    /// full decodes of earlier requests are dropped, so the exact frame only
+    /// lands once the playhead rests. Each full frame of the newest request
+    /// then queues the next neighbor, warming the cache around a resting
+    /// playhead until the next request.
+    fn request_preview_at(&mut self, t_tl: i64) -> Result<Option<Event>> {
+        self.preview_generation += 1;
+        let generation = self.preview_generation;
+        self.preview_workers.cancel_before(generation);
+
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        if let Some((width, height)) = project.disabled_frame_size_at(t_tl) {
+            debug!(t_tl, "disabled segment previews as black");
+            self.last_preview = None;
+            self.shown_preview = (generation, PreviewQuality::Full);
+            return Ok(Some(Event::PreviewFrameReady {
+                t_tl,
+                generation,
+                quality: PreviewQuality::Full,
+                frame: PreviewFrame::black(width, height),
+            }));
         }
+        let request = match project.preview_request_at(t_tl) {
+            Ok(request) => request,
+            Err(EngineError::SegmentNotFound { .. }) => return Ok(None),
+            Err(error) => return Err(error),
+        };
 
-        debug!(source_tl, path = ?path, "preview cache miss");
-        let frame = self
-            .media
-            .decode_preview_frame(path, timeline_ticks_to_seconds(source_tl))?;
-        self.preview_cache.insert(path, source_tl, frame.clone());
-        Ok((frame, false))
+        let direction = self.scrub_direction(&request);
+        let cached = self
+            .preview_cache
+            .get_best(&request.path, request.source_tl);
+        let quality = cached.as_ref().map(|(_, quality)| *quality);
+        match quality {
+            Some(PreviewQuality::Full) => {
+                debug!(source_tl = request.source_tl, path = ?request.path, "preview cache hit");
+                if direction == ScrubDirection::Unknown {
+                    self.prefetch_neighbors(t_tl, &request, generation);
+                }
+            }
+            Some(PreviewQuality::Draft) => {
+                debug!(source_tl = request.source_tl, path = ?request.path, "preview draft cache hit");
+                self.submit_playhead_job(generation, PreviewQuality::Full, t_tl, &request);
+            }
+            None => {
+                debug!(source_tl = request.source_tl, path = ?request.path, "preview cache miss");
+                self.submit_playhead_job(generation, PreviewQuality::Draft, t_tl, &request);
+                self.submit_playhead_job(generation, PreviewQuality::Full, t_tl, &request);
+            }
+        }
+        self.last_preview = Some(LastPreviewTarget {
+            path: request.path,
+            source_tl: request.source_tl,
+        });
+
+        Ok(cached.map(|(frame, quality)| {
+            self.shown_preview = (generation, quality);
+            Event::PreviewFrameReady {
+                t_tl,
+                generation,
+                quality,
+                frame,
+            }
+        }))
     }
 
-    fn scrub_direction(&self, request: &PreviewRequest) -> ScrubDirection {
-        let Some(previous) = self.last_preview.as_ref() else {
-            return ScrubDirection::Unknown;
+    /// Requests the source monitor frame of `asset_id` at `t_src`.
+    ///
+    /// Mirrors [`Engine::request_preview_at`] without prefetching: a cached
+    /// frame is returned immediately and missing tiers are queued.
+    fn set_source_playhead(&mut self, asset_id: u64, t_src: i64) -> Result<Vec<Event>> {
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        let asset = project.asset_by_id(asset_id)?;
+        let t_src = normalize_playhead(t_src, asset.duration_tl);
+        let request = asset
+            .video
+            .is_some()
+            .then(|| asset.preview_request_at(t_src))
+            .transpose()?;
+
+        self.source_playhead = Some((asset_id, t_src));
+        self.source_generation += 1;
+        let generation = self.source_generation;
+        self.preview_workers.cancel_source_before(generation);
+        let mut events = vec![Event::SourcePlayheadChanged { asset_id, t_src }];
+        let Some(request) = request else {
+            debug!(asset_id, "audio-only asset has no source frame");
+            return Ok(events);
         };
-        if previous.path != request.path {
-            return ScrubDirection::Unknown;
+
+        let cached = self
+            .preview_cache
+            .get_best(&request.path, request.source_tl);
+        let missing: &[PreviewQuality] = match cached.as_ref().map(|(_, quality)| *quality) {
+            Some(PreviewQuality::Full) => &[],
+            Some(PreviewQuality::Draft) => &[PreviewQuality::Full],
+            None => &[PreviewQuality::Draft, PreviewQuality::Full],
+        };
+        debug!(
+            asset_id,
+            t_src,
+            source_tl = request.source_tl,
+            ?missing,
+            "source preview requested"
+        );
+        for quality in missing {
+            self.preview_workers.submit(PreviewJob {
+                generation,
+                kind: PreviewJobKind::Source { asset_id },
+                quality: *quality,
+                command_id: self.current_command,
+                t_tl: t_src,
+                path: request.path.clone(),
+                source_tl: request.source_tl,
+            });
         }
-        if request.source_tl > previous.source_tl {
-            ScrubDirection::Forward
-        } else if request.source_tl < previous.source_tl {
-            ScrubDirection::Backward
-        } else {
-            ScrubDirection::Unknown
+
+        if let Some((frame, quality)) = cached {
+            self.shown_source_preview = (generation, quality);
+            events.push(Event::SourcePreviewFrameReady {
+                asset_id,
+                t_src,
+                generation,
+                quality,
+                frame,
+            });
         }
+        Ok(events)
     }
 
-    fn prefetch_neighbors(&mut self, request: &PreviewRequest) {
-        let mut decoded = 0usize;
-        for offset in prefetch_offsets() {
-            if decoded >= PREFETCH_MAX_DECODES_PER_REQUEST {
-                break;
-            }
-            let Some(delta) = self.preview_cache.bucket_size_tl().checked_mul(offset) else {
-                continue;
-            };
-            let Some(source_tl) = request.source_tl.checked_add(delta) else {
-                continue;
-            };
-            if source_tl < 0 || self.preview_cache.contains(&request.path, source_tl) {
-                continue;
-            }
+    fn submit_playhead_job(
+        &mut self,
+        generation: u64,
+        quality: PreviewQuality,
+        t_tl: i64,
+        request: &PreviewRequest,
+    ) {
+        self.preview_workers.submit(PreviewJob {
+            generation,
+            kind: PreviewJobKind::Playhead,
+            quality,
+            command_id: self.current_command,
+            t_tl,
+            path: request.path.clone(),
+            source_tl: request.source_tl,
+        });
+    }
 
-            match self
-                .media
-                .decode_preview_frame(&request.path, timeline_ticks_to_seconds(source_tl))
-            {
-                Ok(frame) => {
-                    self.preview_cache.insert(&request.path, source_tl, frame);
-                    decoded += 1;
-                }
-                Err(error) => {
-                    debug!(
-                        source_tl,
-                        path = ?request.path,
-                        %error,
-                        "prefetch decode failed"
-                    );
+    /// Caches a finished decode and turns it into an event when it is newer
+    /// than the frame currently shown.
+    fn accept_preview_result(&mut self, result: PreviewResult) -> Option<Event> {
+        let PreviewResult { job, frame } = result;
+        let current_generation = match job.kind {
+            PreviewJobKind::Source { .. } => self.source_generation,
+            PreviewJobKind::Playhead | PreviewJobKind::Prefetch => self.preview_generation,
+        };
+        let frame = match frame {
+            Ok(frame) => frame,
+            Err(error) => {
+                // A failed draft is not reported; the full decode still follows.
+                if job.kind != PreviewJobKind::Prefetch
+                    && job.quality == PreviewQuality::Full
+                    && job.generation == current_generation
+                {
+                    return Some(Event::Error(EngineErrorEvent::from_error(&error)));
                 }
+                debug!(
+                    source_tl = job.source_tl,
+                    path = ?job.path,
+                    generation = job.generation,
+                    quality = ?job.quality,
+                    %error,
+                    "background preview decode failed"
+                );
+                return None;
+            }
+        };
+
+        self.preview_cache
+            .insert(&job.path, job.source_tl, job.quality, frame.clone());
+        let rank = (job.generation, job.quality);
+        if let PreviewJobKind::Source { asset_id } = job.kind {
+            if rank <= self.shown_source_preview {
+                debug!(generation = job.generation, quality = ?job.quality, "stale source preview cached without event");
+                return None;
             }
+            self.shown_source_preview = rank;
+            return Some(Event::SourcePreviewFrameReady {
+                asset_id,
+                t_src: job.t_tl,
+                generation: job.generation,
+                quality: job.quality,
+                frame,
+            });
+        }
+        // Until a newer request bumps the generation the playhead rests, so
+        // keep warming its neighbors one decode at a time.
+        if job.quality == PreviewQuality::Full
+            && job.generation == self.preview_generation
+            && self.playback.is_none()
+        {
+            self.continue_prefetch(job.t_tl, job.generation);
+        }
+        if job.kind != PreviewJobKind::Playhead || rank <= self.shown_preview {
+            debug!(
+                generation = job.generation,
+                quality = ?job.quality,
+                shown = ?self.shown_preview,
+                "stale preview result cached without event"
+            );
+            return None;
         }
+
+        self.shown_preview = rank;
+        Some(Event::PreviewFrameReady {
+            t_tl: job.t_tl,
+            generation: job.generation,
+            quality: job.quality,
+            frame,
+        })
     }
 
-    fn invalidate_preview_cache(&mut self) {
-        self.preview_cache.clear();
-        self.last_preview = None;
+    fn shuttle(&mut self, rate: i32) -> Result<Vec<Event>> {
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        let duration_tl = project.duration_tl();
+        if self.playback.is_some_and(|session| session.rate == rate) {
+            return Ok(Vec::new());
+        }
+
+        let loop_range = self.loop_range();
+
+        let mut events = Vec::new();
+        if self.playback.is_some() {
+            events.extend(self.present_playback_clock()?);
+        } else if let Some((start_tl, end_tl)) = loop_range
+            && !(start_tl..end_tl).contains(&self.playhead_tl)
+        {
+            let loop_start_tl = if rate > 0 { start_tl } else { end_tl - 1 };
+            self.playhead_tl = loop_start_tl;
+            events.push(Event::PlayheadChanged {
+                t_tl: loop_start_tl,
+            });
+            events.extend(self.request_preview_at(loop_start_tl)?);
+        } else if rate > 0 && self.playhead_tl.saturating_add(1) >= duration_tl {
+            self.playhead_tl = 0;
+            events.push(Event::PlayheadChanged { t_tl: 0 });
+            events.extend(self.request_preview_at(0)?);
+        } else if rate < 0 && self.playhead_tl == 0 {
+            return Ok(events);
+        }
+
+        info!(start_tl = self.playhead_tl, rate, "playback started");
+        events.insert(
+            0,
+            Event::PlaybackStateChanged {
+                playing: true,
+                rate,
+            },
+        );
+        self.restart_playback_at(self.playhead_tl, rate)?;
+        events.extend(self.tick_playback()?);
+        Ok(events)
     }
 
-    fn allocate_asset_id(&mut self) -> u64 {
-        let id = self.next_asset_id;
-        self.next_asset_id += 1;
-        id
+    fn shuttle_forward(&mut self) -> Result<Vec<Event>> {
+        let rate = match self.playback {
+            Some(session) if session.rate > 0 => (session.rate * 2).min(MAX_SHUTTLE_RATE),
+            _ => 1,
+        };
+        self.shuttle(rate)
     }
 
-    fn allocate_segment_id(&mut self) -> u64 {
-        let id = self.next_segment_id;
-        self.next_segment_id += 1;
-        id
+    fn shuttle_reverse(&mut self) -> Result<Vec<Event>> {
+        let rate = match self.playback {
+            Some(session) if session.rate < 0 => (session.rate * 2).max(-MAX_SHUTTLE_RATE),
+            _ => -1,
+        };
+        self.shuttle(rate)
     }
-}
 
-fn prefetch_offsets() -> Vec<i64> {
-    let mut offsets = Vec::with_capacity((PREFETCH_RADIUS_IDLE * 2) as usize);
-    for step in 1..=PREFETCH_RADIUS_IDLE {
-        offsets.push(step);
-        offsets.push(-step);
+    fn pause(&mut self) -> Result<Vec<Event>> {
+        if self.playback.is_none() {
+            return Ok(Vec::new());
+        }
+        let mut events = self.present_playback_clock()?;
+        self.stop_playback();
+        info!(playhead_tl = self.playhead_tl, "playback paused");
+
+        events.insert(
+            0,
+            Event::PlaybackStateChanged {
+                playing: false,
+                rate: 0,
+            },
+        );
+        Ok(events)
     }
-    offsets
-}
 
-fn timeline_ticks_to_seconds(t_tl: i64) -> f64 {
-    t_tl as f64 / TIMELINE_TIME_BASE.den as f64
-}
+    fn step_frame(&mut self, delta: i64) -> Result<Vec<Event>> {
+        let mut events = self.pause()?;
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        let target_tl = project.step_frames(self.playhead_tl, delta);
+        debug!(delta, from_tl = self.playhead_tl, target_tl, "frame step");
+        events.extend(self.set_playhead(target_tl)?);
+        Ok(events)
+    }
 
-fn preview_bucket_tl_for_project(project: &Project) -> i64 {
-    let mut derived = None;
+    fn jump_to_edit_point(&mut self, direction: EditPointDirection) -> Result<Vec<Event>> {
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        let playhead_tl = normalize_playhead(self.playback_clock_tl(), project.duration_tl());
+        let Some(target_tl) = project.edit_point(playhead_tl, direction) else {
+            return Ok(Vec::new());
+        };
+        self.set_playhead(target_tl)
+    }
 
-    for asset in &project.assets {
-        let Some(video) = asset.video else {
-            continue;
+    /// Moves the playhead to the current playback clock and emits its frame.
+    fn present_playback_clock(&mut self) -> Result<Vec<Event>> {
+        self.present_clock(self.playback_clock_tl())
+    }
+
+    fn present_clock(&mut self, clock_tl: i64) -> Result<Vec<Event>> {
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        let clock_tl = normalize_playhead(clock_tl, project.duration_tl());
+        if clock_tl == self.playhead_tl {
+            return Ok(Vec::new());
+        }
+        self.playhead_tl = clock_tl;
+        let mut events = vec![Event::PlayheadChanged { t_tl: clock_tl }];
+        events.extend(self.request_preview_at(clock_tl)?);
+        Ok(events)
+    }
+
+    fn advance_playback(&mut self) -> Result<Vec<Event>> {
+        let Some(mut session) = self.playback else {
+            return Ok(Vec::new());
+        };
+        let duration_tl = self
+            .project
+            .as_ref()
+            .ok_or(EngineError::ProjectNotLoaded)?
+            .duration_tl();
+        let (range_start_tl, range_end_tl) = self.loop_range().unwrap_or((0, duration_tl));
+        let clock_tl = self.playback_clock_tl();
+
+        // Sink time needed to reach the range edge at the session rate.
+        let remaining_tl = if session.rate > 0 {
+            range_end_tl - session.start_tl
+        } else {
+            session.start_tl - range_start_tl
         };
+        let rate = i64::from(session.rate.unsigned_abs());
+        let sink_limit_tl = (remaining_tl.max(0) + rate - 1) / rate;
+        let fill_until_tl = self
+            .played_sink_tl(&session)
+            .saturating_add(PLAYBACK_AUDIO_LEAD_TL)
+            .min(sink_limit_tl);
+        while session.queued_sink_tl < fill_until_tl {
+            let chunk_end_tl = session
+                .queued_sink_tl
+                .saturating_add(PLAYBACK_AUDIO_CHUNK_TL)
+                .min(sink_limit_tl);
+            let chunk = if session.rate == 1 {
+                self.render_audio(
+                    session.start_tl + session.queued_sink_tl,
+                    session.start_tl + chunk_end_tl,
+                )?
+            } else {
+                // Shuttle speeds other than 1x stay silent; the silence still
+                // drives the clock at real-time pace.
+                let frames = self.audio_format.frames_at_tl(chunk_end_tl)
+                    - self.audio_format.frames_at_tl(session.queued_sink_tl);
+                PcmChunk::silence(self.audio_format, frames.max(0) as usize)
+            };
+            self.audio_sink.write(&chunk)?;
+            session.queued_sink_tl = chunk_end_tl;
+        }
+        self.playback = Some(session);
 
-        let bucket_tl = if let Some(frame_rate) = video.frame_rate {
-            frame_duration_tl_from_frame_rate(frame_rate)
+        let reached_edge = if session.rate > 0 {
+            clock_tl >= range_end_tl
         } else {
-            rescale(1, video.time_base, TIMELINE_TIME_BASE).abs().max(1)
+            clock_tl <= range_start_tl
         };
+        let looping = self
+            .project
+            .as_ref()
+            .is_some_and(|project| project.loop_playback);
+        if reached_edge && looping {
+            // Wrap without presenting the clock past the loop edge.
+            let wrap_tl = if session.rate > 0 {
+                range_start_tl
+            } else {
+                range_end_tl - 1
+            };
+            debug!(from_tl = clock_tl, wrap_tl, "playback looped");
+            self.restart_playback_at(wrap_tl, session.rate)?;
+            return self.present_clock(wrap_tl);
+        }
 
-        derived = Some(derived.map_or(bucket_tl, |current: i64| current.min(bucket_tl)));
+        let mut events = self.present_clock(clock_tl)?;
+        if reached_edge {
+            info!(
+                playhead_tl = self.playhead_tl,
+                "playback reached timeline edge"
+            );
+            self.stop_playback();
+            events.push(Event::PlaybackStateChanged {
+                playing: false,
+                rate: 0,
+            });
+        }
+        Ok(events)
     }
 
-    derived.unwrap_or(DEFAULT_PREVIEW_CACHE_BUCKET_TL)
-}
+    /// Returns the range playback wraps within while looping: the in/out
+    /// range, or the whole timeline when no mark is set.
+    fn loop_range(&self) -> Option<(i64, i64)> {
+        let project = self
+            .project
+            .as_ref()
+            .filter(|project| project.loop_playback)?;
+        project
+            .in_out_range()
+            .or_else(|| (project.duration_tl() > 0).then(|| (0, project.duration_tl())))
+    }
 
-fn frame_duration_tl_from_frame_rate(frame_rate: crate::time::Rational) -> i64 {
-    let numerator = i128::from(TIMELINE_TIME_BASE.den) * i128::from(frame_rate.den);
-    let denominator = i128::from(frame_rate.num.max(1));
-    let rounded = (numerator + denominator / 2) / denominator;
-    rounded.max(1).min(i128::from(i64::MAX)) as i64
-}
+    fn played_sink_tl(&self, session: &PlaybackSession) -> i64 {
+        let played_frames = i64::try_from(self.audio_sink.played_frames()).unwrap_or(i64::MAX);
+        self.audio_format
+            .frames_to_tl(played_frames)
+            .min(session.queued_sink_tl)
+    }
 
-impl Engine<FfmpegMediaBackend> {
-    /// Creates an engine wired to the FFmpeg backend.
-    pub fn with_ffmpeg() -> Self {
-        Self::new(FfmpegMediaBackend)
+    fn playback_clock_tl(&self) -> i64 {
+        let Some(session) = self.playback else {
+            return self.playhead_tl;
+        };
+        session.start_tl.saturating_add(
+            self.played_sink_tl(&session)
+                .saturating_mul(i64::from(session.rate)),
+        )
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::path::{Path, PathBuf};
-    use std::sync::{Arc, Mutex};
+    fn restart_playback_at(&mut self, t_tl: i64, rate: i32) -> Result<()> {
+        self.audio_sink.reset()?;
+        self.playback = Some(PlaybackSession {
+            start_tl: t_tl,
+            rate,
+            queued_sink_tl: 0,
+        });
+        Ok(())
+    }
+
+    fn stop_playback(&mut self) {
+        if self.playback.take().is_none() {
+            return;
+        }
+        if let Err(error) = self.audio_sink.reset() {
+            warn!(%error, "failed to reset audio sink");
+        }
+    }
+
+    fn play_scrub_snippet(&mut self, t_tl: i64) {
+        let result = self
+            .audio_sink
+            .reset()
+            .and_then(|()| self.render_audio(t_tl, t_tl.saturating_add(SCRUB_AUDIO_SNIPPET_TL)))
+            .and_then(|chunk| self.audio_sink.write(&chunk));
+        if let Err(error) = result {
+            debug!(t_tl, %error, "scrub audio failed");
+        }
+    }
+
+    fn render_audio(&self, start_tl: i64, end_tl: i64) -> Result<PcmChunk> {
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        let format = self.audio_format;
+        let mut chunk = PcmChunk::silence(format, 0);
+        for span in audio_spans(project, start_tl, end_tl, format)? {
+            let Some((path, at_seconds)) = span.source else {
+                chunk.append(&PcmChunk::silence(format, span.frames))?;
+                continue;
+            };
+            let duration_seconds = span.frames as f64 / f64::from(format.sample_rate);
+            let mut decoded =
+                self.media
+                    .decode_audio(&path, at_seconds, duration_seconds, format)?;
+            decoded.resize_frames(span.frames);
+            chunk.append(&decoded)?;
+        }
+        Ok(chunk)
+    }
+
+    fn update_marks(&mut self, update: impl FnOnce(&mut Project)) -> Result<Vec<Event>> {
+        let ((), delta) = self.edit_project(|project| {
+            update(project);
+            debug!(marks = ?project.marks, "in/out marks updated");
+            Ok(())
+        })?;
+        Ok(vec![Event::ProjectUpdated(delta)])
+    }
+
+    fn update_selection(
+        &mut self,
+        update: impl FnOnce(&mut Project) -> Result<()>,
+    ) -> Result<Vec<Event>> {
+        let ((), delta) = self.edit_project(update)?;
+        debug!(selection = ?delta.selection, "selection updated");
+        Ok(vec![Event::ProjectUpdated(delta)])
+    }
+
+    /// Applies an edit to one or more segments, invalidates the preview cache
+    /// and refreshes the playhead.
+    fn edit_segments(
+        &mut self,
+        edit: impl FnOnce(&mut Project) -> Result<()>,
+    ) -> Result<Vec<Event>> {
+        let ((), delta) = self.edit_project(edit)?;
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        let playhead_tl = normalize_playhead(self.playhead_tl, project.duration_tl());
+        info!(
+            added = delta.segments_added.len(),
+            updated = delta.segments_updated.len(),
+            removed = delta.segments_removed.len(),
+            "segments edited"
+        );
+        self.invalidate_preview_cache();
+
+        let mut events = vec![Event::ProjectUpdated(delta)];
+        if playhead_tl != self.playhead_tl {
+            self.playhead_tl = playhead_tl;
+            events.push(Event::PlayheadChanged { t_tl: playhead_tl });
+        }
+        Ok(events)
+    }
+
+    fn set_segment_enabled(&mut self, segment_id: u64, enabled: bool) -> Result<Vec<Event>> {
+        let events =
+            self.edit_segments(|project| project.set_segment_enabled(segment_id, enabled))?;
+        info!(segment_id, enabled, "segment enabled state set");
+        Ok(events)
+    }
+
+    fn copy_selection(&mut self) -> Result<Vec<Event>> {
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        self.clipboard = project.copy_selection()?;
+        info!(segment_count = self.clipboard.len(), "selection copied");
+        Ok(Vec::new())
+    }
+
+    fn paste(&mut self, mode: PasteMode) -> Result<Vec<Event>> {
+        if self.clipboard.is_empty() {
+            return Err(EngineError::ClipboardEmpty);
+        }
+        let at_tl = self.playhead_tl;
+        let clipboard = self.clipboard.clone();
+        let mut next_segment_id = self.next_segment_id;
+        let (end_tl, delta) = self.edit_project(|project| {
+            project.paste_segments(at_tl, &clipboard, mode, || {
+                let id = next_segment_id;
+                next_segment_id += 1;
+                id
+            })
+        })?;
+        self.next_segment_id = next_segment_id;
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        self.playhead_tl = normalize_playhead(end_tl, project.duration_tl());
+
+        info!(
+            at_tl,
+            ?mode,
+            segment_count = project.timeline.segments.len(),
+            playhead_tl = self.playhead_tl,
+            "clipboard pasted"
+        );
+        self.invalidate_preview_cache();
+        Ok(vec![
+            Event::ProjectUpdated(delta),
+            Event::PlayheadChanged {
+                t_tl: self.playhead_tl,
+            },
+        ])
+    }
+
+    fn duplicate_segment(&mut self, segment_id: u64) -> Result<Vec<Event>> {
+        let new_segment_id = self.next_segment_id;
+        let ((), delta) =
+            self.edit_project(|project| project.duplicate_segment(segment_id, new_segment_id))?;
+        let allocated_segment_id = self.allocate_segment_id();
+        debug_assert_eq!(
+            allocated_segment_id, new_segment_id,
+            "allocated segment id diverged from the duplicate id"
+        );
+
+        info!(segment_id, new_segment_id, "segment duplicated");
+        self.invalidate_preview_cache();
+        Ok(vec![Event::ProjectUpdated(delta)])
+    }
+
+    fn update_source_marks(
+        &mut self,
+        asset_id: u64,
+        update: impl FnOnce(&mut Project) -> Result<()>,
+    ) -> Result<Vec<Event>> {
+        let ((), delta) = self.edit_project(update)?;
+        debug!(asset_id, assets = ?delta.assets_updated, "source marks updated");
+        Ok(vec![Event::ProjectUpdated(delta)])
+    }
+
+    /// Edits a bin asset into the timeline and moves the playhead to its end.
+    fn edit_from_asset(
+        &mut self,
+        asset_id: u64,
+        at_tl: i64,
+        mode: PasteMode,
+    ) -> Result<Vec<Event>> {
+        let mut next_segment_id = self.next_segment_id;
+        let (end_tl, delta) = self.edit_project(|project| {
+            project.edit_from_asset(asset_id, at_tl, mode, || {
+                let id = next_segment_id;
+                next_segment_id += 1;
+                id
+            })
+        })?;
+        self.next_segment_id = next_segment_id;
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        self.playhead_tl = normalize_playhead(end_tl, project.duration_tl());
+
+        info!(
+            asset_id,
+            at_tl,
+            ?mode,
+            playhead_tl = self.playhead_tl,
+            "asset edited into timeline"
+        );
+        self.invalidate_preview_cache();
+        Ok(vec![
+            Event::ProjectUpdated(delta),
+            Event::PlayheadChanged {
+                t_tl: self.playhead_tl,
+            },
+        ])
+    }
+
+    fn replace_segment_media(
+        &mut self,
+        segment_id: u64,
+        asset_id: u64,
+        align: ReplaceAlignment,
+    ) -> Result<Vec<Event>> {
+        let playhead_tl = self.playhead_tl;
+        let source_playhead = self.source_playhead;
+        let events = self.edit_segments(|project| {
+            let source_t_src = match source_playhead {
+                Some((shown_asset_id, t_src)) if shown_asset_id == asset_id => t_src,
+                _ => project
+                    .asset_by_id(asset_id)?
+                    .source_marks
+                    .in_tl
+                    .unwrap_or(0),
+            };
+            project.replace_segment_media(segment_id, asset_id, align, playhead_tl, source_t_src)
+        })?;
+        info!(segment_id, asset_id, ?align, "segment media replaced");
+        Ok(events)
+    }
+
+    /// Toggles looping; a session setting, so the revision stays unchanged.
+    fn set_loop_playback(&mut self, enabled: bool) -> Result<Vec<Event>> {
+        let project = self.project.as_mut().ok_or(EngineError::ProjectNotLoaded)?;
+        project.loop_playback = enabled;
+        info!(enabled, "loop playback toggled");
+        Ok(vec![Event::LoopPlaybackChanged { enabled }])
+    }
+
+    fn set_snap_to_frames(&mut self, enabled: bool) -> Result<Vec<Event>> {
+        let ((), delta) = self.edit_project(|project| {
+            project.settings.snap_to_frames = enabled;
+            Ok(())
+        })?;
+        info!(enabled, "frame snapping toggled");
+        Ok(vec![Event::ProjectUpdated(delta)])
+    }
+
+    fn set_project_frame_rate(&mut self, frame_rate: Option<Rational>) -> Result<Vec<Event>> {
+        let ((), delta) = self.edit_project(|project| {
+            project.settings.frame_rate = frame_rate;
+            Ok(())
+        })?;
+        info!(?frame_rate, "project frame rate set");
+        Ok(vec![Event::ProjectUpdated(delta)])
+    }
+
+    fn delete_in_out_range(&mut self, ripple: bool) -> Result<Vec<Event>> {
+        let mut next_segment_id = self.next_segment_id;
+        let ((start_tl, end_tl), delta) = self.edit_project(|project| {
+            let (start_tl, end_tl) = project
+                .in_out_range()
+                .ok_or(EngineError::InOutRangeNotSet)?;
+            project.delete_range(start_tl, end_tl, ripple, || {
+                let id = next_segment_id;
+                next_segment_id += 1;
+                id
+            })?;
+            Ok((start_tl, end_tl))
+        })?;
+        self.next_segment_id = next_segment_id;
+        if ripple && self.playhead_tl >= start_tl {
+            self.playhead_tl = if self.playhead_tl >= end_tl {
+                self.playhead_tl - (end_tl - start_tl)
+            } else {
+                start_tl
+            };
+        }
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        self.playhead_tl = normalize_playhead(self.playhead_tl, project.duration_tl());
+
+        info!(
+            start_tl,
+            end_tl,
+            ripple,
+            segment_count = project.timeline.segments.len(),
+            playhead_tl = self.playhead_tl,
+            "in/out range deleted"
+        );
+        self.invalidate_preview_cache();
+        Ok(vec![
+            Event::ProjectUpdated(delta),
+            Event::PlayheadChanged {
+                t_tl: self.playhead_tl,
+            },
+        ])
+    }
+
+    fn split(&mut self, at_tl: i64) -> Result<Vec<Event>> {
+        let next_segment_id = self.next_segment_id;
+        let ((), delta) = self.edit_project(|project| project.split(at_tl, next_segment_id))?;
+        let allocated_segment_id = self.allocate_segment_id();
+        debug_assert_eq!(
+            allocated_segment_id, next_segment_id,
+            "allocated segment id diverged from the split request id"
+        );
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+
+        info!(
+            at_tl,
+            next_segment_id,
+            segment_count = project.timeline.segments.len(),
+            "split applied"
+        );
+        self.invalidate_preview_cache();
+
+        Ok(vec![Event::ProjectUpdated(delta)])
+    }
+
+    fn cut(&mut self, at_tl: i64) -> Result<Vec<Event>> {
+        let ((), delta) = self.edit_project(|project| project.cut(at_tl))?;
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        self.playhead_tl = normalize_playhead(self.playhead_tl, project.duration_tl());
+
+        info!(
+            at_tl,
+            segment_count = project.timeline.segments.len(),
+            playhead_tl = self.playhead_tl,
+            "cut applied"
+        );
+        self.invalidate_preview_cache();
+
+        Ok(vec![Event::ProjectUpdated(delta)])
+    }
+
+    fn move_segment(&mut self, segment_id: u64, new_start_tl: i64) -> Result<Vec<Event>> {
+        let ((), delta) =
+            self.edit_project(|project| project.move_segment(segment_id, new_start_tl))?;
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        self.playhead_tl = normalize_playhead(self.playhead_tl, project.duration_tl());
+        self.invalidate_preview_cache();
+        Ok(vec![Event::ProjectUpdated(delta)])
+    }
+
+    fn trim_segment_start(&mut self, segment_id: u64, new_start_tl: i64) -> Result<Vec<Event>> {
+        let ((), delta) =
+            self.edit_project(|project| project.trim_segment_start(segment_id, new_start_tl))?;
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        self.playhead_tl = normalize_playhead(self.playhead_tl, project.duration_tl());
+        self.invalidate_preview_cache();
+        Ok(vec![Event::ProjectUpdated(delta)])
+    }
+
+    fn trim_segment_end(&mut self, segment_id: u64, new_end_tl: i64) -> Result<Vec<Event>> {
+        let ((), delta) =
+            self.edit_project(|project| project.trim_segment_end(segment_id, new_end_tl))?;
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        self.playhead_tl = normalize_playhead(self.playhead_tl, project.duration_tl());
+        self.invalidate_preview_cache();
+        Ok(vec![Event::ProjectUpdated(delta)])
+    }
+
+    fn export(&mut self, path: PathBuf, settings: ExportSettings) -> Result<Vec<Event>> {
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        let plan = match settings.range {
+            ExportRange::Timeline => build_video_export_plan(project, path.clone())?,
+            ExportRange::InOut => {
+                let (start_tl, end_tl) = project
+                    .in_out_range()
+                    .ok_or(EngineError::InOutRangeNotSet)?;
+                info!(start_tl, end_tl, "exporting in/out range");
+                build_video_export_plan(&project.clipped_to_range(start_tl, end_tl)?, path.clone())?
+            }
+        };
+        let total = plan.segments.len() as u64;
+
+        self.media.export_video(&plan)?;
+
+        Ok(vec![
+            Event::ExportProgress { done: total, total },
+            Event::ExportFinished { path },
+        ])
+    }
+
+    fn export_edl(&mut self, path: PathBuf) -> Result<Vec<Event>> {
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        let title = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let text = edl::export_edl(project, &title)?;
+        write_file_atomically(&path, text.as_bytes(), 0)?;
+        info!(path = %path.display(), "EDL exported");
+        Ok(vec![Event::ExportFinished { path }])
+    }
+
+    fn import_edl(&mut self, path: PathBuf) -> Result<Vec<Event>> {
+        let text = fs::read_to_string(&path).map_err(|source| EngineError::ProjectIo {
+            context: "read EDL",
+            path: path.clone(),
+            source,
+        })?;
+        let mut next_segment_id = self.next_segment_id;
+        let ((), delta) = self.edit_project(|project| {
+            let segments = edl::import_edl(project, &text, || {
+                let id = next_segment_id;
+                next_segment_id += 1;
+                id
+            })?;
+            project.replace_segments(segments);
+            project.prune_selection();
+            Ok(())
+        })?;
+        self.next_segment_id = next_segment_id;
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        self.playhead_tl = normalize_playhead(self.playhead_tl, project.duration_tl());
+
+        info!(
+            path = %path.display(),
+            segment_count = project.timeline.segments.len(),
+            "EDL imported"
+        );
+        self.invalidate_preview_cache();
+        Ok(vec![
+            Event::ProjectUpdated(delta),
+            Event::PlayheadChanged {
+                t_tl: self.playhead_tl,
+            },
+        ])
+    }
+
+    fn export_otio(&mut self, path: PathBuf) -> Result<Vec<Event>> {
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let text = otio::export_otio(project, &name)?;
+        write_file_atomically(&path, text.as_bytes(), 0)?;
+        info!(path = %path.display(), "OTIO exported");
+        Ok(vec![Event::ExportFinished { path }])
+    }
+
+    fn export_fcp_xml(&mut self, path: PathBuf) -> Result<Vec<Event>> {
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let text = fcp_xml::export_fcp_xml(project, &name)?;
+        write_file_atomically(&path, text.as_bytes(), 0)?;
+        info!(path = %path.display(), "FCP XML exported");
+        Ok(vec![Event::ExportFinished { path }])
+    }
+
+    fn import_otio(&mut self, path: PathBuf) -> Result<Vec<Event>> {
+        let text = fs::read_to_string(&path).map_err(|source| EngineError::ProjectIo {
+            context: "read OTIO",
+            path: path.clone(),
+            source,
+        })?;
+        // Stage on a copy so a failed probe or import leaves the project and
+        // id counters untouched.
+        let mut staged = self.project.clone().unwrap_or_default();
+        let mut next_asset_id = self.next_asset_id;
+        for media_path in otio::missing_media(&staged, &text)? {
+            let probed = self.media.probe(&media_path)?;
+            info!(asset_id = next_asset_id, path = %media_path.display(), "OTIO media added to bin");
+            staged.add_asset(MediaAsset::from_probed(next_asset_id, probed));
+            next_asset_id += 1;
+        }
+        let mut next_segment_id = self.next_segment_id;
+        let segments = otio::import_otio(&staged, &text, || {
+            let id = next_segment_id;
+            next_segment_id += 1;
+            id
+        })?;
+        staged.replace_segments(segments);
+        info!(
+            path = %path.display(),
+            segment_count = staged.timeline.segments.len(),
+            "OTIO imported"
+        );
+
+        if self.project.is_none() {
+            return Ok(self.replace_project(staged, None));
+        }
+        let ((), delta) = self.edit_project(|project| {
+            *project = staged;
+            Ok(())
+        })?;
+        self.next_asset_id = next_asset_id;
+        self.next_segment_id = next_segment_id;
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        self.playhead_tl = normalize_playhead(self.playhead_tl, project.duration_tl());
+        self.invalidate_preview_cache();
+        Ok(vec![
+            Event::ProjectUpdated(delta),
+            Event::PlayheadChanged {
+                t_tl: self.playhead_tl,
+            },
+        ])
+    }
+
+    fn import_cut_list(&mut self, path: PathBuf) -> Result<Vec<Event>> {
+        let text = fs::read_to_string(&path).map_err(|source| EngineError::ProjectIo {
+            context: "read cut list",
+            path: path.clone(),
+            source,
+        })?;
+        let base_dir = path.parent().unwrap_or(Path::new(""));
+        let mut entries = cut_list::parse_cut_list(&text)?;
+        for entry in &mut entries {
+            entry.path = base_dir.join(&entry.path);
+            if let Some(label) = &entry.label {
+                debug!(line = entry.line, path = %entry.path.display(), label, "cut list entry");
+            }
+        }
+
+        // Stage on a copy so a failed probe or import leaves the project and
+        // id counters untouched.
+        let mut staged = self.project.clone().unwrap_or_default();
+        let mut next_asset_id = self.next_asset_id;
+        let mut errors = Vec::new();
+        let mut unprobed_lines = Vec::new();
+        for (media_path, lines) in cut_list::missing_media(&staged, &entries) {
+            match self.media.probe(&media_path) {
+                Ok(probed) => {
+                    info!(asset_id = next_asset_id, path = %media_path.display(), "cut list media added to bin");
+                    staged.add_asset(MediaAsset::from_probed(next_asset_id, probed));
+                    next_asset_id += 1;
+                }
+                Err(error) => {
+                    warn!(path = %media_path.display(), error = %error, "cut list media probe failed");
+                    errors.extend(lines.iter().map(|&line| cut_list::CutListRowError {
+                        line,
+                        reason: format!("cannot probe {}: {error}", media_path.display()),
+                    }));
+                    unprobed_lines.extend(lines);
+                }
+            }
+        }
+        entries.retain(|entry| !unprobed_lines.contains(&entry.line));
+        let mut next_segment_id = self.next_segment_id;
+        match cut_list::build_segments(&staged, &entries, || {
+            let id = next_segment_id;
+            next_segment_id += 1;
+            id
+        }) {
+            Ok(segments) if errors.is_empty() => staged.replace_segments(segments),
+            Ok(_) => {}
+            Err(build_errors) => errors.extend(build_errors),
+        }
+        if !errors.is_empty() {
+            errors.sort_by_key(|error| error.line);
+            return Err(EngineError::InvalidCutList { errors });
+        }
+        staged.prune_selection();
+        info!(
+            path = %path.display(),
+            segment_count = staged.timeline.segments.len(),
+            "cut list imported"
+        );
+
+        if self.project.is_none() {
+            return Ok(self.replace_project(staged, None));
+        }
+        let ((), delta) = self.edit_project(|project| {
+            *project = staged;
+            Ok(())
+        })?;
+        self.next_asset_id = next_asset_id;
+        self.next_segment_id = next_segment_id;
+        let project = self.project.as_ref().ok_or(EngineError::ProjectNotLoaded)?;
+        self.playhead_tl = normalize_playhead(self.playhead_tl, project.duration_tl());
+        self.invalidate_preview_cache();
+        Ok(vec![
+            Event::ProjectUpdated(delta),
+            Event::PlayheadChanged {
+                t_tl: self.playhead_tl,
+            },
+        ])
+    }
+
+    fn scrub_direction(&self, request: &PreviewRequest) -> ScrubDirection {
+        let Some(previous) = self.last_preview.as_ref() else {
+            return ScrubDirection::Unknown;
+        };
+        if previous.path != request.path {
+            return ScrubDirection::Unknown;
+        }
+        if request.source_tl > previous.source_tl {
+            ScrubDirection::Forward
+        } else if request.source_tl < previous.source_tl {
+            ScrubDirection::Backward
+        } else {
+            ScrubDirection::Unknown
+        }
+    }
+
+    /// Queues the next uncached neighbor of the last preview target.
+    fn continue_prefetch(&mut self, t_tl: i64, generation: u64) {
+        let Some(target) = self.last_preview.as_ref() else {
+            return;
+        };
+        let request = PreviewRequest {
+            path: target.path.clone(),
+            source_tl: target.source_tl,
+        };
+        self.prefetch_neighbors(t_tl, &request, generation);
+    }
+
+    fn prefetch_neighbors(&mut self, t_tl: i64, request: &PreviewRequest, generation: u64) {
+        let mut queued = 0usize;
+        for offset in prefetch_offsets() {
+            if queued >= PREFETCH_MAX_DECODES_PER_REQUEST {
+                break;
+            }
+            let Some(delta) = self.preview_cache.bucket_size_tl().checked_mul(offset) else {
+                continue;
+            };
+            let Some(source_tl) = request.source_tl.checked_add(delta) else {
+                continue;
+            };
+            if source_tl < 0
+                || self
+                    .preview_cache
+                    .contains(&request.path, source_tl, PreviewQuality::Full)
+            {
+                continue;
+            }
+
+            self.preview_workers.submit(PreviewJob {
+                generation,
+                kind: PreviewJobKind::Prefetch,
+                quality: PreviewQuality::Full,
+                command_id: self.current_command,
+                t_tl,
+                path: request.path.clone(),
+                source_tl,
+            });
+            queued += 1;
+        }
+    }
+
+    fn snapshot_of(&self, project: &Project) -> ProjectSnapshot {
+        let mut snapshot = project.snapshot(self.preview_cache.bucket_size_tl());
+        snapshot.revision = self.revision;
+        snapshot
+    }
+
+    /// Runs `edit` on the loaded project and describes its effect as a delta.
+    ///
+    /// The revision is bumped only when `edit` succeeds.
+    fn edit_project<T>(
+        &mut self,
+        edit: impl FnOnce(&mut Project) -> Result<T>,
+    ) -> Result<(T, ProjectDelta)> {
+        let project = self.project.as_mut().ok_or(EngineError::ProjectNotLoaded)?;
+        let checkpoint = project.checkpoint();
+        let value = edit(project)?;
+        let delta = self.publish_delta(checkpoint, self.revision)?;
+        Ok((value, delta))
+    }
+
+    /// Describes the changes since `checkpoint` as revision `base_revision + 1`.
+    fn publish_delta(
+        &mut self,
+        checkpoint: ProjectCheckpoint,
+        base_revision: u64,
+    ) -> Result<ProjectDelta> {
+        let project = self.project.as_mut().ok_or(EngineError::ProjectNotLoaded)?;
+        project.prune_selection();
+        let preview_bucket_tl = preview_bucket_tl_for_project(project);
+        self.preview_cache
+            .reconfigure_bucket_size(preview_bucket_tl);
+        let mut delta = project.delta_since(&checkpoint, preview_bucket_tl);
+        self.revision = base_revision + 1;
+        delta.base_revision = base_revision;
+        delta.revision = self.revision;
+        debug!(
+            revision = self.revision,
+            added = delta.segments_added.len(),
+            updated = delta.segments_updated.len(),
+            removed = delta.segments_removed.len(),
+            "project edited"
+        );
+        Ok(delta)
+    }
+
+    fn invalidate_preview_cache(&mut self) {
+        self.preview_cache.clear();
+        self.last_preview = None;
+        // Frames decoded for the old timeline must not be shown after an edit.
+        self.preview_generation += 1;
+        self.shown_preview = (self.preview_generation, PreviewQuality::Full);
+        self.preview_workers.cancel_before(self.preview_generation);
+    }
+
+    fn allocate_asset_id(&mut self) -> u64 {
+        let id = self.next_asset_id;
+        self.next_asset_id += 1;
+        id
+    }
+
+    fn allocate_segment_id(&mut self) -> u64 {
+        let id = self.next_segment_id;
+        self.next_segment_id += 1;
+        id
+    }
+}
+
+fn is_timeline_edit(command: &Command) -> bool {
+    matches!(
+        command,
+        Command::Split { .. }
+            | Command::Cut { .. }
+            | Command::MoveSegment { .. }
+            | Command::TrimSegmentStart { .. }
+            | Command::TrimSegmentEnd { .. }
+            | Command::DeleteInOutRange { .. }
+            | Command::DeleteSelection { .. }
+            | Command::MoveSelection { .. }
+            | Command::SetSelectionEnabled { .. }
+            | Command::SetSegmentEnabled { .. }
+            | Command::Paste { .. }
+            | Command::DuplicateSegment { .. }
+            | Command::InsertFromAsset { .. }
+            | Command::OverwriteFromAsset { .. }
+            | Command::ReplaceSegmentMedia { .. }
+            | Command::RelinkAsset { .. }
+            | Command::RelinkFromDirectory { .. }
+            | Command::ImportEdl { .. }
+            | Command::ImportOtio { .. }
+            | Command::ImportCutList { .. }
+            | Command::Batch(_)
+    )
+}
+
+/// Returns whether `command` only edits the project and can be rolled back.
+fn is_batchable(command: &Command) -> bool {
+    matches!(
+        command,
+        Command::Split { .. }
+            | Command::Cut { .. }
+            | Command::MoveSegment { .. }
+            | Command::TrimSegmentStart { .. }
+            | Command::TrimSegmentEnd { .. }
+            | Command::SetInPoint { .. }
+            | Command::SetOutPoint { .. }
+            | Command::ClearInOut
+            | Command::SetLoopPlayback { .. }
+            | Command::SetSnapToFrames { .. }
+            | Command::SetProjectFrameRate { .. }
+            | Command::DeleteInOutRange { .. }
+            | Command::Select { .. }
+            | Command::AddToSelection { .. }
+            | Command::ToggleSelection { .. }
+            | Command::SelectRange { .. }
+            | Command::SelectAll
+            | Command::ClearSelection
+            | Command::DeleteSelection { .. }
+            | Command::MoveSelection { .. }
+            | Command::SetSelectionEnabled { .. }
+            | Command::SetSegmentEnabled { .. }
+            | Command::Paste { .. }
+            | Command::DuplicateSegment { .. }
+            | Command::SetSourceInPoint { .. }
+            | Command::SetSourceOutPoint { .. }
+            | Command::ClearSourceInOut { .. }
+            | Command::InsertFromAsset { .. }
+            | Command::OverwriteFromAsset { .. }
+            | Command::ReplaceSegmentMedia { .. }
+            | Command::Batch(_)
+    )
+}
+
+fn prefetch_offsets() -> Vec<i64> {
+    let mut offsets = Vec::with_capacity((PREFETCH_RADIUS_IDLE * 2) as usize);
+    for step in 1..=PREFETCH_RADIUS_IDLE {
+        offsets.push(step);
+        offsets.push(-step);
+    }
+    offsets
+}
+
+fn preview_bucket_tl_for_project(project: &Project) -> i64 {
+    let mut derived = None;
+
+    for asset in &project.assets {
+        let Some(video) = asset.video else {
+            continue;
+        };
+
+        let bucket_tl = if let Some(frame_rate) = video.frame_rate {
+            frame_duration_tl_from_frame_rate(frame_rate)
+        } else {
+            rescale(1, video.time_base, TIMELINE_TIME_BASE).abs().max(1)
+        };
+
+        derived = Some(derived.map_or(bucket_tl, |current: i64| current.min(bucket_tl)));
+    }
+
+    derived.unwrap_or(DEFAULT_PREVIEW_CACHE_BUCKET_TL)
+}
+
+fn frame_duration_tl_from_frame_rate(frame_rate: crate::time::Rational) -> i64 {
+    let numerator = i128::from(TIMELINE_TIME_BASE.den) * i128::from(frame_rate.den);
+    let denominator = i128::from(frame_rate.num.max(1));
+    let rounded = (numerator + denominator / 2) / denominator;
+    rounded.max(1).min(i128::from(i64::MAX)) as i64
+}
+
+impl Engine<FfmpegMediaBackend> {
+    /// Creates an engine wired to the FFmpeg backend.
+    pub fn with_ffmpeg() -> Self {
+        Self::new(FfmpegMediaBackend)
+            .with_audio_sink(FfplayAudioSink::new())
+            .with_autosave(AutosaveConfig::from_env())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use super::{
+        Command, CommandId, EditPointDirection, Engine, EngineErrorKind, Event, ExportRange,
+        ExportSettings, MediaPathStyle, PREFETCH_RADIUS_IDLE, PasteMode, Project, ProjectDelta,
+        ProjectSnapshot, ReplaceAlignment, TaggedCommand,
+    };
+    use crate::autosave::AutosaveConfig;
+    use crate::error::EngineError;
+    use crate::export::{ExportAudioSettings, ExportVideoPlan, ExportVideoSegment};
+    use crate::playback::{AudioSink, PcmChunk, PcmFormat};
+    use crate::preview::{
+        MediaBackend, PreviewFrame, PreviewPixelFormat, PreviewQuality, ProbedAudioStream,
+        ProbedMedia, ProbedVideoStream,
+    };
+    use crate::time::{FrameGrid, Rational, rescale};
+    use crate::worker::{PreviewJob, PreviewJobKind, PreviewResult};
+
+    const PREVIEW_WAIT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn import_creates_single_segment_covering_full_duration() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+
+        let events = engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+
+        assert_eq!(events.len(), 2);
+        let Event::ProjectChanged(snapshot) = &events[0] else {
+            panic!("first event must be ProjectChanged");
+        };
+        assert_eq!(events[1], Event::PlayheadChanged { t_tl: 0 });
+
+        assert_eq!(snapshot.assets.len(), 1);
+        assert_eq!(snapshot.segments.len(), 1);
+        assert_eq!(snapshot.duration_tl, 1_200_000);
+        assert_eq!(snapshot.preview_bucket_tl, 33_367);
+
+        let segment = &snapshot.segments[0];
+        assert_eq!(segment.timeline_start, 0);
+        assert_eq!(segment.timeline_duration, 1_200_000);
+        assert_eq!(segment.src_in_video, Some(90_000));
+        assert_eq!(segment.src_out_video, Some(198_000));
+        assert_eq!(segment.src_in_audio, Some(48_000));
+        assert_eq!(segment.src_out_audio, Some(105_600));
+    }
+
+    #[test]
+    fn set_playhead_emits_preview_frame_ready_from_mapped_source_time() {
+        let backend = MockBackend::new(sample_probed_media(), sample_frame());
+        let calls = backend.decode_calls();
+        let mut engine = Engine::new(backend);
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+
+        let events = engine
+            .handle_command(Command::SetPlayhead { t_tl: 500_000 })
+            .expect("set playhead should succeed");
+        let events = settle(&mut engine, events);
+
+        assert_eq!(events[0], Event::PlayheadChanged { t_tl: 500_000 });
+        let Some(Event::PreviewFrameReady {
+            t_tl,
+            quality,
+            frame,
+            ..
+        }) = events.last()
+        else {
+            panic!("last event must be PreviewFrameReady");
+        };
+        assert_eq!(*t_tl, 500_000);
+        assert_eq!(*quality, PreviewQuality::Full);
+        assert_eq!(frame.width, 160);
+        assert_eq!(frame.height, 90);
+
+        let decoded_seconds = calls.lock().expect("lock decode calls")[0];
+        assert!((decoded_seconds - 1.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn set_playhead_on_cache_miss_decodes_requested_frame_before_warming_neighbors() {
+        let backend = MockBackend::new(sample_probed_media(), sample_frame());
+        let calls = backend.decode_calls();
+        let mut engine = Engine::new(backend);
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+
+        let events = engine
+            .handle_command(Command::SetPlayhead { t_tl: 500_000 })
+            .expect("set playhead should succeed");
+        settle(&mut engine, events);
+
+        let calls = calls.lock().expect("lock decode calls");
+        assert!((calls[0] - 1.5).abs() < 1e-6);
+        assert_eq!(count_close_calls(&calls, 1.5), 1);
+        assert!(calls.len() > 1);
+        assert!(calls.len() <= 1 + 2 * PREFETCH_RADIUS_IDLE as usize);
+    }
+
+    #[test]
+    fn set_playhead_on_cache_hit_does_not_prefetch_directional_neighbors() {
+        let backend = MockBackend::new(sample_probed_media(), sample_frame());
+        let calls = backend.decode_calls();
+        let mut engine = Engine::new(backend);
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+
+        let events = engine
+            .handle_command(Command::SetPlayhead { t_tl: 500_000 })
+            .expect("set playhead should succeed");
+        settle(&mut engine, events);
+        let decoded = calls.lock().expect("lock decode calls").len();
+
+        // Both frames were warmed, so scrubbing across them queues nothing.
+        for t_tl in [533_333, 500_000] {
+            engine
+                .handle_command(Command::SetPlayhead { t_tl })
+                .expect("set playhead should succeed");
+            assert!(!engine.has_pending_previews(), "{t_tl}");
+        }
+
+        assert_eq!(calls.lock().expect("lock decode calls").len(), decoded);
+    }
+
+    #[test]
+    fn set_playhead_on_cache_hit_warms_neighbors_when_idle_direction_is_unknown() {
+        let backend = MockBackend::new(sample_probed_media(), sample_frame());
+        let calls = backend.decode_calls();
+        let mut engine = Engine::new(backend);
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        engine.preview_cache.insert(
+            Path::new("demo.mp4"),
+            1_500_000,
+            PreviewQuality::Full,
+            sample_frame(),
+        );
+
+        let events = engine
+            .handle_command(Command::SetPlayhead { t_tl: 500_000 })
+            .expect("set playhead should succeed");
+        assert!(engine.has_pending_previews());
+        settle(&mut engine, events);
+
+        let calls = calls.lock().expect("lock decode calls");
+        assert_eq!(count_close_calls(&calls, 1.5), 0);
+        assert!(!calls.is_empty());
+    }
+
+    #[test]
+    fn edit_commands_invalidate_preview_cache() {
+        let backend = MockBackend::new(sample_probed_media(), sample_frame());
+        let calls = backend.decode_calls();
+        let mut engine = Engine::new(backend);
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        let events = engine
+            .handle_command(Command::SetPlayhead { t_tl: 500_000 })
+            .expect("set playhead should succeed");
+        settle(&mut engine, events);
+
+        engine
+            .handle_command(Command::Split { at_tl: 333_333 })
+            .expect("split should succeed");
+        let events = engine
+            .handle_command(Command::SetPlayhead { t_tl: 500_000 })
+            .expect("set playhead should succeed");
+        settle(&mut engine, events);
+
+        let calls = calls.lock().expect("lock decode calls");
+        assert_eq!(count_close_calls(&calls, 1.5), 2);
+    }
+
+    #[test]
+    fn segment_id_not_found_maps_to_other_error_kind() {
+        let error = crate::error::EngineError::SegmentIdNotFound { segment_id: 99 };
+        assert_eq!(EngineErrorKind::from(&error), EngineErrorKind::Other);
+    }
+
+    #[test]
+    fn split_creates_two_contiguous_segments_with_split_source_ranges() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+
+        let events = engine
+            .handle_command(Command::Split { at_tl: 333_333 })
+            .expect("split should succeed");
+
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("split must emit ProjectUpdated");
+        };
+        let snapshot = &snapshot_after(&mut engine, delta);
+        assert_eq!(snapshot.segments.len(), 2);
+
+        let left = &snapshot.segments[0];
+        assert_eq!(left.timeline_start, 0);
+        assert_eq!(left.timeline_duration, 333_333);
+        assert_eq!(left.src_in_video, Some(90_000));
+        assert_eq!(left.src_out_video, Some(120_000));
+        assert_eq!(left.src_in_audio, Some(48_000));
+        assert_eq!(left.src_out_audio, Some(64_000));
+
+        let right = &snapshot.segments[1];
+        assert_eq!(right.timeline_start, 333_333);
+        assert_eq!(right.timeline_duration, 866_667);
+        assert_eq!(right.src_in_video, Some(120_000));
+        assert_eq!(right.src_out_video, Some(198_000));
+        assert_eq!(right.src_in_audio, Some(64_000));
+        assert_eq!(right.src_out_audio, Some(105_600));
+    }
+
+    #[test]
+    fn frame_snapping_splits_on_the_project_frame_grid() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+
+        let events = engine
+            .handle_command(Command::SetSnapToFrames { enabled: true })
+            .expect("snap toggle should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("snap toggle must emit ProjectUpdated");
+        };
+        assert!(delta.snap_to_frames);
+        assert!(!delta.changes_timeline());
+
+        let events = engine
+            .handle_command(Command::Split { at_tl: 333_333 })
+            .expect("split should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("split must emit ProjectUpdated");
+        };
+        let snapshot = &snapshot_after(&mut engine, delta);
+        assert_eq!(snapshot.segments[1].timeline_start, 333_667);
+        assert_eq!(snapshot.segments[0].src_out_video, Some(120_030));
+        assert_eq!(snapshot.segments[0].src_out_audio, Some(64_016));
+
+        let pal = Rational::new(25, 1).expect("valid rational");
+        let events = engine
+            .handle_command(Command::SetProjectFrameRate {
+                frame_rate: Some(pal),
+            })
+            .expect("frame rate should be set");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("frame rate change must emit ProjectUpdated");
+        };
+        assert_eq!(delta.frame_grid, FrameGrid::from_frame_rate(pal));
+
+        let events = engine
+            .handle_command(Command::Split { at_tl: 710_000 })
+            .expect("split should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("split must emit ProjectUpdated");
+        };
+        let snapshot = &snapshot_after(&mut engine, delta);
+        assert_eq!(snapshot.segments[2].timeline_start, 720_000);
+        assert_eq!(snapshot.timecode_at(720_000).to_string(), "00:00:00:18");
+    }
+
+    #[test]
+    fn split_at_timeline_boundaries_returns_error() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+
+        let start = engine.handle_command(Command::Split { at_tl: 0 });
+        assert!(matches!(
+            start,
+            Err(crate::error::EngineError::SplitPointAtBoundary { at_tl: 0 })
+        ));
+
+        let end = engine.handle_command(Command::Split { at_tl: 1_200_000 });
+        assert!(matches!(
+            end,
+            Err(crate::error::EngineError::SplitPointAtBoundary { at_tl: 1_200_000 })
+        ));
+    }
+
+    #[test]
+    fn cut_middle_segment_preserves_gap_in_timeline() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 300_000 })
+            .expect("first split should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 900_000 })
+            .expect("second split should succeed");
+
+        let events = engine
+            .handle_command(Command::Cut { at_tl: 500_000 })
+            .expect("cut should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("cut must emit ProjectUpdated");
+        };
+        let snapshot = &snapshot_after(&mut engine, delta);
+
+        assert_eq!(snapshot.duration_tl, 1_200_000);
+        assert_eq!(snapshot.segments.len(), 2);
+        assert_eq!(snapshot.segments[0].timeline_start, 0);
+        assert_eq!(snapshot.segments[0].timeline_duration, 300_000);
+        assert_eq!(snapshot.segments[0].src_in_video, Some(90_000));
+        assert_eq!(snapshot.segments[0].src_out_video, Some(117_000));
+        assert_eq!(snapshot.segments[0].src_in_audio, Some(48_000));
+        assert_eq!(snapshot.segments[0].src_out_audio, Some(62_400));
+
+        assert_eq!(snapshot.segments[1].timeline_start, 900_000);
+        assert_eq!(snapshot.segments[1].timeline_duration, 300_000);
+        assert_eq!(snapshot.segments[1].src_in_video, Some(171_000));
+        assert_eq!(snapshot.segments[1].src_out_video, Some(198_000));
+        assert_eq!(snapshot.segments[1].src_in_audio, Some(91_200));
+        assert_eq!(snapshot.segments[1].src_out_audio, Some(105_600));
+    }
+
+    #[test]
+    fn move_segment_repositions_clip_without_changing_source_range() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 300_000 })
+            .expect("first split should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 900_000 })
+            .expect("second split should succeed");
+
+        let events = engine
+            .handle_command(Command::MoveSegment {
+                segment_id: 3,
+                new_start_tl: 1_000_000,
+            })
+            .expect("move should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("move must emit ProjectUpdated");
+        };
+        let snapshot = &snapshot_after(&mut engine, delta);
+
+        assert_eq!(snapshot.duration_tl, 1_300_000);
+        assert_eq!(snapshot.segments.len(), 3);
+
+        let moved = &snapshot.segments[2];
+        assert_eq!(moved.id, 3);
+        assert_eq!(moved.timeline_start, 1_000_000);
+        assert_eq!(moved.timeline_duration, 300_000);
+        assert_eq!(moved.src_in_video, Some(171_000));
+        assert_eq!(moved.src_out_video, Some(198_000));
+        assert_eq!(moved.src_in_audio, Some(91_200));
+        assert_eq!(moved.src_out_audio, Some(105_600));
+    }
+
+    #[test]
+    fn trim_segment_start_updates_timeline_and_source_in() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 300_000 })
+            .expect("first split should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 900_000 })
+            .expect("second split should succeed");
+
+        let events = engine
+            .handle_command(Command::TrimSegmentStart {
+                segment_id: 2,
+                new_start_tl: 400_000,
+            })
+            .expect("trim start should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("trim start must emit ProjectUpdated");
+        };
+        let snapshot = &snapshot_after(&mut engine, delta);
+
+        let trimmed = &snapshot.segments[1];
+        assert_eq!(trimmed.id, 2);
+        assert_eq!(trimmed.timeline_start, 400_000);
+        assert_eq!(trimmed.timeline_duration, 500_000);
+        assert_eq!(trimmed.src_in_video, Some(126_000));
+        assert_eq!(trimmed.src_out_video, Some(171_000));
+        assert_eq!(trimmed.src_in_audio, Some(67_200));
+        assert_eq!(trimmed.src_out_audio, Some(91_200));
+    }
+
+    #[test]
+    fn trim_segment_end_updates_timeline_and_source_out() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 300_000 })
+            .expect("first split should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 900_000 })
+            .expect("second split should succeed");
+
+        let events = engine
+            .handle_command(Command::TrimSegmentEnd {
+                segment_id: 2,
+                new_end_tl: 800_000,
+            })
+            .expect("trim end should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("trim end must emit ProjectUpdated");
+        };
+        let snapshot = &snapshot_after(&mut engine, delta);
+
+        let trimmed = &snapshot.segments[1];
+        assert_eq!(trimmed.id, 2);
+        assert_eq!(trimmed.timeline_start, 300_000);
+        assert_eq!(trimmed.timeline_duration, 500_000);
+        assert_eq!(trimmed.src_in_video, Some(117_000));
+        assert_eq!(trimmed.src_out_video, Some(162_000));
+        assert_eq!(trimmed.src_in_audio, Some(62_400));
+        assert_eq!(trimmed.src_out_audio, Some(86_400));
+    }
+
+    #[test]
+    fn set_playhead_inside_gap_emits_playhead_changed_without_preview_decode() {
+        let backend = MockBackend::new(sample_probed_media(), sample_frame());
+        let calls = backend.decode_calls();
+        let mut engine = Engine::new(backend);
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 300_000 })
+            .expect("first split should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 900_000 })
+            .expect("second split should succeed");
+        engine
+            .handle_command(Command::Cut { at_tl: 500_000 })
+            .expect("cut should succeed");
+
+        let events = engine
+            .handle_command(Command::SetPlayhead { t_tl: 600_000 })
+            .expect("set playhead should succeed");
+
+        assert_eq!(events, vec![Event::PlayheadChanged { t_tl: 600_000 }]);
+        assert!(calls.lock().expect("lock decode calls").is_empty());
+    }
+
+    #[test]
+    fn tagged_command_echoes_id_on_every_event_and_completes() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        let events = engine.handle_tagged_command(TaggedCommand {
+            id: CommandId(3),
+            command: Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            },
+        });
+
+        assert!(events.len() > 1);
+        assert!(events.iter().all(|tagged| tagged.id == Some(CommandId(3))));
+        assert!(matches!(
+            events.last().map(|tagged| &tagged.event),
+            Some(Event::CommandCompleted {
+                id: CommandId(3),
+                result: Ok(())
+            })
+        ));
+    }
+
+    #[test]
+    fn failed_tagged_command_reports_error_in_completion() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+
+        let events = engine.handle_tagged_command(TaggedCommand {
+            id: CommandId(9),
+            command: Command::Split { at_tl: 0 },
+        });
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].id, Some(CommandId(9)));
+        match &events[0].event {
+            Event::CommandCompleted {
+                id: CommandId(9),
+                result: Err(error),
+            } => assert_eq!(error.kind, EngineErrorKind::SplitPointAtBoundary),
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn edit_deltas_replay_onto_snapshot_and_match_full_snapshots() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        let events = engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        let Event::ProjectChanged(mut replayed) = events[0].clone() else {
+            panic!("import must emit ProjectChanged");
+        };
+
+        for command in [
+            Command::Split { at_tl: 300_000 },
+            Command::Split { at_tl: 900_000 },
+            Command::ToggleSelection { segment_id: 2 },
+            Command::TrimSegmentEnd {
+                segment_id: 2,
+                new_end_tl: 800_000,
+            },
+            Command::MoveSegment {
+                segment_id: 3,
+                new_start_tl: 1_000_000,
+            },
+            Command::SetInPoint { at_tl: 100_000 },
+            Command::SetOutPoint { at_tl: 500_000 },
+            Command::DeleteInOutRange { ripple: true },
+            Command::Cut { at_tl: 50_000 },
+            Command::SelectAll,
+            Command::SetSelectionEnabled { enabled: false },
+            Command::ClearSelection,
+            Command::SelectRange {
+                start_tl: 0,
+                end_tl: 400_000,
+                extend: false,
+            },
+            Command::MoveSelection { delta_tl: 20_000 },
+            Command::DeleteSelection { ripple: true },
+        ] {
+            let events = engine
+                .handle_command(command.clone())
+                .expect("edit should succeed");
+            let Event::ProjectUpdated(delta) = &events[0] else {
+                panic!("{command:?} must emit ProjectUpdated");
+            };
+            assert_eq!(delta.base_revision + 1, delta.revision);
+            assert!(replayed.apply_delta(delta));
+            assert_eq!(replayed, snapshot_after(&mut engine, delta), "{command:?}");
+        }
+    }
+
+    #[test]
+    fn split_delta_lists_only_the_changed_segments() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 300_000 })
+            .expect("first split should succeed");
+
+        let events = engine
+            .handle_command(Command::Split { at_tl: 900_000 })
+            .expect("second split should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("split must emit ProjectUpdated");
+        };
+
+        assert_eq!(
+            (delta.base_revision, delta.revision),
+            (2, 3),
+            "import and first split each bump the revision"
+        );
+        let updated: Vec<u64> = delta.segments_updated.iter().map(|s| s.id).collect();
+        let added: Vec<u64> = delta.segments_added.iter().map(|s| s.id).collect();
+        assert_eq!(updated, vec![2]);
+        assert_eq!(added, vec![3]);
+        assert!(delta.segments_removed.is_empty());
+        assert!(delta.assets_added.is_empty());
+    }
+
+    #[test]
+    fn delta_for_another_revision_is_rejected_without_changes() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        let events = engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        let Event::ProjectChanged(mut stale) = events[0].clone() else {
+            panic!("import must emit ProjectChanged");
+        };
+        engine
+            .handle_command(Command::Split { at_tl: 300_000 })
+            .expect("first split should succeed");
+        let events = engine
+            .handle_command(Command::Split { at_tl: 900_000 })
+            .expect("second split should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("split must emit ProjectUpdated");
+        };
+
+        let before = stale.clone();
+        assert!(!stale.apply_delta(delta));
+        assert_eq!(stale, before);
+    }
+
+    #[test]
+    fn applied_delta_keeps_moved_and_added_segments_in_timeline_order() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 300_000 })
+            .expect("first split should succeed");
+        let events = engine
+            .handle_command(Command::Split { at_tl: 900_000 })
+            .expect("second split should succeed");
+        let Event::ProjectUpdated(template) = &events[0] else {
+            panic!("split must emit ProjectUpdated");
+        };
+        let mut snapshot = snapshot_after(&mut engine, template);
+
+        let mut moved = snapshot.segments[0].clone();
+        moved.timeline_start = 2_000_000;
+        let mut added = snapshot.segments[1].clone();
+        added.id = 9;
+        added.timeline_start = 600_000;
+        let delta = ProjectDelta {
+            base_revision: snapshot.revision,
+            revision: snapshot.revision + 1,
+            segments_added: vec![added],
+            segments_updated: vec![moved],
+            segments_removed: Vec::new(),
+            ..template.clone()
+        };
+
+        assert!(snapshot.apply_delta(&delta));
+        let order: Vec<(u64, i64)> = snapshot
+            .segments
+            .iter()
+            .map(|segment| (segment.id, segment.timeline_start))
+            .collect();
+        assert_eq!(
+            order,
+            vec![(2, 300_000), (9, 600_000), (3, 900_000), (1, 2_000_000)]
+        );
+    }
+
+    #[test]
+    fn delta_carries_the_preview_bucket_after_adding_a_faster_asset() {
+        let dir = std::env::temp_dir().join(format!("cutit-bucket-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create project dir");
+        let project_path = dir.join("pal.nle.json");
+        let mut pal = sample_probed_media();
+        pal.video.as_mut().expect("video stream exists").frame_rate =
+            Some(Rational::new(25, 1).expect("valid rational"));
+        let mut saving = Engine::new(MockBackend::new(pal, sample_frame()));
+        saving
+            .handle_command(Command::Import {
+                path: PathBuf::from("pal.mov"),
+            })
+            .expect("import should succeed");
+        saving
+            .handle_command(Command::SaveProject {
+                path: project_path.clone(),
+                paths: MediaPathStyle::Absolute,
+            })
+            .expect("save should succeed");
+
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        let events = engine
+            .handle_command(Command::OpenProject { path: project_path })
+            .expect("open should succeed");
+        let Event::ProjectChanged(mut snapshot) = events[0].clone() else {
+            panic!("open must emit ProjectChanged");
+        };
+        assert_eq!(snapshot.preview_bucket_tl, 40_000);
+        let events = engine
+            .handle_command(Command::AddAsset {
+                path: PathBuf::from("ntsc.mp4"),
+            })
+            .expect("add asset should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("add asset must emit ProjectUpdated");
+        };
+
+        assert_eq!(delta.preview_bucket_tl, 33_367);
+        assert!(snapshot.apply_delta(delta));
+        assert_eq!(snapshot, snapshot_after(&mut engine, delta));
+        std::fs::remove_dir_all(dir).expect("cleanup project dir");
+    }
+
+    #[test]
+    fn failed_edit_keeps_project_revision() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+
+        assert!(engine.handle_command(Command::Split { at_tl: 0 }).is_err());
+        let events = engine
+            .handle_command(Command::RequestProjectSnapshot)
+            .expect("snapshot request should succeed");
+        let Event::ProjectChanged(snapshot) = &events[0] else {
+            panic!("snapshot request must emit ProjectChanged");
+        };
+        assert_eq!(snapshot.revision, 1);
+    }
+
+    #[test]
+    fn batch_applies_all_edits_as_one_revision() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        let events = engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        let Event::ProjectChanged(mut replayed) = events[0].clone() else {
+            panic!("import must emit ProjectChanged");
+        };
+
+        let splits = (1..40).map(|step| Command::Split {
+            at_tl: step * 30_000,
+        });
+        let cuts = (0..20).map(|step| Command::Cut {
+            at_tl: step * 60_000,
+        });
+        let events = engine
+            .handle_command(Command::Batch(splits.chain(cuts).collect()))
+            .expect("batch should succeed");
+
+        assert_eq!(events.len(), 1);
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("batch must emit one ProjectUpdated");
+        };
+        assert_eq!((delta.base_revision, delta.revision), (1, 2));
+        assert!(replayed.apply_delta(delta));
+        let snapshot = snapshot_after(&mut engine, delta);
+        assert_eq!(replayed, snapshot);
+        assert_eq!(snapshot.segments.len(), 20);
+        assert!(
+            snapshot
+                .segments
+                .iter()
+                .all(|segment| segment.timeline_start % 60_000 == 30_000)
+        );
+    }
+
+    #[test]
+    fn failed_batch_rolls_back_earlier_commands() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        let events = engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        let Event::ProjectChanged(before) = events[0].clone() else {
+            panic!("import must emit ProjectChanged");
+        };
+        engine
+            .handle_command(Command::SetPlayhead { t_tl: 900_000 })
+            .expect("set playhead should succeed");
+
+        let error = engine
+            .handle_command(Command::Batch(vec![
+                Command::Split { at_tl: 300_000 },
+                Command::SetInPoint { at_tl: 300_000 },
+                Command::DeleteInOutRange { ripple: true },
+                Command::Split { at_tl: 0 },
+            ]))
+            .expect_err("batch must fail on the boundary split");
+
+        assert!(matches!(
+            error,
+            EngineError::BatchCommandFailed { index: 3, ref source }
+                if matches!(**source, EngineError::SplitPointAtBoundary { at_tl: 0 })
+        ));
+        assert_eq!(
+            EngineErrorKind::from(&error),
+            EngineErrorKind::SplitPointAtBoundary
+        );
+        let events = engine
+            .handle_command(Command::RequestProjectSnapshot)
+            .expect("snapshot request should succeed");
+        assert_eq!(events, vec![Event::ProjectChanged(before)]);
+        assert_eq!(engine.playhead_tl, 900_000);
+        assert_eq!(engine.next_segment_id, 2);
+    }
+
+    #[test]
+    fn batch_rejects_commands_that_cannot_be_rolled_back() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+
+        let error = engine
+            .handle_command(Command::Batch(vec![
+                Command::Split { at_tl: 300_000 },
+                Command::Play,
+            ]))
+            .expect_err("play is not batchable");
+
+        assert!(matches!(
+            error,
+            EngineError::CommandNotBatchable { index: 1 }
+        ));
+        assert_eq!(engine.revision, 1);
+        assert!(!engine.is_playing());
+    }
+
+    #[test]
+    fn ripple_deleting_selection_reports_selection_and_clamped_playhead() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 300_000 })
+            .expect("first split should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 900_000 })
+            .expect("second split should succeed");
+        engine
+            .handle_command(Command::SetPlayhead { t_tl: 1_100_000 })
+            .expect("set playhead should succeed");
+
+        let events = engine
+            .handle_command(Command::Select {
+                segment_ids: vec![2],
+            })
+            .expect("select should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("select must emit ProjectUpdated");
+        };
+        assert_eq!(delta.selection, Some(vec![2]));
+        assert!(!delta.changes_timeline());
+
+        let events = engine
+            .handle_command(Command::DeleteSelection { ripple: true })
+            .expect("delete should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("delete must emit ProjectUpdated");
+        };
+        assert_eq!(delta.segments_removed, vec![2]);
+        assert_eq!(delta.selection, Some(Vec::new()));
+        assert_eq!(delta.duration_tl, 600_000);
+        assert_eq!(events[1], Event::PlayheadChanged { t_tl: 599_999 });
+        assert!(matches!(
+            engine.handle_command(Command::DeleteSelection { ripple: false }),
+            Err(EngineError::SelectionEmpty)
+        ));
+    }
+
+    #[test]
+    fn paste_and_duplicate_allocate_fresh_segment_ids() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 600_000 })
+            .expect("split should succeed");
+        assert!(matches!(
+            engine.handle_command(Command::Paste {
+                mode: PasteMode::Insert
+            }),
+            Err(EngineError::ClipboardEmpty)
+        ));
+        engine
+            .handle_command(Command::Select {
+                segment_ids: vec![2],
+            })
+            .expect("select should succeed");
+        let events = engine
+            .handle_command(Command::CopySelection)
+            .expect("copy should succeed");
+        assert!(events.is_empty());
+        engine
+            .handle_command(Command::SetPlayhead { t_tl: 300_000 })
+            .expect("set playhead should succeed");
+
+        let events = engine
+            .handle_command(Command::Paste {
+                mode: PasteMode::Insert,
+            })
+            .expect("paste should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("paste must emit ProjectUpdated");
+        };
+        let mut added: Vec<_> = delta
+            .segments_added
+            .iter()
+            .map(|segment| segment.id)
+            .collect();
+        added.sort_unstable();
+        assert_eq!(added, vec![3, 4]);
+        assert_eq!(delta.selection, Some(vec![4]));
+        assert_eq!(delta.duration_tl, 1_800_000);
+        assert_eq!(events[1], Event::PlayheadChanged { t_tl: 900_000 });
+
+        let events = engine
+            .handle_command(Command::DuplicateSegment { segment_id: 4 })
+            .expect("duplicate should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("duplicate must emit ProjectUpdated");
+        };
+        assert_eq!(delta.segments_added.len(), 1);
+        assert_eq!(delta.segments_added[0].id, 5);
+        assert_eq!(delta.segments_added[0].timeline_start, 900_000);
+        assert_eq!(delta.selection, Some(vec![5]));
+        assert_eq!(engine.next_segment_id, 6);
+    }
+
+    #[test]
+    fn bin_assets_insert_and_overwrite_their_marked_source_range() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        let events = engine
+            .handle_command(Command::AddAsset {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("add asset should succeed");
+        let Event::ProjectChanged(snapshot) = &events[0] else {
+            panic!("first asset must start a project");
+        };
+        assert_eq!(snapshot.assets.len(), 1);
+        assert!(snapshot.segments.is_empty());
+
+        engine
+            .handle_command(Command::SetSourceInPoint {
+                asset_id: 1,
+                at_src: 200_000,
+            })
+            .expect("source in should succeed");
+        let events = engine
+            .handle_command(Command::SetSourceOutPoint {
+                asset_id: 1,
+                at_src: 500_000,
+            })
+            .expect("source out should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("source marks must emit ProjectUpdated");
+        };
+        assert!(!delta.changes_timeline());
+        assert_eq!(delta.assets_updated[0].source_in_tl, Some(200_000));
+        assert_eq!(delta.assets_updated[0].source_out_tl, Some(500_000));
+
+        let events = engine
+            .handle_command(Command::InsertFromAsset {
+                asset_id: 1,
+                at_tl: 0,
+            })
+            .expect("insert should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("insert must emit ProjectUpdated");
+        };
+        let inserted = &delta.segments_added[0];
+        assert_eq!(inserted.id, 1);
+        assert_eq!(inserted.timeline_duration, 300_000);
+        assert_eq!(inserted.src_in_video, Some(108_000));
+        assert_eq!(inserted.src_out_audio, Some(72_000));
+        assert_eq!(delta.selection, Some(vec![1]));
+        assert_eq!(events[1], Event::PlayheadChanged { t_tl: 299_999 });
+
+        let events = engine
+            .handle_command(Command::AddAsset {
+                path: PathBuf::from("broll.mp4"),
+            })
+            .expect("second asset should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("adding to a loaded project must emit ProjectUpdated");
+        };
+        assert_eq!(delta.assets_added[0].id, 2);
+        assert!(delta.segments_added.is_empty());
+
+        let events = engine
+            .handle_command(Command::OverwriteFromAsset {
+                asset_id: 2,
+                at_tl: 100_000,
+            })
+            .expect("overwrite should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("overwrite must emit ProjectUpdated");
+        };
+        assert_eq!(delta.duration_tl, 1_300_000);
+        assert_eq!(delta.segments_updated[0].timeline_duration, 100_000);
+        let added: Vec<_> = delta
+            .segments_added
+            .iter()
+            .map(|segment| (segment.asset_id, segment.timeline_start))
+            .collect();
+        assert_eq!(added, vec![(2, 100_000)]);
+        assert!(matches!(
+            engine.handle_command(Command::InsertFromAsset {
+                asset_id: 9,
+                at_tl: 0,
+            }),
+            Err(EngineError::MissingAsset { asset_id: 9 })
+        ));
+    }
+
+    #[test]
+    fn failed_split_does_not_consume_next_segment_id() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+
+        let boundary_result = engine.handle_command(Command::Split { at_tl: 0 });
+        assert!(matches!(
+            boundary_result,
+            Err(crate::error::EngineError::SplitPointAtBoundary { at_tl: 0 })
+        ));
+
+        let events = engine
+            .handle_command(Command::Split { at_tl: 333_333 })
+            .expect("split should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("split must emit ProjectUpdated");
+        };
+        let snapshot = &snapshot_after(&mut engine, delta);
+
+        let ids: Vec<u64> = snapshot.segments.iter().map(|segment| segment.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+    }
+
+    #[test]
+    fn repeated_splits_keep_timeline_contiguous_and_duration_stable() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 333_333 })
+            .expect("first split should succeed");
+
+        let events = engine
+            .handle_command(Command::Split { at_tl: 900_000 })
+            .expect("second split should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("split must emit ProjectUpdated");
+        };
+        let snapshot = &snapshot_after(&mut engine, delta);
+
+        assert_eq!(snapshot.duration_tl, 1_200_000);
+        assert_eq!(snapshot.segments.len(), 3);
+
+        let first = &snapshot.segments[0];
+        let second = &snapshot.segments[1];
+        let third = &snapshot.segments[2];
+
+        assert_eq!(
+            first.timeline_start + first.timeline_duration,
+            second.timeline_start
+        );
+        assert_eq!(
+            second.timeline_start + second.timeline_duration,
+            third.timeline_start
+        );
+        assert_eq!(
+            third.timeline_start + third.timeline_duration,
+            snapshot.duration_tl
+        );
+    }
+
+    #[test]
+    fn import_emits_playhead_reset_after_scrubbing_previous_project() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("first.mp4"),
+            })
+            .expect("first import should succeed");
+        engine
+            .handle_command(Command::SetPlayhead { t_tl: 500_000 })
+            .expect("set playhead should succeed");
+
+        let events = engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("second.mp4"),
+            })
+            .expect("second import should succeed");
+
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], Event::ProjectChanged(_)));
+        assert_eq!(events[1], Event::PlayheadChanged { t_tl: 0 });
+    }
+
+    #[test]
+    fn set_playhead_clamps_negative_mapped_source_time_to_zero_seconds() {
+        let backend = MockBackend::new(
+            sample_probed_media_with_negative_video_start(),
+            sample_frame(),
+        );
+        let calls = backend.decode_calls();
+        let mut engine = Engine::new(backend);
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+
+        let events = engine
+            .handle_command(Command::SetPlayhead { t_tl: 0 })
+            .expect("set playhead should succeed");
+        let events = settle(&mut engine, events);
+
+        assert_eq!(events[0], Event::PlayheadChanged { t_tl: 0 });
+        let decoded_seconds = calls.lock().expect("lock decode calls")[0];
+        assert_eq!(decoded_seconds, 0.0);
+    }
+
+    #[test]
+    fn export_calls_backend_with_timeline_ordered_segments() {
+        let backend = MockBackend::new(sample_probed_media(), sample_frame());
+        let export_calls = backend.export_calls();
+        let mut engine = Engine::new(backend);
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 333_333 })
+            .expect("split should succeed");
+
+        let output_path = PathBuf::from("out.mp4");
+        let events = engine
+            .handle_command(Command::Export {
+                path: output_path.clone(),
+                settings: ExportSettings::default(),
+            })
+            .expect("export should succeed");
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0], Event::ExportProgress { done: 2, total: 2 });
+        assert_eq!(events[1], Event::ExportFinished { path: output_path });
+
+        let calls = export_calls.lock().expect("lock export calls");
+        assert_eq!(calls.len(), 1);
+
+        let plan = &calls[0];
+        assert_eq!(plan.inputs, vec![PathBuf::from("demo.mp4")]);
+        assert_eq!(
+            plan.audio,
+            Some(ExportAudioSettings {
+                sample_rate: 48_000,
+                channels: 2,
+            })
+        );
+        assert_eq!(
+            plan.segments,
+            vec![
+                ExportVideoSegment {
+                    input_index: 0,
+                    src_in_video: 90_000,
+                    src_out_video: 120_000,
+                    src_video_time_base: Rational::new(1, 90_000).expect("valid rational"),
+                    src_in_audio: Some(48_000),
+                    src_out_audio: Some(64_000),
+                    src_audio_time_base: Some(Rational::new(1, 48_000).expect("valid rational"),),
+                    enabled: true,
+                },
+                ExportVideoSegment {
+                    input_index: 0,
+                    src_in_video: 120_000,
+                    src_out_video: 198_000,
+                    src_video_time_base: Rational::new(1, 90_000).expect("valid rational"),
+                    src_in_audio: Some(64_000),
+                    src_out_audio: Some(105_600),
+                    src_audio_time_base: Some(Rational::new(1, 48_000).expect("valid rational"),),
+                    enabled: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn disabled_segment_previews_black_and_exports_blanked() {
+        let backend = MockBackend::new(sample_probed_media(), sample_frame());
+        let export_calls = backend.export_calls();
+        let mut engine = Engine::new(backend);
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 333_333 })
+            .expect("split should succeed");
+
+        let events = engine
+            .handle_command(Command::SetSegmentEnabled {
+                segment_id: 2,
+                enabled: false,
+            })
+            .expect("disable should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("disable must emit ProjectUpdated");
+        };
+        assert_eq!(delta.segments_updated.len(), 1);
+        assert!(!delta.segments_updated[0].enabled);
+
+        let events = engine
+            .handle_command(Command::SetPlayhead { t_tl: 500_000 })
+            .expect("set playhead should succeed");
+        let Some(Event::PreviewFrameReady { frame, quality, .. }) = events.get(1) else {
+            panic!("disabled segment must preview immediately");
+        };
+        assert_eq!(*quality, PreviewQuality::Full);
+        assert_eq!((frame.width, frame.height), (160, 90));
+        assert_eq!(&frame.bytes[..4], &[0, 0, 0, 255]);
+
+        engine
+            .handle_command(Command::Export {
+                path: PathBuf::from("out.mp4"),
+                settings: ExportSettings::default(),
+            })
+            .expect("export should succeed");
+        let calls = export_calls.lock().expect("lock export calls");
+        let enabled: Vec<_> = calls[0]
+            .segments
+            .iter()
+            .map(|segment| segment.enabled)
+            .collect();
+        assert_eq!(enabled, vec![true, false]);
+    }
+
+    #[test]
+    fn export_skips_zero_length_video_ranges_created_by_subframe_split() {
+        let backend = MockBackend::new(sample_probed_media(), sample_frame());
+        let export_calls = backend.export_calls();
+        let mut engine = Engine::new(backend);
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 1 })
+            .expect("split should succeed");
+
+        let events = engine
+            .handle_command(Command::Export {
+                path: PathBuf::from("out.mp4"),
+                settings: ExportSettings::default(),
+            })
+            .expect("export should succeed");
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0], Event::ExportProgress { done: 1, total: 1 });
+
+        let calls = export_calls.lock().expect("lock export calls");
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].segments.len(), 1);
+        assert_eq!(
+            calls[0].audio,
+            Some(ExportAudioSettings {
+                sample_rate: 48_000,
+                channels: 2,
+            })
+        );
+        assert_eq!(calls[0].segments[0].src_in_video, 90_000);
+        assert_eq!(calls[0].segments[0].src_out_video, 198_000);
+        assert_eq!(calls[0].segments[0].src_in_audio, Some(48_000));
+        assert_eq!(calls[0].segments[0].src_out_audio, Some(105_600));
+    }
+
+    #[test]
+    fn export_allows_subframe_split_with_zero_length_audio_range() {
+        let backend = MockBackend::new(sample_probed_media(), sample_frame());
+        let export_calls = backend.export_calls();
+        let mut engine = Engine::new(backend);
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 8 })
+            .expect("split should succeed");
+
+        let events = engine
+            .handle_command(Command::Export {
+                path: PathBuf::from("out.mp4"),
+                settings: ExportSettings::default(),
+            })
+            .expect("export should succeed");
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0], Event::ExportProgress { done: 2, total: 2 });
+
+        let calls = export_calls.lock().expect("lock export calls");
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].segments.len(), 2);
+        assert_eq!(calls[0].segments[0].src_in_video, 90_000);
+        assert_eq!(calls[0].segments[0].src_out_video, 90_001);
+        assert_eq!(calls[0].segments[0].src_in_audio, Some(47_999));
+        assert_eq!(calls[0].segments[0].src_out_audio, Some(48_000));
+    }
+
+    #[test]
+    fn export_subframe_split_near_audio_end_keeps_audio_range_in_bounds() {
+        let backend = MockBackend::new(sample_probed_media(), sample_frame());
+        let export_calls = backend.export_calls();
+        let mut engine = Engine::new(backend);
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 1_199_990 })
+            .expect("split should succeed");
+
+        engine
+            .handle_command(Command::Export {
+                path: PathBuf::from("out.mp4"),
+                settings: ExportSettings::default(),
+            })
+            .expect("export should succeed");
 
-    use super::{Command, Engine, EngineErrorKind, Event, ExportSettings};
-    use crate::export::{ExportAudioSettings, ExportVideoPlan, ExportVideoSegment};
-    use crate::preview::{
-        MediaBackend, PreviewFrame, PreviewPixelFormat, ProbedAudioStream, ProbedMedia,
-        ProbedVideoStream,
-    };
-    use crate::time::{Rational, rescale};
+        let calls = export_calls.lock().expect("lock export calls");
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].segments.len(), 2);
+
+        let right = &calls[0].segments[1];
+        assert_eq!(right.src_in_video, 197_999);
+        assert_eq!(right.src_out_video, 198_000);
+        assert_eq!(right.src_in_audio, Some(105_599));
+        assert_eq!(right.src_out_audio, Some(105_600));
+    }
 
     #[test]
-    fn import_creates_single_segment_covering_full_duration() {
+    fn play_advances_playhead_by_audio_clock_until_timeline_end() {
         let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
-
-        let events = engine
+        engine
             .handle_command(Command::Import {
                 path: PathBuf::from("demo.mp4"),
             })
             .expect("import should succeed");
 
-        assert_eq!(events.len(), 2);
-        let Event::ProjectChanged(snapshot) = &events[0] else {
-            panic!("first event must be ProjectChanged");
-        };
-        assert_eq!(events[1], Event::PlayheadChanged { t_tl: 0 });
+        let events = engine
+            .handle_command(Command::Play)
+            .expect("play should succeed");
+        assert_eq!(
+            events,
+            vec![Event::PlaybackStateChanged {
+                playing: true,
+                rate: 1,
+            }]
+        );
+        assert!(engine.is_playing());
 
-        assert_eq!(snapshot.assets.len(), 1);
-        assert_eq!(snapshot.segments.len(), 1);
-        assert_eq!(snapshot.duration_tl, 1_200_000);
-        assert_eq!(snapshot.preview_bucket_tl, 33_367);
+        let events = engine.tick_playback().expect("tick should succeed");
+        let events = settle(&mut engine, events);
+        assert_eq!(events[0], Event::PlayheadChanged { t_tl: 300_000 });
+        assert!(matches!(
+            events[1],
+            Event::PreviewFrameReady { t_tl: 300_000, .. }
+        ));
 
-        let segment = &snapshot.segments[0];
-        assert_eq!(segment.timeline_start, 0);
-        assert_eq!(segment.timeline_duration, 1_200_000);
-        assert_eq!(segment.src_in_video, Some(90_000));
-        assert_eq!(segment.src_out_video, Some(198_000));
-        assert_eq!(segment.src_in_audio, Some(48_000));
-        assert_eq!(segment.src_out_audio, Some(105_600));
+        let mut last_events = Vec::new();
+        for _ in 0..10 {
+            if !engine.is_playing() {
+                break;
+            }
+            last_events = engine.tick_playback().expect("tick should succeed");
+        }
+        assert!(!engine.is_playing());
+        assert_eq!(last_events[0], Event::PlayheadChanged { t_tl: 1_199_999 });
+        assert_eq!(
+            last_events.last(),
+            Some(&Event::PlaybackStateChanged {
+                playing: false,
+                rate: 0,
+            })
+        );
     }
 
     #[test]
-    fn set_playhead_emits_preview_frame_ready_from_mapped_source_time() {
+    fn playback_decodes_audio_from_mapped_source_time() {
         let backend = MockBackend::new(sample_probed_media(), sample_frame());
-        let calls = backend.decode_calls();
-        let mut engine = Engine::new(backend);
+        let audio_calls = backend.audio_calls();
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let mut engine = Engine::new(backend).with_audio_sink(RecordingAudioSink {
+            written_frames: Arc::clone(&written),
+            played_frames: 0,
+        });
         engine
             .handle_command(Command::Import {
                 path: PathBuf::from("demo.mp4"),
             })
             .expect("import should succeed");
-
-        let events = engine
+        engine
             .handle_command(Command::SetPlayhead { t_tl: 500_000 })
             .expect("set playhead should succeed");
 
-        assert_eq!(events.len(), 2);
-        assert_eq!(events[0], Event::PlayheadChanged { t_tl: 500_000 });
-        let Event::PreviewFrameReady { t_tl, frame } = &events[1] else {
-            panic!("second event must be PreviewFrameReady");
-        };
-        assert_eq!(*t_tl, 500_000);
-        assert_eq!(frame.width, 160);
-        assert_eq!(frame.height, 90);
-
-        let decoded_seconds = calls.lock().expect("lock decode calls")[0];
-        assert!((decoded_seconds - 1.5).abs() < 1e-6);
+        engine
+            .handle_command(Command::Play)
+            .expect("play should succeed");
+
+        let audio_calls = audio_calls.lock().expect("lock audio calls");
+        assert_eq!(audio_calls.len(), 3);
+        assert!((audio_calls[0].0 - 1.5).abs() < 1e-6);
+        assert!((audio_calls[0].1 - 0.1).abs() < 1e-6);
+        assert!((audio_calls[2].0 - 1.7).abs() < 1e-6);
+        assert_eq!(
+            *written.lock().expect("lock written frames"),
+            vec![4_800, 4_800, 4_800]
+        );
     }
 
     #[test]
-    fn set_playhead_on_cache_miss_decodes_only_requested_frame() {
-        let backend = MockBackend::new(sample_probed_media(), sample_frame());
-        let calls = backend.decode_calls();
-        let mut engine = Engine::new(backend);
+    fn pause_stops_playback_at_audio_clock() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
         engine
             .handle_command(Command::Import {
                 path: PathBuf::from("demo.mp4"),
             })
             .expect("import should succeed");
-
         engine
-            .handle_command(Command::SetPlayhead { t_tl: 500_000 })
-            .expect("set playhead should succeed");
+            .handle_command(Command::Play)
+            .expect("play should succeed");
+        engine.tick_playback().expect("tick should succeed");
 
-        let calls = calls.lock().expect("lock decode calls");
-        assert_eq!(calls.len(), 1);
-        assert_eq!(count_close_calls(&calls, 1.5), 1);
+        let events = engine
+            .handle_command(Command::Pause)
+            .expect("pause should succeed");
+
+        // The null sink reports queued audio as played, so the clock sits at
+        // the end of the queued lead.
+        assert_eq!(
+            events[0],
+            Event::PlaybackStateChanged {
+                playing: false,
+                rate: 0,
+            }
+        );
+        assert_eq!(events[1], Event::PlayheadChanged { t_tl: 600_000 });
+        assert!(!engine.is_playing());
+        assert!(engine.tick_playback().expect("idle tick").is_empty());
+        let events = engine
+            .handle_command(Command::SetPlayhead { t_tl: 300_000 })
+            .expect("set playhead should succeed");
+        assert_eq!(events[0], Event::PlayheadChanged { t_tl: 300_000 });
     }
 
     #[test]
-    fn set_playhead_on_cache_hit_does_not_prefetch_directional_neighbors() {
-        let backend = MockBackend::new(sample_probed_media(), sample_frame());
-        let calls = backend.decode_calls();
-        let mut engine = Engine::new(backend);
+    fn scrub_audio_writes_short_snippet_only_when_enabled() {
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()))
+            .with_audio_sink(RecordingAudioSink {
+                written_frames: Arc::clone(&written),
+                played_frames: 0,
+            });
         engine
             .handle_command(Command::Import {
                 path: PathBuf::from("demo.mp4"),
@@ -680,47 +4711,68 @@ mod tests {
         engine
             .handle_command(Command::SetPlayhead { t_tl: 500_000 })
             .expect("set playhead should succeed");
+        assert!(written.lock().expect("lock written frames").is_empty());
+
         engine
-            .handle_command(Command::SetPlayhead { t_tl: 533_333 })
-            .expect("set playhead should succeed");
+            .handle_command(Command::SetScrubAudio { enabled: true })
+            .expect("enable scrub audio");
         engine
-            .handle_command(Command::SetPlayhead { t_tl: 500_000 })
+            .handle_command(Command::SetPlayhead { t_tl: 600_000 })
             .expect("set playhead should succeed");
 
-        let calls = calls.lock().expect("lock decode calls");
-        assert_eq!(calls.len(), 2);
-        assert_eq!(count_close_calls(&calls, 1.5), 1);
-        assert_eq!(count_close_calls(&calls, 1.533_333), 1);
+        assert_eq!(*written.lock().expect("lock written frames"), vec![3_840]);
     }
 
     #[test]
-    fn set_playhead_on_cache_hit_prefetches_when_idle_direction_is_unknown() {
-        let backend = MockBackend::new(sample_probed_media(), sample_frame());
-        let calls = backend.decode_calls();
-        let mut engine = Engine::new(backend);
+    fn shuttle_forward_doubles_rate_and_clock_speed() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
         engine
             .handle_command(Command::Import {
                 path: PathBuf::from("demo.mp4"),
             })
             .expect("import should succeed");
-
-        engine
-            .handle_command(Command::SetPlayhead { t_tl: 500_000 })
-            .expect("set playhead should succeed");
         engine
-            .handle_command(Command::SetPlayhead { t_tl: 500_000 })
-            .expect("second set playhead should succeed");
+            .handle_command(Command::ShuttleForward)
+            .expect("shuttle should succeed");
 
-        let calls = calls.lock().expect("lock decode calls");
-        assert_eq!(count_close_calls(&calls, 1.5), 1);
-        assert_eq!(calls.len(), 2);
-        assert!(calls.iter().any(|seconds| (*seconds - 1.5).abs() > 1e-6));
+        let events = engine
+            .handle_command(Command::ShuttleForward)
+            .expect("second shuttle should succeed");
+        assert_eq!(
+            events[0],
+            Event::PlaybackStateChanged {
+                playing: true,
+                rate: 2,
+            }
+        );
+        assert_eq!(events[1], Event::PlayheadChanged { t_tl: 300_000 });
+
+        let events = engine.tick_playback().expect("tick should succeed");
+        assert_eq!(events[0], Event::PlayheadChanged { t_tl: 900_000 });
+
+        for expected_rate in [4, 8] {
+            let events = engine
+                .handle_command(Command::ShuttleForward)
+                .expect("shuttle should succeed");
+            assert_eq!(
+                events[0],
+                Event::PlaybackStateChanged {
+                    playing: true,
+                    rate: expected_rate,
+                }
+            );
+        }
+
+        let events = engine
+            .handle_command(Command::ShuttleForward)
+            .expect("shuttle at the top rate should succeed");
+        assert_eq!(events, Vec::new(), "shuttle is capped at 8x");
     }
 
     #[test]
-    fn edit_commands_invalidate_preview_cache() {
+    fn shuttle_reverse_plays_backwards_with_silence_and_stops_at_start() {
         let backend = MockBackend::new(sample_probed_media(), sample_frame());
-        let calls = backend.decode_calls();
+        let audio_calls = backend.audio_calls();
         let mut engine = Engine::new(backend);
         engine
             .handle_command(Command::Import {
@@ -731,352 +4783,852 @@ mod tests {
             .handle_command(Command::SetPlayhead { t_tl: 500_000 })
             .expect("set playhead should succeed");
 
+        let events = engine
+            .handle_command(Command::ShuttleReverse)
+            .expect("shuttle should succeed");
+        assert_eq!(
+            events[0],
+            Event::PlaybackStateChanged {
+                playing: true,
+                rate: -1,
+            }
+        );
+        let events = engine.tick_playback().expect("tick should succeed");
+        assert_eq!(events[0], Event::PlayheadChanged { t_tl: 200_000 });
+
+        let events = engine.tick_playback().expect("tick should succeed");
+        assert_eq!(events[0], Event::PlayheadChanged { t_tl: 0 });
+        assert_eq!(
+            events.last(),
+            Some(&Event::PlaybackStateChanged {
+                playing: false,
+                rate: 0,
+            })
+        );
+        assert!(audio_calls.lock().expect("lock audio calls").is_empty());
+    }
+
+    #[test]
+    fn step_frame_uses_asset_frame_rate_and_pauses_playback() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
         engine
-            .handle_command(Command::Split { at_tl: 333_333 })
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+
+        let events = engine
+            .handle_command(Command::StepFrame { delta: 1 })
+            .expect("step should succeed");
+        assert_eq!(events[0], Event::PlayheadChanged { t_tl: 33_367 });
+
+        let events = engine
+            .handle_command(Command::StepFrame { delta: 29 })
+            .expect("step should succeed");
+        assert_eq!(events[0], Event::PlayheadChanged { t_tl: 1_001_000 });
+
+        engine
+            .handle_command(Command::Play)
+            .expect("play should succeed");
+        let events = engine
+            .handle_command(Command::StepFrame { delta: -1 })
+            .expect("step should succeed");
+        assert_eq!(
+            events[0],
+            Event::PlaybackStateChanged {
+                playing: false,
+                rate: 0,
+            }
+        );
+        assert!(!engine.is_playing());
+    }
+
+    #[test]
+    fn jump_to_edit_point_moves_between_segment_boundaries() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        engine
+            .handle_command(Command::Split { at_tl: 400_000 })
             .expect("split should succeed");
         engine
-            .handle_command(Command::SetPlayhead { t_tl: 500_000 })
+            .handle_command(Command::SetPlayhead { t_tl: 100_000 })
             .expect("set playhead should succeed");
 
-        let calls = calls.lock().expect("lock decode calls");
-        assert_eq!(count_close_calls(&calls, 1.5), 2);
+        let next = |engine: &mut Engine<MockBackend>, direction| {
+            let events = engine
+                .handle_command(Command::JumpToEditPoint { direction })
+                .expect("jump should succeed");
+            events.first().cloned()
+        };
+
+        assert_eq!(
+            next(&mut engine, EditPointDirection::Next),
+            Some(Event::PlayheadChanged { t_tl: 400_000 })
+        );
+        assert_eq!(
+            next(&mut engine, EditPointDirection::Next),
+            Some(Event::PlayheadChanged { t_tl: 1_199_999 })
+        );
+        assert_eq!(next(&mut engine, EditPointDirection::Next), None);
+        assert_eq!(
+            next(&mut engine, EditPointDirection::Previous),
+            Some(Event::PlayheadChanged { t_tl: 400_000 })
+        );
     }
 
     #[test]
-    fn segment_id_not_found_maps_to_other_error_kind() {
-        let error = crate::error::EngineError::SegmentIdNotFound { segment_id: 99 };
-        assert_eq!(EngineErrorKind::from(&error), EngineErrorKind::Other);
+    fn in_out_marks_and_loop_flag_are_reported_in_snapshot() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+
+        engine
+            .handle_command(Command::SetInPoint { at_tl: 300_000 })
+            .expect("set in should succeed");
+        let events = engine
+            .handle_command(Command::SetOutPoint { at_tl: 2_000_000 })
+            .expect("set out should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("mark commands must emit ProjectUpdated");
+        };
+        let snapshot = &snapshot_after(&mut engine, delta);
+        assert_eq!(snapshot.in_point_tl, Some(300_000));
+        assert_eq!(snapshot.out_point_tl, Some(1_200_000));
+        assert!(!snapshot.loop_playback);
+
+        let revision = snapshot.revision;
+        let events = engine
+            .handle_command(Command::SetLoopPlayback { enabled: true })
+            .expect("loop toggle should succeed");
+        assert_eq!(events, vec![Event::LoopPlaybackChanged { enabled: true }]);
+        let events = engine
+            .handle_command(Command::RequestProjectSnapshot)
+            .expect("snapshot request should succeed");
+        let Event::ProjectChanged(snapshot) = &events[0] else {
+            panic!("snapshot request must emit ProjectChanged");
+        };
+        assert!(snapshot.loop_playback);
+        assert_eq!(snapshot.revision, revision, "looping is not a project edit");
+
+        let events = engine
+            .handle_command(Command::ClearInOut)
+            .expect("clear should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("clear must emit ProjectUpdated");
+        };
+        let snapshot = &snapshot_after(&mut engine, delta);
+        assert_eq!(snapshot.in_point_tl, None);
+        assert_eq!(snapshot.out_point_tl, None);
     }
 
     #[test]
-    fn split_creates_two_contiguous_segments_with_split_source_ranges() {
+    fn loop_playback_starts_at_in_point_and_wraps_from_out_to_in() {
         let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
         engine
             .handle_command(Command::Import {
                 path: PathBuf::from("demo.mp4"),
             })
             .expect("import should succeed");
+        engine
+            .handle_command(Command::SetInPoint { at_tl: 100_000 })
+            .expect("set in should succeed");
+        engine
+            .handle_command(Command::SetOutPoint { at_tl: 1_000_000 })
+            .expect("set out should succeed");
+        engine
+            .handle_command(Command::SetLoopPlayback { enabled: true })
+            .expect("loop toggle should succeed");
 
         let events = engine
-            .handle_command(Command::Split { at_tl: 333_333 })
-            .expect("split should succeed");
+            .handle_command(Command::Play)
+            .expect("play should succeed");
+        assert_eq!(events[1], Event::PlayheadChanged { t_tl: 100_000 });
+
+        let mut playheads = Vec::new();
+        for _ in 0..3 {
+            let events = engine.tick_playback().expect("tick should succeed");
+            playheads.extend(events.iter().filter_map(|event| match event {
+                Event::PlayheadChanged { t_tl } => Some(*t_tl),
+                _ => None,
+            }));
+        }
+        assert_eq!(playheads, vec![400_000, 700_000, 100_000]);
+        assert!(engine.is_playing());
+    }
 
-        let Event::ProjectChanged(snapshot) = &events[0] else {
-            panic!("split must emit ProjectChanged");
+    #[test]
+    fn delete_in_out_range_ripples_and_moves_playhead_to_range_start() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        let error = engine
+            .handle_command(Command::DeleteInOutRange { ripple: true })
+            .expect_err("delete without marks must fail");
+        assert!(matches!(error, EngineError::InOutRangeNotSet));
+
+        engine
+            .handle_command(Command::SetInPoint { at_tl: 300_000 })
+            .expect("set in should succeed");
+        engine
+            .handle_command(Command::SetOutPoint { at_tl: 500_000 })
+            .expect("set out should succeed");
+        engine
+            .handle_command(Command::SetPlayhead { t_tl: 400_000 })
+            .expect("set playhead should succeed");
+
+        let events = engine
+            .handle_command(Command::DeleteInOutRange { ripple: true })
+            .expect("extract should succeed");
+
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("extract must emit ProjectUpdated");
         };
+        let snapshot = &snapshot_after(&mut engine, delta);
+        assert_eq!(snapshot.duration_tl, 1_000_000);
         assert_eq!(snapshot.segments.len(), 2);
+        assert_eq!(snapshot.segments[1].timeline_start, 300_000);
+        assert_eq!(snapshot.segments[1].src_in_video, Some(135_000));
+        assert_eq!(snapshot.in_point_tl, None);
+        assert_eq!(events[1], Event::PlayheadChanged { t_tl: 300_000 });
+    }
 
-        let left = &snapshot.segments[0];
-        assert_eq!(left.timeline_start, 0);
-        assert_eq!(left.timeline_duration, 333_333);
-        assert_eq!(left.src_in_video, Some(90_000));
-        assert_eq!(left.src_out_video, Some(120_000));
-        assert_eq!(left.src_in_audio, Some(48_000));
-        assert_eq!(left.src_out_audio, Some(64_000));
+    #[test]
+    fn export_in_out_range_exports_only_marked_span() {
+        let backend = MockBackend::new(sample_probed_media(), sample_frame());
+        let export_calls = backend.export_calls();
+        let mut engine = Engine::new(backend);
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+        let settings = ExportSettings {
+            range: ExportRange::InOut,
+        };
+        let error = engine
+            .handle_command(Command::Export {
+                path: PathBuf::from("out.mp4"),
+                settings,
+            })
+            .expect_err("in/out export without marks must fail");
+        assert!(matches!(error, EngineError::InOutRangeNotSet));
 
-        let right = &snapshot.segments[1];
-        assert_eq!(right.timeline_start, 333_333);
-        assert_eq!(right.timeline_duration, 866_667);
-        assert_eq!(right.src_in_video, Some(120_000));
-        assert_eq!(right.src_out_video, Some(198_000));
-        assert_eq!(right.src_in_audio, Some(64_000));
-        assert_eq!(right.src_out_audio, Some(105_600));
+        engine
+            .handle_command(Command::SetInPoint { at_tl: 300_000 })
+            .expect("set in should succeed");
+        engine
+            .handle_command(Command::SetOutPoint { at_tl: 500_000 })
+            .expect("set out should succeed");
+        engine
+            .handle_command(Command::Export {
+                path: PathBuf::from("out.mp4"),
+                settings,
+            })
+            .expect("export should succeed");
+
+        let calls = export_calls.lock().expect("lock export calls");
+        assert_eq!(calls[0].segments.len(), 1);
+        assert_eq!(calls[0].segments[0].src_in_video, 117_000);
+        assert_eq!(calls[0].segments[0].src_out_video, 135_000);
+        assert_eq!(calls[0].segments[0].src_in_audio, Some(62_400));
+        assert_eq!(calls[0].segments[0].src_out_audio, Some(72_000));
+    }
+
+    #[test]
+    fn set_playhead_cache_miss_returns_before_decode_and_reports_generation_later() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        engine
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
+            })
+            .expect("import should succeed");
+
+        let events = engine
+            .handle_command(Command::SetPlayhead { t_tl: 500_000 })
+            .expect("set playhead should succeed");
+        assert_eq!(events, vec![Event::PlayheadChanged { t_tl: 500_000 }]);
+        assert!(engine.has_pending_previews());
+
+        let events = engine.wait_for_previews(PREVIEW_WAIT);
+        assert!(!engine.has_pending_previews());
+        // The draft may lose the race against the exact frame and be dropped.
+        assert!(matches!(
+            events.as_slice(),
+            [.., Event::PreviewFrameReady {
+                t_tl: 500_000,
+                generation,
+                quality: PreviewQuality::Full,
+                ..
+            }] if *generation == engine.preview_generation
+        ));
+        assert!(events.len() <= 2);
     }
 
     #[test]
-    fn split_at_timeline_boundaries_returns_error() {
-        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+    fn source_playhead_previews_bin_asset_through_shared_cache() {
+        let backend = MockBackend::new(sample_probed_media(), sample_frame());
+        let calls = backend.decode_calls();
+        let mut engine = Engine::new(backend);
         engine
             .handle_command(Command::Import {
                 path: PathBuf::from("demo.mp4"),
             })
             .expect("import should succeed");
 
-        let start = engine.handle_command(Command::Split { at_tl: 0 });
+        let events = engine
+            .handle_command(Command::SetSourcePlayhead {
+                asset_id: 1,
+                t_src: 500_000,
+            })
+            .expect("source playhead should succeed");
+        assert_eq!(
+            events,
+            vec![Event::SourcePlayheadChanged {
+                asset_id: 1,
+                t_src: 500_000
+            }]
+        );
+        let events = engine.wait_for_previews(PREVIEW_WAIT);
         assert!(matches!(
-            start,
-            Err(crate::error::EngineError::SplitPointAtBoundary { at_tl: 0 })
+            events.as_slice(),
+            [.., Event::SourcePreviewFrameReady {
+                asset_id: 1,
+                t_src: 500_000,
+                generation,
+                quality: PreviewQuality::Full,
+                ..
+            }] if *generation == engine.source_generation
         ));
+        assert_eq!(
+            count_close_calls(&calls.lock().expect("lock decode calls"), 1.5),
+            1
+        );
 
-        let end = engine.handle_command(Command::Split { at_tl: 1_200_000 });
+        // The timeline frame at the same source time comes from the cache.
+        let events = engine
+            .handle_command(Command::SetPlayhead { t_tl: 500_000 })
+            .expect("set playhead should succeed");
         assert!(matches!(
-            end,
-            Err(crate::error::EngineError::SplitPointAtBoundary { at_tl: 1_200_000 })
+            events.as_slice(),
+            [
+                Event::PlayheadChanged { t_tl: 500_000 },
+                Event::PreviewFrameReady {
+                    quality: PreviewQuality::Full,
+                    ..
+                }
+            ]
+        ));
+
+        let events = engine
+            .handle_command(Command::SetSourcePlayhead {
+                asset_id: 1,
+                t_src: 5_000_000,
+            })
+            .expect("source playhead should clamp");
+        assert_eq!(
+            events[0],
+            Event::SourcePlayheadChanged {
+                asset_id: 1,
+                t_src: 1_199_999
+            }
+        );
+        assert!(matches!(
+            engine.handle_command(Command::SetSourcePlayhead {
+                asset_id: 7,
+                t_src: 0
+            }),
+            Err(EngineError::MissingAsset { asset_id: 7 })
         ));
     }
 
     #[test]
-    fn cut_middle_segment_preserves_gap_in_timeline() {
+    fn match_frame_loads_source_position_under_timeline_playhead() {
         let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
         engine
-            .handle_command(Command::Import {
+            .handle_command(Command::AddAsset {
                 path: PathBuf::from("demo.mp4"),
             })
-            .expect("import should succeed");
+            .expect("add asset should succeed");
         engine
-            .handle_command(Command::Split { at_tl: 300_000 })
-            .expect("first split should succeed");
+            .handle_command(Command::SetSourceInPoint {
+                asset_id: 1,
+                at_src: 200_000,
+            })
+            .expect("source in should succeed");
         engine
-            .handle_command(Command::Split { at_tl: 900_000 })
-            .expect("second split should succeed");
-
+            .handle_command(Command::InsertFromAsset {
+                asset_id: 1,
+                at_tl: 0,
+            })
+            .expect("insert should succeed");
         let events = engine
-            .handle_command(Command::Cut { at_tl: 500_000 })
-            .expect("cut should succeed");
-        let Event::ProjectChanged(snapshot) = &events[0] else {
-            panic!("cut must emit ProjectChanged");
-        };
-
-        assert_eq!(snapshot.duration_tl, 1_200_000);
-        assert_eq!(snapshot.segments.len(), 2);
-        assert_eq!(snapshot.segments[0].timeline_start, 0);
-        assert_eq!(snapshot.segments[0].timeline_duration, 300_000);
-        assert_eq!(snapshot.segments[0].src_in_video, Some(90_000));
-        assert_eq!(snapshot.segments[0].src_out_video, Some(117_000));
-        assert_eq!(snapshot.segments[0].src_in_audio, Some(48_000));
-        assert_eq!(snapshot.segments[0].src_out_audio, Some(62_400));
+            .handle_command(Command::SetPlayhead { t_tl: 100_000 })
+            .expect("set playhead should succeed");
+        settle(&mut engine, events);
 
-        assert_eq!(snapshot.segments[1].timeline_start, 900_000);
-        assert_eq!(snapshot.segments[1].timeline_duration, 300_000);
-        assert_eq!(snapshot.segments[1].src_in_video, Some(171_000));
-        assert_eq!(snapshot.segments[1].src_out_video, Some(198_000));
-        assert_eq!(snapshot.segments[1].src_in_audio, Some(91_200));
-        assert_eq!(snapshot.segments[1].src_out_audio, Some(105_600));
+        let events = engine
+            .handle_command(Command::MatchFrame)
+            .expect("match frame should succeed");
+        assert_eq!(
+            events[0],
+            Event::SourcePlayheadChanged {
+                asset_id: 1,
+                t_src: 300_000
+            }
+        );
     }
 
     #[test]
-    fn move_segment_repositions_clip_without_changing_source_range() {
+    fn replace_segment_media_aligns_source_monitor_with_playhead() {
         let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        for path in ["demo.mp4", "demo_graded.mp4"] {
+            engine
+                .handle_command(Command::AddAsset {
+                    path: PathBuf::from(path),
+                })
+                .expect("add asset should succeed");
+        }
         engine
-            .handle_command(Command::Import {
-                path: PathBuf::from("demo.mp4"),
+            .handle_command(Command::SetSourceOutPoint {
+                asset_id: 1,
+                at_src: 300_000,
             })
-            .expect("import should succeed");
-        engine
-            .handle_command(Command::Split { at_tl: 300_000 })
-            .expect("first split should succeed");
+            .expect("source out should succeed");
         engine
-            .handle_command(Command::Split { at_tl: 900_000 })
-            .expect("second split should succeed");
+            .handle_command(Command::InsertFromAsset {
+                asset_id: 1,
+                at_tl: 0,
+            })
+            .expect("insert should succeed");
+        let events = engine
+            .handle_command(Command::SetSourcePlayhead {
+                asset_id: 2,
+                t_src: 600_000,
+            })
+            .expect("source playhead should succeed");
+        settle(&mut engine, events);
+        let events = engine
+            .handle_command(Command::SetPlayhead { t_tl: 100_000 })
+            .expect("set playhead should succeed");
+        settle(&mut engine, events);
 
         let events = engine
-            .handle_command(Command::MoveSegment {
-                segment_id: 3,
-                new_start_tl: 1_000_000,
+            .handle_command(Command::ReplaceSegmentMedia {
+                segment_id: 1,
+                asset_id: 2,
+                align: ReplaceAlignment::Playhead,
             })
-            .expect("move should succeed");
-        let Event::ProjectChanged(snapshot) = &events[0] else {
-            panic!("move must emit ProjectChanged");
+            .expect("replace should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("replace must emit ProjectUpdated");
         };
+        let replaced = &delta.segments_updated[0];
+        assert_eq!(replaced.asset_id, 2);
+        assert_eq!(replaced.timeline_start, 0);
+        assert_eq!(replaced.timeline_duration, 300_000);
+        assert_eq!(replaced.src_in_video, Some(135_000));
 
-        assert_eq!(snapshot.duration_tl, 1_300_000);
-        assert_eq!(snapshot.segments.len(), 3);
-
-        let moved = &snapshot.segments[2];
-        assert_eq!(moved.id, 3);
-        assert_eq!(moved.timeline_start, 1_000_000);
-        assert_eq!(moved.timeline_duration, 300_000);
-        assert_eq!(moved.src_in_video, Some(171_000));
-        assert_eq!(moved.src_out_video, Some(198_000));
-        assert_eq!(moved.src_in_audio, Some(91_200));
-        assert_eq!(moved.src_out_audio, Some(105_600));
+        let events = engine
+            .handle_command(Command::SetSourcePlayhead {
+                asset_id: 2,
+                t_src: 1_100_000,
+            })
+            .expect("source playhead should succeed");
+        settle(&mut engine, events);
+        assert!(matches!(
+            engine.handle_command(Command::ReplaceSegmentMedia {
+                segment_id: 1,
+                asset_id: 2,
+                align: ReplaceAlignment::Playhead,
+            }),
+            Err(EngineError::InsufficientMedia {
+                asset_id: 2,
+                start_tl: 1_000_000,
+                end_tl: 1_300_000,
+                duration_tl: 1_200_000,
+            })
+        ));
+        let project = engine.project.as_ref().expect("project");
+        assert_eq!(project.timeline.segments[0].src_in_video, Some(135_000));
     }
 
     #[test]
-    fn trim_segment_start_updates_timeline_and_source_in() {
+    fn save_project_writes_media_paths_relative_to_the_project_file() {
+        let dir = std::env::temp_dir().join(format!("cutit-save-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("media")).expect("create media dir");
+        let media_path = dir.join("media/clip.mp4");
+        std::fs::write(&media_path, b"media").expect("write media");
+        let project_path = dir.join("cut.nle.json");
         let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        assert!(matches!(
+            engine.handle_command(Command::SaveProject {
+                path: project_path.clone(),
+                paths: MediaPathStyle::Relative,
+            }),
+            Err(EngineError::ProjectNotLoaded)
+        ));
         engine
             .handle_command(Command::Import {
-                path: PathBuf::from("demo.mp4"),
+                path: media_path.clone(),
             })
             .expect("import should succeed");
-        engine
-            .handle_command(Command::Split { at_tl: 300_000 })
-            .expect("first split should succeed");
-        engine
-            .handle_command(Command::Split { at_tl: 900_000 })
-            .expect("second split should succeed");
 
         let events = engine
-            .handle_command(Command::TrimSegmentStart {
-                segment_id: 2,
-                new_start_tl: 400_000,
+            .handle_command(Command::SaveProject {
+                path: project_path.clone(),
+                paths: MediaPathStyle::Relative,
             })
-            .expect("trim start should succeed");
+            .expect("save should succeed");
+        assert_eq!(
+            events,
+            vec![Event::ProjectSaved {
+                path: project_path.clone()
+            }]
+        );
+        let text = std::fs::read_to_string(&project_path).expect("read project");
+        assert!(text.contains("\"path\": \"media/clip.mp4\""));
+
+        let events = engine
+            .handle_command(Command::OpenProject { path: project_path })
+            .expect("open should succeed");
         let Event::ProjectChanged(snapshot) = &events[0] else {
-            panic!("trim start must emit ProjectChanged");
+            panic!("open must emit ProjectChanged");
         };
-
-        let trimmed = &snapshot.segments[1];
-        assert_eq!(trimmed.id, 2);
-        assert_eq!(trimmed.timeline_start, 400_000);
-        assert_eq!(trimmed.timeline_duration, 500_000);
-        assert_eq!(trimmed.src_in_video, Some(126_000));
-        assert_eq!(trimmed.src_out_video, Some(171_000));
-        assert_eq!(trimmed.src_in_audio, Some(67_200));
-        assert_eq!(trimmed.src_out_audio, Some(91_200));
+        assert_eq!(snapshot.assets[0].path, media_path);
+        assert!(!snapshot.assets[0].offline);
+        std::fs::remove_dir_all(dir).expect("cleanup save dir");
     }
 
     #[test]
-    fn trim_segment_end_updates_timeline_and_source_out() {
-        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
-        engine
+    fn open_project_reports_offline_assets_until_relinked() {
+        let dir = std::env::temp_dir().join(format!("cutit-relink-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create relink dir");
+        std::fs::write(dir.join("offline-demo.mp4"), b"media").expect("write moved media");
+        let project_path = dir.join("cut.nle.json");
+        let mut saved = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        saved
             .handle_command(Command::Import {
-                path: PathBuf::from("demo.mp4"),
+                path: PathBuf::from("offline-demo.mp4"),
             })
             .expect("import should succeed");
-        engine
-            .handle_command(Command::Split { at_tl: 300_000 })
-            .expect("first split should succeed");
-        engine
-            .handle_command(Command::Split { at_tl: 900_000 })
-            .expect("second split should succeed");
+        saved
+            .project
+            .as_ref()
+            .expect("project")
+            .save_to_file(&project_path)
+            .expect("save should succeed");
 
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
         let events = engine
-            .handle_command(Command::TrimSegmentEnd {
-                segment_id: 2,
-                new_end_tl: 800_000,
+            .handle_command(Command::OpenProject {
+                path: project_path.clone(),
             })
-            .expect("trim end should succeed");
+            .expect("offline media must not fail the load");
         let Event::ProjectChanged(snapshot) = &events[0] else {
-            panic!("trim end must emit ProjectChanged");
+            panic!("open must emit ProjectChanged");
         };
+        assert!(snapshot.assets[0].offline);
+        assert!(matches!(
+            engine.handle_command(Command::SetPlayhead { t_tl: 0 }),
+            Err(EngineError::AssetOffline { asset_id: 1, .. })
+        ));
 
-        let trimmed = &snapshot.segments[1];
-        assert_eq!(trimmed.id, 2);
-        assert_eq!(trimmed.timeline_start, 300_000);
-        assert_eq!(trimmed.timeline_duration, 500_000);
-        assert_eq!(trimmed.src_in_video, Some(117_000));
-        assert_eq!(trimmed.src_out_video, Some(162_000));
-        assert_eq!(trimmed.src_in_audio, Some(62_400));
-        assert_eq!(trimmed.src_out_audio, Some(86_400));
+        let events = engine
+            .handle_command(Command::RelinkFromDirectory { dir: dir.clone() })
+            .expect("relink from directory should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("relink must emit ProjectUpdated");
+        };
+        assert_eq!(delta.assets_updated[0].path, dir.join("offline-demo.mp4"));
+        assert!(!delta.assets_updated[0].offline);
+
+        let events = engine
+            .handle_command(Command::RelinkAsset {
+                asset_id: 1,
+                new_path: PathBuf::from("graded/offline-demo.mp4"),
+            })
+            .expect("relink should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("relink must emit ProjectUpdated");
+        };
+        assert_eq!(
+            delta.assets_updated[0].path,
+            PathBuf::from("graded/offline-demo.mp4")
+        );
+        let events = engine
+            .handle_command(Command::AddAsset {
+                path: PathBuf::from("broll.mp4"),
+            })
+            .expect("add asset should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("add asset must emit ProjectUpdated");
+        };
+        assert_eq!(delta.assets_added[0].id, 2);
+        std::fs::remove_dir_all(dir).expect("cleanup relink dir");
     }
 
     #[test]
-    fn set_playhead_inside_gap_emits_playhead_changed_without_preview_decode() {
-        let backend = MockBackend::new(sample_probed_media(), sample_frame());
-        let calls = backend.decode_calls();
-        let mut engine = Engine::new(backend);
+    fn autosave_writes_a_recovery_copy_that_a_new_engine_offers_to_restore() {
+        let dir = std::env::temp_dir().join(format!("cutit-autosave-{}", std::process::id()));
+        let config = AutosaveConfig {
+            debounce: Duration::ZERO,
+            state_dir: Some(dir.join("state")),
+        };
+        let untitled = dir.join("state/untitled.nle.json.recovery");
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()))
+            .with_autosave(config.clone());
         engine
             .handle_command(Command::Import {
                 path: PathBuf::from("demo.mp4"),
             })
             .expect("import should succeed");
-        engine
-            .handle_command(Command::Split { at_tl: 300_000 })
-            .expect("first split should succeed");
-        engine
-            .handle_command(Command::Split { at_tl: 900_000 })
-            .expect("second split should succeed");
-        engine
-            .handle_command(Command::Cut { at_tl: 500_000 })
-            .expect("cut should succeed");
+        assert!(engine.autosave_due().is_some());
 
-        let events = engine
-            .handle_command(Command::SetPlayhead { t_tl: 600_000 })
-            .expect("set playhead should succeed");
+        let events = engine.tick_autosave().expect("autosave should succeed");
+        assert_eq!(
+            events,
+            vec![Event::RecoverySaved {
+                path: untitled.clone()
+            }]
+        );
+        assert_eq!(engine.autosave_due(), None);
+        assert!(untitled.exists());
 
-        assert_eq!(events, vec![Event::PlayheadChanged { t_tl: 600_000 }]);
-        assert!(calls.lock().expect("lock decode calls").is_empty());
+        let mut restored = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()))
+            .with_autosave(config.clone());
+        assert!(matches!(
+            restored.handle_command(Command::RestoreRecovery),
+            Err(EngineError::RecoveryNotAvailable)
+        ));
+        let events = restored
+            .handle_command(Command::CheckForRecovery)
+            .expect("check should succeed");
+        assert_eq!(
+            events,
+            vec![Event::RecoveryAvailable {
+                recovery_path: untitled.clone(),
+                project_path: None,
+            }]
+        );
+        let events = restored
+            .handle_command(Command::RestoreRecovery)
+            .expect("restore should succeed");
+        let Event::ProjectChanged(snapshot) = &events[0] else {
+            panic!("restore must emit ProjectChanged");
+        };
+        assert!(snapshot.assets[0].path.ends_with("demo.mp4"));
+
+        let mut discarded = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()))
+            .with_autosave(config);
+        discarded
+            .handle_command(Command::CheckForRecovery)
+            .expect("check should succeed");
+        discarded
+            .handle_command(Command::DiscardRecovery)
+            .expect("discard should succeed");
+        assert!(!untitled.exists());
+        std::fs::remove_dir_all(dir).expect("cleanup autosave dir");
     }
 
     #[test]
-    fn failed_split_does_not_consume_next_segment_id() {
-        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+    fn saving_removes_recovery_copies_and_open_offers_newer_ones() {
+        let dir = std::env::temp_dir().join(format!("cutit-recover-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create recovery dir");
+        let config = AutosaveConfig {
+            debounce: Duration::ZERO,
+            state_dir: Some(dir.join("state")),
+        };
+        let project_path = dir.join("cut.nle.json");
+        let recovery_path = dir.join("cut.nle.json.recovery");
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()))
+            .with_autosave(config.clone());
         engine
             .handle_command(Command::Import {
                 path: PathBuf::from("demo.mp4"),
             })
             .expect("import should succeed");
+        engine.tick_autosave().expect("autosave should succeed");
+        assert!(dir.join("state/untitled.nle.json.recovery").exists());
 
-        let boundary_result = engine.handle_command(Command::Split { at_tl: 0 });
-        assert!(matches!(
-            boundary_result,
-            Err(crate::error::EngineError::SplitPointAtBoundary { at_tl: 0 })
-        ));
+        engine
+            .handle_command(Command::SaveProject {
+                path: project_path.clone(),
+                paths: MediaPathStyle::Relative,
+            })
+            .expect("save should succeed");
+        assert!(!dir.join("state/untitled.nle.json.recovery").exists());
+        assert_eq!(engine.autosave_due(), None);
+        engine
+            .handle_command(Command::SetLoopPlayback { enabled: true })
+            .expect("loop toggle should succeed");
+        assert_eq!(
+            engine.autosave_due(),
+            None,
+            "looping is not an unsaved edit"
+        );
 
-        let events = engine
-            .handle_command(Command::Split { at_tl: 333_333 })
+        engine
+            .handle_command(Command::Split { at_tl: 600_000 })
             .expect("split should succeed");
+        let events = engine.tick_autosave().expect("autosave should succeed");
+        assert_eq!(
+            events,
+            vec![Event::RecoverySaved {
+                path: recovery_path.clone()
+            }]
+        );
+
+        let mut reopened = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()))
+            .with_autosave(config);
+        let events = reopened
+            .handle_command(Command::OpenProject {
+                path: project_path.clone(),
+            })
+            .expect("open should succeed");
+        assert_eq!(
+            events.last(),
+            Some(&Event::RecoveryAvailable {
+                recovery_path: recovery_path.clone(),
+                project_path: Some(project_path),
+            })
+        );
+        let events = reopened
+            .handle_command(Command::RestoreRecovery)
+            .expect("restore should succeed");
         let Event::ProjectChanged(snapshot) = &events[0] else {
-            panic!("split must emit ProjectChanged");
+            panic!("restore must emit ProjectChanged");
         };
-
-        let ids: Vec<u64> = snapshot.segments.iter().map(|segment| segment.id).collect();
-        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(snapshot.segments.len(), 2);
+        std::fs::remove_dir_all(dir).expect("cleanup recovery dir");
     }
 
     #[test]
-    fn repeated_splits_keep_timeline_contiguous_and_duration_stable() {
+    fn open_offers_a_backup_when_the_project_file_fails_validation() {
+        let dir = std::env::temp_dir().join(format!("cutit-invalid-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create backup dir");
+        let project_path = dir.join("cut.nle.json");
         let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
         engine
             .handle_command(Command::Import {
                 path: PathBuf::from("demo.mp4"),
             })
             .expect("import should succeed");
-        engine
-            .handle_command(Command::Split { at_tl: 333_333 })
-            .expect("first split should succeed");
-
-        let events = engine
-            .handle_command(Command::Split { at_tl: 900_000 })
-            .expect("second split should succeed");
-        let Event::ProjectChanged(snapshot) = &events[0] else {
-            panic!("split must emit ProjectChanged");
+        for _ in 0..2 {
+            engine
+                .handle_command(Command::SaveProject {
+                    path: project_path.clone(),
+                    paths: MediaPathStyle::Relative,
+                })
+                .expect("save should succeed");
+        }
+        let text = std::fs::read_to_string(&project_path).expect("read project");
+        let mut document: serde_json::Value = serde_json::from_str(&text).expect("parse project");
+        document["segments"][0]["asset_id"] = serde_json::json!(99);
+        std::fs::write(&project_path, document.to_string()).expect("write project");
+
+        let mut reopened = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        let events = reopened
+            .handle_command(Command::OpenProject {
+                path: project_path.clone(),
+            })
+            .expect("an invalid file with a backup should be offered");
+        let [Event::BackupAvailable { reason, .. }] = events.as_slice() else {
+            panic!("open must offer the backup, got {events:?}");
         };
+        assert!(reason.contains("asset not found: 99"), "{reason}");
 
-        assert_eq!(snapshot.duration_tl, 1_200_000);
-        assert_eq!(snapshot.segments.len(), 3);
-
-        let first = &snapshot.segments[0];
-        let second = &snapshot.segments[1];
-        let third = &snapshot.segments[2];
-
-        assert_eq!(
-            first.timeline_start + first.timeline_duration,
-            second.timeline_start
-        );
-        assert_eq!(
-            second.timeline_start + second.timeline_duration,
-            third.timeline_start
-        );
-        assert_eq!(
-            third.timeline_start + third.timeline_duration,
-            snapshot.duration_tl
-        );
+        std::fs::remove_dir_all(dir).expect("cleanup backup dir");
     }
 
     #[test]
-    fn import_emits_playhead_reset_after_scrubbing_previous_project() {
+    fn open_offers_the_newest_valid_backup_of_a_damaged_project_file() {
+        let dir = std::env::temp_dir().join(format!("cutit-backup-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create backup dir");
+        let project_path = dir.join("cut.nle.json");
+        let backup_path = dir.join("cut.nle.json.1");
         let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
         engine
             .handle_command(Command::Import {
-                path: PathBuf::from("first.mp4"),
+                path: PathBuf::from("demo.mp4"),
             })
-            .expect("first import should succeed");
-        engine
-            .handle_command(Command::SetPlayhead { t_tl: 500_000 })
-            .expect("set playhead should succeed");
+            .expect("import should succeed");
+        for _ in 0..2 {
+            engine
+                .handle_command(Command::SaveProject {
+                    path: project_path.clone(),
+                    paths: MediaPathStyle::Relative,
+                })
+                .expect("save should succeed");
+        }
+        std::fs::write(&project_path, b"{ \"schema_version\": 2,").expect("damage project");
 
-        let events = engine
-            .handle_command(Command::Import {
-                path: PathBuf::from("second.mp4"),
+        let mut reopened = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        let events = reopened
+            .handle_command(Command::OpenProject {
+                path: project_path.clone(),
             })
-            .expect("second import should succeed");
+            .expect("a damaged file with a backup should be offered");
+        let [
+            Event::BackupAvailable {
+                backup_path: offered,
+                project_path: damaged,
+                reason,
+            },
+        ] = events.as_slice()
+        else {
+            panic!("open must offer the backup, got {events:?}");
+        };
+        assert_eq!(offered, &backup_path);
+        assert_eq!(damaged, &project_path);
+        assert!(reason.contains("cut.nle.json"));
+
+        reopened
+            .handle_command(Command::DiscardRecovery)
+            .expect("discard should succeed");
+        assert!(backup_path.exists(), "discarding keeps the backup");
+        reopened
+            .handle_command(Command::OpenProject {
+                path: project_path.clone(),
+            })
+            .expect("backup should be offered again");
+        let events = reopened
+            .handle_command(Command::RestoreRecovery)
+            .expect("restore should succeed");
+        assert!(
+            matches!(&events[0], Event::ProjectChanged(snapshot) if snapshot.assets.len() == 1)
+        );
+        reopened
+            .handle_command(Command::SaveProject {
+                path: project_path.clone(),
+                paths: MediaPathStyle::Relative,
+            })
+            .expect("save over the damaged file should succeed");
+        Project::load_from_file(&project_path).expect("project file is repaired");
 
-        assert_eq!(events.len(), 2);
-        assert!(matches!(events[0], Event::ProjectChanged(_)));
-        assert_eq!(events[1], Event::PlayheadChanged { t_tl: 0 });
+        std::fs::write(dir.join("cut.nle.json.2"), b"").expect("damage older backup");
+        std::fs::write(&backup_path, b"").expect("damage newest backup");
+        std::fs::write(&project_path, b"").expect("damage project");
+        assert!(matches!(
+            reopened.handle_command(Command::OpenProject { path: project_path }),
+            Err(EngineError::ProjectSerialization { .. })
+        ));
+        std::fs::remove_dir_all(dir).expect("cleanup backup dir");
     }
 
     #[test]
-    fn set_playhead_clamps_negative_mapped_source_time_to_zero_seconds() {
-        let backend = MockBackend::new(
-            sample_probed_media_with_negative_video_start(),
-            sample_frame(),
-        );
-        let calls = backend.decode_calls();
-        let mut engine = Engine::new(backend);
+    fn edl_export_and_import_rebuild_the_timeline() {
+        let path = std::env::temp_dir().join(format!("cutit-edl-{}.edl", std::process::id()));
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
         engine
             .handle_command(Command::Import {
                 path: PathBuf::from("demo.mp4"),
@@ -1084,181 +5636,339 @@ mod tests {
             .expect("import should succeed");
 
         let events = engine
-            .handle_command(Command::SetPlayhead { t_tl: 0 })
-            .expect("set playhead should succeed");
+            .handle_command(Command::ExportEdl { path: path.clone() })
+            .expect("EDL export should succeed");
+        assert_eq!(events, vec![Event::ExportFinished { path: path.clone() }]);
+        assert!(
+            std::fs::read_to_string(&path)
+                .expect("read EDL")
+                .contains("* FROM CLIP NAME: demo.mp4")
+        );
 
-        assert_eq!(events.len(), 2);
-        assert_eq!(events[0], Event::PlayheadChanged { t_tl: 0 });
-        let decoded_seconds = calls.lock().expect("lock decode calls")[0];
-        assert_eq!(decoded_seconds, 0.0);
+        engine
+            .handle_command(Command::Split { at_tl: 600_000 })
+            .expect("split should succeed");
+        let events = engine
+            .handle_command(Command::ImportEdl { path: path.clone() })
+            .expect("EDL import should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("EDL import must emit ProjectUpdated");
+        };
+        assert_eq!(delta.segments_removed.len(), 2);
+        let segments = &engine.project.as_ref().expect("project").timeline.segments;
+        assert_eq!(segments.len(), 1);
+        assert_eq!(
+            (
+                segments[0].id,
+                segments[0].timeline_start,
+                segments[0].timeline_duration
+            ),
+            (3, 0, 1_200_000)
+        );
+
+        std::fs::write(
+            &path,
+            "001  NOPE V C 00:00:00:00 00:00:01:00 00:00:00:00 00:00:01:00",
+        )
+        .expect("write EDL");
+        assert!(matches!(
+            engine.handle_command(Command::ImportEdl { path: path.clone() }),
+            Err(EngineError::InvalidEdl { line: 1, .. })
+        ));
+        assert_eq!(
+            engine
+                .project
+                .as_ref()
+                .expect("project")
+                .timeline
+                .segments
+                .len(),
+            1
+        );
+        std::fs::remove_file(path).expect("cleanup EDL");
     }
 
     #[test]
-    fn export_calls_backend_with_timeline_ordered_segments() {
-        let backend = MockBackend::new(sample_probed_media(), sample_frame());
-        let export_calls = backend.export_calls();
-        let mut engine = Engine::new(backend);
+    fn otio_import_adds_missing_media_and_rebuilds_the_timeline() {
+        let path = std::env::temp_dir().join(format!("cutit-otio-{}.otio", std::process::id()));
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
         engine
             .handle_command(Command::Import {
                 path: PathBuf::from("demo.mp4"),
             })
             .expect("import should succeed");
+        let events = engine
+            .handle_command(Command::ExportOtio { path: path.clone() })
+            .expect("OTIO export should succeed");
+        assert_eq!(events, vec![Event::ExportFinished { path: path.clone() }]);
+
         engine
-            .handle_command(Command::Split { at_tl: 333_333 })
+            .handle_command(Command::Split { at_tl: 600_000 })
             .expect("split should succeed");
+        engine
+            .handle_command(Command::ImportOtio { path: path.clone() })
+            .expect("OTIO import should succeed");
+        let project = engine.project.as_ref().expect("project");
+        assert_eq!(project.assets.len(), 1);
+        assert_eq!(
+            project
+                .timeline
+                .segments
+                .iter()
+                .map(|segment| (
+                    segment.id,
+                    segment.timeline_start,
+                    segment.timeline_duration
+                ))
+                .collect::<Vec<_>>(),
+            vec![(3, 0, 1_200_000)]
+        );
+
+        let text = std::fs::read_to_string(&path).expect("read OTIO");
+        std::fs::write(
+            &path,
+            text.replace(
+                "\"target_url\": \"demo.mp4\"",
+                "\"target_url\": \"/ingest/other.mov\"",
+            ),
+        )
+        .expect("write OTIO");
+        let mut fresh = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        let events = fresh
+            .handle_command(Command::ImportOtio { path: path.clone() })
+            .expect("OTIO import without a project should succeed");
+        let Event::ProjectChanged(snapshot) = &events[0] else {
+            panic!("OTIO import without a project must emit ProjectChanged");
+        };
+        assert_eq!(snapshot.assets[0].path, PathBuf::from("/ingest/other.mov"));
+        assert_eq!(snapshot.segments.len(), 1);
 
-        let output_path = PathBuf::from("out.mp4");
         let events = engine
-            .handle_command(Command::Export {
-                path: output_path.clone(),
-                settings: ExportSettings::default(),
-            })
-            .expect("export should succeed");
+            .handle_command(Command::ImportOtio { path: path.clone() })
+            .expect("OTIO import should succeed");
+        let Event::ProjectUpdated(delta) = &events[0] else {
+            panic!("OTIO import must emit ProjectUpdated");
+        };
+        assert_eq!(delta.assets_added[0].id, 2);
+        assert_eq!(delta.segments_added[0].asset_id, 2);
 
-        assert_eq!(events.len(), 2);
-        assert_eq!(events[0], Event::ExportProgress { done: 2, total: 2 });
-        assert_eq!(events[1], Event::ExportFinished { path: output_path });
+        std::fs::write(&path, "{}").expect("write OTIO");
+        assert!(matches!(
+            engine.handle_command(Command::ImportOtio { path: path.clone() }),
+            Err(EngineError::InvalidOtio { .. })
+        ));
+        std::fs::remove_file(path).expect("cleanup OTIO");
+    }
 
-        let calls = export_calls.lock().expect("lock export calls");
-        assert_eq!(calls.len(), 1);
+    #[test]
+    fn cut_list_import_probes_media_and_lays_cuts_back_to_back() {
+        let dir = std::env::temp_dir().join(format!("cutit-cut-list-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create cut list dir");
+        let path = dir.join("cuts.csv");
+        std::fs::write(
+            &path,
+            "path,in,out,label\ndemo.mp4,0.2,0.5,intro\ndemo.mp4,00:00:00:03,1\n",
+        )
+        .expect("write cut list");
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
 
-        let plan = &calls[0];
-        assert_eq!(plan.inputs, vec![PathBuf::from("demo.mp4")]);
+        let events = engine
+            .handle_command(Command::ImportCutList { path: path.clone() })
+            .expect("cut list import should succeed");
+        let Event::ProjectChanged(snapshot) = &events[0] else {
+            panic!("cut list import without a project must emit ProjectChanged");
+        };
+        assert_eq!(snapshot.assets.len(), 1);
+        assert_eq!(snapshot.assets[0].path, dir.join("demo.mp4"));
         assert_eq!(
-            plan.audio,
-            Some(ExportAudioSettings {
-                sample_rate: 48_000,
-                channels: 2,
-            })
+            snapshot
+                .segments
+                .iter()
+                .map(|segment| (segment.timeline_start, segment.timeline_duration))
+                .collect::<Vec<_>>(),
+            vec![(0, 300_000), (300_000, 899_900)]
         );
+
+        std::fs::write(&path, "demo.mp4,0,0.5\ndemo.mp4,0.5,0.4\ndemo.mp4,1,9\n")
+            .expect("write cut list");
+        let error = engine
+            .handle_command(Command::ImportCutList { path: path.clone() })
+            .expect_err("bad rows must fail the import");
+        let EngineError::InvalidCutList { errors } = &error else {
+            panic!("expected InvalidCutList, got {error:?}");
+        };
         assert_eq!(
-            plan.segments,
-            vec![
-                ExportVideoSegment {
-                    input_index: 0,
-                    src_in_video: 90_000,
-                    src_out_video: 120_000,
-                    src_video_time_base: Rational::new(1, 90_000).expect("valid rational"),
-                    src_in_audio: Some(48_000),
-                    src_out_audio: Some(64_000),
-                    src_audio_time_base: Some(Rational::new(1, 48_000).expect("valid rational"),),
-                },
-                ExportVideoSegment {
-                    input_index: 0,
-                    src_in_video: 120_000,
-                    src_out_video: 198_000,
-                    src_video_time_base: Rational::new(1, 90_000).expect("valid rational"),
-                    src_in_audio: Some(64_000),
-                    src_out_audio: Some(105_600),
-                    src_audio_time_base: Some(Rational::new(1, 48_000).expect("valid rational"),),
-                },
-            ]
+            errors.iter().map(|error| error.line).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert!(error.to_string().starts_with("invalid cut list: line 2: "));
+        assert_eq!(
+            engine
+                .project
+                .as_ref()
+                .expect("project")
+                .timeline
+                .segments
+                .len(),
+            2
         );
+        std::fs::remove_dir_all(dir).expect("cleanup cut list");
     }
 
     #[test]
-    fn export_skips_zero_length_video_ranges_created_by_subframe_split() {
-        let backend = MockBackend::new(sample_probed_media(), sample_frame());
-        let export_calls = backend.export_calls();
-        let mut engine = Engine::new(backend);
+    fn fcp_xml_export_writes_the_sequence_to_the_requested_path() {
+        let path = std::env::temp_dir().join(format!("cutit-fcp-{}.xml", std::process::id()));
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
+        assert!(matches!(
+            engine.handle_command(Command::ExportFcpXml { path: path.clone() }),
+            Err(EngineError::ProjectNotLoaded)
+        ));
         engine
             .handle_command(Command::Import {
                 path: PathBuf::from("demo.mp4"),
             })
             .expect("import should succeed");
         engine
-            .handle_command(Command::Split { at_tl: 1 })
+            .handle_command(Command::Split { at_tl: 600_000 })
             .expect("split should succeed");
 
         let events = engine
-            .handle_command(Command::Export {
-                path: PathBuf::from("out.mp4"),
-                settings: ExportSettings::default(),
-            })
-            .expect("export should succeed");
-
-        assert_eq!(events.len(), 2);
-        assert_eq!(events[0], Event::ExportProgress { done: 1, total: 1 });
-
-        let calls = export_calls.lock().expect("lock export calls");
-        assert_eq!(calls.len(), 1);
-        assert_eq!(calls[0].segments.len(), 1);
-        assert_eq!(
-            calls[0].audio,
-            Some(ExportAudioSettings {
-                sample_rate: 48_000,
-                channels: 2,
-            })
-        );
-        assert_eq!(calls[0].segments[0].src_in_video, 90_000);
-        assert_eq!(calls[0].segments[0].src_out_video, 198_000);
-        assert_eq!(calls[0].segments[0].src_in_audio, Some(48_000));
-        assert_eq!(calls[0].segments[0].src_out_audio, Some(105_600));
+            .handle_command(Command::ExportFcpXml { path: path.clone() })
+            .expect("FCP XML export should succeed");
+        assert_eq!(events, vec![Event::ExportFinished { path: path.clone() }]);
+        let text = std::fs::read_to_string(&path).expect("read FCP XML");
+        assert!(text.contains("<!DOCTYPE xmeml>"));
+        assert!(text.contains(&format!("<name>cutit-fcp-{}</name>", std::process::id())));
+        assert!(text.contains("<ntsc>TRUE</ntsc>"));
+        assert!(text.contains("demo.mp4</pathurl>"));
+        assert_eq!(text.matches("<clipitem ").count(), 6);
+        std::fs::remove_file(path).expect("cleanup FCP XML");
     }
 
     #[test]
-    fn export_allows_subframe_split_with_zero_length_audio_range() {
+    fn cached_draft_is_shown_immediately_and_refined_to_full_quality() {
         let backend = MockBackend::new(sample_probed_media(), sample_frame());
-        let export_calls = backend.export_calls();
+        let draft_calls = backend.draft_calls();
+        let decode_calls = backend.decode_calls();
         let mut engine = Engine::new(backend);
         engine
             .handle_command(Command::Import {
                 path: PathBuf::from("demo.mp4"),
             })
             .expect("import should succeed");
-        engine
-            .handle_command(Command::Split { at_tl: 8 })
-            .expect("split should succeed");
+        engine.preview_cache.insert(
+            Path::new("demo.mp4"),
+            1_500_000,
+            PreviewQuality::Draft,
+            sample_frame(),
+        );
 
         let events = engine
-            .handle_command(Command::Export {
-                path: PathBuf::from("out.mp4"),
-                settings: ExportSettings::default(),
-            })
-            .expect("export should succeed");
-
-        assert_eq!(events.len(), 2);
-        assert_eq!(events[0], Event::ExportProgress { done: 2, total: 2 });
+            .handle_command(Command::SetPlayhead { t_tl: 500_000 })
+            .expect("set playhead should succeed");
+        assert!(matches!(
+            events.as_slice(),
+            [
+                Event::PlayheadChanged { t_tl: 500_000 },
+                Event::PreviewFrameReady {
+                    quality: PreviewQuality::Draft,
+                    ..
+                }
+            ]
+        ));
 
-        let calls = export_calls.lock().expect("lock export calls");
-        assert_eq!(calls.len(), 1);
-        assert_eq!(calls[0].segments.len(), 2);
-        assert_eq!(calls[0].segments[0].src_in_video, 90_000);
-        assert_eq!(calls[0].segments[0].src_out_video, 90_001);
-        assert_eq!(calls[0].segments[0].src_in_audio, Some(47_999));
-        assert_eq!(calls[0].segments[0].src_out_audio, Some(48_000));
+        let events = engine.wait_for_previews(PREVIEW_WAIT);
+        assert!(matches!(
+            events.as_slice(),
+            [Event::PreviewFrameReady {
+                quality: PreviewQuality::Full,
+                ..
+            }]
+        ));
+        assert!(draft_calls.lock().expect("lock draft calls").is_empty());
+        let decode_calls = decode_calls.lock().expect("lock decode calls");
+        assert!((decode_calls[0] - 1.5).abs() < 1e-6);
+        assert_eq!(count_close_calls(&decode_calls, 1.5), 1);
     }
 
     #[test]
-    fn export_subframe_split_near_audio_end_keeps_audio_range_in_bounds() {
-        let backend = MockBackend::new(sample_probed_media(), sample_frame());
-        let export_calls = backend.export_calls();
-        let mut engine = Engine::new(backend);
+    fn draft_result_is_not_emitted_after_full_frame_of_same_generation() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
         engine
             .handle_command(Command::Import {
                 path: PathBuf::from("demo.mp4"),
             })
             .expect("import should succeed");
-        engine
-            .handle_command(Command::Split { at_tl: 1_199_990 })
-            .expect("split should succeed");
+        engine.preview_generation = 4;
+        engine.shown_preview = (3, PreviewQuality::Full);
+        let result = |quality| PreviewResult {
+            job: PreviewJob {
+                generation: 4,
+                kind: PreviewJobKind::Playhead,
+                quality,
+                t_tl: 500_000,
+                path: PathBuf::from("demo.mp4"),
+                source_tl: 1_500_000,
+                command_id: None,
+            },
+            frame: Ok(sample_frame()),
+        };
+
+        assert!(matches!(
+            engine.accept_preview_result(result(PreviewQuality::Full)),
+            Some(Event::PreviewFrameReady {
+                quality: PreviewQuality::Full,
+                ..
+            })
+        ));
+        assert_eq!(
+            engine.accept_preview_result(result(PreviewQuality::Draft)),
+            None
+        );
+    }
 
+    #[test]
+    fn preview_results_older_than_shown_frame_are_cached_without_event() {
+        let mut engine = Engine::new(MockBackend::new(sample_probed_media(), sample_frame()));
         engine
-            .handle_command(Command::Export {
-                path: PathBuf::from("out.mp4"),
-                settings: ExportSettings::default(),
+            .handle_command(Command::Import {
+                path: PathBuf::from("demo.mp4"),
             })
-            .expect("export should succeed");
+            .expect("import should succeed");
+        engine.preview_generation = 7;
+        engine.shown_preview = (6, PreviewQuality::Full);
+        let result = |generation, source_tl| PreviewResult {
+            job: PreviewJob {
+                generation,
+                kind: PreviewJobKind::Playhead,
+                quality: PreviewQuality::Full,
+                t_tl: source_tl,
+                path: PathBuf::from("demo.mp4"),
+                source_tl,
+                command_id: None,
+            },
+            frame: Ok(sample_frame()),
+        };
 
-        let calls = export_calls.lock().expect("lock export calls");
-        assert_eq!(calls.len(), 1);
-        assert_eq!(calls[0].segments.len(), 2);
+        assert_eq!(engine.accept_preview_result(result(5, 1_000_000)), None);
+        assert!(engine.preview_cache.contains(
+            Path::new("demo.mp4"),
+            1_000_000,
+            PreviewQuality::Full
+        ));
 
-        let right = &calls[0].segments[1];
-        assert_eq!(right.src_in_video, 197_999);
-        assert_eq!(right.src_out_video, 198_000);
-        assert_eq!(right.src_in_audio, Some(105_599));
-        assert_eq!(right.src_out_audio, Some(105_600));
+        let event = engine.accept_preview_result(result(7, 2_000_000));
+        assert!(matches!(
+            event,
+            Some(Event::PreviewFrameReady { generation: 7, .. })
+        ));
+        assert_eq!(engine.accept_preview_result(result(6, 3_000_000)), None);
+    }
+
+    /// Appends the events of background preview decodes started so far.
+    fn settle(engine: &mut Engine<MockBackend>, mut events: Vec<Event>) -> Vec<Event> {
+        events.extend(engine.wait_for_previews(PREVIEW_WAIT));
+        events
     }
 
     fn sample_probed_media() -> ProbedMedia {
@@ -1282,6 +5992,7 @@ mod tests {
                 src_out: video_src_out,
                 width: 160,
                 height: 90,
+                start_timecode: None,
             }),
             audio: Some(ProbedAudioStream {
                 stream_index: 1,
@@ -1294,6 +6005,18 @@ mod tests {
         }
     }
 
+    /// Returns the full snapshot after `delta`, checking both agree on the revision.
+    fn snapshot_after(engine: &mut Engine<MockBackend>, delta: &ProjectDelta) -> ProjectSnapshot {
+        let events = engine
+            .handle_command(Command::RequestProjectSnapshot)
+            .expect("snapshot request should succeed");
+        let [Event::ProjectChanged(snapshot)] = events.as_slice() else {
+            panic!("snapshot request must emit one ProjectChanged");
+        };
+        assert_eq!(snapshot.revision, delta.revision);
+        snapshot.clone()
+    }
+
     fn sample_frame() -> PreviewFrame {
         PreviewFrame {
             width: 160,
@@ -1317,6 +6040,8 @@ mod tests {
         probe: ProbedMedia,
         frame: PreviewFrame,
         decode_calls: Arc<Mutex<Vec<f64>>>,
+        draft_calls: Arc<Mutex<Vec<f64>>>,
+        audio_calls: Arc<Mutex<Vec<(f64, f64)>>>,
         export_calls: Arc<Mutex<Vec<ExportVideoPlan>>>,
     }
 
@@ -1326,6 +6051,8 @@ mod tests {
                 probe,
                 frame,
                 decode_calls: Arc::new(Mutex::new(Vec::new())),
+                draft_calls: Arc::new(Mutex::new(Vec::new())),
+                audio_calls: Arc::new(Mutex::new(Vec::new())),
                 export_calls: Arc::new(Mutex::new(Vec::new())),
             }
         }
@@ -1334,14 +6061,51 @@ mod tests {
             Arc::clone(&self.decode_calls)
         }
 
+        fn draft_calls(&self) -> Arc<Mutex<Vec<f64>>> {
+            Arc::clone(&self.draft_calls)
+        }
+
         fn export_calls(&self) -> Arc<Mutex<Vec<ExportVideoPlan>>> {
             Arc::clone(&self.export_calls)
         }
+
+        fn audio_calls(&self) -> Arc<Mutex<Vec<(f64, f64)>>> {
+            Arc::clone(&self.audio_calls)
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct RecordingAudioSink {
+        written_frames: Arc<Mutex<Vec<usize>>>,
+        played_frames: u64,
+    }
+
+    impl AudioSink for RecordingAudioSink {
+        fn write(&mut self, chunk: &PcmChunk) -> crate::Result<()> {
+            self.written_frames
+                .lock()
+                .expect("lock written frames")
+                .push(chunk.frames());
+            self.played_frames += chunk.frames() as u64;
+            Ok(())
+        }
+
+        fn played_frames(&self) -> u64 {
+            self.played_frames
+        }
+
+        fn reset(&mut self) -> crate::Result<()> {
+            self.played_frames = 0;
+            Ok(())
+        }
     }
 
     impl MediaBackend for MockBackend {
-        fn probe(&self, _path: &Path) -> crate::Result<ProbedMedia> {
-            Ok(self.probe.clone())
+        fn probe(&self, path: &Path) -> crate::Result<ProbedMedia> {
+            Ok(ProbedMedia {
+                path: path.to_path_buf(),
+                ..self.probe.clone()
+            })
         }
 
         fn decode_preview_frame(
@@ -1356,6 +6120,43 @@ mod tests {
             Ok(self.frame.clone())
         }
 
+        /// Drafts are half the size of the exact frame so tests can tell them apart.
+        fn decode_preview_draft_frame(
+            &self,
+            _path: &Path,
+            at_seconds: f64,
+        ) -> crate::Result<PreviewFrame> {
+            self.draft_calls
+                .lock()
+                .expect("lock draft calls")
+                .push(at_seconds);
+            let (width, height) = (self.frame.width / 2, self.frame.height / 2);
+            Ok(PreviewFrame {
+                width,
+                height,
+                format: PreviewPixelFormat::Rgba8,
+                bytes: Arc::from(vec![0; width as usize * height as usize * 4]),
+            })
+        }
+
+        fn decode_audio(
+            &self,
+            _path: &Path,
+            at_seconds: f64,
+            duration_seconds: f64,
+            format: PcmFormat,
+        ) -> crate::Result<PcmChunk> {
+            self.audio_calls
+                .lock()
+                .expect("lock audio calls")
+                .push((at_seconds, duration_seconds));
+            let frames = (duration_seconds * f64::from(format.sample_rate)).round() as usize;
+            Ok(PcmChunk {
+                format,
+                samples: vec![0.25; frames * usize::from(format.channels)],
+            })
+        }
+
         fn export_video(&self, plan: &ExportVideoPlan) -> crate::Result<()> {
             self.export_calls
                 .lock()
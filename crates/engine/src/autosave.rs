@@ -0,0 +1,130 @@
+//! Autosave and crash recovery.
+//!
+//! After an edit the engine waits until no further edit arrived for
+//! `AutosaveConfig::debounce`, then atomically writes a recovery copy of the
+//! project. A saved project keeps its copy next to the project file
+//! (`cut.nle.json` → `cut.nle.json.recovery`); an untitled project uses the
+//! state directory. A recovery copy newer than its project file is offered to
+//! the UI through `Event::RecoveryAvailable`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Quiet period after the last edit before a recovery copy is written.
+pub const DEFAULT_AUTOSAVE_DEBOUNCE: Duration = Duration::from_secs(2);
+
+const RECOVERY_EXTENSION: &str = "recovery";
+const UNTITLED_RECOVERY_FILE: &str = "untitled.nle.json.recovery";
+
+/// Where and how often the engine writes recovery copies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutosaveConfig {
+    pub debounce: Duration,
+    /// Directory for recovery copies of untitled projects; `None` skips them.
+    pub state_dir: Option<PathBuf>,
+}
+
+impl AutosaveConfig {
+    /// Uses `$XDG_STATE_HOME/cutit`, falling back to `~/.local/state/cutit`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use engine::{AutosaveConfig, Engine, FfmpegMediaBackend};
+    ///
+    /// let _engine = Engine::new(FfmpegMediaBackend).with_autosave(AutosaveConfig::from_env());
+    /// ```
+    pub fn from_env() -> Self {
+        let state_home = std::env::var_os("XDG_STATE_HOME")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| {
+                std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/state"))
+            });
+        Self {
+            debounce: DEFAULT_AUTOSAVE_DEBOUNCE,
+            state_dir: state_home.map(|dir| dir.join("cutit")),
+        }
+    }
+
+    /// Returns the recovery copy location for a project saved at
+    /// `project_path`, or for an untitled project when it is `None`.
+    pub fn recovery_path(&self, project_path: Option<&Path>) -> Option<PathBuf> {
+        match project_path {
+            Some(path) => {
+                let mut name = path.file_name()?.to_os_string();
+                name.push(".");
+                name.push(RECOVERY_EXTENSION);
+                Some(path.with_file_name(name))
+            }
+            None => Some(self.state_dir.as_ref()?.join(UNTITLED_RECOVERY_FILE)),
+        }
+    }
+}
+
+/// Returns whether `recovery_path` exists and was written after the project
+/// file; a missing project file counts as older.
+pub(crate) fn recovery_is_newer(recovery_path: &Path, project_path: Option<&Path>) -> bool {
+    let modified = |path: &Path| fs::metadata(path).and_then(|metadata| metadata.modified());
+    let Ok(recovery_modified) = modified(recovery_path) else {
+        return false;
+    };
+    match project_path.map(modified) {
+        Some(Ok(project_modified)) => recovery_modified > project_modified,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::time::{Duration, SystemTime};
+
+    use super::{AutosaveConfig, recovery_is_newer};
+
+    #[test]
+    fn recovery_copies_live_next_to_the_project_or_in_the_state_dir() {
+        let config = AutosaveConfig {
+            debounce: Duration::from_secs(1),
+            state_dir: Some(PathBuf::from("/state/cutit")),
+        };
+
+        assert_eq!(
+            config.recovery_path(Some(Path::new("/show/cut.nle.json"))),
+            Some(PathBuf::from("/show/cut.nle.json.recovery"))
+        );
+        assert_eq!(
+            config.recovery_path(None),
+            Some(PathBuf::from("/state/cutit/untitled.nle.json.recovery"))
+        );
+        let without_state = AutosaveConfig {
+            state_dir: None,
+            ..config
+        };
+        assert_eq!(without_state.recovery_path(None), None);
+    }
+
+    #[test]
+    fn recovery_is_offered_only_when_newer_than_the_project_file() {
+        let dir = std::env::temp_dir().join(format!("cutit-recovery-{}", std::process::id()));
+        fs::create_dir_all(&dir).expect("create dir");
+        let project = dir.join("cut.nle.json");
+        let recovery = dir.join("cut.nle.json.recovery");
+
+        assert!(!recovery_is_newer(&recovery, Some(&project)));
+        fs::write(&recovery, b"{}").expect("write recovery");
+        assert!(recovery_is_newer(&recovery, Some(&project)));
+        assert!(recovery_is_newer(&recovery, None));
+
+        fs::write(&project, b"{}").expect("write project");
+        let later = SystemTime::now() + Duration::from_secs(60);
+        fs::File::options()
+            .write(true)
+            .open(&project)
+            .and_then(|file| file.set_modified(later))
+            .expect("touch project");
+        assert!(!recovery_is_newer(&recovery, Some(&project)));
+        fs::remove_dir_all(dir).expect("cleanup dir");
+    }
+}
@@ -1,27 +1,32 @@
 use std::collections::{HashMap, VecDeque};
 use std::path::{Path, PathBuf};
 
-use crate::preview::PreviewFrame;
+use crate::preview::{PreviewFrame, PreviewQuality};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct PreviewCacheKey {
     path: PathBuf,
     bucket: i64,
+    quality: PreviewQuality,
 }
 
 /// LRU cache for decoded preview frames bucketed by source timeline ticks.
 ///
+/// Draft and full-quality frames are stored side by side; inserting a full
+/// frame drops the draft frame of the same bucket.
+///
 /// # Example
 /// ```
 /// use std::sync::Arc;
 ///
 /// use engine::cache::PreviewFrameCache;
-/// use engine::{PreviewFrame, PreviewPixelFormat};
+/// use engine::{PreviewFrame, PreviewPixelFormat, PreviewQuality};
 ///
 /// let mut cache = PreviewFrameCache::new(8, 33_333);
 /// cache.insert(
 ///     "demo.mp4",
 ///     1_500_000,
+///     PreviewQuality::Full,
 ///     PreviewFrame {
 ///         width: 2,
 ///         height: 2,
@@ -30,7 +35,8 @@ struct PreviewCacheKey {
 ///     },
 /// );
 ///
-/// assert!(cache.get("demo.mp4", 1_500_010).is_some());
+/// assert!(cache.get("demo.mp4", 1_500_010, PreviewQuality::Full).is_some());
+/// assert!(cache.get("demo.mp4", 1_500_010, PreviewQuality::Draft).is_none());
 /// ```
 #[derive(Debug)]
 pub struct PreviewFrameCache {
@@ -82,32 +88,71 @@ impl PreviewFrameCache {
         self.clear();
     }
 
-    /// Returns true when a frame for the same key bucket already exists.
-    pub fn contains(&self, path: impl AsRef<Path>, source_tl: i64) -> bool {
-        let key = self.make_key(path.as_ref(), source_tl);
+    /// Returns true when a frame of `quality` for the same key bucket exists.
+    pub fn contains(
+        &self,
+        path: impl AsRef<Path>,
+        source_tl: i64,
+        quality: PreviewQuality,
+    ) -> bool {
+        let key = self.make_key(path.as_ref(), source_tl, quality);
         self.entries.contains_key(&key)
     }
 
-    /// Returns one cached frame and marks it as recently used.
-    pub fn get(&mut self, path: impl AsRef<Path>, source_tl: i64) -> Option<PreviewFrame> {
-        let key = self.make_key(path.as_ref(), source_tl);
+    /// Returns one cached frame of `quality` and marks it as recently used.
+    pub fn get(
+        &mut self,
+        path: impl AsRef<Path>,
+        source_tl: i64,
+        quality: PreviewQuality,
+    ) -> Option<PreviewFrame> {
+        let key = self.make_key(path.as_ref(), source_tl, quality);
         let frame = self.entries.get(&key)?.clone();
         self.touch(&key);
         Some(frame)
     }
 
+    /// Returns the highest-quality cached frame for the bucket.
+    pub fn get_best(
+        &mut self,
+        path: impl AsRef<Path>,
+        source_tl: i64,
+    ) -> Option<(PreviewFrame, PreviewQuality)> {
+        let path = path.as_ref();
+        [PreviewQuality::Full, PreviewQuality::Draft]
+            .into_iter()
+            .find_map(|quality| {
+                self.get(path, source_tl, quality)
+                    .map(|frame| (frame, quality))
+            })
+    }
+
     /// Inserts or updates one cached frame.
-    pub fn insert(&mut self, path: impl AsRef<Path>, source_tl: i64, frame: PreviewFrame) {
-        let key = self.make_key(path.as_ref(), source_tl);
+    pub fn insert(
+        &mut self,
+        path: impl AsRef<Path>,
+        source_tl: i64,
+        quality: PreviewQuality,
+        frame: PreviewFrame,
+    ) {
+        let path = path.as_ref();
+        if quality == PreviewQuality::Full {
+            let draft = self.make_key(path, source_tl, PreviewQuality::Draft);
+            if self.entries.remove(&draft).is_some() {
+                self.lru_order.retain(|existing| existing != &draft);
+            }
+        }
+        let key = self.make_key(path, source_tl, quality);
         self.entries.insert(key.clone(), frame);
         self.touch(&key);
         self.evict_if_needed();
     }
 
-    fn make_key(&self, path: &Path, source_tl: i64) -> PreviewCacheKey {
+    fn make_key(&self, path: &Path, source_tl: i64, quality: PreviewQuality) -> PreviewCacheKey {
         PreviewCacheKey {
             path: path.to_path_buf(),
             bucket: source_tl.max(0).div_euclid(self.bucket_size_tl),
+            quality,
         }
     }
 
@@ -132,17 +177,20 @@ impl PreviewFrameCache {
 mod tests {
     use std::sync::Arc;
 
-    use crate::preview::PreviewPixelFormat;
+    use crate::preview::{PreviewPixelFormat, PreviewQuality};
 
     use super::PreviewFrameCache;
 
+    const FULL: PreviewQuality = PreviewQuality::Full;
+    const DRAFT: PreviewQuality = PreviewQuality::Draft;
+
     #[test]
     fn get_hits_for_timestamps_in_the_same_bucket() {
         let mut cache = PreviewFrameCache::new(8, 33_333);
-        cache.insert("demo.mp4", 1_500_000, sample_frame(10));
+        cache.insert("demo.mp4", 1_500_000, FULL, sample_frame(10));
 
         let frame = cache
-            .get("demo.mp4", 1_500_010)
+            .get("demo.mp4", 1_500_010, FULL)
             .expect("frame should be cached");
         assert_eq!(frame.bytes[0], 10);
     }
@@ -150,30 +198,50 @@ mod tests {
     #[test]
     fn insert_evicts_least_recently_used_frame_when_capacity_is_reached() {
         let mut cache = PreviewFrameCache::new(2, 33_333);
-        cache.insert("demo.mp4", 1_000_000, sample_frame(1));
-        cache.insert("demo.mp4", 2_000_000, sample_frame(2));
+        cache.insert("demo.mp4", 1_000_000, FULL, sample_frame(1));
+        cache.insert("demo.mp4", 2_000_000, FULL, sample_frame(2));
 
         let _ = cache
-            .get("demo.mp4", 1_000_000)
+            .get("demo.mp4", 1_000_000, FULL)
             .expect("first frame should exist");
-        cache.insert("demo.mp4", 3_000_000, sample_frame(3));
+        cache.insert("demo.mp4", 3_000_000, FULL, sample_frame(3));
 
-        assert!(cache.get("demo.mp4", 1_000_000).is_some());
-        assert!(cache.get("demo.mp4", 2_000_000).is_none());
-        assert!(cache.get("demo.mp4", 3_000_000).is_some());
+        assert!(cache.get("demo.mp4", 1_000_000, FULL).is_some());
+        assert!(cache.get("demo.mp4", 2_000_000, FULL).is_none());
+        assert!(cache.get("demo.mp4", 3_000_000, FULL).is_some());
     }
 
     #[test]
     fn reconfigure_bucket_size_clears_existing_entries() {
         let mut cache = PreviewFrameCache::new(8, 33_333);
-        cache.insert("demo.mp4", 1_500_000, sample_frame(10));
+        cache.insert("demo.mp4", 1_500_000, FULL, sample_frame(10));
 
         cache.reconfigure_bucket_size(16_667);
 
-        assert!(cache.get("demo.mp4", 1_500_000).is_none());
+        assert!(cache.get("demo.mp4", 1_500_000, FULL).is_none());
         assert_eq!(cache.bucket_size_tl(), 16_667);
     }
 
+    #[test]
+    fn draft_and_full_frames_are_cached_per_tier() {
+        let mut cache = PreviewFrameCache::new(8, 33_333);
+        cache.insert("demo.mp4", 1_500_000, DRAFT, sample_frame(1));
+
+        assert!(cache.get("demo.mp4", 1_500_000, FULL).is_none());
+        let (frame, quality) = cache
+            .get_best("demo.mp4", 1_500_000)
+            .expect("draft frame should be cached");
+        assert_eq!((frame.bytes[0], quality), (1, DRAFT));
+
+        cache.insert("demo.mp4", 1_500_000, FULL, sample_frame(2));
+
+        assert!(!cache.contains("demo.mp4", 1_500_000, DRAFT));
+        let (frame, quality) = cache
+            .get_best("demo.mp4", 1_500_000)
+            .expect("full frame should be cached");
+        assert_eq!((frame.bytes[0], quality), (2, FULL));
+    }
+
     fn sample_frame(value: u8) -> crate::preview::PreviewFrame {
         crate::preview::PreviewFrame {
             width: 1,
@@ -0,0 +1,599 @@
+//! Cut lists: plain `path, in, out, label` rows from logging tools.
+//!
+//! Two formats are read. CSV has one cut per line; an optional header row
+//! names the `path`, `in`, `out` and `label` columns in any order, otherwise
+//! they come in that order. Blank lines and lines starting with `#` are
+//! skipped, and fields may be quoted.
+//! JSON is an array of `{"path", "in", "out", "label"}` objects. Times are
+//! seconds from the start of the media (`12.5`) or source timecode at the
+//! asset frame rate (`HH:MM:SS:FF`, `;` for drop-frame), which counts from the
+//! asset's start timecode. Labels are informational and only appear in logs.
+//!
+//! Import is all-or-nothing: every row that fails is collected with its
+//! 1-based line number into one `InvalidCutList` error.
+
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::{EngineError, Result};
+use crate::project::{MediaAsset, Project};
+use crate::time::{FrameGrid, TIMELINE_TIME_BASE, Timecode};
+use crate::timeline::{Segment, SegmentId};
+
+/// One cut read from a cut list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CutListEntry {
+    /// 1-based line of the row (of the object's opening brace for JSON).
+    pub line: usize,
+    pub path: PathBuf,
+    pub(crate) source_in: CutTime,
+    pub(crate) source_out: CutTime,
+    pub label: Option<String>,
+}
+
+/// A row that could not be imported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CutListRowError {
+    /// 1-based line of the row.
+    pub line: usize,
+    pub reason: String,
+}
+
+impl Display for CutListRowError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+/// A source position as written in the cut list.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum CutTime {
+    /// Timeline ticks from the asset start.
+    Ticks(i64),
+    Timecode(Timecode),
+}
+
+impl CutTime {
+    fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        if text.contains(':') {
+            return Timecode::parse(text).ok().map(Self::Timecode);
+        }
+        text.parse::<f64>().ok().and_then(Self::from_seconds)
+    }
+
+    fn from_seconds(seconds: f64) -> Option<Self> {
+        (seconds.is_finite() && seconds >= 0.0)
+            .then(|| Self::Ticks((seconds * f64::from(TIMELINE_TIME_BASE.den)).round() as i64))
+    }
+
+    /// Returns timeline ticks from the start of `asset`.
+    fn to_ticks(self, asset: &MediaAsset) -> std::result::Result<i64, String> {
+        match self {
+            Self::Ticks(ticks) => Ok(ticks),
+            Self::Timecode(timecode) => asset
+                .source_tl_at_timecode(timecode)
+                .map_err(|error| error.to_string()),
+        }
+    }
+}
+
+/// Reads a CSV or JSON cut list; JSON is recognised by a leading `[`.
+///
+/// # Example
+/// ```ignore
+/// let entries = engine::cut_list::parse_cut_list("clips/a.mov,1.5,4\n")?;
+/// assert_eq!(entries[0].line, 1);
+/// ```
+pub fn parse_cut_list(text: &str) -> Result<Vec<CutListEntry>> {
+    let rows = if text.trim_start().starts_with('[') {
+        parse_json(text)?
+    } else {
+        parse_csv(text)
+    };
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+    for row in rows {
+        match row {
+            Ok(entry) => entries.push(entry),
+            Err(error) => errors.push(error),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(EngineError::InvalidCutList { errors });
+    }
+    if entries.is_empty() {
+        return Err(EngineError::InvalidCutList {
+            errors: vec![row_error(1, "the cut list has no cuts")],
+        });
+    }
+    Ok(entries)
+}
+
+/// Returns each referenced path missing from the bin with the lines that use
+/// it, in order of first use.
+pub fn missing_media(project: &Project, entries: &[CutListEntry]) -> Vec<(PathBuf, Vec<usize>)> {
+    let mut missing: Vec<(PathBuf, Vec<usize>)> = Vec::new();
+    for entry in entries {
+        if find_asset(project, &entry.path).is_some() {
+            continue;
+        }
+        match missing.iter_mut().find(|(path, _)| *path == entry.path) {
+            Some((_, lines)) => lines.push(entry.line),
+            None => missing.push((entry.path.clone(), vec![entry.line])),
+        }
+    }
+    missing
+}
+
+/// Lays the cuts out back-to-back from timeline zero against the project's
+/// assets, with ids from `allocate_segment_id`.
+///
+/// Source times convert to stream ticks through `MediaAsset::segment_for_range`;
+/// an out point past the media end by less than one frame is clamped to it.
+/// Every failing row is reported.
+pub fn build_segments(
+    project: &Project,
+    entries: &[CutListEntry],
+    mut allocate_segment_id: impl FnMut() -> SegmentId,
+) -> std::result::Result<Vec<Segment>, Vec<CutListRowError>> {
+    let mut segments = Vec::new();
+    let mut errors = Vec::new();
+    let mut position_tl = 0;
+    for entry in entries {
+        let Some(asset) = find_asset(project, &entry.path) else {
+            errors.push(row_error(
+                entry.line,
+                format!("{} is not in the bin", entry.path.display()),
+            ));
+            continue;
+        };
+        match source_range(asset, entry) {
+            Ok((start_tl, end_tl)) => {
+                let mut segment = asset.segment_for_range(allocate_segment_id(), start_tl, end_tl);
+                segment.timeline_start = position_tl;
+                position_tl += segment.timeline_duration;
+                segments.push(segment);
+            }
+            Err(reason) => errors.push(row_error(entry.line, reason)),
+        }
+    }
+    if errors.is_empty() {
+        Ok(segments)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Returns the entry's source range in timeline ticks from the asset start.
+fn source_range(
+    asset: &MediaAsset,
+    entry: &CutListEntry,
+) -> std::result::Result<(i64, i64), String> {
+    let grid = asset.frame_grid().unwrap_or(FrameGrid::DEFAULT);
+    let start_tl = entry.source_in.to_ticks(asset)?;
+    let mut end_tl = entry.source_out.to_ticks(asset)?;
+    if end_tl <= start_tl {
+        return Err(String::from("out point must be after the in point"));
+    }
+    let frame_tl = grid.frame_start_tl(1);
+    if end_tl > asset.duration_tl && end_tl - asset.duration_tl < frame_tl {
+        end_tl = asset.duration_tl;
+    }
+    if end_tl > asset.duration_tl {
+        return Err(format!(
+            "out point {end_tl} us is past the end of {} ({} us)",
+            asset.path.display(),
+            asset.duration_tl
+        ));
+    }
+    Ok((start_tl, end_tl))
+}
+
+fn find_asset<'a>(project: &'a Project, path: &Path) -> Option<&'a MediaAsset> {
+    project.assets.iter().find(|asset| asset.path == path)
+}
+
+fn row_error(line: usize, reason: impl Into<String>) -> CutListRowError {
+    CutListRowError {
+        line,
+        reason: reason.into(),
+    }
+}
+
+type Row = std::result::Result<CutListEntry, CutListRowError>;
+
+const COLUMNS: [&str; 4] = ["path", "in", "out", "label"];
+
+fn parse_csv(text: &str) -> Vec<Row> {
+    let mut rows = Vec::new();
+    // Column index of path, in, out and label.
+    let mut columns = [Some(0), Some(1), Some(2), Some(3)];
+    let mut seen_first_row = false;
+    for (index, raw_line) in text.lines().enumerate() {
+        let line = index + 1;
+        let trimmed = raw_line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let fields = match split_csv_fields(trimmed) {
+            Ok(fields) => fields,
+            Err(reason) => {
+                rows.push(Err(row_error(line, reason)));
+                continue;
+            }
+        };
+        let is_header = fields
+            .iter()
+            .any(|field| field.trim().eq_ignore_ascii_case(COLUMNS[0]));
+        if !std::mem::replace(&mut seen_first_row, true) && is_header {
+            match header_columns(&fields) {
+                Ok(header) => columns = header,
+                Err(reason) => rows.push(Err(row_error(line, reason))),
+            }
+            continue;
+        }
+        let field = |column: usize| {
+            columns[column]
+                .and_then(|index| fields.get(index))
+                .map(|field| field.trim())
+                .filter(|field| !field.is_empty())
+        };
+        let source_in = field(1).map(|text| (text, CutTime::parse(text)));
+        let source_out = field(2).map(|text| (text, CutTime::parse(text)));
+        rows.push(entry(
+            line,
+            field(0).map(PathBuf::from),
+            source_in,
+            source_out,
+            field(3).map(str::to_owned),
+        ));
+    }
+    rows
+}
+
+/// Maps header names to column indices; `path`, `in` and `out` are required.
+fn header_columns(fields: &[String]) -> std::result::Result<[Option<usize>; 4], String> {
+    let mut columns = [None; 4];
+    for (index, name) in fields.iter().enumerate() {
+        let name = name.trim();
+        match COLUMNS
+            .iter()
+            .position(|column| column.eq_ignore_ascii_case(name))
+        {
+            Some(column) => columns[column] = Some(index),
+            None => return Err(format!("unknown column `{name}`")),
+        }
+    }
+    match COLUMNS[..3]
+        .iter()
+        .zip(columns)
+        .find(|(_, index)| index.is_none())
+    {
+        Some((name, _)) => Err(format!("missing `{name}` column")),
+        None => Ok(columns),
+    }
+}
+
+/// Splits one CSV line on commas, honouring `"quoted, fields"` with `""`
+/// escapes.
+fn split_csv_fields(line: &str) -> std::result::Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+    while let Some(ch) = chars.next() {
+        match (quoted, ch) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (false, '"') if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            (false, ',') => fields.push(std::mem::take(&mut field)),
+            _ => field.push(ch),
+        }
+    }
+    if quoted {
+        return Err(String::from("unterminated quoted field"));
+    }
+    fields.push(field);
+    Ok(fields)
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonRow {
+    path: Option<String>,
+    #[serde(rename = "in")]
+    source_in: Option<JsonTime>,
+    #[serde(rename = "out")]
+    source_out: Option<JsonTime>,
+    #[serde(default)]
+    label: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonTime {
+    Seconds(f64),
+    Text(String),
+}
+
+impl JsonTime {
+    fn parse(&self) -> (String, Option<CutTime>) {
+        match self {
+            Self::Seconds(seconds) => (seconds.to_string(), CutTime::from_seconds(*seconds)),
+            Self::Text(text) => (text.clone(), CutTime::parse(text)),
+        }
+    }
+}
+
+fn parse_json(text: &str) -> Result<Vec<Row>> {
+    let values: Vec<Value> =
+        serde_json::from_str(text).map_err(|error| EngineError::InvalidCutList {
+            errors: vec![row_error(error.line().max(1), error.to_string())],
+        })?;
+    let lines = json_element_lines(text);
+    Ok(values
+        .into_iter()
+        .zip(lines)
+        .map(
+            |(value, line)| match serde_json::from_value::<JsonRow>(value) {
+                Ok(row) => {
+                    let source_in = row.source_in.as_ref().map(JsonTime::parse);
+                    let source_out = row.source_out.as_ref().map(JsonTime::parse);
+                    entry(
+                        line,
+                        row.path.filter(|path| !path.is_empty()).map(PathBuf::from),
+                        source_in
+                            .as_ref()
+                            .map(|(text, time)| (text.as_str(), *time)),
+                        source_out
+                            .as_ref()
+                            .map(|(text, time)| (text.as_str(), *time)),
+                        row.label,
+                    )
+                }
+                Err(error) => Err(row_error(line, error.to_string())),
+            },
+        )
+        .collect())
+}
+
+/// Returns the 1-based line where each element of the top-level JSON array
+/// starts. `text` must already be valid JSON.
+fn json_element_lines(text: &str) -> Vec<usize> {
+    let mut lines = Vec::new();
+    let mut line = 1;
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut expecting_element = false;
+    for byte in text.bytes() {
+        if byte == b'\n' {
+            line += 1;
+        }
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        if byte.is_ascii_whitespace() {
+            continue;
+        }
+        if expecting_element && depth == 1 && byte != b']' {
+            lines.push(line);
+            expecting_element = false;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'[' | b'{' => {
+                depth += 1;
+                expecting_element |= depth == 1;
+            }
+            b']' | b'}' => depth = depth.saturating_sub(1),
+            b',' => expecting_element |= depth == 1,
+            _ => {}
+        }
+    }
+    lines
+}
+
+/// Builds an entry from optional fields, naming the first one that is missing
+/// or malformed.
+fn entry(
+    line: usize,
+    path: Option<PathBuf>,
+    source_in: Option<(&str, Option<CutTime>)>,
+    source_out: Option<(&str, Option<CutTime>)>,
+    label: Option<String>,
+) -> Row {
+    let path = path.ok_or_else(|| row_error(line, "missing path"))?;
+    let time = |name: &str, value: Option<(&str, Option<CutTime>)>| match value {
+        None => Err(row_error(line, format!("missing {name} point"))),
+        Some((text, None)) => Err(row_error(
+            line,
+            format!("invalid {name} point `{text}`: expected seconds or HH:MM:SS:FF"),
+        )),
+        Some((_, Some(time))) => Ok(time),
+    };
+    Ok(CutListEntry {
+        line,
+        path,
+        source_in: time("in", source_in)?,
+        source_out: time("out", source_out)?,
+        label,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{CutListRowError, build_segments, missing_media, parse_cut_list};
+    use crate::error::EngineError;
+    use crate::project::{MediaAsset, Project, TimelineMarks, VideoStreamInfo};
+    use crate::time::Rational;
+
+    fn asset(id: u64, path: &str) -> MediaAsset {
+        MediaAsset {
+            id,
+            path: PathBuf::from(path),
+            video_stream_index: Some(0),
+            audio_stream_index: None,
+            video: Some(VideoStreamInfo {
+                time_base: Rational::new(1, 90_000).expect("valid rational"),
+                frame_rate: Some(Rational::new(25, 1).expect("valid rational")),
+                width: 1920,
+                height: 1080,
+                start_pts: 9_000,
+                start_timecode: None,
+            }),
+            audio: None,
+            duration_tl: 10_000_000,
+            source_marks: TimelineMarks::default(),
+            file_size: None,
+            offline: false,
+        }
+    }
+
+    fn row_errors(result: crate::Result<impl std::fmt::Debug>) -> Vec<CutListRowError> {
+        match result {
+            Err(EngineError::InvalidCutList { errors }) => errors,
+            other => panic!("expected InvalidCutList, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn csv_reads_header_columns_quotes_and_comments() {
+        let text = "# logged by the day crew\n\
+                    label,out,in,path\n\
+                    \n\
+                    \"opening, wide\",2.5,1,a.mov\n\
+                    ,00:00:04:10,00:00:03:00,\"b \"\"cam\"\".mov\"\n";
+        let entries = parse_cut_list(text).expect("valid cut list");
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].line, 4);
+        assert_eq!(entries[0].path, PathBuf::from("a.mov"));
+        assert_eq!(entries[0].label.as_deref(), Some("opening, wide"));
+        assert_eq!(entries[1].line, 5);
+        assert_eq!(entries[1].path, PathBuf::from("b \"cam\".mov"));
+        assert_eq!(entries[1].label, None);
+    }
+
+    #[test]
+    fn csv_reports_every_bad_row_with_its_line() {
+        let text = "a.mov,1,2\n\
+                    a.mov,soon,2\n\
+                    ,1,2\n\
+                    a.mov,1\n\
+                    a.mov,00:00:01:99,00:00:02:00\n";
+        let errors = row_errors(parse_cut_list(text));
+
+        assert_eq!(
+            errors.iter().map(|error| error.line).collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+        assert!(errors[0].reason.contains("invalid in point `soon`"));
+        assert_eq!(errors[1].reason, "missing path");
+        assert_eq!(errors[2].reason, "missing out point");
+        assert!(
+            row_errors(parse_cut_list("# nothing\n"))[0]
+                .reason
+                .contains("no cuts")
+        );
+    }
+
+    #[test]
+    fn json_rows_carry_the_line_of_their_object() {
+        let text = r#"[
+  {"path": "a.mov", "in": 1, "out": "2.5", "label": "{tricky, \"label\"]"},
+
+  {"path": "b.mov",
+   "in": "00:00:01:00", "out": "00:00:02:00"}
+]"#;
+        let entries = parse_cut_list(text).expect("valid cut list");
+        assert_eq!(
+            entries.iter().map(|entry| entry.line).collect::<Vec<_>>(),
+            vec![2, 4]
+        );
+        assert_eq!(entries[0].label.as_deref(), Some("{tricky, \"label\"]"));
+
+        let text = "[\n  {\"path\": \"a.mov\", \"in\": 1, \"out\": 2},\n  {\"path\": \"a.mov\", \"in\": -1, \"out\": 2},\n  {\"path\": \"a.mov\", \"in\": 1, \"out\": 2, \"speed\": 2}\n]";
+        let errors = row_errors(parse_cut_list(text));
+        assert_eq!(
+            errors.iter().map(|error| error.line).collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+        assert!(errors[1].reason.contains("speed"));
+
+        let errors = row_errors(parse_cut_list("[\n  {\"path\": \"a.mov\",\n"));
+        assert_eq!(errors[0].line, 3);
+    }
+
+    #[test]
+    fn cuts_are_laid_back_to_back_and_checked_against_the_media() {
+        let project = Project {
+            assets: vec![asset(1, "a.mov")],
+            ..Project::default()
+        };
+        let entries = parse_cut_list("a.mov,1,2.5\nb.mov,0,1\na.mov,00:00:09:00,00:00:10:00\n")
+            .expect("valid cut list");
+        assert_eq!(
+            missing_media(&project, &entries),
+            vec![(PathBuf::from("b.mov"), vec![2])]
+        );
+
+        let mut next_id = 10;
+        let errors = build_segments(&project, &entries, || {
+            next_id += 1;
+            next_id
+        })
+        .expect_err("b.mov is not in the bin");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 2);
+
+        let entries =
+            parse_cut_list("a.mov,1,2.5\na.mov,00:00:09:00,10.02\na.mov,3,2\na.mov,9,11\n")
+                .expect("valid cut list");
+        let errors = build_segments(&project, &entries, || 0).expect_err("bad ranges");
+        assert_eq!(
+            errors.iter().map(|error| error.line).collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+
+        let segments = build_segments(&project, &entries[..2], || {
+            next_id += 1;
+            next_id
+        })
+        .expect("valid cuts");
+        assert_eq!(
+            segments
+                .iter()
+                .map(|segment| (
+                    segment.id,
+                    segment.timeline_start,
+                    segment.timeline_duration,
+                    segment.src_in_video
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (13, 0, 1_500_000, Some(99_000)),
+                // The out point 20 ms past the end is clamped to the media end.
+                (14, 1_500_000, 1_000_000, Some(819_000)),
+            ]
+        );
+    }
+}
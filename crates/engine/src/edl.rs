@@ -0,0 +1,667 @@
+//! CMX3600 edit decision lists.
+//!
+//! Export writes one cut event per enabled segment: the reel name comes from
+//! the asset file name, source timecode from the segment's source range at the
+//! asset frame rate, and record timecode from `timeline_start` on the project
+//! frame grid. A `* FROM CLIP NAME:` comment carries the full file name so
+//! import can find the asset again even when reel names were shortened.
+//! Source timecode counts from the asset's start timecode; record timecode
+//! counts from zero in the project's timecode format, which sets the `FCM`.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::error::{EngineError, Result};
+use crate::project::{MediaAsset, Project};
+use crate::time::{FrameGrid, TIMELINE_TIME_BASE, Timecode};
+use crate::timeline::{AssetId, Segment, SegmentId};
+
+const REEL_NAME_LEN: usize = 8;
+const CLIP_NAME_COMMENT: &str = "* FROM CLIP NAME:";
+
+/// Writes the project's enabled segments as a CMX3600 EDL.
+///
+/// Disabled segments are left out, since EDLs have no way to mark them.
+///
+/// # Example
+/// ```ignore
+/// let text = engine::edl::export_edl(&project, "cut")?;
+/// std::fs::write("cut.edl", text)?;
+/// ```
+pub fn export_edl(project: &Project, title: &str) -> Result<String> {
+    let (record_grid, record_drop_frame) = project.timecode_format();
+    let record_drop_frame = record_drop_frame && record_grid.supports_drop_frame();
+    let reels = reel_names(&project.assets);
+    let mut text = format!(
+        "TITLE: {title}\nFCM: {}\n",
+        if record_drop_frame {
+            "DROP FRAME"
+        } else {
+            "NON-DROP FRAME"
+        }
+    );
+
+    let segments = project
+        .timeline
+        .segments
+        .iter()
+        .filter(|segment| segment.enabled);
+    for (index, segment) in segments.enumerate() {
+        let asset = project.asset_by_id(segment.asset_id)?;
+        let (source_grid, src_in, src_out) = source_frames(asset, segment, record_grid)?;
+        let record_in = record_grid.nearest_frame(segment.timeline_start, TIMELINE_TIME_BASE);
+        let record_out = record_grid.nearest_frame(
+            segment.timeline_start + segment.timeline_duration,
+            TIMELINE_TIME_BASE,
+        );
+        let source_start_frame = asset.start_frame(source_grid)?;
+        let source_drop_frame = source_drop_frame(asset, source_grid, record_drop_frame);
+        let source_timecode = |frame: i64| {
+            Timecode::from_frame(source_start_frame + frame, source_grid, source_drop_frame)
+        };
+        let record_timecode =
+            |frame: i64| Timecode::from_frame(frame, record_grid, record_drop_frame);
+        let channels = match (asset.video.is_some(), asset.audio.is_some()) {
+            (true, true) => "AA/V",
+            (true, false) => "V",
+            _ => "AA",
+        };
+        text.push_str(&format!(
+            "\n{:03}  {:<8} {:<5} C        {} {} {} {}\n",
+            index + 1,
+            reels[&asset.id],
+            channels,
+            source_timecode(src_in),
+            source_timecode(src_out),
+            record_timecode(record_in),
+            record_timecode(record_out),
+        ));
+        if let Some(name) = asset.path.file_name() {
+            text.push_str(&format!("{CLIP_NAME_COMMENT} {}\n", name.to_string_lossy()));
+        }
+    }
+    Ok(text)
+}
+
+/// Rebuilds timeline segments from a CMX3600 EDL against the project's assets.
+///
+/// Events are matched to assets by their `* FROM CLIP NAME:` comment, falling
+/// back to the reel name `export_edl` would write. Only cuts are supported,
+/// and source and record durations must agree when source and record share a
+/// frame rate; otherwise the source range sets the duration. Returns the
+/// segments ordered by
+/// `timeline_start`, with ids from `allocate_segment_id`.
+///
+/// # Example
+/// ```ignore
+/// let mut next_id = 1;
+/// let segments = engine::edl::import_edl(&project, &text, || {
+///     next_id += 1;
+///     next_id - 1
+/// })?;
+/// ```
+pub fn import_edl(
+    project: &Project,
+    text: &str,
+    mut allocate_segment_id: impl FnMut() -> SegmentId,
+) -> Result<Vec<Segment>> {
+    let record_grid = project.frame_grid().unwrap_or(FrameGrid::DEFAULT);
+    let reels = reel_names(&project.assets);
+    let mut segments = Vec::new();
+    let mut record_ranges = Vec::new();
+
+    for event in parse_events(text)? {
+        let invalid = |reason: String| EngineError::InvalidEdl {
+            line: event.line,
+            reason,
+        };
+        let asset = find_asset(&project.assets, &reels, &event)
+            .ok_or_else(|| invalid(format!("no asset matches reel {}", event.reel)))?;
+        let source_grid = asset.frame_grid().unwrap_or(record_grid);
+        let source_start_frame = asset.start_frame(source_grid)?;
+        // `:` timecode follows the FCM on the record side; sources keep their
+        // own count.
+        let source_drop_frame = source_drop_frame(asset, source_grid, event.fcm_drop_frame);
+        let [src_in, src_out, record_in, record_out] = [
+            (event.timecodes[0], source_grid, source_drop_frame),
+            (event.timecodes[1], source_grid, source_drop_frame),
+            (event.timecodes[2], record_grid, event.fcm_drop_frame),
+            (event.timecodes[3], record_grid, event.fcm_drop_frame),
+        ]
+        .map(|(timecode, grid, drop_frame)| {
+            Timecode {
+                drop_frame: timecode.drop_frame || drop_frame,
+                ..timecode
+            }
+            .frame(grid)
+            .map_err(|error| error.to_string())
+        });
+        // Source timecode counts from the asset's start timecode.
+        let source_frame = |frame: i64| {
+            let frames_per_day = Timecode::frames_per_day(
+                source_grid,
+                source_drop_frame || event.timecodes[0].drop_frame,
+            );
+            (frame - source_start_frame).rem_euclid(frames_per_day)
+        };
+        let (src_in, src_out) = (
+            source_frame(src_in.map_err(&invalid)?),
+            source_frame(src_out.map_err(&invalid)?),
+        );
+        let (record_in, record_out) = (record_in.map_err(&invalid)?, record_out.map_err(&invalid)?);
+        if src_out <= src_in || record_out <= record_in {
+            return Err(invalid(String::from(
+                "source and record ranges must not be empty",
+            )));
+        }
+        if source_grid == record_grid && record_out - record_in != src_out - src_in {
+            return Err(invalid(String::from(
+                "source and record durations must be equal",
+            )));
+        }
+
+        let start_tl = source_grid.frame_start_tl(src_in);
+        let mut end_tl = source_grid.frame_start_tl(src_out);
+        // A partial last frame is written as a whole one; map it back to the
+        // media end.
+        if end_tl > asset.duration_tl && source_grid.frame_start_tl(src_out - 1) < asset.duration_tl
+        {
+            end_tl = asset.duration_tl;
+        }
+        if end_tl > asset.duration_tl {
+            return Err(EngineError::InsufficientMedia {
+                asset_id: asset.id,
+                start_tl,
+                end_tl,
+                duration_tl: asset.duration_tl,
+            });
+        }
+        let mut segment = asset.segment_for_range(allocate_segment_id(), start_tl, end_tl);
+        segment.timeline_start = record_grid.frame_start_tl(record_in);
+        record_ranges.push((
+            segment.timeline_start,
+            segment.timeline_start + segment.timeline_duration,
+            event.line,
+        ));
+        segments.push(segment);
+    }
+
+    record_ranges.sort_unstable();
+    for pair in record_ranges.windows(2) {
+        if pair[1].0 < pair[0].1 {
+            return Err(EngineError::InvalidEdl {
+                line: pair[1].2,
+                reason: String::from("event overlaps another event on the record side"),
+            });
+        }
+    }
+    segments.sort_by_key(|segment| segment.timeline_start);
+    Ok(segments)
+}
+
+/// Returns `(grid, in, out)` for the segment's source range in frames from
+/// the asset start, on the asset grid or `record_grid` for audio-only assets.
+fn source_frames(
+    asset: &MediaAsset,
+    segment: &Segment,
+    record_grid: FrameGrid,
+) -> Result<(FrameGrid, i64, i64)> {
+    let (time_base, src_in, src_out) = asset.segment_source_ticks(segment)?;
+    let grid = asset.frame_grid().unwrap_or(record_grid);
+    Ok((
+        grid,
+        grid.nearest_frame(src_in, time_base),
+        grid.nearest_frame(src_out, time_base),
+    ))
+}
+
+/// Returns whether `asset`'s source timecode counts drop-frame: the asset's
+/// own start timecode decides when the file carries one, otherwise the EDL's
+/// `FCM` applies on rates that have a drop-frame count.
+fn source_drop_frame(asset: &MediaAsset, grid: FrameGrid, fcm_drop_frame: bool) -> bool {
+    match asset.video.as_ref().and_then(|video| video.start_timecode) {
+        Some(start) => start.drop_frame,
+        None => fcm_drop_frame && grid.supports_drop_frame(),
+    }
+}
+
+/// Assigns each asset a unique CMX3600 reel name built from its file stem:
+/// upper-case ASCII letters, digits and `_`, at most eight characters.
+fn reel_names(assets: &[MediaAsset]) -> HashMap<AssetId, String> {
+    let mut taken = HashSet::new();
+    let mut names = HashMap::with_capacity(assets.len());
+    for asset in assets {
+        let stem = asset
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut base: String = stem
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .take(REEL_NAME_LEN)
+            .collect();
+        if base.is_empty() {
+            base = String::from("AX");
+        }
+        let mut name = base.clone();
+        if taken.contains(&name) {
+            let suffix = asset.id.to_string();
+            let keep = REEL_NAME_LEN.saturating_sub(suffix.len()).min(base.len());
+            name = format!("{}{suffix}", &base[..keep]);
+        }
+        taken.insert(name.clone());
+        names.insert(asset.id, name);
+    }
+    names
+}
+
+fn find_asset<'a>(
+    assets: &'a [MediaAsset],
+    reels: &HashMap<AssetId, String>,
+    event: &EdlEvent,
+) -> Option<&'a MediaAsset> {
+    let by_clip_name = event.clip_name.as_deref().and_then(|clip_name| {
+        assets.iter().find(|asset| {
+            asset
+                .path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy() == clip_name)
+        })
+    });
+    by_clip_name.or_else(|| {
+        assets
+            .iter()
+            .find(|asset| reels.get(&asset.id) == Some(&event.reel))
+    })
+}
+
+/// One parsed cut event; `line` is 1-based.
+#[derive(Debug)]
+struct EdlEvent {
+    line: usize,
+    reel: String,
+    clip_name: Option<String>,
+    /// Source in/out and record in/out, drop-frame only when written with `;`.
+    timecodes: [Timecode; 4],
+    /// The `FCM` in effect: `:` record timecode counts drop-frame.
+    fcm_drop_frame: bool,
+}
+
+fn parse_events(text: &str) -> Result<Vec<EdlEvent>> {
+    let mut events: Vec<EdlEvent> = Vec::new();
+    let mut drop_frame = false;
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let invalid = |reason: &str| EngineError::InvalidEdl {
+            line: line_number,
+            reason: reason.to_owned(),
+        };
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("TITLE:") {
+            continue;
+        }
+        if let Some(mode) = line.strip_prefix("FCM:") {
+            drop_frame = match mode.trim() {
+                "NON-DROP FRAME" => false,
+                "DROP FRAME" => true,
+                _ => return Err(invalid("unknown frame code mode")),
+            };
+            continue;
+        }
+        if let Some(clip_name) = line.strip_prefix(CLIP_NAME_COMMENT) {
+            if let Some(event) = events.last_mut() {
+                event.clip_name = Some(clip_name.trim().to_owned());
+            }
+            continue;
+        }
+        if line.starts_with('*') {
+            continue;
+        }
+        if line.starts_with("M2") {
+            return Err(invalid("motion effects are not supported"));
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 4 || !fields[0].bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(invalid("expected an event line"));
+        }
+        if fields[3] != "C" {
+            return Err(invalid("only cut transitions are supported"));
+        }
+        let [src_in, src_out, record_in, record_out] = fields[4..] else {
+            return Err(invalid("expected four timecodes after the transition"));
+        };
+        let timecodes = [src_in, src_out, record_in, record_out]
+            .map(|field| Timecode::parse(field).map_err(|error| invalid(&error.to_string())));
+        let [src_in, src_out, record_in, record_out] = timecodes;
+        events.push(EdlEvent {
+            line: line_number,
+            reel: fields[1].to_owned(),
+            clip_name: None,
+            timecodes: [src_in?, src_out?, record_in?, record_out?],
+            fcm_drop_frame: drop_frame,
+        });
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::path::PathBuf;
+
+    use super::{export_edl, import_edl};
+    use crate::error::EngineError;
+    use crate::project::{
+        AudioStreamInfo, MediaAsset, Project, ProjectSettings, TimelineMarks, VideoStreamInfo,
+    };
+    use crate::time::{FrameGrid, Rational, Timecode};
+    use crate::timeline::{Segment, Timeline};
+
+    fn asset(id: u64, path: &str, duration_tl: i64) -> MediaAsset {
+        MediaAsset {
+            id,
+            path: PathBuf::from(path),
+            video_stream_index: Some(0),
+            audio_stream_index: Some(1),
+            video: Some(VideoStreamInfo {
+                time_base: Rational::new(1, 90_000).expect("valid rational"),
+                frame_rate: Some(Rational::new(30_000, 1_001).expect("valid rational")),
+                width: 1920,
+                height: 1080,
+                start_pts: 9_000,
+                start_timecode: None,
+            }),
+            audio: Some(AudioStreamInfo {
+                time_base: Rational::new(1, 48_000).expect("valid rational"),
+                sample_rate: 48_000,
+                channels: 2,
+                start_pts: 0,
+            }),
+            duration_tl,
+            source_marks: TimelineMarks::default(),
+            file_size: None,
+            offline: false,
+        }
+    }
+
+    /// Segment covering source frames `src_frames` placed at record frame
+    /// `record_frame`, on the 29.97 fps grid.
+    fn segment(asset: &MediaAsset, id: u64, src_frames: (i64, i64), record_frame: i64) -> Segment {
+        let grid = asset.frame_grid().expect("video asset");
+        let mut segment = asset.segment_for_range(
+            id,
+            grid.frame_start_tl(src_frames.0),
+            grid.frame_start_tl(src_frames.1),
+        );
+        segment.timeline_start = grid.frame_start_tl(record_frame);
+        segment
+    }
+
+    fn sample_project() -> Project {
+        let interview = asset(1, "/media/interview take 1.mov", 600_000_000);
+        let broll = asset(2, "/media/b-roll.mp4", 120_000_000);
+        let segments = vec![
+            segment(&interview, 1, (1_800, 2_100), 0),
+            segment(&broll, 2, (45, 195), 300),
+            segment(&interview, 3, (4_000, 4_090), 500),
+        ];
+        Project {
+            assets: vec![interview, broll],
+            timeline: Timeline { segments },
+            settings: ProjectSettings::default(),
+            marks: TimelineMarks::default(),
+            selection: BTreeSet::new(),
+            loop_playback: false,
+            changes: Default::default(),
+        }
+    }
+
+    fn ids() -> impl FnMut() -> u64 {
+        let mut next = 10;
+        move || {
+            next += 1;
+            next - 1
+        }
+    }
+
+    #[test]
+    fn export_writes_reels_and_source_and_record_timecode() {
+        let mut project = sample_project();
+        project.timeline.segments[2].enabled = false;
+
+        let text = export_edl(&project, "cut").expect("export should succeed");
+
+        assert_eq!(
+            text,
+            "TITLE: cut\n\
+             FCM: NON-DROP FRAME\n\
+             \n\
+             001  INTERVIE AA/V  C        00:01:00:00 00:01:10:00 00:00:00:00 00:00:10:00\n\
+             * FROM CLIP NAME: interview take 1.mov\n\
+             \n\
+             002  B_ROLL   AA/V  C        00:00:01:15 00:00:06:15 00:00:10:00 00:00:15:00\n\
+             * FROM CLIP NAME: b-roll.mp4\n"
+        );
+    }
+
+    #[test]
+    fn edl_round_trip_keeps_segment_timings() {
+        let project = sample_project();
+        let text = export_edl(&project, "cut").expect("export should succeed");
+
+        let segments = import_edl(&project, &text, ids()).expect("import should succeed");
+
+        assert_eq!(segments.len(), project.timeline.segments.len());
+        for (imported, original) in segments.iter().zip(&project.timeline.segments) {
+            assert_eq!(
+                Segment {
+                    id: original.id,
+                    ..imported.clone()
+                },
+                *original
+            );
+        }
+        assert_eq!(
+            segments
+                .iter()
+                .map(|segment| segment.id)
+                .collect::<Vec<_>>(),
+            vec![10, 11, 12]
+        );
+    }
+
+    #[test]
+    fn mixed_rate_sources_keep_record_timecode_on_the_project_grid() {
+        let mut project = sample_project();
+        let mut pal = asset(3, "/media/pal.mov", 10_000_000);
+        pal.video.as_mut().expect("video").frame_rate =
+            Some(Rational::new(25, 1).expect("valid rational"));
+        let ntsc = FrameGrid::from_frame_rate(Rational::new(30_000, 1_001).expect("valid"));
+        let mut inserted = segment(&pal, 4, (0, 25), 0);
+        inserted.timeline_start = ntsc.frame_start_tl(590);
+        project.assets.push(pal);
+        project.timeline.segments.push(inserted.clone());
+
+        let text = export_edl(&project, "cut").expect("export should succeed");
+        assert!(text.contains("00:00:00:00 00:00:01:00 00:00:19:20 00:00:20:20\n"));
+
+        let segments = import_edl(&project, &text, ids()).expect("import should succeed");
+        assert_eq!(segments.len(), 4);
+        assert_eq!(
+            Segment {
+                id: inserted.id,
+                ..segments[3].clone()
+            },
+            inserted
+        );
+    }
+
+    #[test]
+    fn drop_frame_projects_write_drop_frame_timecode_offset_by_the_source_start() {
+        let mut project = sample_project();
+        project.assets[0]
+            .video
+            .as_mut()
+            .expect("video")
+            .start_timecode = Some(Timecode::parse("01:00:00;00").expect("valid"));
+        project.timeline.segments[0].timeline_start =
+            FrameGrid::from_frame_rate(Rational::new(30_000, 1_001).expect("valid"))
+                .frame_start_tl(17_982);
+        project.timeline.segments.truncate(1);
+
+        let text = export_edl(&project, "cut").expect("export should succeed");
+        assert!(text.contains("FCM: DROP FRAME\n"));
+        assert!(text.contains("01:01:00;02 01:01:10;02 00:10:00;00 00:10:10;00"));
+
+        let segments = import_edl(&project, &text, ids()).expect("import should succeed");
+        assert_eq!(
+            Segment {
+                id: project.timeline.segments[0].id,
+                ..segments[0].clone()
+            },
+            project.timeline.segments[0]
+        );
+    }
+
+    #[test]
+    fn drop_frame_projects_round_trip_mixed_rate_and_non_drop_sources() {
+        let mut project = sample_project();
+        let ntsc = FrameGrid::from_frame_rate(Rational::new(30_000, 1_001).expect("valid"));
+        project.assets[0]
+            .video
+            .as_mut()
+            .expect("video")
+            .start_timecode = Some(Timecode::parse("01:00:00;00").expect("valid"));
+        project.timeline.segments[0].timeline_start = ntsc.frame_start_tl(17_982);
+        project.timeline.segments.truncate(1);
+        let mut pal = asset(3, "/media/pal.mov", 10_000_000);
+        pal.video.as_mut().expect("video").frame_rate =
+            Some(Rational::new(25, 1).expect("valid rational"));
+        let mut non_drop = asset(4, "/media/non drop.mov", 600_000_000);
+        non_drop.video.as_mut().expect("video").start_timecode =
+            Some(Timecode::parse("10:00:00:00").expect("valid"));
+        let mut pal_segment = segment(&pal, 4, (0, 25), 0);
+        pal_segment.timeline_start = ntsc.frame_start_tl(18_300);
+        let non_drop_segment = segment(&non_drop, 5, (3_600, 3_630), 18_400);
+        project.assets.extend([pal, non_drop]);
+        project
+            .timeline
+            .segments
+            .extend([pal_segment, non_drop_segment]);
+
+        let text = export_edl(&project, "cut").expect("export should succeed");
+        assert!(text.contains("FCM: DROP FRAME\n"));
+        assert!(text.contains("00:00:00:00 00:00:01:00 00:10:10;18 00:10:11;18"));
+        assert!(text.contains("10:02:00:00 10:02:01:00 00:10:13;28 00:10:14;28"));
+
+        let segments = import_edl(&project, &text, ids()).expect("import should succeed");
+        assert_eq!(segments.len(), 3);
+        for (imported, original) in segments.iter().zip(&project.timeline.segments) {
+            assert_eq!(
+                Segment {
+                    id: original.id,
+                    ..imported.clone()
+                },
+                *original
+            );
+        }
+    }
+
+    #[test]
+    fn import_matches_reels_when_clip_names_are_missing() {
+        let project = sample_project();
+        let text = "TITLE: handoff\r\n\
+                    \r\n\
+                    002  B_ROLL   V     C        00:00:01:15 00:00:02:15 00:00:05:00 00:00:06:00\r\n\
+                    001  INTERVIE A     C        00:00:00:00 00:00:01:00 00:00:00:00 00:00:01:00\r\n";
+
+        let segments = import_edl(&project, text, ids()).expect("import should succeed");
+
+        assert_eq!(
+            segments
+                .iter()
+                .map(|segment| (segment.asset_id, segment.timeline_start))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, 0),
+                (
+                    2,
+                    FrameGrid::from_frame_rate(Rational::new(30_000, 1_001).expect("valid"))
+                        .frame_start_tl(150)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn import_reports_the_offending_line() {
+        let project = sample_project();
+        let cases = [
+            (
+                "001  AX V C 00:00:00:00 00:00:01:00 00:00:00:00 00:00:01:00",
+                1,
+            ),
+            ("FCM: HALF FRAME", 1),
+            (
+                "FCM: DROP FRAME\n001  B_ROLL V C 00:00:00:00 00:00:01:00 00:01:00:00 00:01:01:00",
+                2,
+            ),
+            (
+                "TITLE: x\n001  B_ROLL V D 010 00:00:00:00 00:00:01:00 00:00:00:00 00:00:01:00",
+                2,
+            ),
+            (
+                "\n\n001  B_ROLL V C 00:00:00:00 00:00:01:00 00:00:00:00 00:00:02:00",
+                3,
+            ),
+            (
+                "001  B_ROLL V C 00:00:00:30 00:00:01:00 00:00:00:00 00:00:01:00",
+                1,
+            ),
+            ("001  B_ROLL V C 00:00:00:00", 1),
+            ("garbage", 1),
+            (
+                "001  B_ROLL V C 00:00:00:00 00:00:02:00 00:00:00:00 00:00:02:00\n\
+                 002  B_ROLL V C 00:00:00:00 00:00:01:00 00:00:01:00 00:00:02:00",
+                2,
+            ),
+        ];
+        for (text, expected_line) in cases {
+            assert!(
+                matches!(
+                    import_edl(&project, text, ids()),
+                    Err(EngineError::InvalidEdl { line, .. }) if line == expected_line
+                ),
+                "{text:?} should fail at line {expected_line}"
+            );
+        }
+        assert!(matches!(
+            import_edl(
+                &project,
+                "001  B_ROLL V C 00:01:00:00 00:03:00:00 00:00:00:00 00:02:00:00",
+                ids()
+            ),
+            Err(EngineError::InsufficientMedia { asset_id: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn start_timecode_that_does_not_fit_the_rate_fails_the_export() {
+        let mut project = sample_project();
+        let video = project.assets[0].video.as_mut().expect("video");
+        video.frame_rate = Some(Rational::new(25, 1).expect("valid rational"));
+        video.start_timecode = Some(Timecode::parse("01:00:00;00").expect("valid"));
+
+        assert!(matches!(
+            export_edl(&project, "cut"),
+            Err(EngineError::InvalidTimecode { .. })
+        ));
+    }
+}
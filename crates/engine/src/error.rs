@@ -49,6 +49,10 @@ pub enum EngineError {
         num: i32,
         den: i32,
     },
+    InvalidTimecode {
+        value: String,
+        reason: String,
+    },
     ProjectIo {
         context: &'static str,
         path: PathBuf,
@@ -61,6 +65,69 @@ pub enum EngineError {
     InvalidProjectFile {
         reason: String,
     },
+    /// No recovery copy was offered, or it was already handled.
+    RecoveryNotAvailable,
+    /// The project file was written by a newer build.
+    ProjectFileTooNew {
+        version: u32,
+        supported: u32,
+    },
+    InOutRangeNotSet,
+    SelectionEmpty,
+    ClipboardEmpty,
+    EmptyRange {
+        start_tl: i64,
+        end_tl: i64,
+    },
+    /// The source range `start_tl..end_tl`, in ticks from the asset start,
+    /// does not fit inside the asset.
+    InsufficientMedia {
+        asset_id: u64,
+        start_tl: i64,
+        end_tl: i64,
+        duration_tl: i64,
+    },
+    /// The asset's file was missing when the project was loaded.
+    AssetOffline {
+        asset_id: u64,
+        path: PathBuf,
+    },
+    /// An edit decision list could not be imported; `line` is 1-based.
+    InvalidEdl {
+        line: usize,
+        reason: String,
+    },
+    /// An OpenTimelineIO document could not be imported.
+    InvalidOtio {
+        reason: String,
+    },
+    /// Rows of a cut list could not be imported, in line order.
+    InvalidCutList {
+        errors: Vec<crate::cut_list::CutListRowError>,
+    },
+    /// A relink candidate's streams do not match the asset.
+    IncompatibleMedia {
+        asset_id: u64,
+        path: PathBuf,
+        reason: String,
+    },
+    UnsupportedAudioFormat {
+        sample_rate: u32,
+        channels: u16,
+    },
+    AudioOutput {
+        context: &'static str,
+        source: std::io::Error,
+    },
+    /// Command `index` of a batch failed; the whole batch was rolled back.
+    BatchCommandFailed {
+        index: usize,
+        source: Box<EngineError>,
+    },
+    /// Command `index` cannot run inside a batch.
+    CommandNotBatchable {
+        index: usize,
+    },
     Media(media_ffmpeg::MediaFfmpegError),
 }
 
@@ -116,6 +183,9 @@ impl Display for EngineError {
                 write!(f, "audio metadata is missing: {}", path.display())
             }
             Self::InvalidRational { num, den } => write!(f, "invalid rational {num}/{den}"),
+            Self::InvalidTimecode { value, reason } => {
+                write!(f, "invalid timecode {value}: {reason}")
+            }
             Self::ProjectIo {
                 context,
                 path,
@@ -129,6 +199,63 @@ impl Display for EngineError {
                 )
             }
             Self::InvalidProjectFile { reason } => write!(f, "invalid project file: {reason}"),
+            Self::RecoveryNotAvailable => write!(f, "no recovery copy is available"),
+            Self::ProjectFileTooNew { version, supported } => write!(
+                f,
+                "project file schema version {version} is newer than the supported version {supported}"
+            ),
+            Self::InOutRangeNotSet => write!(f, "in/out range is not set"),
+            Self::SelectionEmpty => write!(f, "no segment is selected"),
+            Self::ClipboardEmpty => write!(f, "clipboard is empty"),
+            Self::EmptyRange { start_tl, end_tl } => {
+                write!(f, "timeline range is empty: {start_tl}..{end_tl}")
+            }
+            Self::InsufficientMedia {
+                asset_id,
+                start_tl,
+                end_tl,
+                duration_tl,
+            } => write!(
+                f,
+                "asset {asset_id} has no media for source range {start_tl}..{end_tl} (duration {duration_tl})"
+            ),
+            Self::AssetOffline { asset_id, path } => {
+                write!(f, "asset {asset_id} is offline: {}", path.display())
+            }
+            Self::InvalidEdl { line, reason } => {
+                write!(f, "invalid EDL at line {line}: {reason}")
+            }
+            Self::InvalidOtio { reason } => write!(f, "invalid OTIO document: {reason}"),
+            Self::InvalidCutList { errors } => {
+                write!(f, "invalid cut list")?;
+                for (index, error) in errors.iter().enumerate() {
+                    write!(f, "{} {error}", if index == 0 { ":" } else { ";" })?;
+                }
+                Ok(())
+            }
+            Self::IncompatibleMedia {
+                asset_id,
+                path,
+                reason,
+            } => write!(
+                f,
+                "cannot relink asset {asset_id} to {}: {reason}",
+                path.display()
+            ),
+            Self::UnsupportedAudioFormat {
+                sample_rate,
+                channels,
+            } => write!(
+                f,
+                "unsupported audio format: {sample_rate} Hz, {channels} channels"
+            ),
+            Self::AudioOutput { context, source } => write!(f, "{context} ({source})"),
+            Self::BatchCommandFailed { index, source } => {
+                write!(f, "batch command {index} failed: {source}")
+            }
+            Self::CommandNotBatchable { index } => {
+                write!(f, "batch command {index} cannot run inside a batch")
+            }
             Self::Media(err) => write!(f, "media backend error: {err}"),
         }
     }
@@ -139,6 +266,8 @@ impl std::error::Error for EngineError {
         match self {
             Self::ProjectIo { source, .. } => Some(source),
             Self::ProjectSerialization { source, .. } => Some(source),
+            Self::AudioOutput { source, .. } => Some(source),
+            Self::BatchCommandFailed { source, .. } => Some(source.as_ref()),
             Self::Media(err) => Some(err),
             _ => None,
         }
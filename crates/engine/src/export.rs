@@ -30,6 +30,8 @@ pub struct ExportVideoSegment {
     pub src_in_audio: Option<i64>,
     pub src_out_audio: Option<i64>,
     pub src_audio_time_base: Option<Rational>,
+    /// Disabled segments keep their length but render as black and silence.
+    pub enabled: bool,
 }
 
 /// Builds an export plan from the current project timeline.
@@ -46,6 +48,7 @@ pub fn build_video_export_plan(project: &Project, output_path: PathBuf) -> Resul
             .ok_or(EngineError::MissingAsset {
                 asset_id: timeline_segment.asset_id,
             })?;
+        asset.ensure_online()?;
         let video = asset
             .video
             .ok_or(EngineError::MissingVideoStream { asset_id: asset.id })?;
@@ -88,6 +91,7 @@ pub fn build_video_export_plan(project: &Project, output_path: PathBuf) -> Resul
             src_in_audio: None,
             src_out_audio: None,
             src_audio_time_base: None,
+            enabled: timeline_segment.enabled,
         });
         selected.push((timeline_segment, asset));
     }
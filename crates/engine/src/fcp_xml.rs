@@ -0,0 +1,392 @@
+//! Final Cut Pro 7 XML (`xmeml`) export.
+//!
+//! Writes a single sequence at the project frame rate with one video track
+//! and one audio track per source channel (at most two). Segments become
+//! `clipitem`s placed by `start`/`end` in sequence frames, so timeline gaps
+//! are left implicit; `in`/`out` are source frames at the asset rate (the
+//! project rate for audio-only assets). Each asset is described by a `file`
+//! element the first time it is referenced and by its id afterwards, which is
+//! what Premiere Pro and DaVinci Resolve expect when relinking.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::error::Result;
+use crate::otio::path_to_url;
+use crate::project::{MediaAsset, Project};
+use crate::time::{FrameGrid, TIMELINE_TIME_BASE};
+use crate::timeline::{AssetId, Segment};
+
+/// Audio tracks written for stereo and wider sources.
+const MAX_AUDIO_TRACKS: u16 = 2;
+const AUDIO_SAMPLE_DEPTH: u32 = 16;
+
+/// Writes the timeline as an FCP7 XML document with a sequence named `name`.
+///
+/// # Example
+/// ```ignore
+/// let text = engine::fcp_xml::export_fcp_xml(&project, "cut")?;
+/// std::fs::write("cut.xml", text)?;
+/// ```
+pub fn export_fcp_xml(project: &Project, name: &str) -> Result<String> {
+    let record_grid = project.frame_grid().unwrap_or(FrameGrid::DEFAULT);
+    let mut writer = XmlWriter::default();
+    let mut written_files = HashSet::new();
+
+    writer.open("xmeml", &[("version", "4")]);
+    writer.open("sequence", &[("id", "sequence-1")]);
+    writer.leaf("name", name);
+    writer.leaf(
+        "duration",
+        record_grid.nearest_frame(project.duration_tl(), TIMELINE_TIME_BASE),
+    );
+    write_rate(&mut writer, record_grid);
+    writer.open("timecode", &[]);
+    write_rate(&mut writer, record_grid);
+    writer.leaf("string", "00:00:00:00");
+    writer.leaf("frame", 0);
+    writer.leaf("displayformat", "NDF");
+    writer.close("timecode");
+
+    writer.open("media", &[]);
+    writer.open("video", &[]);
+    if let Some(video) = project.assets.iter().find_map(|asset| asset.video) {
+        writer.open("format", &[]);
+        writer.open("samplecharacteristics", &[]);
+        write_rate(&mut writer, record_grid);
+        writer.leaf("width", video.width);
+        writer.leaf("height", video.height);
+        writer.close("samplecharacteristics");
+        writer.close("format");
+    }
+    writer.open("track", &[]);
+    let mut clip_index = 0;
+    for segment in &project.timeline.segments {
+        let asset = project.asset_by_id(segment.asset_id)?;
+        if asset.video.is_none() {
+            continue;
+        }
+        clip_index += 1;
+        let clip = ClipItem::new(asset, segment, record_grid)?;
+        clip.write(&mut writer, clip_index, &mut written_files, None);
+    }
+    writer.close("track");
+    writer.close("video");
+
+    writer.open("audio", &[]);
+    let audio_tracks = project
+        .assets
+        .iter()
+        .filter_map(|asset| asset.audio)
+        .map(|audio| audio.channels.min(MAX_AUDIO_TRACKS))
+        .max()
+        .unwrap_or(0);
+    for track_index in 1..=audio_tracks {
+        writer.open("track", &[]);
+        for segment in &project.timeline.segments {
+            let asset = project.asset_by_id(segment.asset_id)?;
+            let Some(audio) = asset.audio else {
+                continue;
+            };
+            if audio.channels < track_index {
+                continue;
+            }
+            clip_index += 1;
+            let clip = ClipItem::new(asset, segment, record_grid)?;
+            clip.write(
+                &mut writer,
+                clip_index,
+                &mut written_files,
+                Some(track_index),
+            );
+        }
+        writer.close("track");
+    }
+    writer.close("audio");
+    writer.close("media");
+    writer.close("sequence");
+    writer.close("xmeml");
+
+    Ok(format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE xmeml>\n{}",
+        writer.text
+    ))
+}
+
+/// One segment placed on a track, in sequence frames and source frames.
+struct ClipItem<'a> {
+    asset: &'a MediaAsset,
+    segment: &'a Segment,
+    source_grid: FrameGrid,
+    record_in: i64,
+    record_out: i64,
+    src_in: i64,
+    src_out: i64,
+}
+
+impl<'a> ClipItem<'a> {
+    fn new(asset: &'a MediaAsset, segment: &'a Segment, record_grid: FrameGrid) -> Result<Self> {
+        let (time_base, src_in, src_out) = asset.segment_source_ticks(segment)?;
+        let source_grid = asset.frame_grid().unwrap_or(record_grid);
+        let segment_end_tl = segment.timeline_start + segment.timeline_duration;
+        Ok(Self {
+            asset,
+            segment,
+            source_grid,
+            record_in: record_grid.nearest_frame(segment.timeline_start, TIMELINE_TIME_BASE),
+            record_out: record_grid.nearest_frame(segment_end_tl, TIMELINE_TIME_BASE),
+            src_in: source_grid.nearest_frame(src_in, time_base),
+            src_out: source_grid.nearest_frame(src_out, time_base),
+        })
+    }
+
+    /// Writes the `clipitem`, with a `sourcetrack` for audio channel
+    /// `audio_track` when it sits on an audio track.
+    fn write(
+        &self,
+        writer: &mut XmlWriter,
+        index: usize,
+        written_files: &mut HashSet<AssetId>,
+        audio_track: Option<u16>,
+    ) {
+        writer.open("clipitem", &[("id", &format!("clipitem-{index}"))]);
+        writer.leaf("name", file_name(&self.asset.path));
+        writer.leaf("enabled", xml_bool(self.segment.enabled));
+        writer.leaf("duration", asset_frames(self.asset, self.source_grid));
+        write_rate(writer, self.source_grid);
+        writer.leaf("start", self.record_in);
+        writer.leaf("end", self.record_out);
+        writer.leaf("in", self.src_in);
+        writer.leaf("out", self.src_out);
+        if written_files.insert(self.asset.id) {
+            write_file(writer, self.asset, self.source_grid);
+        } else {
+            writer.empty("file", &[("id", &file_id(self.asset))]);
+        }
+        if let Some(track_index) = audio_track {
+            writer.open("sourcetrack", &[]);
+            writer.leaf("mediatype", "audio");
+            writer.leaf("trackindex", track_index);
+            writer.close("sourcetrack");
+        }
+        writer.close("clipitem");
+    }
+}
+
+/// Writes the full `file` element describing `asset`.
+fn write_file(writer: &mut XmlWriter, asset: &MediaAsset, grid: FrameGrid) {
+    let path = std::path::absolute(&asset.path).unwrap_or_else(|_| asset.path.clone());
+    writer.open("file", &[("id", &file_id(asset))]);
+    writer.leaf("name", file_name(&asset.path));
+    writer.leaf("pathurl", path_to_url(&path));
+    write_rate(writer, grid);
+    writer.leaf("duration", asset_frames(asset, grid));
+    writer.open("media", &[]);
+    if let Some(video) = asset.video {
+        writer.open("video", &[]);
+        writer.open("samplecharacteristics", &[]);
+        write_rate(writer, grid);
+        writer.leaf("width", video.width);
+        writer.leaf("height", video.height);
+        writer.close("samplecharacteristics");
+        writer.close("video");
+    }
+    if let Some(audio) = asset.audio {
+        writer.open("audio", &[]);
+        writer.open("samplecharacteristics", &[]);
+        writer.leaf("depth", AUDIO_SAMPLE_DEPTH);
+        writer.leaf("samplerate", audio.sample_rate);
+        writer.close("samplecharacteristics");
+        writer.leaf("channelcount", audio.channels);
+        writer.close("audio");
+    }
+    writer.close("media");
+    writer.close("file");
+}
+
+/// Writes a `rate` element: the nominal whole-frame timebase, flagged NTSC for
+/// the 1000/1001 rates.
+fn write_rate(writer: &mut XmlWriter, grid: FrameGrid) {
+    let rate = grid.frame_rate();
+    let timebase = (i64::from(rate.num) + i64::from(rate.den) - 1) / i64::from(rate.den);
+    writer.open("rate", &[]);
+    writer.leaf("timebase", timebase);
+    writer.leaf("ntsc", xml_bool(rate.den == 1001));
+    writer.close("rate");
+}
+
+fn asset_frames(asset: &MediaAsset, grid: FrameGrid) -> i64 {
+    grid.nearest_frame(asset.duration_tl, TIMELINE_TIME_BASE)
+}
+
+fn file_id(asset: &MediaAsset) -> String {
+    format!("file-{}", asset.id)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn xml_bool(value: bool) -> &'static str {
+    if value { "TRUE" } else { "FALSE" }
+}
+
+/// Minimal indented XML writer; callers keep open and close tags balanced.
+#[derive(Default)]
+struct XmlWriter {
+    text: String,
+    depth: usize,
+}
+
+impl XmlWriter {
+    fn open(&mut self, tag: &str, attributes: &[(&str, &str)]) {
+        self.start_tag(tag, attributes);
+        self.text.push_str(">\n");
+        self.depth += 1;
+    }
+
+    fn close(&mut self, tag: &str) {
+        self.depth -= 1;
+        self.indent();
+        self.text.push_str(&format!("</{tag}>\n"));
+    }
+
+    fn empty(&mut self, tag: &str, attributes: &[(&str, &str)]) {
+        self.start_tag(tag, attributes);
+        self.text.push_str("/>\n");
+    }
+
+    fn leaf(&mut self, tag: &str, value: impl ToString) {
+        self.indent();
+        self.text
+            .push_str(&format!("<{tag}>{}</{tag}>\n", escape(&value.to_string())));
+    }
+
+    fn start_tag(&mut self, tag: &str, attributes: &[(&str, &str)]) {
+        self.indent();
+        self.text.push('<');
+        self.text.push_str(tag);
+        for (name, value) in attributes {
+            self.text
+                .push_str(&format!(" {name}=\"{}\"", escape(value)));
+        }
+    }
+
+    fn indent(&mut self) {
+        for _ in 0..self.depth {
+            self.text.push_str("  ");
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::project::{AudioStreamInfo, TimelineMarks, VideoStreamInfo};
+    use crate::time::Rational;
+    use crate::timeline::Timeline;
+
+    fn asset(id: u64, path: &str, audio_channels: Option<u16>) -> MediaAsset {
+        MediaAsset {
+            id,
+            path: PathBuf::from(path),
+            video_stream_index: Some(0),
+            audio_stream_index: audio_channels.map(|_| 1),
+            video: Some(VideoStreamInfo {
+                time_base: Rational::new(1, 90_000).expect("valid rational"),
+                frame_rate: Some(Rational::new(30_000, 1_001).expect("valid rational")),
+                width: 1920,
+                height: 1080,
+                start_pts: 0,
+                start_timecode: None,
+            }),
+            audio: audio_channels.map(|channels| AudioStreamInfo {
+                time_base: Rational::new(1, 48_000).expect("valid rational"),
+                sample_rate: 48_000,
+                channels,
+                start_pts: 0,
+            }),
+            duration_tl: 10_010_000,
+            source_marks: TimelineMarks::default(),
+            file_size: None,
+            offline: false,
+        }
+    }
+
+    fn project() -> Project {
+        let first = asset(1, "/media/A & B.mov", Some(2));
+        let second = asset(2, "/media/b.mov", None);
+        let a = first.segment_for_range(1, 1_001_000, 2_002_000);
+        let mut b = second.segment_for_range(2, 0, 1_001_000);
+        let mut c = first.segment_for_range(3, 3_003_000, 4_004_000);
+        b.timeline_start = 1_001_000;
+        c.timeline_start = 3_003_000;
+        c.enabled = false;
+        Project {
+            assets: vec![first, second],
+            timeline: Timeline {
+                segments: vec![a, b, c],
+            },
+            ..Project::default()
+        }
+    }
+
+    #[test]
+    fn exports_sequence_rate_format_and_clip_ranges() {
+        let xml = export_fcp_xml(&project(), "Cut <1>").expect("export");
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE xmeml>\n"));
+        assert!(xml.contains("<name>Cut &lt;1&gt;</name>"));
+        assert!(xml.contains("<timebase>30</timebase>\n"));
+        assert!(xml.contains("<ntsc>TRUE</ntsc>"));
+        assert!(xml.contains("<width>1920</width>"));
+        assert!(xml.contains("<height>1080</height>"));
+        // Sequence runs to the end of the last segment: 120 frames at 29.97.
+        assert!(xml.contains("<duration>120</duration>"));
+        // First segment: record 0..30, source 30..60.
+        assert!(xml.contains(
+            "<start>0</start>\n            <end>30</end>\n            <in>30</in>\n            <out>60</out>"
+        ));
+        // The gap between the second and third segment stays implicit.
+        assert!(xml.contains("<start>90</start>"));
+        assert!(xml.contains("<enabled>FALSE</enabled>"));
+        assert!(xml.contains("<pathurl>file:///media/A%20%26%20B.mov</pathurl>"));
+        assert!(xml.contains("<name>A &amp; B.mov</name>"));
+    }
+
+    #[test]
+    fn writes_each_file_once_and_one_audio_track_per_channel() {
+        let xml = export_fcp_xml(&project(), "cut").expect("export");
+
+        assert_eq!(xml.matches("<file id=\"file-1\">").count(), 1);
+        assert_eq!(xml.matches("<file id=\"file-1\"/>").count(), 5);
+        assert_eq!(xml.matches("<file id=\"file-2\">").count(), 1);
+        assert_eq!(xml.matches("<track>").count(), 3);
+        assert_eq!(xml.matches("<trackindex>1</trackindex>").count(), 2);
+        assert_eq!(xml.matches("<trackindex>2</trackindex>").count(), 2);
+        assert!(xml.contains("<samplerate>48000</samplerate>"));
+        assert!(xml.contains("<channelcount>2</channelcount>"));
+        assert_eq!(xml.matches("<clipitem ").count(), 7);
+        assert_eq!(xml.matches("</clipitem>").count(), 7);
+        assert!(xml.trim_end().ends_with("</xmeml>"));
+    }
+}
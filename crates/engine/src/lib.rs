@@ -1,21 +1,34 @@
 //! UI-agnostic editing engine for the Cutit MVP.
 
 pub mod api;
+pub mod autosave;
 pub mod cache;
+pub mod cut_list;
+pub mod edl;
 pub mod error;
 pub mod export;
+pub mod fcp_xml;
+pub mod migration;
+pub mod otio;
+pub mod playback;
 pub mod preview;
 pub mod project;
 pub mod time;
 pub mod timeline;
+mod worker;
 
 pub use api::{
-    Command, DEFAULT_PREVIEW_CACHE_BUCKET_TL, Engine, EngineErrorEvent, EngineErrorKind, Event,
-    ExportSettings, ProjectSnapshot,
+    Command, CommandId, DEFAULT_PREVIEW_CACHE_BUCKET_TL, EditPointDirection, Engine,
+    EngineErrorEvent, EngineErrorKind, Event, ExportRange, ExportSettings, PasteMode, ProjectDelta,
+    ProjectSnapshot, ReplaceAlignment, TaggedCommand, TaggedEvent,
 };
+pub use autosave::AutosaveConfig;
 pub use error::{EngineError, Result};
+pub use migration::AppliedMigration;
+pub use playback::{AudioSink, NullAudioSink, PcmChunk, PcmFormat};
 pub use preview::{
-    FfmpegMediaBackend, MediaBackend, PreviewFrame, PreviewPixelFormat, ProbedAudioStream,
-    ProbedMedia, ProbedVideoStream,
+    FfmpegMediaBackend, MediaBackend, PreviewFrame, PreviewPixelFormat, PreviewQuality,
+    ProbedAudioStream, ProbedMedia, ProbedVideoStream,
 };
-pub use time::{Rational, TIMELINE_TIME_BASE, rescale};
+pub use project::{MediaPathStyle, PROJECT_BACKUP_COUNT, TimelineMarks};
+pub use time::{FrameGrid, Rational, TIMELINE_TIME_BASE, Timecode, rescale};
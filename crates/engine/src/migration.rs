@@ -0,0 +1,166 @@
+//! Project file schema migrations.
+//!
+//! Older project files are upgraded one version at a time on the raw JSON
+//! value, before typed deserialization, so `ProjectFile` only ever describes
+//! the current schema. Each step rewrites the document from its version to the
+//! next and bumps `schema_version`; files from a newer build are refused.
+
+use serde_json::{Map, Value};
+
+use crate::error::{EngineError, Result};
+
+/// Schema version written by this build.
+pub const PROJECT_FILE_SCHEMA_VERSION: u32 = 2;
+
+/// One schema upgrade applied while loading a project file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AppliedMigration {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub description: &'static str,
+}
+
+struct Migration {
+    from_version: u32,
+    description: &'static str,
+    apply: fn(&mut Map<String, Value>) -> Result<()>,
+}
+
+/// Upgrade steps in order; `MIGRATIONS[i]` upgrades version `i + 1`.
+const MIGRATIONS: &[Migration] = &[Migration {
+    from_version: 1,
+    description: "optional fields added, no rewrite needed",
+    apply: v1_to_v2,
+}];
+
+/// Upgrades `document` to `PROJECT_FILE_SCHEMA_VERSION` in place.
+///
+/// Returns the steps that ran, oldest first; a current file returns none.
+///
+/// # Example
+/// ```ignore
+/// let mut document: serde_json::Value = serde_json::from_str(&text)?;
+/// for step in migrate(&mut document)? {
+///     println!("{} -> {}: {}", step.from_version, step.to_version, step.description);
+/// }
+/// ```
+pub(crate) fn migrate(document: &mut Value) -> Result<Vec<AppliedMigration>> {
+    let root = document
+        .as_object_mut()
+        .ok_or_else(|| invalid("project file is not a JSON object"))?;
+    let mut version = schema_version(root)?;
+    if version > PROJECT_FILE_SCHEMA_VERSION {
+        return Err(EngineError::ProjectFileTooNew {
+            version,
+            supported: PROJECT_FILE_SCHEMA_VERSION,
+        });
+    }
+
+    let mut applied = Vec::new();
+    while version < PROJECT_FILE_SCHEMA_VERSION {
+        let migration = MIGRATIONS
+            .iter()
+            .find(|migration| migration.from_version == version)
+            .ok_or_else(|| invalid(&format!("unsupported project schema version {version}")))?;
+        (migration.apply)(root)?;
+        version += 1;
+        root.insert(String::from("schema_version"), Value::from(version));
+        applied.push(AppliedMigration {
+            from_version: migration.from_version,
+            to_version: version,
+            description: migration.description,
+        });
+    }
+    Ok(applied)
+}
+
+fn schema_version(root: &Map<String, Value>) -> Result<u32> {
+    root.get("schema_version")
+        .and_then(Value::as_u64)
+        .and_then(|version| u32::try_from(version).ok())
+        .ok_or_else(|| invalid("missing or invalid schema_version"))
+}
+
+/// v2 only adds optional fields (disabled segments, timeline and source
+/// marks, stream start timestamps, relative media paths and file sizes) whose
+/// absence reads back as the v1 meaning, so a v1 document needs no rewrite.
+/// The bump exists because v1 builds silently drop those fields; they refuse
+/// any version other than 1.
+fn v1_to_v2(_root: &mut Map<String, Value>) -> Result<()> {
+    Ok(())
+}
+
+fn invalid(reason: &str) -> EngineError {
+    EngineError::InvalidProjectFile {
+        reason: reason.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{AppliedMigration, PROJECT_FILE_SCHEMA_VERSION, migrate};
+    use crate::error::EngineError;
+
+    /// Written by the first release.
+    const V1_FIXTURE: &str = include_str!("../tests/fixtures/project_v1.json");
+    /// Written by v1 builds that already carried the fields v2 gates.
+    const V1_LATE_FIXTURE: &str = include_str!("../tests/fixtures/project_v1_late.json");
+    const V2_FIXTURE: &str = include_str!("../tests/fixtures/project_v2.json");
+
+    fn fixture(text: &str) -> serde_json::Value {
+        serde_json::from_str(text).expect("fixture is valid json")
+    }
+
+    #[test]
+    fn v1_fixtures_upgrade_by_bumping_the_schema_version() {
+        for text in [V1_FIXTURE, V1_LATE_FIXTURE] {
+            let mut document = fixture(text);
+
+            let applied = migrate(&mut document).expect("v1 should migrate");
+
+            assert_eq!(
+                applied,
+                vec![AppliedMigration {
+                    from_version: 1,
+                    to_version: 2,
+                    description: "optional fields added, no rewrite needed",
+                }]
+            );
+            let mut expected = fixture(text);
+            expected["schema_version"] = json!(2);
+            assert_eq!(document, expected);
+        }
+    }
+
+    #[test]
+    fn current_fixture_needs_no_migration() {
+        let mut document = fixture(V2_FIXTURE);
+
+        assert!(migrate(&mut document).expect("v2 is current").is_empty());
+        assert_eq!(document, fixture(V2_FIXTURE));
+    }
+
+    #[test]
+    fn newer_and_malformed_versions_are_refused() {
+        let mut newer = json!({ "schema_version": PROJECT_FILE_SCHEMA_VERSION + 1 });
+        assert!(matches!(
+            migrate(&mut newer),
+            Err(EngineError::ProjectFileTooNew { version, supported })
+                if version == PROJECT_FILE_SCHEMA_VERSION + 1
+                    && supported == PROJECT_FILE_SCHEMA_VERSION
+        ));
+        for mut document in [
+            json!({ "schema_version": 0 }),
+            json!({ "schema_version": "1" }),
+            json!({ "assets": [] }),
+            json!([]),
+        ] {
+            assert!(matches!(
+                migrate(&mut document),
+                Err(EngineError::InvalidProjectFile { .. })
+            ));
+        }
+    }
+}
@@ -0,0 +1,914 @@
+//! OpenTimelineIO (`.otio`) interchange.
+//!
+//! Export writes a `Timeline.1` with a video track and, when any asset has
+//! audio, a mirrored audio track. Segments become `Clip.1` items whose
+//! `source_range` is the stream range in frames of the asset frame rate (in
+//! frames of the project rate for audio-only assets), timeline gaps become
+//! `Gap.1` items in frames of the project rate, and assets become
+//! `ExternalReference.1` media with `file://` URLs whose `available_range`
+//! starts at the asset's start timecode, so `source_range` reads as source
+//! timecode the way other tools write it.
+//!
+//! Import reads the first video track (or the first track), accepting
+//! `Clip.1`/`Clip.2` and `Gap.1` items, and rebuilds linked audio/video
+//! segments from it. Whole-frame times map onto the frame grid so frame-aligned
+//! edits survive a round trip exactly; fractional times round to the nearest
+//! timeline tick.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::error::{EngineError, Result};
+use crate::project::{MediaAsset, Project};
+use crate::time::{FrameGrid, Rational, TIMELINE_TIME_BASE};
+use crate::timeline::{Segment, SegmentId};
+
+const VIDEO_TRACK_KIND: &str = "Video";
+const AUDIO_TRACK_KIND: &str = "Audio";
+
+/// Writes the timeline as an OTIO JSON document named `name`.
+///
+/// # Example
+/// ```ignore
+/// let text = engine::otio::export_otio(&project, "cut")?;
+/// std::fs::write("cut.otio", text)?;
+/// ```
+pub fn export_otio(project: &Project, name: &str) -> Result<String> {
+    let record_grid = project.frame_grid().unwrap_or(FrameGrid::DEFAULT);
+    let mut video = Vec::new();
+    let mut audio = Vec::new();
+    let mut position_tl = 0;
+    for segment in &project.timeline.segments {
+        let asset = project.asset_by_id(segment.asset_id)?;
+        if segment.timeline_start > position_tl {
+            let gap = Item::gap(RationalTime::new(
+                record_grid,
+                frames(position_tl, segment.timeline_start, record_grid),
+            ));
+            video.push(gap.clone());
+            audio.push(gap);
+        }
+        let clip = Clip::from_segment(asset, segment, record_grid)?;
+        audio.push(if asset.audio.is_some() {
+            Item::Clip(clip.clone())
+        } else {
+            Item::gap(clip.duration())
+        });
+        video.push(Item::Clip(clip));
+        position_tl = segment.timeline_start + segment.timeline_duration;
+    }
+
+    let mut tracks = vec![Track::new("V1", VIDEO_TRACK_KIND, video)];
+    if project.assets.iter().any(|asset| asset.audio.is_some()) {
+        tracks.push(Track::new("A1", AUDIO_TRACK_KIND, audio));
+    }
+    let document = OtioTimeline {
+        schema: String::from("Timeline.1"),
+        name: name.to_owned(),
+        metadata: Map::new(),
+        global_start_time: None,
+        tracks: Stack {
+            schema: String::from("Stack.1"),
+            name: String::from("tracks"),
+            metadata: Map::new(),
+            source_range: None,
+            effects: Vec::new(),
+            markers: Vec::new(),
+            children: tracks,
+        },
+    };
+    serde_json::to_string_pretty(&document).map_err(|error| invalid(error.to_string()))
+}
+
+/// Returns the media files referenced by the imported track that match no
+/// bin asset, in first-use order, so the caller can probe and add them
+/// before `import_otio`.
+pub fn missing_media(project: &Project, text: &str) -> Result<Vec<PathBuf>> {
+    let document = parse(text)?;
+    let mut missing = Vec::new();
+    for (index, item) in imported_track(&document)?.children.iter().enumerate() {
+        let Some(clip) = item.clip() else {
+            continue;
+        };
+        let path = clip.media_path(index)?;
+        if find_asset(&project.assets, &path).is_none() && !missing.contains(&path) {
+            missing.push(path);
+        }
+    }
+    Ok(missing)
+}
+
+/// Rebuilds timeline segments from an OTIO JSON document.
+///
+/// Clips are matched to bin assets by path, then by file name; see
+/// `missing_media`. Returns the segments in track order with ids from
+/// `allocate_segment_id`.
+///
+/// # Example
+/// ```ignore
+/// let mut next_id = 1;
+/// let segments = engine::otio::import_otio(&project, &text, || {
+///     next_id += 1;
+///     next_id - 1
+/// })?;
+/// ```
+pub fn import_otio(
+    project: &Project,
+    text: &str,
+    mut allocate_segment_id: impl FnMut() -> SegmentId,
+) -> Result<Vec<Segment>> {
+    let document = parse(text)?;
+    let record_grid = project.frame_grid().unwrap_or(FrameGrid::DEFAULT);
+    let mut segments = Vec::new();
+    let mut position_tl = 0;
+    for (index, item) in imported_track(&document)?.children.iter().enumerate() {
+        match item {
+            Item::Gap(gap) => {
+                let duration = gap.source_range.as_ref().map_or(0.0, |range| {
+                    range.duration.value * frames_per_second(record_grid) / range.duration.rate
+                });
+                let start = frame_position(position_tl, record_grid);
+                position_tl = RationalTime::new(record_grid, start + duration).to_tl(record_grid);
+            }
+            Item::Clip(clip) | Item::Clip2(clip) => {
+                let path = clip.media_path(index)?;
+                let asset = find_asset(&project.assets, &path).ok_or_else(|| {
+                    invalid(format!("item {index}: no asset for {}", path.display()))
+                })?;
+                let range = clip
+                    .source_range
+                    .as_ref()
+                    .or_else(|| clip.available_range())
+                    .ok_or_else(|| invalid(format!("item {index}: clip has no source range")))?;
+                // `source_range` counts from the start of the available media.
+                let origin = clip
+                    .available_range()
+                    .map(|available| available.start_time)
+                    .unwrap_or_default();
+                let range = TimeRange {
+                    start_time: range.start_time.since(origin),
+                    ..range.clone()
+                };
+                let source_grid = asset.frame_grid().unwrap_or(record_grid);
+                let start_tl = range.start_time.to_tl(source_grid);
+                let end_tl = range.end_time().to_tl(source_grid);
+                if end_tl <= start_tl {
+                    return Err(invalid(format!("item {index}: clip is empty")));
+                }
+                if start_tl < 0 || end_tl > asset.duration_tl {
+                    return Err(EngineError::InsufficientMedia {
+                        asset_id: asset.id,
+                        start_tl,
+                        end_tl,
+                        duration_tl: asset.duration_tl,
+                    });
+                }
+                let mut segment = asset.segment_for_range(allocate_segment_id(), start_tl, end_tl);
+                segment.timeline_start = position_tl;
+                segment.enabled = clip.enabled;
+                position_tl += segment.timeline_duration;
+                segments.push(segment);
+            }
+            Item::Unsupported => {
+                return Err(invalid(format!(
+                    "item {index}: only clips and gaps are supported"
+                )));
+            }
+        }
+    }
+    Ok(segments)
+}
+
+fn parse(text: &str) -> Result<OtioTimeline> {
+    let document: OtioTimeline =
+        serde_json::from_str(text).map_err(|error| invalid(error.to_string()))?;
+    if !document.schema.starts_with("Timeline.") {
+        return Err(invalid(format!(
+            "expected a Timeline, found {}",
+            document.schema
+        )));
+    }
+    Ok(document)
+}
+
+fn imported_track(document: &OtioTimeline) -> Result<&Track> {
+    let tracks = &document.tracks.children;
+    tracks
+        .iter()
+        .find(|track| track.kind == VIDEO_TRACK_KIND)
+        .or_else(|| tracks.first())
+        .ok_or_else(|| invalid(String::from("timeline has no tracks")))
+}
+
+fn find_asset<'a>(assets: &'a [MediaAsset], path: &Path) -> Option<&'a MediaAsset> {
+    assets.iter().find(|asset| asset.path == path).or_else(|| {
+        let name = path.file_name()?;
+        assets
+            .iter()
+            .find(|asset| asset.path.file_name() == Some(name))
+    })
+}
+
+fn invalid(reason: String) -> EngineError {
+    EngineError::InvalidOtio { reason }
+}
+
+/// Frames of `grid` between the timeline ticks `start_tl` and `end_tl`.
+fn frames(start_tl: i64, end_tl: i64, grid: FrameGrid) -> f64 {
+    frame_position(end_tl, grid) - frame_position(start_tl, grid)
+}
+
+/// Frame position of `t_tl`, whole when it starts a frame of `grid`.
+fn frame_position(t_tl: i64, grid: FrameGrid) -> f64 {
+    let frame = grid.frame_at(t_tl);
+    if grid.frame_start_tl(frame) == t_tl {
+        frame as f64
+    } else {
+        ticks_as_frames(t_tl, TIMELINE_TIME_BASE, grid)
+    }
+}
+
+fn frames_per_second(grid: FrameGrid) -> f64 {
+    let rate = grid.frame_rate();
+    f64::from(rate.num) / f64::from(rate.den)
+}
+
+/// `ticks` in `time_base` as (possibly fractional) frames of `grid`.
+fn ticks_as_frames(ticks: i64, time_base: Rational, grid: FrameGrid) -> f64 {
+    let rate = grid.frame_rate();
+    let numerator = i128::from(ticks) * i128::from(time_base.num) * i128::from(rate.num);
+    let denominator = i128::from(time_base.den) * i128::from(rate.den);
+    numerator as f64 / denominator as f64
+}
+
+/// Percent-encodes an absolute path as a `file://` URL; relative paths are
+/// written as plain relative URLs.
+pub(crate) fn path_to_url(path: &Path) -> String {
+    let text = path.to_string_lossy().replace('\\', "/");
+    let mut url = String::with_capacity(text.len() + 8);
+    if path.is_absolute() {
+        url.push_str("file://");
+        if !text.starts_with('/') {
+            url.push('/');
+        }
+    }
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-._~".contains(&byte) {
+            url.push(char::from(byte));
+        } else {
+            url.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    url
+}
+
+fn url_to_path(url: &str) -> Option<PathBuf> {
+    let path = match url.strip_prefix("file://") {
+        Some(rest) => rest.strip_prefix("localhost").unwrap_or(rest),
+        None if url.contains("://") => return None,
+        None => url,
+    };
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut rest = path.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' && tail.len() >= 2 {
+            let hex = std::str::from_utf8(&tail[..2]).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    // `file:///C:/media` names a Windows drive path.
+    let text = String::from_utf8(bytes).ok()?;
+    let text = match text.as_bytes() {
+        [b'/', _, b':', ..] => &text[1..],
+        _ => &text,
+    };
+    Some(PathBuf::from(text))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct OtioTimeline {
+    #[serde(rename = "OTIO_SCHEMA")]
+    schema: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    metadata: Map<String, Value>,
+    #[serde(default)]
+    global_start_time: Option<RationalTime>,
+    tracks: Stack,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Stack {
+    #[serde(rename = "OTIO_SCHEMA")]
+    schema: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    metadata: Map<String, Value>,
+    #[serde(default)]
+    source_range: Option<TimeRange>,
+    #[serde(default)]
+    effects: Vec<Value>,
+    #[serde(default)]
+    markers: Vec<Value>,
+    #[serde(default)]
+    children: Vec<Track>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Track {
+    #[serde(rename = "OTIO_SCHEMA")]
+    schema: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    metadata: Map<String, Value>,
+    #[serde(default)]
+    source_range: Option<TimeRange>,
+    #[serde(default)]
+    effects: Vec<Value>,
+    #[serde(default)]
+    markers: Vec<Value>,
+    #[serde(default)]
+    kind: String,
+    #[serde(default)]
+    children: Vec<Item>,
+}
+
+impl Track {
+    fn new(name: &str, kind: &str, children: Vec<Item>) -> Self {
+        Self {
+            schema: String::from("Track.1"),
+            name: name.to_owned(),
+            metadata: Map::new(),
+            source_range: None,
+            effects: Vec::new(),
+            markers: Vec::new(),
+            kind: kind.to_owned(),
+            children,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "OTIO_SCHEMA")]
+enum Item {
+    #[serde(rename = "Clip.1")]
+    Clip(Clip),
+    #[serde(rename = "Clip.2")]
+    Clip2(Clip),
+    #[serde(rename = "Gap.1")]
+    Gap(Gap),
+    #[serde(other)]
+    Unsupported,
+}
+
+impl Item {
+    fn gap(duration: RationalTime) -> Self {
+        Self::Gap(Gap {
+            name: String::new(),
+            metadata: Map::new(),
+            source_range: Some(TimeRange {
+                schema: String::from("TimeRange.1"),
+                start_time: RationalTime {
+                    value: 0.0,
+                    ..duration
+                },
+                duration,
+            }),
+            effects: Vec::new(),
+            markers: Vec::new(),
+        })
+    }
+
+    fn clip(&self) -> Option<&Clip> {
+        match self {
+            Self::Clip(clip) | Self::Clip2(clip) => Some(clip),
+            Self::Gap(_) | Self::Unsupported => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Gap {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    metadata: Map<String, Value>,
+    #[serde(default)]
+    source_range: Option<TimeRange>,
+    #[serde(default)]
+    effects: Vec<Value>,
+    #[serde(default)]
+    markers: Vec<Value>,
+}
+
+/// `Clip.1` keeps one `media_reference`; `Clip.2` keys several by name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Clip {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    metadata: Map<String, Value>,
+    #[serde(default)]
+    source_range: Option<TimeRange>,
+    #[serde(default)]
+    effects: Vec<Value>,
+    #[serde(default)]
+    markers: Vec<Value>,
+    #[serde(default = "enabled_by_default")]
+    enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    media_reference: Option<MediaReference>,
+    #[serde(default, skip_serializing)]
+    media_references: BTreeMap<String, MediaReference>,
+    #[serde(default, skip_serializing)]
+    active_media_reference_key: Option<String>,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+impl Clip {
+    fn from_segment(asset: &MediaAsset, segment: &Segment, record_grid: FrameGrid) -> Result<Self> {
+        let (time_base, src_in, src_out) = asset.segment_source_ticks(segment)?;
+        let grid = asset.frame_grid().unwrap_or(record_grid);
+        let origin = asset.start_frame(grid)? as f64;
+        let start = origin + ticks_as_frames(src_in, time_base, grid);
+        let end = origin + ticks_as_frames(src_out, time_base, grid);
+        let name = asset
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Ok(Self {
+            name: name.clone(),
+            metadata: Map::new(),
+            source_range: Some(TimeRange::new(grid, start, end - start)),
+            effects: Vec::new(),
+            markers: Vec::new(),
+            enabled: segment.enabled,
+            media_reference: Some(MediaReference::External(ExternalReference {
+                name,
+                metadata: Map::new(),
+                target_url: path_to_url(&asset.path),
+                available_range: Some(TimeRange::new(
+                    grid,
+                    origin,
+                    ticks_as_frames(asset.duration_tl, TIMELINE_TIME_BASE, grid),
+                )),
+            })),
+            media_references: BTreeMap::new(),
+            active_media_reference_key: None,
+        })
+    }
+
+    fn duration(&self) -> RationalTime {
+        self.source_range
+            .as_ref()
+            .map(|range| range.duration)
+            .unwrap_or_default()
+    }
+
+    fn active_reference(&self) -> Option<&MediaReference> {
+        self.media_reference.as_ref().or_else(|| {
+            let key = self
+                .active_media_reference_key
+                .as_deref()
+                .unwrap_or("DEFAULT_MEDIA");
+            self.media_references.get(key)
+        })
+    }
+
+    fn available_range(&self) -> Option<&TimeRange> {
+        match self.active_reference()? {
+            MediaReference::External(reference) => reference.available_range.as_ref(),
+            MediaReference::Unsupported => None,
+        }
+    }
+
+    fn media_path(&self, index: usize) -> Result<PathBuf> {
+        let Some(MediaReference::External(reference)) = self.active_reference() else {
+            return Err(invalid(format!(
+                "item {index}: clip {} has no external media reference",
+                self.name
+            )));
+        };
+        url_to_path(&reference.target_url).ok_or_else(|| {
+            invalid(format!(
+                "item {index}: unsupported media URL {}",
+                reference.target_url
+            ))
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "OTIO_SCHEMA")]
+enum MediaReference {
+    #[serde(rename = "ExternalReference.1")]
+    External(ExternalReference),
+    #[serde(other)]
+    Unsupported,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ExternalReference {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    metadata: Map<String, Value>,
+    target_url: String,
+    #[serde(default)]
+    available_range: Option<TimeRange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TimeRange {
+    #[serde(rename = "OTIO_SCHEMA", default = "time_range_schema")]
+    schema: String,
+    start_time: RationalTime,
+    duration: RationalTime,
+}
+
+fn time_range_schema() -> String {
+    String::from("TimeRange.1")
+}
+
+impl TimeRange {
+    fn new(grid: FrameGrid, start: f64, duration: f64) -> Self {
+        Self {
+            schema: time_range_schema(),
+            start_time: RationalTime::new(grid, start),
+            duration: RationalTime::new(grid, duration),
+        }
+    }
+
+    fn end_time(&self) -> RationalTime {
+        RationalTime {
+            value: self.start_time.value
+                + self.duration.value * self.start_time.rate / self.duration.rate,
+            ..self.start_time
+        }
+    }
+}
+
+/// `value` units of `1 / rate` seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct RationalTime {
+    #[serde(
+        rename = "OTIO_SCHEMA",
+        skip_deserializing,
+        default = "rational_time_schema"
+    )]
+    schema: &'static str,
+    rate: f64,
+    value: f64,
+}
+
+fn rational_time_schema() -> &'static str {
+    "RationalTime.1"
+}
+
+impl Default for RationalTime {
+    fn default() -> Self {
+        Self {
+            schema: rational_time_schema(),
+            rate: 1.0,
+            value: 0.0,
+        }
+    }
+}
+
+impl RationalTime {
+    fn new(grid: FrameGrid, frames: f64) -> Self {
+        Self {
+            schema: rational_time_schema(),
+            rate: frames_per_second(grid),
+            value: frames,
+        }
+    }
+
+    /// Returns this time measured from `origin`, in this time's rate.
+    fn since(self, origin: RationalTime) -> Self {
+        Self {
+            value: self.value - origin.value * self.rate / origin.rate,
+            ..self
+        }
+    }
+
+    /// Converts to timeline ticks, snapping whole frames of `grid` to the
+    /// grid so they match `FrameGrid::frame_start_tl`.
+    fn to_tl(self, grid: FrameGrid) -> i64 {
+        let rounded = self.value.round();
+        if (self.rate - frames_per_second(grid)).abs() < 1e-6 && (self.value - rounded).abs() < 1e-6
+        {
+            return grid.frame_start_tl(rounded as i64);
+        }
+        (self.value / self.rate * f64::from(TIMELINE_TIME_BASE.den)).round() as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::path::{Path, PathBuf};
+
+    use serde_json::Value;
+
+    use super::{export_otio, import_otio, missing_media, path_to_url, url_to_path};
+    use crate::error::EngineError;
+    use crate::project::{
+        AudioStreamInfo, MediaAsset, Project, ProjectSettings, TimelineMarks, VideoStreamInfo,
+    };
+    use crate::time::{Rational, Timecode};
+    use crate::timeline::{Segment, Timeline};
+
+    fn asset(id: u64, path: &str, audio: bool) -> MediaAsset {
+        MediaAsset {
+            id,
+            path: PathBuf::from(path),
+            video_stream_index: Some(0),
+            audio_stream_index: audio.then_some(1),
+            video: Some(VideoStreamInfo {
+                time_base: Rational::new(1, 90_000).expect("valid rational"),
+                frame_rate: Some(Rational::new(30_000, 1_001).expect("valid rational")),
+                width: 1920,
+                height: 1080,
+                start_pts: 9_000,
+                start_timecode: None,
+            }),
+            audio: audio.then(|| AudioStreamInfo {
+                time_base: Rational::new(1, 48_000).expect("valid rational"),
+                sample_rate: 48_000,
+                channels: 2,
+                start_pts: 0,
+            }),
+            duration_tl: 600_000_000,
+            source_marks: TimelineMarks::default(),
+            file_size: None,
+            offline: false,
+        }
+    }
+
+    fn segment(asset: &MediaAsset, id: u64, src: (i64, i64), record_tl: i64) -> Segment {
+        let grid = asset.frame_grid().expect("video asset");
+        let mut segment =
+            asset.segment_for_range(id, grid.frame_start_tl(src.0), grid.frame_start_tl(src.1));
+        segment.timeline_start = record_tl;
+        segment
+    }
+
+    fn sample_project() -> Project {
+        let interview = asset(1, "/media/interview take 1.mov", true);
+        let broll = asset(2, "/media/b-roll.mp4", false);
+        let grid = interview.frame_grid().expect("video asset");
+        let mut disabled = segment(&interview, 3, (4_000, 4_090), grid.frame_start_tl(500));
+        disabled.enabled = false;
+        let mut partial = interview.segment_for_range(4, 1_000_000, 1_200_000);
+        partial.timeline_start = disabled.timeline_start + disabled.timeline_duration;
+        let segments = vec![
+            segment(&interview, 1, (1_800, 2_100), 0),
+            segment(&broll, 2, (45, 195), grid.frame_start_tl(320)),
+            disabled,
+            partial,
+        ];
+        Project {
+            assets: vec![interview, broll],
+            timeline: Timeline { segments },
+            settings: ProjectSettings::default(),
+            marks: TimelineMarks::default(),
+            selection: BTreeSet::new(),
+            loop_playback: false,
+            changes: Default::default(),
+        }
+    }
+
+    fn ids() -> impl FnMut() -> u64 {
+        let mut next = 10;
+        move || {
+            next += 1;
+            next - 1
+        }
+    }
+
+    #[test]
+    fn export_maps_segments_to_clips_gaps_and_external_references() {
+        let text = export_otio(&sample_project(), "cut").expect("export should succeed");
+        let document: Value = serde_json::from_str(&text).expect("valid json");
+
+        assert_eq!(document["OTIO_SCHEMA"], "Timeline.1");
+        let tracks = document["tracks"]["children"].as_array().expect("tracks");
+        assert_eq!(
+            tracks
+                .iter()
+                .map(|track| (&track["name"], &track["kind"]))
+                .collect::<Vec<_>>(),
+            [
+                (&Value::from("V1"), &Value::from("Video")),
+                (&Value::from("A1"), &Value::from("Audio"))
+            ]
+        );
+        let schemas = |track: &Value| {
+            track["children"]
+                .as_array()
+                .expect("items")
+                .iter()
+                .map(|item| item["OTIO_SCHEMA"].as_str().expect("schema").to_owned())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            schemas(&tracks[0]),
+            ["Clip.1", "Gap.1", "Clip.1", "Gap.1", "Clip.1", "Clip.1"]
+        );
+        assert_eq!(
+            schemas(&tracks[1]),
+            ["Clip.1", "Gap.1", "Gap.1", "Gap.1", "Clip.1", "Clip.1"]
+        );
+
+        let first = &tracks[0]["children"][0];
+        assert_eq!(first["name"], "interview take 1.mov");
+        assert_eq!(first["source_range"]["start_time"]["value"], 1_800.0);
+        assert_eq!(first["source_range"]["duration"]["value"], 300.0);
+        assert_eq!(
+            first["media_reference"]["target_url"],
+            "file:///media/interview%20take%201.mov"
+        );
+        assert_eq!(
+            tracks[0]["children"][1]["source_range"]["duration"]["value"],
+            20.0
+        );
+        assert_eq!(tracks[0]["children"][4]["enabled"], false);
+    }
+
+    #[test]
+    fn otio_round_trip_keeps_segment_timings() {
+        let project = sample_project();
+        let text = export_otio(&project, "cut").expect("export should succeed");
+
+        assert!(missing_media(&project, &text).expect("parse").is_empty());
+        let segments = import_otio(&project, &text, ids()).expect("import should succeed");
+
+        assert_eq!(segments.len(), project.timeline.segments.len());
+        for (imported, original) in segments.iter().zip(&project.timeline.segments) {
+            assert_eq!(
+                Segment {
+                    id: original.id,
+                    ..imported.clone()
+                },
+                *original
+            );
+        }
+    }
+
+    #[test]
+    fn source_ranges_count_from_the_asset_start_timecode() {
+        let mut project = sample_project();
+        project.assets[0]
+            .video
+            .as_mut()
+            .expect("video")
+            .start_timecode = Some(Timecode::parse("01:00:00;00").expect("valid"));
+        let text = export_otio(&project, "cut").expect("export should succeed");
+        let document: Value = serde_json::from_str(&text).expect("valid json");
+
+        let first = &document["tracks"]["children"][0]["children"][0];
+        assert_eq!(first["source_range"]["start_time"]["value"], 109_692.0);
+        assert_eq!(
+            first["media_reference"]["available_range"]["start_time"]["value"],
+            107_892.0
+        );
+
+        let segments = import_otio(&project, &text, ids()).expect("import should succeed");
+        for (imported, original) in segments.iter().zip(&project.timeline.segments) {
+            assert_eq!(
+                Segment {
+                    id: original.id,
+                    ..imported.clone()
+                },
+                *original
+            );
+        }
+    }
+
+    #[test]
+    fn import_reads_clip2_documents_from_other_tools() {
+        let project = sample_project();
+        let text = r#"{
+            "OTIO_SCHEMA": "Timeline.1",
+            "name": "from elsewhere",
+            "tracks": {
+                "OTIO_SCHEMA": "Stack.1",
+                "children": [
+                    { "OTIO_SCHEMA": "Track.1", "kind": "Audio", "children": [] },
+                    {
+                        "OTIO_SCHEMA": "Track.1",
+                        "kind": "Video",
+                        "children": [
+                            {
+                                "OTIO_SCHEMA": "Gap.1",
+                                "source_range": {
+                                    "OTIO_SCHEMA": "TimeRange.1",
+                                    "start_time": { "OTIO_SCHEMA": "RationalTime.1", "rate": 24.0, "value": 0.0 },
+                                    "duration": { "OTIO_SCHEMA": "RationalTime.1", "rate": 24.0, "value": 12.0 }
+                                }
+                            },
+                            {
+                                "OTIO_SCHEMA": "Clip.2",
+                                "name": "b-roll",
+                                "media_references": {
+                                    "DEFAULT_MEDIA": {
+                                        "OTIO_SCHEMA": "ExternalReference.1",
+                                        "target_url": "file://localhost/elsewhere/b-roll.mp4",
+                                        "available_range": null
+                                    }
+                                },
+                                "active_media_reference_key": "DEFAULT_MEDIA",
+                                "source_range": {
+                                    "OTIO_SCHEMA": "TimeRange.1",
+                                    "start_time": { "OTIO_SCHEMA": "RationalTime.1", "rate": 48000.0, "value": 48000.0 },
+                                    "duration": { "OTIO_SCHEMA": "RationalTime.1", "rate": 48000.0, "value": 24000.0 }
+                                }
+                            },
+                            {
+                                "OTIO_SCHEMA": "Clip.1",
+                                "name": "new",
+                                "media_reference": {
+                                    "OTIO_SCHEMA": "ExternalReference.1",
+                                    "target_url": "/ingest/new%20clip.mov"
+                                },
+                                "source_range": {
+                                    "OTIO_SCHEMA": "TimeRange.1",
+                                    "start_time": { "OTIO_SCHEMA": "RationalTime.1", "rate": 25.0, "value": 0.0 },
+                                    "duration": { "OTIO_SCHEMA": "RationalTime.1", "rate": 25.0, "value": 25.0 }
+                                }
+                            }
+                        ]
+                    }
+                ]
+            }
+        }"#;
+
+        assert_eq!(
+            missing_media(&project, text).expect("parse"),
+            vec![PathBuf::from("/ingest/new clip.mov")]
+        );
+        let mut with_new = project.clone();
+        with_new.assets.push(asset(3, "/ingest/new clip.mov", true));
+        let segments = import_otio(&with_new, text, ids()).expect("import should succeed");
+
+        assert_eq!(
+            segments
+                .iter()
+                .map(|segment| (
+                    segment.asset_id,
+                    segment.timeline_start,
+                    segment.timeline_duration
+                ))
+                .collect::<Vec<_>>(),
+            vec![(2, 500_000, 500_000), (3, 1_000_000, 1_000_000)]
+        );
+        assert_eq!(segments[0].src_in_video, Some(9_000 + 90_000));
+    }
+
+    #[test]
+    fn import_rejects_unsupported_documents() {
+        let project = sample_project();
+        for text in [
+            "not json",
+            r#"{ "OTIO_SCHEMA": "Clip.1", "tracks": { "OTIO_SCHEMA": "Stack.1" } }"#,
+            r#"{ "OTIO_SCHEMA": "Timeline.1", "tracks": { "OTIO_SCHEMA": "Stack.1", "children": [] } }"#,
+            r#"{ "OTIO_SCHEMA": "Timeline.1", "tracks": { "OTIO_SCHEMA": "Stack.1", "children": [
+                { "OTIO_SCHEMA": "Track.1", "kind": "Video", "children": [
+                    { "OTIO_SCHEMA": "Transition.1", "name": "dissolve" }
+                ] }
+            ] } }"#,
+        ] {
+            assert!(
+                matches!(
+                    import_otio(&project, text, ids()),
+                    Err(EngineError::InvalidOtio { .. })
+                ),
+                "{text} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn media_urls_round_trip_paths() {
+        for path in ["/media/a b#1%.mov", "relative/clip.mp4"] {
+            let url = path_to_url(Path::new(path));
+            assert_eq!(url_to_path(&url), Some(PathBuf::from(path)), "{url}");
+        }
+        assert_eq!(url_to_path("https://example.com/a.mov"), None);
+    }
+}
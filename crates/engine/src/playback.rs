@@ -0,0 +1,565 @@
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::error::{EngineError, Result};
+use crate::project::Project;
+use crate::time::{Rational, TIMELINE_TIME_BASE, rescale, ticks_to_seconds};
+
+/// Interleaved PCM layout used between the engine and audio sinks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PcmFormat {
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+impl PcmFormat {
+    /// Default playback format (48 kHz stereo).
+    pub const DEFAULT: Self = Self {
+        sample_rate: 48_000,
+        channels: 2,
+    };
+
+    /// Converts timeline ticks into an absolute sample-frame position.
+    ///
+    /// Spans are sized from absolute positions so consecutive chunks never
+    /// accumulate rounding drift.
+    pub fn frames_at_tl(self, t_tl: i64) -> i64 {
+        rescale(t_tl, TIMELINE_TIME_BASE, self.frame_time_base())
+    }
+
+    /// Converts a sample-frame count into timeline ticks.
+    pub fn frames_to_tl(self, frames: i64) -> i64 {
+        rescale(frames, self.frame_time_base(), TIMELINE_TIME_BASE)
+    }
+
+    fn frame_time_base(self) -> Rational {
+        Rational {
+            num: 1,
+            den: i32::try_from(self.sample_rate.max(1)).unwrap_or(i32::MAX),
+        }
+    }
+}
+
+impl Default for PcmFormat {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Interleaved `f32` PCM samples.
+///
+/// # Example
+/// ```
+/// use engine::playback::{PcmChunk, PcmFormat};
+///
+/// let chunk = PcmChunk::silence(PcmFormat::DEFAULT, 480);
+/// assert_eq!(chunk.frames(), 480);
+/// assert_eq!(chunk.samples.len(), 960);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PcmChunk {
+    pub format: PcmFormat,
+    pub samples: Vec<f32>,
+}
+
+impl PcmChunk {
+    /// Creates `frames` sample frames of silence.
+    pub fn silence(format: PcmFormat, frames: usize) -> Self {
+        Self {
+            format,
+            samples: vec![0.0; frames * usize::from(format.channels)],
+        }
+    }
+
+    /// Returns the number of complete sample frames.
+    pub fn frames(&self) -> usize {
+        self.samples.len() / usize::from(self.format.channels.max(1))
+    }
+
+    /// Pads with silence or truncates to exactly `frames` sample frames.
+    pub fn resize_frames(&mut self, frames: usize) {
+        self.samples
+            .resize(frames * usize::from(self.format.channels), 0.0);
+    }
+
+    /// Appends samples of another chunk with the same format.
+    pub fn append(&mut self, other: &PcmChunk) -> Result<()> {
+        if other.format != self.format {
+            return Err(EngineError::UnsupportedAudioFormat {
+                sample_rate: other.format.sample_rate,
+                channels: other.format.channels,
+            });
+        }
+        self.samples.extend_from_slice(&other.samples);
+        Ok(())
+    }
+}
+
+/// Destination for rendered timeline audio.
+///
+/// The engine treats [`AudioSink::played_frames`] as the master clock for
+/// playback, so implementations must report how many frames have actually
+/// been heard since the last [`AudioSink::reset`].
+pub trait AudioSink: Debug + Send {
+    /// Queues one chunk for output.
+    fn write(&mut self, chunk: &PcmChunk) -> Result<()>;
+
+    /// Returns sample frames played since the last reset.
+    fn played_frames(&self) -> u64;
+
+    /// Drops queued audio and restarts the played-frame counter.
+    fn reset(&mut self) -> Result<()>;
+}
+
+/// Sink that discards audio and reports every written frame as played.
+///
+/// Playback driven by this sink advances exactly as far as audio has been
+/// queued, which keeps engine tests deterministic without a sound device.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NullAudioSink {
+    played_frames: u64,
+}
+
+impl NullAudioSink {
+    /// Creates an empty null sink.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl AudioSink for NullAudioSink {
+    fn write(&mut self, chunk: &PcmChunk) -> Result<()> {
+        self.played_frames += chunk.frames() as u64;
+        Ok(())
+    }
+
+    fn played_frames(&self) -> u64 {
+        self.played_frames
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.played_frames = 0;
+        Ok(())
+    }
+}
+
+/// Sink that records all written audio into a 32-bit float WAV file.
+///
+/// Like [`NullAudioSink`], written frames count as played immediately.
+///
+/// # Example
+/// ```no_run
+/// use engine::playback::{AudioSink, PcmChunk, PcmFormat, WavFileAudioSink};
+///
+/// let mut sink = WavFileAudioSink::create("playback.wav", PcmFormat::DEFAULT)
+///     .expect("wav file should be created");
+/// sink.write(&PcmChunk::silence(PcmFormat::DEFAULT, 480))
+///     .expect("write should succeed");
+/// ```
+#[derive(Debug)]
+pub struct WavFileAudioSink {
+    file: File,
+    path: PathBuf,
+    format: PcmFormat,
+    total_frames: u64,
+    played_frames: u64,
+}
+
+const WAV_HEADER_LEN: u64 = 44;
+const WAV_FORMAT_IEEE_FLOAT: u16 = 3;
+const WAV_BYTES_PER_SAMPLE: u16 = 4;
+
+impl WavFileAudioSink {
+    /// Creates (or truncates) `path` and writes an empty WAV header.
+    pub fn create(path: impl AsRef<Path>, format: PcmFormat) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if format.sample_rate == 0 || format.channels == 0 {
+            return Err(EngineError::UnsupportedAudioFormat {
+                sample_rate: format.sample_rate,
+                channels: format.channels,
+            });
+        }
+        let file = File::create(&path).map_err(|source| EngineError::AudioOutput {
+            context: "failed to create wav file",
+            source,
+        })?;
+        let mut sink = Self {
+            file,
+            path,
+            format,
+            total_frames: 0,
+            played_frames: 0,
+        };
+        sink.write_header()?;
+        Ok(sink)
+    }
+
+    /// Returns the output file path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn data_len(&self) -> u64 {
+        self.total_frames * u64::from(self.format.channels) * u64::from(WAV_BYTES_PER_SAMPLE)
+    }
+
+    fn write_header(&mut self) -> Result<()> {
+        let data_len = u32::try_from(self.data_len()).unwrap_or(u32::MAX);
+        let block_align = self.format.channels * WAV_BYTES_PER_SAMPLE;
+        let byte_rate = self.format.sample_rate * u32::from(block_align);
+
+        let mut header = Vec::with_capacity(WAV_HEADER_LEN as usize);
+        header.extend_from_slice(b"RIFF");
+        header.extend_from_slice(&data_len.saturating_add(36).to_le_bytes());
+        header.extend_from_slice(b"WAVE");
+        header.extend_from_slice(b"fmt ");
+        header.extend_from_slice(&16u32.to_le_bytes());
+        header.extend_from_slice(&WAV_FORMAT_IEEE_FLOAT.to_le_bytes());
+        header.extend_from_slice(&self.format.channels.to_le_bytes());
+        header.extend_from_slice(&self.format.sample_rate.to_le_bytes());
+        header.extend_from_slice(&byte_rate.to_le_bytes());
+        header.extend_from_slice(&block_align.to_le_bytes());
+        header.extend_from_slice(&(WAV_BYTES_PER_SAMPLE * 8).to_le_bytes());
+        header.extend_from_slice(b"data");
+        header.extend_from_slice(&data_len.to_le_bytes());
+
+        self.file
+            .seek(SeekFrom::Start(0))
+            .and_then(|_| self.file.write_all(&header))
+            .and_then(|_| self.file.seek(SeekFrom::End(0)).map(|_| ()))
+            .map_err(|source| EngineError::AudioOutput {
+                context: "failed to write wav header",
+                source,
+            })
+    }
+}
+
+impl AudioSink for WavFileAudioSink {
+    fn write(&mut self, chunk: &PcmChunk) -> Result<()> {
+        if chunk.format != self.format {
+            return Err(EngineError::UnsupportedAudioFormat {
+                sample_rate: chunk.format.sample_rate,
+                channels: chunk.format.channels,
+            });
+        }
+
+        let frames = chunk.frames();
+        let mut bytes = Vec::with_capacity(frames * usize::from(self.format.channels) * 4);
+        for sample in &chunk.samples[..frames * usize::from(self.format.channels)] {
+            bytes.extend_from_slice(&sample.to_le_bytes());
+        }
+        self.file
+            .write_all(&bytes)
+            .map_err(|source| EngineError::AudioOutput {
+                context: "failed to write wav samples",
+                source,
+            })?;
+        self.total_frames += frames as u64;
+        self.played_frames += frames as u64;
+        self.write_header()
+    }
+
+    fn played_frames(&self) -> u64 {
+        self.played_frames
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.played_frames = 0;
+        Ok(())
+    }
+}
+
+/// Sink that plays audio through an `ffplay` child process.
+///
+/// The played position is estimated from wall-clock time since the first
+/// write and never runs ahead of what has been queued.
+#[derive(Debug)]
+pub struct FfplayAudioSink {
+    playback: Option<media_ffmpeg::PcmPlayback>,
+    started_at: Option<Instant>,
+    written_frames: u64,
+}
+
+impl FfplayAudioSink {
+    /// Creates a sink; the `ffplay` process starts on the first write.
+    pub fn new() -> Self {
+        Self {
+            playback: None,
+            started_at: None,
+            written_frames: 0,
+        }
+    }
+}
+
+impl Default for FfplayAudioSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioSink for FfplayAudioSink {
+    fn write(&mut self, chunk: &PcmChunk) -> Result<()> {
+        let playback = match self.playback.as_mut() {
+            Some(playback)
+                if playback.sample_rate() == chunk.format.sample_rate
+                    && playback.channels() == chunk.format.channels =>
+            {
+                playback
+            }
+            _ => {
+                self.started_at = None;
+                self.written_frames = 0;
+                self.playback.insert(media_ffmpeg::PcmPlayback::spawn(
+                    chunk.format.sample_rate,
+                    chunk.format.channels,
+                )?)
+            }
+        };
+        playback.write_samples(&chunk.samples)?;
+        self.started_at.get_or_insert_with(Instant::now);
+        self.written_frames += chunk.frames() as u64;
+        Ok(())
+    }
+
+    fn played_frames(&self) -> u64 {
+        let (Some(started_at), Some(playback)) = (self.started_at, self.playback.as_ref()) else {
+            return 0;
+        };
+        let elapsed_frames =
+            (started_at.elapsed().as_secs_f64() * f64::from(playback.sample_rate())) as u64;
+        elapsed_frames.min(self.written_frames)
+    }
+
+    fn reset(&mut self) -> Result<()> {
+        self.playback = None;
+        self.started_at = None;
+        self.written_frames = 0;
+        Ok(())
+    }
+}
+
+/// One contiguous piece of timeline audio.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct AudioSpan {
+    /// Source media and start position, or `None` for silence.
+    pub(crate) source: Option<(PathBuf, f64)>,
+    pub(crate) frames: usize,
+}
+
+/// Maps `start_tl..end_tl` onto source audio ranges across segments.
+///
+/// Gaps, disabled segments and segments without an audio range become silent
+/// spans.
+pub(crate) fn audio_spans(
+    project: &Project,
+    start_tl: i64,
+    end_tl: i64,
+    format: PcmFormat,
+) -> Result<Vec<AudioSpan>> {
+    let mut spans = Vec::new();
+    let mut cursor_tl = start_tl.max(0);
+    let end_tl = end_tl.min(project.duration_tl());
+
+    while cursor_tl < end_tl {
+        let segment = project.timeline.segments.iter().find(|segment| {
+            segment.timeline_start <= cursor_tl
+                && cursor_tl < segment.timeline_start + segment.timeline_duration
+        });
+        let (piece_end_tl, source) = match segment {
+            Some(segment) => {
+                let piece_end_tl = (segment.timeline_start + segment.timeline_duration).min(end_tl);
+                let asset = project.asset_by_id(segment.asset_id)?;
+                let source = match (asset.audio, segment.src_in_audio) {
+                    (Some(audio), Some(src_in_audio)) if segment.enabled && !asset.offline => {
+                        let src_in_tl = rescale(src_in_audio, audio.time_base, TIMELINE_TIME_BASE);
+                        let source_tl = src_in_tl + (cursor_tl - segment.timeline_start);
+                        Some((asset.path.clone(), ticks_to_seconds(source_tl.max(0))))
+                    }
+                    _ => None,
+                };
+                (piece_end_tl, source)
+            }
+            None => {
+                let next_start_tl = project
+                    .timeline
+                    .segments
+                    .iter()
+                    .map(|segment| segment.timeline_start)
+                    .filter(|start| *start > cursor_tl)
+                    .min()
+                    .unwrap_or(end_tl);
+                (next_start_tl.min(end_tl), None)
+            }
+        };
+
+        let frames = format.frames_at_tl(piece_end_tl) - format.frames_at_tl(cursor_tl);
+        if frames > 0 {
+            spans.push(AudioSpan {
+                source,
+                frames: frames as usize,
+            });
+        }
+        cursor_tl = piece_end_tl;
+    }
+
+    Ok(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use super::{AudioSink, PcmChunk, PcmFormat, WavFileAudioSink, audio_spans};
+    use crate::project::{AudioStreamInfo, MediaAsset, Project, ProjectSettings, TimelineMarks};
+    use crate::time::Rational;
+    use crate::timeline::{Segment, Timeline};
+
+    #[test]
+    fn audio_spans_cover_gap_with_silence() {
+        let project = project_with_gap();
+
+        let spans =
+            audio_spans(&project, 0, 300_000, PcmFormat::DEFAULT).expect("spans should resolve");
+
+        assert_eq!(spans.len(), 3);
+        assert_eq!(spans[0].frames, 4_800);
+        let (path, seconds) = spans[0].source.clone().expect("first span has audio");
+        assert_eq!(path, PathBuf::from("demo.mp4"));
+        assert!((seconds - 1.0).abs() < 1e-9);
+        assert_eq!(spans[1].source, None);
+        assert_eq!(spans[1].frames, 4_800);
+        let (_, seconds) = spans[2].source.clone().expect("last span has audio");
+        assert!((seconds - 1.5).abs() < 1e-9);
+        assert_eq!(spans[2].frames, 4_800);
+    }
+
+    #[test]
+    fn audio_spans_silence_disabled_segments() {
+        let mut project = project_with_gap();
+        project.timeline.segments[1].enabled = false;
+
+        let spans =
+            audio_spans(&project, 0, 300_000, PcmFormat::DEFAULT).expect("spans should resolve");
+
+        assert!(spans[0].source.is_some());
+        assert_eq!(spans[1].source, None);
+        assert_eq!(spans[2].source, None);
+        assert_eq!(spans[2].frames, 4_800);
+    }
+
+    #[test]
+    fn audio_spans_frame_counts_do_not_drift_across_chunks() {
+        let project = project_with_gap();
+        let format = PcmFormat {
+            sample_rate: 44_100,
+            channels: 1,
+        };
+
+        let total: usize = (0..30)
+            .map(|index| {
+                audio_spans(&project, index * 10_000, (index + 1) * 10_000, format)
+                    .expect("spans should resolve")
+                    .iter()
+                    .map(|span| span.frames)
+                    .sum::<usize>()
+            })
+            .sum();
+
+        assert_eq!(total, 13_230);
+    }
+
+    #[test]
+    fn wav_sink_writes_float_header_and_samples() {
+        let path = std::env::temp_dir().join(format!(
+            "cutit-wav-sink-{}.wav",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("clock")
+                .as_nanos()
+        ));
+        let mut sink =
+            WavFileAudioSink::create(&path, PcmFormat::DEFAULT).expect("create wav sink");
+        let mut chunk = PcmChunk::silence(PcmFormat::DEFAULT, 3);
+        chunk.samples[0] = 0.5;
+
+        sink.write(&chunk).expect("write chunk");
+        sink.write(&chunk).expect("write second chunk");
+
+        let bytes = fs::read(&path).expect("read wav file");
+        let _ = fs::remove_file(&path);
+        assert_eq!(bytes.len(), 44 + 2 * 3 * 2 * 4);
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(&bytes[8..12], b"WAVE");
+        assert_eq!(u16::from_le_bytes([bytes[20], bytes[21]]), 3);
+        assert_eq!(u16::from_le_bytes([bytes[22], bytes[23]]), 2);
+        assert_eq!(
+            u32::from_le_bytes([bytes[40], bytes[41], bytes[42], bytes[43]]),
+            48
+        );
+        assert_eq!(
+            f32::from_le_bytes([bytes[44], bytes[45], bytes[46], bytes[47]]),
+            0.5
+        );
+        assert_eq!(sink.played_frames(), 6);
+    }
+
+    fn project_with_gap() -> Project {
+        let time_base = Rational::new(1, 48_000).expect("valid rational");
+        Project {
+            assets: vec![MediaAsset {
+                id: 1,
+                path: PathBuf::from("demo.mp4"),
+                video_stream_index: None,
+                audio_stream_index: Some(1),
+                video: None,
+                audio: Some(AudioStreamInfo {
+                    time_base,
+                    sample_rate: 48_000,
+                    channels: 2,
+                    start_pts: 0,
+                }),
+                duration_tl: 2_000_000,
+                source_marks: TimelineMarks::default(),
+                file_size: None,
+                offline: false,
+            }],
+            timeline: Timeline {
+                segments: vec![
+                    Segment {
+                        id: 1,
+                        asset_id: 1,
+                        src_in_video: None,
+                        src_out_video: None,
+                        src_in_audio: Some(48_000),
+                        src_out_audio: Some(52_800),
+                        timeline_start: 0,
+                        timeline_duration: 100_000,
+                        enabled: true,
+                    },
+                    Segment {
+                        id: 2,
+                        asset_id: 1,
+                        src_in_video: None,
+                        src_out_video: None,
+                        src_in_audio: Some(72_000),
+                        src_out_audio: Some(76_800),
+                        timeline_start: 200_000,
+                        timeline_duration: 100_000,
+                        enabled: true,
+                    },
+                ],
+            },
+            settings: ProjectSettings::default(),
+            marks: TimelineMarks::default(),
+            selection: BTreeSet::new(),
+            loop_playback: false,
+            changes: Default::default(),
+        }
+    }
+}
@@ -3,8 +3,10 @@ use std::sync::Arc;
 
 use crate::error::{EngineError, Result};
 use crate::export::ExportVideoPlan;
+use crate::playback::{PcmChunk, PcmFormat};
 use crate::project::ensure_non_empty_duration;
-use crate::time::{Rational, TIMELINE_TIME_BASE, rescale};
+use crate::time::{FrameGrid, Rational, TIMELINE_TIME_BASE, Timecode, rescale};
+use tracing::warn;
 
 /// Pixel format for preview frames passed to the UI.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -13,6 +15,21 @@ pub enum PreviewPixelFormat {
     Nv12,
 }
 
+/// Widest draft preview frame decoded by [`FfmpegMediaBackend`].
+pub const DRAFT_PREVIEW_MAX_WIDTH: u32 = 480;
+
+/// Fidelity tier of a preview frame.
+///
+/// Tiers are ordered: a `Full` frame supersedes a `Draft` frame for the same
+/// request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PreviewQuality {
+    /// Nearest keyframe at reduced size, cheap enough to follow a drag.
+    Draft,
+    /// The exact frame at source resolution.
+    Full,
+}
+
 /// Raw preview frame payload.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PreviewFrame {
@@ -22,6 +39,20 @@ pub struct PreviewFrame {
     pub bytes: Arc<[u8]>,
 }
 
+impl PreviewFrame {
+    /// Returns an opaque black RGBA frame, shown for disabled segments.
+    pub fn black(width: u32, height: u32) -> Self {
+        let pixels = width as usize * height as usize;
+        let bytes: Vec<u8> = [0, 0, 0, u8::MAX].repeat(pixels);
+        Self {
+            width,
+            height,
+            format: PreviewPixelFormat::Rgba8,
+            bytes: Arc::from(bytes),
+        }
+    }
+}
+
 /// Result of probing one media asset.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProbedMedia {
@@ -41,6 +72,8 @@ pub struct ProbedVideoStream {
     pub src_out: i64,
     pub width: u32,
     pub height: u32,
+    /// Source timecode of the first frame, from the `timecode` tag.
+    pub start_timecode: Option<Timecode>,
 }
 
 /// Probed audio stream information used by timeline mapping.
@@ -55,13 +88,35 @@ pub struct ProbedAudioStream {
 }
 
 /// Media operations required by the engine.
-pub trait MediaBackend {
+///
+/// Preview decodes run on background worker threads, so backends must be
+/// shareable across threads.
+pub trait MediaBackend: Send + Sync {
     /// Probes media information for import.
     fn probe(&self, path: &Path) -> Result<ProbedMedia>;
 
     /// Decodes one preview frame around `at_seconds`.
     fn decode_preview_frame(&self, path: &Path, at_seconds: f64) -> Result<PreviewFrame>;
 
+    /// Decodes a fast, approximate preview frame around `at_seconds`.
+    ///
+    /// Backends without a cheaper path fall back to the exact frame.
+    fn decode_preview_draft_frame(&self, path: &Path, at_seconds: f64) -> Result<PreviewFrame> {
+        self.decode_preview_frame(path, at_seconds)
+    }
+
+    /// Decodes `duration_seconds` of audio starting at `at_seconds`.
+    ///
+    /// Samples are resampled to `format`. The result may be shorter than
+    /// requested near the end of the source; callers pad it with silence.
+    fn decode_audio(
+        &self,
+        path: &Path,
+        at_seconds: f64,
+        duration_seconds: f64,
+        format: PcmFormat,
+    ) -> Result<PcmChunk>;
+
     /// Exports timeline segments into a single MP4 file.
     fn export_video(&self, plan: &ExportVideoPlan) -> Result<()>;
 }
@@ -85,10 +140,15 @@ impl MediaBackend for FfmpegMediaBackend {
                     .unwrap_or_else(|| {
                         src_in + rescale(duration_tl, TIMELINE_TIME_BASE, stream.time_base.into())
                     });
+                let frame_rate = stream.r_frame_rate.map(Rational::from);
+                let grid = match frame_rate {
+                    Some(frame_rate) => FrameGrid::from_frame_rate(frame_rate),
+                    None => FrameGrid::from_time_base(stream.time_base.into()),
+                };
                 Ok(ProbedVideoStream {
                     stream_index: stream.index,
                     time_base: stream.time_base.into(),
-                    frame_rate: stream.r_frame_rate.map(Into::into),
+                    frame_rate,
                     src_in,
                     src_out,
                     width: stream
@@ -97,6 +157,9 @@ impl MediaBackend for FfmpegMediaBackend {
                     height: stream
                         .height
                         .ok_or_else(|| EngineError::MissingVideoDimensions(path.to_path_buf()))?,
+                    start_timecode: info
+                        .start_timecode()
+                        .and_then(|text| start_timecode(path, text, grid)),
                 })
             })
             .transpose()?;
@@ -148,6 +211,43 @@ impl MediaBackend for FfmpegMediaBackend {
         })
     }
 
+    fn decode_preview_draft_frame(&self, path: &Path, at_seconds: f64) -> Result<PreviewFrame> {
+        let decoded = media_ffmpeg::decode_video_keyframe_near_seconds(
+            path,
+            at_seconds,
+            DRAFT_PREVIEW_MAX_WIDTH,
+        )?;
+        Ok(PreviewFrame {
+            width: decoded.width,
+            height: decoded.height,
+            format: PreviewPixelFormat::Rgba8,
+            bytes: decoded.rgba.into(),
+        })
+    }
+
+    fn decode_audio(
+        &self,
+        path: &Path,
+        at_seconds: f64,
+        duration_seconds: f64,
+        format: PcmFormat,
+    ) -> Result<PcmChunk> {
+        let decoded = media_ffmpeg::decode_audio_pcm_seconds(
+            path,
+            at_seconds,
+            duration_seconds,
+            format.sample_rate,
+            format.channels,
+        )?;
+        Ok(PcmChunk {
+            format: PcmFormat {
+                sample_rate: decoded.sample_rate,
+                channels: decoded.channels,
+            },
+            samples: decoded.samples,
+        })
+    }
+
     fn export_video(&self, plan: &ExportVideoPlan) -> Result<()> {
         let request = media_ffmpeg::VideoExportRequest {
             inputs: plan.inputs.clone(),
@@ -162,6 +262,7 @@ impl MediaBackend for FfmpegMediaBackend {
                     src_in_audio: segment.src_in_audio,
                     src_out_audio: segment.src_out_audio,
                     src_audio_time_base: segment.src_audio_time_base.map(Into::into),
+                    enabled: segment.enabled,
                 })
                 .collect(),
             audio: plan.audio.map(|audio| media_ffmpeg::AudioExportSettings {
@@ -175,6 +276,17 @@ impl MediaBackend for FfmpegMediaBackend {
     }
 }
 
+/// Parses the probed start timecode, dropping tags that do not fit the rate.
+fn start_timecode(path: &Path, text: &str, grid: FrameGrid) -> Option<Timecode> {
+    match Timecode::parse(text).and_then(|timecode| timecode.frame(grid).map(|_| timecode)) {
+        Ok(timecode) => Some(timecode),
+        Err(error) => {
+            warn!(path = %path.display(), %error, "ignoring source timecode tag");
+            None
+        }
+    }
+}
+
 fn duration_tl_from_probe(info: &media_ffmpeg::MediaInfo) -> Option<i64> {
     if let Some(seconds) = info.duration_seconds {
         return Some((seconds * 1_000_000.0).round() as i64);
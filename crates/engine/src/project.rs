@@ -1,22 +1,76 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
-use crate::api::{MediaAssetSummary, ProjectSnapshot, SegmentSummary};
+use crate::api::{
+    EditPointDirection, MediaAssetSummary, PasteMode, ProjectDelta, ProjectSnapshot,
+    ReplaceAlignment, SegmentSummary,
+};
 use crate::error::{EngineError, Result};
+use crate::migration::{AppliedMigration, PROJECT_FILE_SCHEMA_VERSION, migrate};
 use crate::preview::{ProbedAudioStream, ProbedMedia, ProbedVideoStream};
-use crate::time::{Rational, TIMELINE_TIME_BASE, rescale};
+use crate::time::{FrameGrid, Rational, TIMELINE_TIME_BASE, Timecode, rescale};
 use crate::timeline::{AssetId, Segment, SegmentId, Timeline};
 use serde::{Deserialize, Serialize};
-
-const PROJECT_FILE_SCHEMA_VERSION: u32 = 1;
+use tracing::warn;
 
 /// Project state managed by the engine thread.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// `assets` is the media bin: assets stay in the project whether or not a
+/// segment references them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Project {
     pub assets: Vec<MediaAsset>,
     pub timeline: Timeline,
     pub settings: ProjectSettings,
+    #[serde(default, skip_serializing_if = "TimelineMarks::is_empty")]
+    pub marks: TimelineMarks,
+    /// Selected segment ids; editing state that is never persisted.
+    #[serde(skip)]
+    pub selection: BTreeSet<SegmentId>,
+    /// Playback loops over the in/out range; session state that is never
+    /// persisted.
+    #[serde(skip)]
+    pub loop_playback: bool,
+    /// Ids touched by edits since the engine last published a delta.
+    #[serde(skip)]
+    pub(crate) changes: ChangeLog,
+}
+
+/// In/out marks on the timeline or on an asset's source.
+///
+/// `in_tl` is inclusive and `out_tl` is exclusive. An unset mark falls back to
+/// the start or end when the marked range is resolved. Source marks count
+/// timeline ticks from the start of the asset.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimelineMarks {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_tl: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub out_tl: Option<i64>,
+}
+
+impl TimelineMarks {
+    /// Returns `true` when neither mark is set.
+    pub fn is_empty(&self) -> bool {
+        self.in_tl.is_none() && self.out_tl.is_none()
+    }
+}
+
+/// Number of previous versions kept next to a saved project file
+/// (`cut.nle.json.1` is the newest).
+pub const PROJECT_BACKUP_COUNT: u32 = 3;
+
+/// How `Project::save_to_file_with` writes asset paths.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MediaPathStyle {
+    /// Media under the project file's directory is stored relative to it,
+    /// with the absolute path kept as a fallback.
+    #[default]
+    Relative,
+    /// Every asset path is stored absolute.
+    Absolute,
 }
 
 /// Project-wide defaults and persisted settings.
@@ -24,6 +78,12 @@ pub struct Project {
 pub struct ProjectSettings {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub export_settings: Option<ProjectExportSettings>,
+    /// Project frame rate; `None` follows the first video asset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame_rate: Option<Rational>,
+    /// Snaps every timeline edit point and mark to the project frame grid.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub snap_to_frames: bool,
 }
 
 /// Optional export defaults persisted in a project file.
@@ -44,6 +104,262 @@ pub struct MediaAsset {
     pub video: Option<VideoStreamInfo>,
     pub audio: Option<AudioStreamInfo>,
     pub duration_tl: i64,
+    /// Source in/out marks used by insert and overwrite edits.
+    #[serde(default, skip_serializing_if = "TimelineMarks::is_empty")]
+    pub source_marks: TimelineMarks,
+    /// File size in bytes when the asset was probed, used to find moved files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_size: Option<u64>,
+    /// Set when `path` was missing on load; cleared by a successful relink.
+    #[serde(skip)]
+    pub offline: bool,
+}
+
+impl MediaAsset {
+    /// Builds a bin entry from probed media.
+    pub fn from_probed(asset_id: AssetId, probed: ProbedMedia) -> Self {
+        Self {
+            id: asset_id,
+            file_size: fs::metadata(&probed.path)
+                .ok()
+                .map(|metadata| metadata.len()),
+            path: probed.path,
+            video_stream_index: probed.video.as_ref().map(|video| video.stream_index),
+            audio_stream_index: probed.audio.as_ref().map(|audio| audio.stream_index),
+            video: probed.video.map(VideoStreamInfo::from),
+            audio: probed.audio.map(AudioStreamInfo::from),
+            duration_tl: probed.duration_tl,
+            source_marks: TimelineMarks::default(),
+            offline: false,
+        }
+    }
+
+    /// Checks that `candidate` can stand in for this asset's media.
+    ///
+    /// Segments keep their stream timestamps across a relink, so the streams
+    /// must match in presence, time base, start timestamp and video size, and
+    /// the durations must agree.
+    pub fn check_relink_compatible(&self, candidate: &MediaAsset) -> Result<()> {
+        let incompatible = |reason: String| EngineError::IncompatibleMedia {
+            asset_id: self.id,
+            path: candidate.path.clone(),
+            reason,
+        };
+        match (self.video, candidate.video) {
+            (Some(expected), Some(found)) => {
+                if expected.time_base != found.time_base || expected.start_pts != found.start_pts {
+                    return Err(incompatible(String::from(
+                        "video time base or start does not match",
+                    )));
+                }
+                if (expected.width, expected.height) != (found.width, found.height) {
+                    return Err(incompatible(format!(
+                        "video size {}x{} does not match {}x{}",
+                        found.width, found.height, expected.width, expected.height
+                    )));
+                }
+            }
+            (None, None) => {}
+            (expected, _) => {
+                return Err(incompatible(format!(
+                    "video stream {}",
+                    if expected.is_some() {
+                        "missing"
+                    } else {
+                        "unexpected"
+                    }
+                )));
+            }
+        }
+        match (self.audio, candidate.audio) {
+            (Some(expected), Some(found)) => {
+                if expected.time_base != found.time_base || expected.start_pts != found.start_pts {
+                    return Err(incompatible(String::from(
+                        "audio time base or start does not match",
+                    )));
+                }
+            }
+            (None, None) => {}
+            (expected, _) => {
+                return Err(incompatible(format!(
+                    "audio stream {}",
+                    if expected.is_some() {
+                        "missing"
+                    } else {
+                        "unexpected"
+                    }
+                )));
+            }
+        }
+        if candidate.duration_tl != self.duration_tl {
+            return Err(incompatible(format!(
+                "duration {} does not match {}",
+                candidate.duration_tl, self.duration_tl
+            )));
+        }
+        Ok(())
+    }
+
+    /// Resolves the source marks as `(start, end)` ticks from the asset start.
+    ///
+    /// Unset marks fall back to the asset start or end; the result is never
+    /// empty.
+    pub fn source_range(&self) -> (i64, i64) {
+        let duration_tl = self.duration_tl.max(1);
+        let start_tl = self
+            .source_marks
+            .in_tl
+            .unwrap_or(0)
+            .clamp(0, duration_tl - 1);
+        let end_tl = self
+            .source_marks
+            .out_tl
+            .unwrap_or(duration_tl)
+            .clamp(start_tl + 1, duration_tl);
+        (start_tl, end_tl)
+    }
+
+    /// Computes the preview request for a source position in timeline ticks
+    /// from the asset start.
+    pub fn preview_request_at(&self, t_src: i64) -> Result<PreviewRequest> {
+        self.ensure_online()?;
+        let video = self
+            .video
+            .ok_or(EngineError::MissingVideoStream { asset_id: self.id })?;
+        let src_target_video_ts =
+            video.start_pts + rescale(t_src, TIMELINE_TIME_BASE, video.time_base);
+        let source_tl = rescale(src_target_video_ts, video.time_base, TIMELINE_TIME_BASE).max(0);
+        Ok(PreviewRequest {
+            path: self.path.clone(),
+            source_tl,
+        })
+    }
+
+    /// Returns `AssetOffline` when the asset's file is missing.
+    pub fn ensure_online(&self) -> Result<()> {
+        if self.offline {
+            return Err(EngineError::AssetOffline {
+                asset_id: self.id,
+                path: self.path.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Builds a segment at timeline zero covering the marked source range.
+    pub fn source_segment(&self, segment_id: SegmentId) -> Segment {
+        let (start_tl, end_tl) = self.source_range();
+        self.segment_for_range(segment_id, start_tl, end_tl)
+    }
+
+    /// Builds a segment at timeline zero covering `start_tl..end_tl`, in
+    /// ticks from the asset start.
+    pub(crate) fn segment_for_range(
+        &self,
+        segment_id: SegmentId,
+        start_tl: i64,
+        end_tl: i64,
+    ) -> Segment {
+        let to_stream = |t_tl: i64, time_base: Rational, start_pts: i64| {
+            start_pts + rescale(t_tl, TIMELINE_TIME_BASE, time_base)
+        };
+        Segment {
+            id: segment_id,
+            asset_id: self.id,
+            src_in_video: self
+                .video
+                .map(|video| to_stream(start_tl, video.time_base, video.start_pts)),
+            src_out_video: self
+                .video
+                .map(|video| to_stream(end_tl, video.time_base, video.start_pts)),
+            src_in_audio: self
+                .audio
+                .map(|audio| to_stream(start_tl, audio.time_base, audio.start_pts)),
+            src_out_audio: self
+                .audio
+                .map(|audio| to_stream(end_tl, audio.time_base, audio.start_pts)),
+            timeline_start: 0,
+            timeline_duration: end_tl - start_tl,
+            enabled: true,
+        }
+    }
+
+    /// Returns `(time_base, in, out)` for the segment's source range in stream
+    /// ticks from the asset start, using video timestamps when the asset has
+    /// video.
+    pub(crate) fn segment_source_ticks(&self, segment: &Segment) -> Result<(Rational, i64, i64)> {
+        let segment_id = segment.id;
+        let ((src_in, src_out), time_base, start_pts) = match (self.video, self.audio) {
+            (Some(video), _) => (
+                segment
+                    .src_in_video
+                    .zip(segment.src_out_video)
+                    .ok_or(EngineError::MissingVideoRange { segment_id })?,
+                video.time_base,
+                video.start_pts,
+            ),
+            (None, Some(audio)) => (
+                segment
+                    .src_in_audio
+                    .zip(segment.src_out_audio)
+                    .ok_or(EngineError::MissingAudioRange { segment_id })?,
+                audio.time_base,
+                audio.start_pts,
+            ),
+            (None, None) => return Err(EngineError::MissingVideoStream { asset_id: self.id }),
+        };
+        Ok((time_base, src_in - start_pts, src_out - start_pts))
+    }
+
+    /// Returns the video frame grid, preferring `frame_rate` over the time base.
+    pub fn frame_grid(&self) -> Option<FrameGrid> {
+        let video = self.video?;
+        Some(match video.frame_rate {
+            Some(frame_rate) => FrameGrid::from_frame_rate(frame_rate),
+            None => FrameGrid::from_time_base(video.time_base),
+        })
+    }
+
+    /// Returns the source timecode of the first frame: the probed start
+    /// timecode, or `00:00:00:00`.
+    pub fn start_timecode(&self) -> Timecode {
+        self.video
+            .and_then(|video| video.start_timecode)
+            .unwrap_or(Timecode::ZERO)
+    }
+
+    /// Returns the frame the start timecode labels at `grid`'s rate.
+    ///
+    /// Fails with `InvalidTimecode` when the tag does not fit the rate, e.g. a
+    /// drop-frame tag on 25 fps media, instead of counting from `00:00:00:00`.
+    pub fn start_frame(&self, grid: FrameGrid) -> Result<i64> {
+        self.start_timecode().frame(grid).inspect_err(|error| {
+            warn!(asset_id = self.id, %error, "start timecode does not fit the frame rate");
+        })
+    }
+
+    /// Returns the source timecode of the frame `t_src` ticks after the asset
+    /// start, counted from the start timecode at the asset frame rate.
+    pub fn source_timecode_at(&self, t_src: i64) -> Result<Timecode> {
+        let grid = self.frame_grid().unwrap_or(FrameGrid::DEFAULT);
+        let start_frame = self.start_frame(grid)?;
+        Ok(Timecode::from_frame(
+            start_frame + grid.frame_at(t_src),
+            grid,
+            self.start_timecode().drop_frame,
+        ))
+    }
+
+    /// Returns ticks from the asset start for a source timecode; the inverse of
+    /// [`Self::source_timecode_at`]. Labels before the start timecode wrap
+    /// around midnight like tape timecode does.
+    pub fn source_tl_at_timecode(&self, timecode: Timecode) -> Result<i64> {
+        let grid = self.frame_grid().unwrap_or(FrameGrid::DEFAULT);
+        let start_frame = self.start_frame(grid)?;
+        let frame = timecode.frame(grid)?;
+        let frames_per_day = Timecode::frames_per_day(grid, timecode.drop_frame);
+        Ok(grid.frame_start_tl((frame - start_frame).rem_euclid(frames_per_day)))
+    }
 }
 
 /// Video metadata required by timeline mapping.
@@ -54,6 +370,12 @@ pub struct VideoStreamInfo {
     pub frame_rate: Option<crate::time::Rational>,
     pub width: u32,
     pub height: u32,
+    /// First presentation timestamp in `time_base` units.
+    #[serde(default)]
+    pub start_pts: i64,
+    /// Source timecode of the first frame, when the file carries one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_timecode: Option<Timecode>,
 }
 
 /// Audio metadata required by timeline mapping.
@@ -62,6 +384,54 @@ pub struct AudioStreamInfo {
     pub time_base: crate::time::Rational,
     pub sample_rate: u32,
     pub channels: u16,
+    /// First presentation timestamp in `time_base` units.
+    #[serde(default)]
+    pub start_pts: i64,
+}
+
+/// Segment and asset ids touched by edits, in the order they were touched.
+///
+/// Each entry also records whether the id existed before that touch, so the
+/// first entry for an id tells an added segment from a changed one.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ChangeLog {
+    segments: Vec<(SegmentId, bool)>,
+    assets: Vec<(AssetId, bool)>,
+}
+
+impl ChangeLog {
+    fn segment_touched(&mut self, segment_id: SegmentId) {
+        self.segments.push((segment_id, true));
+    }
+
+    fn segment_added(&mut self, segment_id: SegmentId) {
+        self.segments.push((segment_id, false));
+    }
+
+    fn asset_touched(&mut self, asset_id: AssetId) {
+        self.assets.push((asset_id, true));
+    }
+
+    fn asset_added(&mut self, asset_id: AssetId) {
+        self.assets.push((asset_id, false));
+    }
+}
+
+/// The log is bookkeeping for the next delta, not project state.
+impl PartialEq for ChangeLog {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for ChangeLog {}
+
+/// Project state captured before an edit, used to describe the edit as a delta.
+#[derive(Debug, Clone)]
+pub(crate) struct ProjectCheckpoint {
+    segment_changes: usize,
+    asset_changes: usize,
+    selection: BTreeSet<SegmentId>,
 }
 
 /// Preview request computed from timeline and source mapping.
@@ -78,16 +448,6 @@ impl Project {
         segment_id: SegmentId,
         probed: ProbedMedia,
     ) -> Result<Self> {
-        let asset = MediaAsset {
-            id: asset_id,
-            path: probed.path.clone(),
-            video_stream_index: probed.video.as_ref().map(|video| video.stream_index),
-            audio_stream_index: probed.audio.as_ref().map(|audio| audio.stream_index),
-            video: probed.video.map(VideoStreamInfo::from),
-            audio: probed.audio.map(AudioStreamInfo::from),
-            duration_tl: probed.duration_tl,
-        };
-
         let segment = Segment {
             id: segment_id,
             asset_id,
@@ -97,19 +457,27 @@ impl Project {
             src_out_audio: probed.audio.as_ref().map(|audio| audio.src_out),
             timeline_start: 0,
             timeline_duration: probed.duration_tl,
+            enabled: true,
         };
 
         Ok(Self {
-            assets: vec![asset],
+            assets: vec![MediaAsset::from_probed(asset_id, probed)],
             timeline: Timeline {
                 segments: vec![segment],
             },
             settings: ProjectSettings::default(),
+            marks: TimelineMarks::default(),
+            selection: BTreeSet::new(),
+            loop_playback: false,
+            changes: ChangeLog::default(),
         })
     }
 
     /// Persists the current project to a JSON file.
     ///
+    /// Media under the project file's directory is stored relative to it; see
+    /// `save_to_file_with`.
+    ///
     /// # Example
     /// ```ignore
     /// use std::path::PathBuf;
@@ -118,19 +486,52 @@ impl Project {
     /// project.save_to_file(PathBuf::from("project.nle.json")).unwrap();
     /// ```
     pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<()> {
-        self.validate_for_persistence()?;
+        self.save_to_file_with(path, MediaPathStyle::Relative)
+    }
+
+    /// Persists the project, writing asset paths in `style`.
+    ///
+    /// Paths are recomputed for the target file, so a save-as to another
+    /// folder rewrites every relative path; `Absolute` drops them entirely.
+    /// The file is replaced atomically, and the version it replaces becomes
+    /// backup `.1` (up to `PROJECT_BACKUP_COUNT` are kept).
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::project::MediaPathStyle;
+    ///
+    /// project
+    ///     .save_to_file_with("/share/cut.nle.json", MediaPathStyle::Absolute)
+    ///     .unwrap();
+    /// ```
+    pub fn save_to_file_with(&self, path: impl AsRef<Path>, style: MediaPathStyle) -> Result<()> {
+        let path = path.as_ref();
+        let text = self.to_file_text(path, style)?;
+        write_file_atomically(path, text.as_bytes(), PROJECT_BACKUP_COUNT)
+    }
 
+    /// Atomically writes a recovery copy of the project to `path`.
+    ///
+    /// Paths are stored absolute so the copy can live away from the project
+    /// file; missing parent directories are created.
+    pub fn save_recovery_file(&self, path: impl AsRef<Path>) -> Result<()> {
         let path = path.as_ref();
-        let file = ProjectFile::from_project(self);
-        let text = serde_json::to_string_pretty(&file).map_err(|source| {
-            EngineError::ProjectSerialization {
-                path: path.to_path_buf(),
+        let text = self.to_file_text(path, MediaPathStyle::Absolute)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|source| EngineError::ProjectIo {
+                context: "create recovery directory",
+                path: parent.to_path_buf(),
                 source,
-            }
-        })?;
+            })?;
+        }
+        write_file_atomically(path, text.as_bytes(), 0)
+    }
 
-        fs::write(path, text).map_err(|source| EngineError::ProjectIo {
-            context: "write project file",
+    fn to_file_text(&self, path: &Path, style: MediaPathStyle) -> Result<String> {
+        self.validate_for_persistence()?;
+        let project_dir = absolute_parent(path)?;
+        let file = ProjectFile::from_project(self, &project_dir, style);
+        serde_json::to_string_pretty(&file).map_err(|source| EngineError::ProjectSerialization {
             path: path.to_path_buf(),
             source,
         })
@@ -138,6 +539,12 @@ impl Project {
 
     /// Loads a project from a JSON file.
     ///
+    /// Relative asset paths resolve against the project file's directory,
+    /// falling back to the stored absolute path when that file is missing.
+    /// Assets whose file is missing load as `offline` instead of failing; see
+    /// `offline_asset_ids` and `relink_asset`. Older schema versions are
+    /// migrated; see `load_from_file_with_migrations`.
+    ///
     /// # Example
     /// ```ignore
     /// use engine::project::Project;
@@ -146,6 +553,28 @@ impl Project {
     /// assert!(!project.assets.is_empty());
     /// ```
     pub fn load_from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::load_from_file_with_migrations(path).map(|(project, _)| project)
+    }
+
+    /// Loads a project and reports the schema migrations that upgraded it.
+    ///
+    /// Files from a newer build fail with `ProjectFileTooNew`; files that parse
+    /// but fail validation (unknown asset ids, bad stream ranges, overlaps)
+    /// fail with `InvalidProjectFile`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::project::Project;
+    ///
+    /// let (project, migrations) =
+    ///     Project::load_from_file_with_migrations("old.nle.json").unwrap();
+    /// for step in &migrations {
+    ///     println!("upgraded v{} -> v{}", step.from_version, step.to_version);
+    /// }
+    /// ```
+    pub fn load_from_file_with_migrations(
+        path: impl AsRef<Path>,
+    ) -> Result<(Self, Vec<AppliedMigration>)> {
         let path = path.as_ref();
         let text = fs::read_to_string(path).map_err(|source| EngineError::ProjectIo {
             context: "read project file",
@@ -153,21 +582,129 @@ impl Project {
             source,
         })?;
 
-        let file: ProjectFile =
-            serde_json::from_str(&text).map_err(|source| EngineError::ProjectSerialization {
-                path: path.to_path_buf(),
-                source,
+        let serialization = |source| EngineError::ProjectSerialization {
+            path: path.to_path_buf(),
+            source,
+        };
+        let mut document: serde_json::Value = serde_json::from_str(&text).map_err(serialization)?;
+        let migrations = migrate(&mut document)?;
+        let file: ProjectFile = serde_json::from_value(document).map_err(serialization)?;
+
+        let mut project = file.into_project(&absolute_parent(path)?);
+        project
+            .validate_for_persistence()
+            .map_err(|error| match error {
+                EngineError::InvalidProjectFile { .. } => error,
+                other => EngineError::InvalidProjectFile {
+                    reason: other.to_string(),
+                },
             })?;
+        project.mark_offline_assets();
+        Ok((project, migrations))
+    }
 
-        if file.schema_version != PROJECT_FILE_SCHEMA_VERSION {
-            return Err(EngineError::InvalidProjectFile {
-                reason: format!("unsupported project schema version {}", file.schema_version),
-            });
+    /// Returns the newest backup of `path` that loads and validates, if any.
+    ///
+    /// Used when the project file itself is damaged.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use engine::project::Project;
+    ///
+    /// if Project::load_from_file("cut.nle.json").is_err() {
+    ///     if let Some(backup) = Project::newest_valid_backup("cut.nle.json") {
+    ///         let project = Project::load_from_file(backup).unwrap();
+    ///     }
+    /// }
+    /// ```
+    pub fn newest_valid_backup(path: impl AsRef<Path>) -> Option<PathBuf> {
+        let path = path.as_ref();
+        (1..=PROJECT_BACKUP_COUNT)
+            .map(|index| backup_path(path, index))
+            .find(|backup| backup.is_file() && Self::load_from_file(backup).is_ok())
+    }
+
+    /// Flags assets whose file no longer exists as offline.
+    pub fn mark_offline_assets(&mut self) {
+        for asset in &mut self.assets {
+            asset.offline = !asset.path.is_file();
         }
+    }
+
+    /// Returns the ids of offline assets in bin order.
+    pub fn offline_asset_ids(&self) -> Vec<AssetId> {
+        self.assets
+            .iter()
+            .filter(|asset| asset.offline)
+            .map(|asset| asset.id)
+            .collect()
+    }
+
+    /// Points `asset_id` at the file probed into `candidate`.
+    ///
+    /// Fails with `IncompatibleMedia` when the streams differ; the asset keeps
+    /// its id, marks and segments either way.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let probed = backend.probe(Path::new("/media/moved/shot.mov"))?;
+    /// project.relink_asset(1, MediaAsset::from_probed(1, probed))?;
+    /// ```
+    pub fn relink_asset(&mut self, asset_id: AssetId, candidate: MediaAsset) -> Result<()> {
+        let asset = self.asset_by_id_mut(asset_id)?;
+        asset.check_relink_compatible(&candidate)?;
+        asset.path = candidate.path;
+        asset.file_size = candidate.file_size;
+        asset.video_stream_index = candidate.video_stream_index;
+        asset.audio_stream_index = candidate.audio_stream_index;
+        asset.offline = false;
+        Ok(())
+    }
 
-        let project = file.into_project();
-        project.validate_for_persistence()?;
-        Ok(project)
+    /// Searches `dir` recursively for files that may replace offline assets.
+    ///
+    /// A file matches when its name equals the asset's file name and, if the
+    /// asset recorded one, its size equals the recorded size. Returns at most
+    /// one candidate per asset; unreadable subdirectories are skipped.
+    pub fn find_relink_candidates(&self, dir: &Path) -> Result<Vec<(AssetId, PathBuf)>> {
+        let mut wanted: Vec<&MediaAsset> =
+            self.assets.iter().filter(|asset| asset.offline).collect();
+        let mut candidates = Vec::new();
+        let mut pending_dirs = vec![dir.to_path_buf()];
+        let mut root = true;
+        while let Some(current) = pending_dirs.pop() {
+            let entries = match fs::read_dir(&current) {
+                Ok(entries) => entries,
+                Err(source) if root => {
+                    return Err(EngineError::ProjectIo {
+                        context: "read relink directory",
+                        path: current,
+                        source,
+                    });
+                }
+                Err(_) => continue,
+            };
+            root = false;
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let Ok(metadata) = entry.metadata() else {
+                    continue;
+                };
+                if metadata.is_dir() {
+                    pending_dirs.push(path);
+                    continue;
+                }
+                let Some(position) = wanted.iter().position(|asset| {
+                    asset.path.file_name() == path.file_name()
+                        && asset.file_size.is_none_or(|size| size == metadata.len())
+                }) else {
+                    continue;
+                };
+                candidates.push((wanted.swap_remove(position).id, path));
+            }
+        }
+        candidates.sort_unstable_by_key(|(asset_id, _)| *asset_id);
+        Ok(candidates)
     }
 
     /// Returns project duration in timeline ticks.
@@ -177,38 +714,112 @@ impl Project {
 
     /// Creates an immutable snapshot for the UI.
     pub fn snapshot(&self, preview_bucket_tl: i64) -> ProjectSnapshot {
+        let (frame_grid, drop_frame) = self.timecode_format();
         ProjectSnapshot {
-            assets: self
-                .assets
-                .iter()
-                .map(|asset| MediaAssetSummary {
-                    id: asset.id,
-                    path: asset.path.clone(),
-                    has_video: asset.video.is_some(),
-                    has_audio: asset.audio.is_some(),
-                    duration_tl: asset.duration_tl,
-                })
-                .collect(),
+            revision: 0,
+            assets: self.assets.iter().map(MediaAssetSummary::from).collect(),
             segments: self
                 .timeline
                 .segments
                 .iter()
-                .map(|segment| SegmentSummary {
-                    id: segment.id,
-                    asset_id: segment.asset_id,
-                    timeline_start: segment.timeline_start,
-                    timeline_duration: segment.timeline_duration,
-                    src_in_video: segment.src_in_video,
-                    src_out_video: segment.src_out_video,
-                    src_in_audio: segment.src_in_audio,
-                    src_out_audio: segment.src_out_audio,
-                })
+                .map(SegmentSummary::from)
                 .collect(),
             duration_tl: self.duration_tl(),
             preview_bucket_tl,
+            in_point_tl: self.marks.in_tl,
+            out_point_tl: self.marks.out_tl,
+            loop_playback: self.loop_playback,
+            frame_grid,
+            drop_frame,
+            snap_to_frames: self.settings.snap_to_frames,
+            selection: self.selection.iter().copied().collect(),
+        }
+    }
+
+    /// Captures the state [`Project::delta_since`] compares against.
+    pub(crate) fn checkpoint(&self) -> ProjectCheckpoint {
+        ProjectCheckpoint {
+            segment_changes: self.changes.segments.len(),
+            asset_changes: self.changes.assets.len(),
+            selection: self.selection.clone(),
+        }
+    }
+
+    /// Describes the changes made since `checkpoint`.
+    ///
+    /// Only the segments and assets that edits recorded as touched are listed,
+    /// so the delta stays small for local edits on long timelines. Revisions
+    /// are left for the caller to fill in.
+    pub(crate) fn delta_since(
+        &self,
+        checkpoint: &ProjectCheckpoint,
+        preview_bucket_tl: i64,
+    ) -> ProjectDelta {
+        // The first touch of an id tells whether it existed at the checkpoint.
+        let mut touched: HashMap<SegmentId, bool> = HashMap::new();
+        for &(segment_id, existed) in &self.changes.segments[checkpoint.segment_changes..] {
+            touched.entry(segment_id).or_insert(existed);
+        }
+        let mut segments_added = Vec::new();
+        let mut segments_updated = Vec::new();
+        if !touched.is_empty() {
+            for segment in &self.timeline.segments {
+                match touched.remove(&segment.id) {
+                    Some(true) => segments_updated.push(SegmentSummary::from(segment)),
+                    Some(false) => segments_added.push(SegmentSummary::from(segment)),
+                    None => {}
+                }
+            }
+        }
+        let mut segments_removed: Vec<SegmentId> = touched
+            .into_iter()
+            .filter_map(|(segment_id, existed)| existed.then_some(segment_id))
+            .collect();
+        segments_removed.sort_unstable();
+
+        let mut touched: HashMap<AssetId, bool> = HashMap::new();
+        for &(asset_id, existed) in &self.changes.assets[checkpoint.asset_changes..] {
+            touched.entry(asset_id).or_insert(existed);
+        }
+        let mut assets_added = Vec::new();
+        let mut assets_updated = Vec::new();
+        if !touched.is_empty() {
+            for asset in &self.assets {
+                match touched.get(&asset.id) {
+                    Some(true) => assets_updated.push(MediaAssetSummary::from(asset)),
+                    Some(false) => assets_added.push(MediaAssetSummary::from(asset)),
+                    None => {}
+                }
+            }
+        }
+
+        let (frame_grid, drop_frame) = self.timecode_format();
+        ProjectDelta {
+            base_revision: 0,
+            revision: 0,
+            assets_added,
+            assets_updated,
+            segments_added,
+            segments_updated,
+            segments_removed,
+            duration_tl: self.duration_tl(),
+            preview_bucket_tl,
+            in_point_tl: self.marks.in_tl,
+            out_point_tl: self.marks.out_tl,
+            loop_playback: self.loop_playback,
+            frame_grid,
+            drop_frame,
+            snap_to_frames: self.settings.snap_to_frames,
+            selection: (self.selection != checkpoint.selection)
+                .then(|| self.selection.iter().copied().collect()),
         }
     }
 
+    /// Forgets the ids recorded by earlier edits once their delta is out.
+    pub(crate) fn clear_changes(&mut self) {
+        self.changes = ChangeLog::default();
+    }
+
     /// Computes the preview request for a timeline timestamp.
     pub fn preview_request_at(&self, t_tl: i64) -> Result<PreviewRequest> {
         let index = self
@@ -217,6 +828,7 @@ impl Project {
             .ok_or(EngineError::SegmentNotFound { at_tl: t_tl })?;
         let segment = &self.timeline.segments[index];
         let asset = self.asset_by_id(segment.asset_id)?;
+        asset.ensure_online()?;
         let video = asset
             .video
             .ok_or(EngineError::MissingVideoStream { asset_id: asset.id })?;
@@ -236,59 +848,434 @@ impl Project {
         })
     }
 
-    /// Splits one segment at `at_tl`.
-    ///
-    /// The timeline remains contiguous on success. The operation fails when
-    /// `at_tl` points to a segment boundary or to a position outside the
-    /// current timeline.
+    /// Returns the asset and source position shown at `t_tl`.
     ///
-    /// # Example
-    /// ```ignore
-    /// let mut project = /* construct project */;
-    /// project.split(500_000, 2).unwrap();
-    /// ```
-    pub fn split(&mut self, at_tl: i64, next_segment_id: SegmentId) -> Result<()> {
-        let index = match self.timeline.find_segment_index(at_tl) {
-            Some(index) => index,
-            None => {
-                if self.timeline.is_boundary_split_point(at_tl) {
-                    return Err(EngineError::SplitPointAtBoundary { at_tl });
-                }
-
-                return Err(EngineError::SegmentNotFound { at_tl });
-            }
-        };
+    /// The source position counts timeline ticks from the asset start and is
+    /// clamped to the asset. Returns `SegmentNotFound` in gaps.
+    pub fn match_frame(&self, t_tl: i64) -> Result<(AssetId, i64)> {
+        let index = self
+            .timeline
+            .find_segment_index(t_tl)
+            .ok_or(EngineError::SegmentNotFound { at_tl: t_tl })?;
         let segment = &self.timeline.segments[index];
         let asset = self.asset_by_id(segment.asset_id)?;
-
-        self.timeline.split_segment(
-            at_tl,
-            next_segment_id,
-            asset.video.map(|video| video.time_base),
-            asset.audio.map(|audio| audio.time_base),
-        )
+        let local_tl = t_tl - segment.timeline_start;
+        let to_source = |src_in: i64, time_base: Rational, start_pts: i64| {
+            let src_ts = src_in + rescale(local_tl, TIMELINE_TIME_BASE, time_base);
+            rescale(src_ts - start_pts, time_base, TIMELINE_TIME_BASE)
+        };
+        let t_src = match (
+            asset.video,
+            segment.src_in_video,
+            asset.audio,
+            segment.src_in_audio,
+        ) {
+            (Some(video), Some(src_in), _, _) => {
+                to_source(src_in, video.time_base, video.start_pts)
+            }
+            (_, _, Some(audio), Some(src_in)) => {
+                to_source(src_in, audio.time_base, audio.start_pts)
+            }
+            _ => local_tl,
+        };
+        Ok((asset.id, normalize_playhead(t_src, asset.duration_tl)))
     }
 
-    /// Cuts one segment at `at_tl` and keeps timeline gaps.
+    /// Points `segment_id` at `asset_id`, keeping its timeline position,
+    /// duration and enabled flag.
     ///
-    /// When `at_tl` points to a segment start boundary, the segment starting
-    /// at that boundary is removed.
+    /// `align` picks the new source in point: `SourceTimecode` finds the
+    /// source timecode of the current in point in the new asset, `StartOfFile`
+    /// uses the first frame, and `Playhead` lines up `source_t_src` of the new
+    /// asset with the timeline at `playhead_tl`. Returns `InsufficientMedia`
+    /// when the new asset cannot cover the whole segment.
     ///
     /// # Example
     /// ```ignore
-    /// let mut project = /* construct project */;
-    /// project.cut(500_000).unwrap();
+    /// let mut project = /* construct project with a graded asset 2 */;
+    /// project
+    ///     .replace_segment_media(1, 2, ReplaceAlignment::SourceTimecode, 0, 0)
+    ///     .unwrap();
     /// ```
-    pub fn cut(&mut self, at_tl: i64) -> Result<()> {
-        let _ = self.timeline.cut_segment(at_tl)?;
-        Ok(())
-    }
-
-    /// Moves one segment start time without changing its source range.
-    ///
-    /// The move is clamped so that segment order stays stable and no overlap is
-    /// introduced with adjacent segments.
-    pub fn move_segment(&mut self, segment_id: SegmentId, new_start_tl: i64) -> Result<()> {
+    pub fn replace_segment_media(
+        &mut self,
+        segment_id: SegmentId,
+        asset_id: AssetId,
+        align: ReplaceAlignment,
+        playhead_tl: i64,
+        source_t_src: i64,
+    ) -> Result<()> {
+        let index = self
+            .timeline
+            .find_segment_index_by_id(segment_id)
+            .ok_or(EngineError::SegmentIdNotFound { segment_id })?;
+        let segment = &self.timeline.segments[index];
+        let asset = self.asset_by_id(asset_id)?;
+        let start_tl = match align {
+            ReplaceAlignment::SourceTimecode => {
+                let (current_asset_id, in_src) = self.match_frame(segment.timeline_start)?;
+                let current = self.asset_by_id(current_asset_id)?;
+                let timecode = current.source_timecode_at(in_src)?;
+                // Timecode names whole frames; keep the in point's offset into its frame.
+                let into_frame = in_src - current.source_tl_at_timecode(timecode)?;
+                asset.source_tl_at_timecode(timecode)? + into_frame
+            }
+            ReplaceAlignment::StartOfFile => 0,
+            ReplaceAlignment::Playhead => source_t_src - (playhead_tl - segment.timeline_start),
+        };
+        let end_tl = start_tl.saturating_add(segment.timeline_duration);
+        if start_tl < 0 || end_tl > asset.duration_tl {
+            return Err(EngineError::InsufficientMedia {
+                asset_id,
+                start_tl,
+                end_tl,
+                duration_tl: asset.duration_tl,
+            });
+        }
+
+        let replacement = Segment {
+            timeline_start: segment.timeline_start,
+            enabled: segment.enabled,
+            ..asset.segment_for_range(segment_id, start_tl, end_tl)
+        };
+        self.timeline.segments[index] = replacement;
+        self.changes.segment_touched(segment_id);
+        Ok(())
+    }
+
+    /// Returns the video size of a disabled segment at `t_tl`.
+    ///
+    /// Disabled segments preview as black frames of their source size.
+    pub fn disabled_frame_size_at(&self, t_tl: i64) -> Option<(u32, u32)> {
+        let index = self.timeline.find_segment_index(t_tl)?;
+        let segment = &self.timeline.segments[index];
+        if segment.enabled {
+            return None;
+        }
+        let video = self.asset_by_id(segment.asset_id).ok()?.video?;
+        Some((video.width, video.height))
+    }
+
+    /// Adds `asset` to the bin without touching the timeline.
+    pub fn add_asset(&mut self, asset: MediaAsset) {
+        self.changes.asset_added(asset.id);
+        self.assets.push(asset);
+    }
+
+    /// Replaces every timeline segment, e.g. with an imported edit.
+    pub fn replace_segments(&mut self, segments: Vec<Segment>) {
+        for segment in &self.timeline.segments {
+            self.changes.segment_touched(segment.id);
+        }
+        for segment in &segments {
+            self.changes.segment_added(segment.id);
+        }
+        self.timeline.segments = segments;
+    }
+
+    /// Sets the source in mark of `asset_id`, clamped to the asset.
+    ///
+    /// An out mark at or before the new in mark is cleared.
+    pub fn set_source_in_point(&mut self, asset_id: AssetId, at_src: i64) -> Result<()> {
+        let asset = self.asset_by_id_mut(asset_id)?;
+        let at_src = at_src.clamp(0, asset.duration_tl);
+        let marks = &mut asset.source_marks;
+        if marks.out_tl.is_some_and(|out_tl| out_tl <= at_src) {
+            marks.out_tl = None;
+        }
+        marks.in_tl = Some(at_src);
+        Ok(())
+    }
+
+    /// Sets the exclusive source out mark of `asset_id`, clamped to the asset.
+    ///
+    /// An in mark at or after the new out mark is cleared.
+    pub fn set_source_out_point(&mut self, asset_id: AssetId, at_src: i64) -> Result<()> {
+        let asset = self.asset_by_id_mut(asset_id)?;
+        let at_src = at_src.clamp(0, asset.duration_tl);
+        let marks = &mut asset.source_marks;
+        if marks.in_tl.is_some_and(|in_tl| in_tl >= at_src) {
+            marks.in_tl = None;
+        }
+        marks.out_tl = Some(at_src);
+        Ok(())
+    }
+
+    /// Clears both source marks of `asset_id`.
+    pub fn clear_source_in_out(&mut self, asset_id: AssetId) -> Result<()> {
+        self.asset_by_id_mut(asset_id)?.source_marks = TimelineMarks::default();
+        Ok(())
+    }
+
+    /// Edits the marked source range of `asset_id` into the timeline at `at_tl`.
+    ///
+    /// `Insert` ripples later segments right; `Overwrite` replaces whatever
+    /// lies underneath, splitting segments at the edit edges. The new segment
+    /// becomes the selection. Returns the end of the edit. `at_tl` is snapped
+    /// when frame snapping is on.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut project = /* construct project */;
+    /// project.set_source_in_point(2, 1_000_000).unwrap();
+    /// project.set_source_out_point(2, 3_000_000).unwrap();
+    /// let mut next_id = 10;
+    /// let end_tl = project
+    ///     .edit_from_asset(2, 500_000, PasteMode::Overwrite, || {
+    ///         next_id += 1;
+    ///         next_id
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(end_tl, 2_500_000);
+    /// ```
+    pub fn edit_from_asset(
+        &mut self,
+        asset_id: AssetId,
+        at_tl: i64,
+        mode: PasteMode,
+        allocate_segment_id: impl FnMut() -> SegmentId,
+    ) -> Result<i64> {
+        let segment = self.asset_by_id(asset_id)?.source_segment(0);
+        let at_tl = self.snap_tl(at_tl);
+        self.paste_segments_at(at_tl, &[segment], mode, allocate_segment_id)
+    }
+
+    /// Enables or disables one segment.
+    pub fn set_segment_enabled(&mut self, segment_id: SegmentId, enabled: bool) -> Result<()> {
+        let index = self
+            .timeline
+            .find_segment_index_by_id(segment_id)
+            .ok_or(EngineError::SegmentIdNotFound { segment_id })?;
+        self.timeline.segments[index].enabled = enabled;
+        self.changes.segment_touched(segment_id);
+        Ok(())
+    }
+
+    /// Returns the project frame grid: `ProjectSettings::frame_rate` when
+    /// set, otherwise the first video asset's.
+    pub fn frame_grid(&self) -> Option<FrameGrid> {
+        self.settings
+            .frame_rate
+            .map(FrameGrid::from_frame_rate)
+            .or_else(|| self.assets.iter().find_map(MediaAsset::frame_grid))
+    }
+
+    /// Returns the record timecode rate and count: the project frame grid,
+    /// drop-frame when the first video asset runs at that rate with drop-frame
+    /// source timecode.
+    pub fn timecode_format(&self) -> (FrameGrid, bool) {
+        let Some(grid) = self.frame_grid() else {
+            return (FrameGrid::DEFAULT, false);
+        };
+        let drop_frame = self
+            .assets
+            .iter()
+            .find_map(|asset| Some((asset.frame_grid()?, asset.start_timecode().drop_frame)))
+            .is_some_and(|(asset_grid, drop_frame)| asset_grid == grid && drop_frame);
+        (grid, drop_frame)
+    }
+
+    /// Snaps an edit point to the nearest project frame start when
+    /// `ProjectSettings::snap_to_frames` is on; otherwise returns `t_tl`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// project.settings.snap_to_frames = true;
+    /// // 29.97 fps: frame 1 starts at 33_367.
+    /// assert_eq!(project.snap_tl(30_000), 33_367);
+    /// ```
+    pub fn snap_tl(&self, t_tl: i64) -> i64 {
+        if !self.settings.snap_to_frames {
+            return t_tl;
+        }
+        self.frame_grid()
+            .unwrap_or(FrameGrid::DEFAULT)
+            .nearest_frame_start_tl(t_tl)
+    }
+
+    /// Returns the record timecode at `t_tl`, counted from `00:00:00:00`.
+    pub fn timecode_at(&self, t_tl: i64) -> Timecode {
+        let (grid, drop_frame) = self.timecode_format();
+        Timecode::from_tl(t_tl, grid, drop_frame)
+    }
+
+    /// Moves `t_tl` by `delta` video frames.
+    ///
+    /// Inside a segment, frames are counted from the segment start using the
+    /// referenced asset's frame rate (or video time base). Gaps fall back to the
+    /// project frame grid. Steps never skip a segment boundary, and the result
+    /// is clamped to the timeline like a playhead.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let project = /* 29.97 fps project */;
+    /// assert_eq!(project.step_frames(0, 2), 66_734);
+    /// ```
+    pub fn step_frames(&self, t_tl: i64, delta: i64) -> i64 {
+        let duration_tl = self.duration_tl();
+        let fallback = self.frame_grid().unwrap_or(FrameGrid::DEFAULT);
+        let mut current = normalize_playhead(t_tl, duration_tl);
+        for _ in 0..delta.unsigned_abs() {
+            let next = if delta > 0 {
+                self.next_frame_tl(current, fallback)
+            } else {
+                self.previous_frame_tl(current, fallback)
+            };
+            let next = normalize_playhead(next, duration_tl);
+            if next == current {
+                break;
+            }
+            current = next;
+        }
+        current
+    }
+
+    /// Returns the nearest segment boundary before or after `t_tl`.
+    ///
+    /// Boundaries are segment starts and ends (including gap edges). The end
+    /// of the timeline maps to its last playable tick.
+    pub fn edit_point(&self, t_tl: i64, direction: EditPointDirection) -> Option<i64> {
+        let duration_tl = self.duration_tl();
+        let boundaries = self
+            .timeline
+            .segments
+            .iter()
+            .flat_map(|segment| {
+                [
+                    segment.timeline_start,
+                    segment.timeline_start + segment.timeline_duration,
+                ]
+            })
+            .chain([0])
+            .map(|boundary| normalize_playhead(boundary, duration_tl));
+        match direction {
+            EditPointDirection::Previous => boundaries.filter(|boundary| *boundary < t_tl).max(),
+            EditPointDirection::Next => boundaries.filter(|boundary| *boundary > t_tl).min(),
+        }
+    }
+
+    fn next_frame_tl(&self, t_tl: i64, fallback: FrameGrid) -> i64 {
+        if let Some(index) = self.timeline.find_segment_index(t_tl) {
+            let segment = &self.timeline.segments[index];
+            let grid = self.segment_frame_grid(segment).unwrap_or(fallback);
+            let local_tl = t_tl - segment.timeline_start;
+            let next_local_tl = grid
+                .frame_start_tl(grid.frame_at(local_tl) + 1)
+                .max(local_tl + 1);
+            return (segment.timeline_start + next_local_tl)
+                .min(segment.timeline_start + segment.timeline_duration);
+        }
+
+        let next_segment_start = self
+            .timeline
+            .segments
+            .iter()
+            .map(|segment| segment.timeline_start)
+            .filter(|start| *start > t_tl)
+            .min()
+            .unwrap_or(i64::MAX);
+        fallback
+            .frame_start_tl(fallback.frame_at(t_tl) + 1)
+            .max(t_tl + 1)
+            .min(next_segment_start)
+    }
+
+    fn previous_frame_tl(&self, t_tl: i64, fallback: FrameGrid) -> i64 {
+        if t_tl <= 0 {
+            return 0;
+        }
+        let probe_tl = t_tl - 1;
+        if let Some(index) = self.timeline.find_segment_index(probe_tl) {
+            let segment = &self.timeline.segments[index];
+            let grid = self.segment_frame_grid(segment).unwrap_or(fallback);
+            let local_tl = probe_tl - segment.timeline_start;
+            return segment.timeline_start + grid.frame_start_tl(grid.frame_at(local_tl));
+        }
+
+        let previous_segment_end = self
+            .timeline
+            .segments
+            .iter()
+            .map(|segment| segment.timeline_start + segment.timeline_duration)
+            .filter(|end| *end <= probe_tl)
+            .max()
+            .unwrap_or(0);
+        fallback
+            .frame_start_tl(fallback.frame_at(probe_tl))
+            .max(previous_segment_end)
+    }
+
+    fn segment_frame_grid(&self, segment: &Segment) -> Option<FrameGrid> {
+        self.asset_by_id(segment.asset_id)
+            .ok()
+            .and_then(MediaAsset::frame_grid)
+    }
+
+    /// Splits one segment at `at_tl`.
+    ///
+    /// The timeline remains contiguous on success. The operation fails when
+    /// `at_tl` points to a segment boundary or to a position outside the
+    /// current timeline. With frame snapping on, `at_tl` first moves to the
+    /// nearest frame start (see [`Project::snap_tl`]).
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut project = /* construct project */;
+    /// project.split(500_000, 2).unwrap();
+    /// ```
+    pub fn split(&mut self, at_tl: i64, next_segment_id: SegmentId) -> Result<()> {
+        self.split_at(self.snap_tl(at_tl), next_segment_id)
+    }
+
+    /// Splits one segment at exactly `at_tl`; see [`Project::split`].
+    fn split_at(&mut self, at_tl: i64, next_segment_id: SegmentId) -> Result<()> {
+        let index = match self.timeline.find_segment_index(at_tl) {
+            Some(index) => index,
+            None => {
+                if self.timeline.is_boundary_split_point(at_tl) {
+                    return Err(EngineError::SplitPointAtBoundary { at_tl });
+                }
+
+                return Err(EngineError::SegmentNotFound { at_tl });
+            }
+        };
+        let segment = &self.timeline.segments[index];
+        let segment_id = segment.id;
+        let asset = self.asset_by_id(segment.asset_id)?;
+
+        self.timeline.split_segment(
+            at_tl,
+            next_segment_id,
+            asset.video.map(|video| video.time_base),
+            asset.audio.map(|audio| audio.time_base),
+        )?;
+        self.changes.segment_touched(segment_id);
+        self.changes.segment_added(next_segment_id);
+        Ok(())
+    }
+
+    /// Cuts one segment at `at_tl` and keeps timeline gaps.
+    ///
+    /// When `at_tl` points to a segment start boundary, the segment starting
+    /// at that boundary is removed.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut project = /* construct project */;
+    /// project.cut(500_000).unwrap();
+    /// ```
+    pub fn cut(&mut self, at_tl: i64) -> Result<()> {
+        let removed = self.timeline.cut_segment(self.snap_tl(at_tl))?;
+        self.changes.segment_touched(removed.id);
+        Ok(())
+    }
+
+    /// Moves one segment start time without changing its source range.
+    ///
+    /// The move is clamped so that segment order stays stable and no overlap is
+    /// introduced with adjacent segments. The new start is snapped first when
+    /// frame snapping is on.
+    pub fn move_segment(&mut self, segment_id: SegmentId, new_start_tl: i64) -> Result<()> {
         let index = self
             .timeline
             .find_segment_index_by_id(segment_id)
@@ -308,12 +1295,17 @@ impl Project {
         } else {
             i64::MAX.saturating_sub(duration.max(0))
         };
-        let clamped = new_start_tl.max(0).clamp(prev_end, max_start.max(prev_end));
+        let clamped = self
+            .snap_tl(new_start_tl)
+            .max(0)
+            .clamp(prev_end, max_start.max(prev_end));
         self.timeline.segments[index].timeline_start = clamped;
+        self.changes.segment_touched(segment_id);
         Ok(())
     }
 
-    /// Trims the start edge of one segment.
+    /// Trims the start edge of one segment, snapping the new start when frame
+    /// snapping is on.
     pub fn trim_segment_start(&mut self, segment_id: SegmentId, new_start_tl: i64) -> Result<()> {
         let index = self
             .timeline
@@ -332,7 +1324,7 @@ impl Project {
         };
         let old_start = segment.timeline_start;
         let old_end = old_start + segment.timeline_duration;
-        let clamped_start = new_start_tl.clamp(prev_end, old_end - 1);
+        let clamped_start = self.snap_tl(new_start_tl).clamp(prev_end, old_end - 1);
         let delta_tl = clamped_start - old_start;
 
         let segment = &mut self.timeline.segments[index];
@@ -340,10 +1332,12 @@ impl Project {
         segment.timeline_duration = old_end - clamped_start;
         segment.src_in_video = shift_stream_point(segment.src_in_video, delta_tl, video_tb);
         segment.src_in_audio = shift_stream_point(segment.src_in_audio, delta_tl, audio_tb);
+        self.changes.segment_touched(segment_id);
         Ok(())
     }
 
-    /// Trims the end edge of one segment.
+    /// Trims the end edge of one segment, snapping the new end when frame
+    /// snapping is on.
     pub fn trim_segment_end(&mut self, segment_id: SegmentId, new_end_tl: i64) -> Result<()> {
         let index = self
             .timeline
@@ -361,115 +1355,602 @@ impl Project {
         } else {
             i64::MAX
         };
-        let clamped_end = new_end_tl.clamp(old_start + 1, next_start);
+        let clamped_end = self.snap_tl(new_end_tl).clamp(old_start + 1, next_start);
         let delta_tl = clamped_end - old_end;
 
         let segment = &mut self.timeline.segments[index];
         segment.timeline_duration = clamped_end - old_start;
         segment.src_out_video = shift_stream_point(segment.src_out_video, delta_tl, video_tb);
         segment.src_out_audio = shift_stream_point(segment.src_out_audio, delta_tl, audio_tb);
+        self.changes.segment_touched(segment_id);
         Ok(())
     }
 
-    fn asset_by_id(&self, asset_id: AssetId) -> Result<&MediaAsset> {
-        self.assets
-            .iter()
-            .find(|asset| asset.id == asset_id)
-            .ok_or(EngineError::MissingAsset { asset_id })
+    /// Sets the in mark, clamped to the timeline and snapped when frame
+    /// snapping is on.
+    ///
+    /// An out mark at or before the new in mark is cleared.
+    pub fn set_in_point(&mut self, at_tl: i64) {
+        let at_tl = self.snap_tl(at_tl).clamp(0, self.duration_tl());
+        if self.marks.out_tl.is_some_and(|out_tl| out_tl <= at_tl) {
+            self.marks.out_tl = None;
+        }
+        self.marks.in_tl = Some(at_tl);
     }
 
-    fn validate_for_persistence(&self) -> Result<()> {
-        let mut seen_asset_ids = HashSet::new();
-        for asset in &self.assets {
-            if !seen_asset_ids.insert(asset.id) {
-                return Err(EngineError::InvalidProjectFile {
-                    reason: format!("duplicate asset id {}", asset.id),
-                });
-            }
+    /// Sets the exclusive out mark, clamped to the timeline and snapped when
+    /// frame snapping is on.
+    ///
+    /// An in mark at or after the new out mark is cleared.
+    pub fn set_out_point(&mut self, at_tl: i64) {
+        let at_tl = self.snap_tl(at_tl).clamp(0, self.duration_tl());
+        if self.marks.in_tl.is_some_and(|in_tl| in_tl >= at_tl) {
+            self.marks.in_tl = None;
+        }
+        self.marks.out_tl = Some(at_tl);
+    }
 
-            if asset.video.is_some() && asset.video_stream_index.is_none() {
-                return Err(EngineError::InvalidProjectFile {
-                    reason: format!("asset {} is missing video stream index", asset.id),
-                });
-            }
-            if asset.video.is_none() && asset.video_stream_index.is_some() {
-                return Err(EngineError::InvalidProjectFile {
-                    reason: format!(
-                        "asset {} has video stream index without video stream",
-                        asset.id
-                    ),
-                });
-            }
+    /// Clears both in and out marks.
+    pub fn clear_in_out(&mut self) {
+        self.marks = TimelineMarks::default();
+    }
 
-            if asset.audio.is_some() && asset.audio_stream_index.is_none() {
-                return Err(EngineError::InvalidProjectFile {
-                    reason: format!("asset {} is missing audio stream index", asset.id),
-                });
-            }
-            if asset.audio.is_none() && asset.audio_stream_index.is_some() {
-                return Err(EngineError::InvalidProjectFile {
-                    reason: format!(
-                        "asset {} has audio stream index without audio stream",
-                        asset.id
-                    ),
-                });
-            }
+    /// Resolves the marked range as `(start, end)` timeline ticks.
+    ///
+    /// Returns `None` when no mark is set or the resolved range is empty. Marks
+    /// past the current timeline end are clamped to it.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut project = /* construct project */;
+    /// project.set_in_point(250_000);
+    /// assert_eq!(project.in_out_range(), Some((250_000, project.duration_tl())));
+    /// ```
+    pub fn in_out_range(&self) -> Option<(i64, i64)> {
+        if self.marks.is_empty() {
+            return None;
         }
 
-        let mut previous_end: Option<i64> = None;
-        let mut seen_segment_ids = HashSet::new();
-        for segment in &self.timeline.segments {
-            if !seen_segment_ids.insert(segment.id) {
-                return Err(EngineError::InvalidProjectFile {
-                    reason: format!("duplicate segment id {}", segment.id),
-                });
-            }
+        let duration_tl = self.duration_tl();
+        let start_tl = self.marks.in_tl.unwrap_or(0).clamp(0, duration_tl);
+        let end_tl = self
+            .marks
+            .out_tl
+            .unwrap_or(duration_tl)
+            .clamp(0, duration_tl);
+        (start_tl < end_tl).then_some((start_tl, end_tl))
+    }
 
-            if segment.timeline_start < 0 {
-                return Err(EngineError::InvalidProjectFile {
-                    reason: format!(
-                        "segment {} starts at negative timeline {}",
-                        segment.id, segment.timeline_start
-                    ),
-                });
-            }
-            if segment.timeline_duration <= 0 {
-                return Err(EngineError::InvalidProjectFile {
-                    reason: format!(
-                        "segment {} has non-positive duration {}",
-                        segment.id, segment.timeline_duration
-                    ),
-                });
-            }
+    /// Removes everything inside `[start_tl, end_tl)`.
+    ///
+    /// Segments crossing a range edge are split first, using ids from
+    /// `allocate_segment_id`. With `ripple`, later segments shift left to close
+    /// the gap and the in/out marks are cleared; otherwise a gap is left behind.
+    /// Both edges are snapped when frame snapping is on.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut project = /* construct project */;
+    /// let mut next_id = 10;
+    /// project
+    ///     .delete_range(250_000, 500_000, true, || {
+    ///         next_id += 1;
+    ///         next_id
+    ///     })
+    ///     .unwrap();
+    /// ```
+    pub fn delete_range(
+        &mut self,
+        start_tl: i64,
+        end_tl: i64,
+        ripple: bool,
+        allocate_segment_id: impl FnMut() -> SegmentId,
+    ) -> Result<()> {
+        let (start_tl, end_tl) = (self.snap_tl(start_tl), self.snap_tl(end_tl));
+        self.delete_range_at(start_tl, end_tl, ripple, allocate_segment_id)
+    }
 
-            let asset = self.asset_by_id(segment.asset_id)?;
-            validate_segment_ranges(asset, segment)?;
+    /// Removes exactly `[start_tl, end_tl)`; see [`Project::delete_range`].
+    fn delete_range_at(
+        &mut self,
+        start_tl: i64,
+        end_tl: i64,
+        ripple: bool,
+        mut allocate_segment_id: impl FnMut() -> SegmentId,
+    ) -> Result<()> {
+        if start_tl >= end_tl {
+            return Err(EngineError::EmptyRange { start_tl, end_tl });
+        }
 
-            let segment_end = segment
-                .timeline_start
-                .checked_add(segment.timeline_duration)
-                .ok_or_else(|| EngineError::InvalidProjectFile {
-                    reason: format!("segment {} timeline range overflowed i64", segment.id),
-                })?;
-            if let Some(previous_end) = previous_end
-                && segment.timeline_start < previous_end
-            {
-                return Err(EngineError::InvalidProjectFile {
-                    reason: format!(
-                        "segment {} overlaps previous segment at {}",
-                        segment.id, segment.timeline_start
-                    ),
-                });
+        self.split_inside_segment(start_tl, &mut allocate_segment_id)?;
+        self.split_inside_segment(end_tl, &mut allocate_segment_id)?;
+        let changes = &mut self.changes;
+        self.timeline.segments.retain(|segment| {
+            let keep = segment.timeline_start < start_tl
+                || segment.timeline_start + segment.timeline_duration > end_tl;
+            if !keep {
+                changes.segment_touched(segment.id);
             }
-            previous_end = Some(segment_end);
-        }
+            keep
+        });
 
+        if ripple {
+            let removed_tl = end_tl - start_tl;
+            for segment in &mut self.timeline.segments {
+                if segment.timeline_start >= end_tl {
+                    segment.timeline_start -= removed_tl;
+                    self.changes.segment_touched(segment.id);
+                }
+            }
+            self.marks = TimelineMarks::default();
+        }
         Ok(())
     }
-}
 
-fn shift_stream_point(
-    point: Option<i64>,
+    /// Returns a copy of the project holding only `[start_tl, end_tl)`.
+    ///
+    /// The clipped timeline starts at zero and carries no in/out marks. Used to
+    /// export a marked range through the regular export planner.
+    pub fn clipped_to_range(&self, start_tl: i64, end_tl: i64) -> Result<Self> {
+        if start_tl >= end_tl {
+            return Err(EngineError::EmptyRange { start_tl, end_tl });
+        }
+
+        let mut clipped = self.clone();
+        let mut next_segment_id = clipped
+            .timeline
+            .segments
+            .iter()
+            .map(|segment| segment.id)
+            .max()
+            .unwrap_or(0);
+        let mut allocate_segment_id = || {
+            next_segment_id += 1;
+            next_segment_id
+        };
+        clipped.split_inside_segment(start_tl, &mut allocate_segment_id)?;
+        clipped.split_inside_segment(end_tl, &mut allocate_segment_id)?;
+        clipped.timeline.segments.retain(|segment| {
+            segment.timeline_start >= start_tl
+                && segment.timeline_start + segment.timeline_duration <= end_tl
+        });
+        if clipped.timeline.segments.is_empty() {
+            return Err(EngineError::EmptyRange { start_tl, end_tl });
+        }
+
+        for segment in &mut clipped.timeline.segments {
+            segment.timeline_start -= start_tl;
+        }
+        clipped.marks = TimelineMarks::default();
+        Ok(clipped)
+    }
+
+    /// Selects `segment_ids`, replacing the selection unless `extend` is set.
+    ///
+    /// Returns `SegmentIdNotFound` for an unknown id and leaves the selection
+    /// unchanged in that case.
+    pub fn select(&mut self, segment_ids: &[SegmentId], extend: bool) -> Result<()> {
+        if let Some(&segment_id) = segment_ids
+            .iter()
+            .find(|id| self.timeline.find_segment_index_by_id(**id).is_none())
+        {
+            return Err(EngineError::SegmentIdNotFound { segment_id });
+        }
+        if !extend {
+            self.selection.clear();
+        }
+        self.selection.extend(segment_ids.iter().copied());
+        Ok(())
+    }
+
+    /// Adds `segment_id` to the selection, or removes it when already
+    /// selected.
+    ///
+    /// Returns `SegmentIdNotFound` for an unknown id.
+    pub fn toggle_selection(&mut self, segment_id: SegmentId) -> Result<()> {
+        if self.timeline.find_segment_index_by_id(segment_id).is_none() {
+            return Err(EngineError::SegmentIdNotFound { segment_id });
+        }
+        if !self.selection.remove(&segment_id) {
+            self.selection.insert(segment_id);
+        }
+        Ok(())
+    }
+
+    /// Selects every segment overlapping `[start_tl, end_tl)`.
+    ///
+    /// The bounds may be given in either order; the selection is replaced
+    /// unless `extend` is set.
+    pub fn select_range(&mut self, start_tl: i64, end_tl: i64, extend: bool) {
+        let (start_tl, end_tl) = (start_tl.min(end_tl), start_tl.max(end_tl));
+        if !extend {
+            self.selection.clear();
+        }
+        self.selection.extend(
+            self.timeline
+                .segments
+                .iter()
+                .filter(|segment| {
+                    segment.timeline_start < end_tl.max(start_tl + 1)
+                        && segment.timeline_start + segment.timeline_duration > start_tl
+                })
+                .map(|segment| segment.id),
+        );
+    }
+
+    /// Selects every segment on the timeline.
+    pub fn select_all(&mut self) {
+        self.selection = self
+            .timeline
+            .segments
+            .iter()
+            .map(|segment| segment.id)
+            .collect();
+    }
+
+    /// Drops selected ids whose segments no longer exist.
+    pub(crate) fn prune_selection(&mut self) {
+        let timeline = &self.timeline;
+        self.selection
+            .retain(|id| timeline.find_segment_index_by_id(*id).is_some());
+    }
+
+    /// Removes the selected segments.
+    ///
+    /// Without `ripple` gaps are left behind; with it, every later segment
+    /// shifts left by the duration removed before it. Returns `SelectionEmpty`
+    /// when nothing is selected.
+    pub fn delete_selection(&mut self, ripple: bool) -> Result<()> {
+        if self.selection.is_empty() {
+            return Err(EngineError::SelectionEmpty);
+        }
+
+        let mut removed_tl = 0;
+        let selection = std::mem::take(&mut self.selection);
+        let changes = &mut self.changes;
+        self.timeline.segments.retain_mut(|segment| {
+            if selection.contains(&segment.id) {
+                removed_tl += segment.timeline_duration;
+                changes.segment_touched(segment.id);
+                return false;
+            }
+            if ripple && removed_tl != 0 {
+                segment.timeline_start -= removed_tl;
+                changes.segment_touched(segment.id);
+            }
+            true
+        });
+        Ok(())
+    }
+
+    /// Moves every selected segment by `delta_tl`.
+    ///
+    /// The delta is clamped as a whole so selected segments keep their spacing
+    /// and never overlap unselected neighbours or start before zero. With frame
+    /// snapping on, the delta lands the first selected segment on a frame
+    /// start. Returns the applied delta, or `SelectionEmpty` when nothing is
+    /// selected.
+    pub fn move_selection(&mut self, delta_tl: i64) -> Result<i64> {
+        if self.selection.is_empty() {
+            return Err(EngineError::SelectionEmpty);
+        }
+
+        let segments = &self.timeline.segments;
+        let delta_tl = match segments
+            .iter()
+            .find(|segment| self.selection.contains(&segment.id))
+        {
+            Some(first) => self.snap_tl(first.timeline_start + delta_tl) - first.timeline_start,
+            None => delta_tl,
+        };
+        let mut min_delta = i64::MIN;
+        let mut max_delta = i64::MAX;
+        let mut previous_unselected_end = 0;
+        for segment in segments {
+            let end = segment.timeline_start + segment.timeline_duration;
+            if self.selection.contains(&segment.id) {
+                min_delta = min_delta.max(previous_unselected_end - segment.timeline_start);
+            } else {
+                previous_unselected_end = end;
+            }
+        }
+        let mut next_unselected_start = i64::MAX;
+        for segment in segments.iter().rev() {
+            let end = segment.timeline_start + segment.timeline_duration;
+            if self.selection.contains(&segment.id) {
+                max_delta = max_delta.min(next_unselected_start.saturating_sub(end));
+            } else {
+                next_unselected_start = segment.timeline_start;
+            }
+        }
+        let applied = delta_tl.clamp(min_delta.min(0), max_delta.max(0));
+
+        for segment in &mut self.timeline.segments {
+            if self.selection.contains(&segment.id) {
+                segment.timeline_start += applied;
+                self.changes.segment_touched(segment.id);
+            }
+        }
+        Ok(applied)
+    }
+
+    /// Enables or disables every selected segment.
+    ///
+    /// Returns `SelectionEmpty` when nothing is selected.
+    pub fn set_selection_enabled(&mut self, enabled: bool) -> Result<()> {
+        if self.selection.is_empty() {
+            return Err(EngineError::SelectionEmpty);
+        }
+        for segment in &mut self.timeline.segments {
+            if self.selection.contains(&segment.id) {
+                segment.enabled = enabled;
+                self.changes.segment_touched(segment.id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Clones the selected segments for the clipboard.
+    ///
+    /// The copies keep their source ranges, flags and spacing, shifted so the
+    /// earliest starts at zero. Returns `SelectionEmpty` when nothing is
+    /// selected.
+    pub fn copy_selection(&self) -> Result<Vec<Segment>> {
+        let mut copied: Vec<Segment> = self
+            .timeline
+            .segments
+            .iter()
+            .filter(|segment| self.selection.contains(&segment.id))
+            .cloned()
+            .collect();
+        let Some(origin_tl) = copied.first().map(|segment| segment.timeline_start) else {
+            return Err(EngineError::SelectionEmpty);
+        };
+        for segment in &mut copied {
+            segment.timeline_start -= origin_tl;
+        }
+        Ok(copied)
+    }
+
+    /// Places copied `segments` at `at_tl` with ids from `allocate_segment_id`.
+    ///
+    /// `Insert` splits the segment under `at_tl` and shifts everything after
+    /// it right by the pasted span; `Overwrite` clears the span first and
+    /// leaves the rest of the timeline in place. The pasted segments become
+    /// the selection. Returns the end of the pasted span. `at_tl` is snapped
+    /// when frame snapping is on.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut project = /* construct project */;
+    /// project.select_all();
+    /// let copied = project.copy_selection().unwrap();
+    /// let mut next_id = 10;
+    /// let end_tl = project
+    ///     .paste_segments(250_000, &copied, PasteMode::Insert, || {
+    ///         next_id += 1;
+    ///         next_id
+    ///     })
+    ///     .unwrap();
+    /// ```
+    pub fn paste_segments(
+        &mut self,
+        at_tl: i64,
+        segments: &[Segment],
+        mode: PasteMode,
+        allocate_segment_id: impl FnMut() -> SegmentId,
+    ) -> Result<i64> {
+        self.paste_segments_at(self.snap_tl(at_tl), segments, mode, allocate_segment_id)
+    }
+
+    /// Pastes at exactly `at_tl`; see [`Project::paste_segments`].
+    fn paste_segments_at(
+        &mut self,
+        at_tl: i64,
+        segments: &[Segment],
+        mode: PasteMode,
+        mut allocate_segment_id: impl FnMut() -> SegmentId,
+    ) -> Result<i64> {
+        for segment in segments {
+            self.asset_by_id(segment.asset_id)?;
+        }
+        let span_tl = segments
+            .iter()
+            .map(|segment| segment.timeline_start + segment.timeline_duration)
+            .max()
+            .ok_or(EngineError::ClipboardEmpty)?;
+        let at_tl = at_tl.clamp(0, self.duration_tl());
+        let end_tl = at_tl + span_tl;
+
+        match mode {
+            PasteMode::Insert => {
+                self.split_inside_segment(at_tl, &mut allocate_segment_id)?;
+                for segment in &mut self.timeline.segments {
+                    if segment.timeline_start >= at_tl {
+                        segment.timeline_start += span_tl;
+                        self.changes.segment_touched(segment.id);
+                    }
+                }
+            }
+            PasteMode::Overwrite => {
+                self.delete_range_at(at_tl, end_tl, false, &mut allocate_segment_id)?;
+            }
+        }
+
+        self.selection.clear();
+        for segment in segments {
+            let id = allocate_segment_id();
+            self.changes.segment_added(id);
+            self.timeline.segments.push(Segment {
+                id,
+                timeline_start: at_tl + segment.timeline_start,
+                ..segment.clone()
+            });
+            self.selection.insert(id);
+        }
+        self.timeline
+            .segments
+            .sort_by_key(|segment| segment.timeline_start);
+        Ok(end_tl)
+    }
+
+    /// Inserts a copy of `segment_id` right after it, shifting later segments
+    /// right by its duration. The copy takes `new_segment_id` and becomes the
+    /// selection.
+    pub fn duplicate_segment(
+        &mut self,
+        segment_id: SegmentId,
+        new_segment_id: SegmentId,
+    ) -> Result<()> {
+        let index = self
+            .timeline
+            .find_segment_index_by_id(segment_id)
+            .ok_or(EngineError::SegmentIdNotFound { segment_id })?;
+        let original = &self.timeline.segments[index];
+        let end_tl = original.timeline_start + original.timeline_duration;
+        let copy = Segment {
+            id: new_segment_id,
+            timeline_start: end_tl,
+            ..original.clone()
+        };
+
+        for segment in &mut self.timeline.segments[index + 1..] {
+            if segment.timeline_start >= end_tl {
+                segment.timeline_start += copy.timeline_duration;
+                self.changes.segment_touched(segment.id);
+            }
+        }
+        self.changes.segment_added(new_segment_id);
+        self.timeline.segments.insert(index + 1, copy);
+        self.selection = BTreeSet::from([new_segment_id]);
+        Ok(())
+    }
+
+    /// Splits the segment containing `at_tl` unless `at_tl` is already a boundary.
+    fn split_inside_segment(
+        &mut self,
+        at_tl: i64,
+        allocate_segment_id: &mut impl FnMut() -> SegmentId,
+    ) -> Result<()> {
+        let Some(index) = self.timeline.find_segment_index(at_tl) else {
+            return Ok(());
+        };
+        if self.timeline.segments[index].timeline_start == at_tl {
+            return Ok(());
+        }
+        self.split_at(at_tl, allocate_segment_id())
+    }
+
+    pub(crate) fn asset_by_id(&self, asset_id: AssetId) -> Result<&MediaAsset> {
+        self.assets
+            .iter()
+            .find(|asset| asset.id == asset_id)
+            .ok_or(EngineError::MissingAsset { asset_id })
+    }
+
+    /// Looks up an asset for changing it; the change goes into the next delta.
+    fn asset_by_id_mut(&mut self, asset_id: AssetId) -> Result<&mut MediaAsset> {
+        let asset = self
+            .assets
+            .iter_mut()
+            .find(|asset| asset.id == asset_id)
+            .ok_or(EngineError::MissingAsset { asset_id })?;
+        self.changes.asset_touched(asset_id);
+        Ok(asset)
+    }
+
+    fn validate_for_persistence(&self) -> Result<()> {
+        let mut seen_asset_ids = HashSet::new();
+        for asset in &self.assets {
+            if !seen_asset_ids.insert(asset.id) {
+                return Err(EngineError::InvalidProjectFile {
+                    reason: format!("duplicate asset id {}", asset.id),
+                });
+            }
+
+            if asset.video.is_some() && asset.video_stream_index.is_none() {
+                return Err(EngineError::InvalidProjectFile {
+                    reason: format!("asset {} is missing video stream index", asset.id),
+                });
+            }
+            if asset.video.is_none() && asset.video_stream_index.is_some() {
+                return Err(EngineError::InvalidProjectFile {
+                    reason: format!(
+                        "asset {} has video stream index without video stream",
+                        asset.id
+                    ),
+                });
+            }
+
+            if asset.audio.is_some() && asset.audio_stream_index.is_none() {
+                return Err(EngineError::InvalidProjectFile {
+                    reason: format!("asset {} is missing audio stream index", asset.id),
+                });
+            }
+            if asset.audio.is_none() && asset.audio_stream_index.is_some() {
+                return Err(EngineError::InvalidProjectFile {
+                    reason: format!(
+                        "asset {} has audio stream index without audio stream",
+                        asset.id
+                    ),
+                });
+            }
+        }
+
+        let mut previous_end: Option<i64> = None;
+        let mut seen_segment_ids = HashSet::new();
+        for segment in &self.timeline.segments {
+            if !seen_segment_ids.insert(segment.id) {
+                return Err(EngineError::InvalidProjectFile {
+                    reason: format!("duplicate segment id {}", segment.id),
+                });
+            }
+
+            if segment.timeline_start < 0 {
+                return Err(EngineError::InvalidProjectFile {
+                    reason: format!(
+                        "segment {} starts at negative timeline {}",
+                        segment.id, segment.timeline_start
+                    ),
+                });
+            }
+            if segment.timeline_duration <= 0 {
+                return Err(EngineError::InvalidProjectFile {
+                    reason: format!(
+                        "segment {} has non-positive duration {}",
+                        segment.id, segment.timeline_duration
+                    ),
+                });
+            }
+
+            let asset = self.asset_by_id(segment.asset_id)?;
+            validate_segment_ranges(asset, segment)?;
+
+            let segment_end = segment
+                .timeline_start
+                .checked_add(segment.timeline_duration)
+                .ok_or_else(|| EngineError::InvalidProjectFile {
+                    reason: format!("segment {} timeline range overflowed i64", segment.id),
+                })?;
+            if let Some(previous_end) = previous_end
+                && segment.timeline_start < previous_end
+            {
+                return Err(EngineError::InvalidProjectFile {
+                    reason: format!(
+                        "segment {} overlaps previous segment at {}",
+                        segment.id, segment.timeline_start
+                    ),
+                });
+            }
+            previous_end = Some(segment_end);
+        }
+
+        Ok(())
+    }
+}
+
+fn shift_stream_point(
+    point: Option<i64>,
     delta_tl: i64,
     time_base: Option<Rational>,
 ) -> Option<i64> {
@@ -484,31 +1965,196 @@ fn shift_stream_point(
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 struct ProjectFile {
     schema_version: u32,
-    assets: Vec<MediaAsset>,
+    assets: Vec<AssetFileEntry>,
     segments: Vec<Segment>,
     #[serde(default)]
     settings: ProjectSettings,
+    #[serde(default, skip_serializing_if = "TimelineMarks::is_empty")]
+    marks: TimelineMarks,
+}
+
+/// One asset as written to a project file.
+///
+/// `path` is relative to the project file when the media lives under its
+/// directory; `absolute_path` then keeps the original location.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct AssetFileEntry {
+    #[serde(flatten)]
+    asset: MediaAsset,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    absolute_path: Option<PathBuf>,
+}
+
+impl AssetFileEntry {
+    fn from_asset(asset: &MediaAsset, project_dir: &Path, style: MediaPathStyle) -> Self {
+        let absolute = std::path::absolute(&asset.path).unwrap_or_else(|_| asset.path.clone());
+        let relative = match style {
+            MediaPathStyle::Relative => portable_relative_path(&absolute, project_dir),
+            MediaPathStyle::Absolute => None,
+        };
+        let (path, absolute_path) = match relative {
+            Some(relative) => (relative, Some(absolute)),
+            None => (absolute, None),
+        };
+        Self {
+            asset: MediaAsset {
+                path,
+                ..asset.clone()
+            },
+            absolute_path,
+        }
+    }
+
+    fn into_asset(self, project_dir: &Path) -> MediaAsset {
+        let mut asset = self.asset;
+        if asset.path.is_relative() {
+            let resolved = project_dir.join(&asset.path);
+            asset.path = match self.absolute_path {
+                Some(fallback) if !resolved.is_file() && fallback.is_file() => fallback,
+                _ => resolved,
+            };
+        }
+        asset
+    }
 }
 
 impl ProjectFile {
-    fn from_project(project: &Project) -> Self {
+    fn from_project(project: &Project, project_dir: &Path, style: MediaPathStyle) -> Self {
         Self {
             schema_version: PROJECT_FILE_SCHEMA_VERSION,
-            assets: project.assets.clone(),
+            assets: project
+                .assets
+                .iter()
+                .map(|asset| AssetFileEntry::from_asset(asset, project_dir, style))
+                .collect(),
             segments: project.timeline.segments.clone(),
             settings: project.settings.clone(),
+            marks: project.marks,
         }
     }
 
-    fn into_project(self) -> Project {
+    fn into_project(self, project_dir: &Path) -> Project {
         Project {
-            assets: self.assets,
+            assets: self
+                .assets
+                .into_iter()
+                .map(|entry| entry.into_asset(project_dir))
+                .collect(),
             timeline: Timeline {
                 segments: self.segments,
             },
             settings: self.settings,
+            marks: self.marks,
+            selection: BTreeSet::new(),
+            loop_playback: false,
+            changes: ChangeLog::default(),
+        }
+    }
+}
+
+/// Writes `contents` to a temporary sibling of `path`, syncs it and renames it
+/// over `path`, so readers never see a partially written file. The parent
+/// directory is synced after the rename so the rename itself survives a crash.
+///
+/// With `backups > 0` the previous file is first copied to `path.1`, shifting
+/// older backups up to `path.{backups}`. Interchange exports pass `0`.
+pub(crate) fn write_file_atomically(path: &Path, contents: &[u8], backups: u32) -> Result<()> {
+    let io_error = |context, source| EngineError::ProjectIo {
+        context,
+        path: path.to_path_buf(),
+        source,
+    };
+    let mut temp_name = path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_default();
+    temp_name.push(format!(".tmp-{}", std::process::id()));
+    let temp_path = path.with_file_name(temp_name);
+
+    let written = fs::File::create(&temp_path).and_then(|mut file| {
+        file.write_all(contents)?;
+        file.sync_all()
+    });
+    if let Err(source) = written {
+        let _ = fs::remove_file(&temp_path);
+        return Err(io_error("write temporary file", source));
+    }
+    if backups > 0
+        && path.is_file()
+        && let Err(source) = rotate_backups(path, backups)
+    {
+        let _ = fs::remove_file(&temp_path);
+        return Err(io_error("rotate backups", source));
+    }
+    fs::rename(&temp_path, path).map_err(|source| {
+        let _ = fs::remove_file(&temp_path);
+        io_error("replace file", source)
+    })?;
+    sync_parent_dir(path).map_err(|source| io_error("sync project directory", source))
+}
+
+/// Flushes the directory entry of `path` to disk.
+#[cfg(unix)]
+fn sync_parent_dir(path: &Path) -> std::io::Result<()> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::File::open(parent)?.sync_all()
+}
+
+/// Other platforms cannot open a directory to sync it; the rename is left to
+/// the file system.
+#[cfg(not(unix))]
+fn sync_parent_dir(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Shifts `path.1..path.{backups - 1}` up by one and copies `path` to
+/// `path.1`; the original stays in place until it is replaced.
+fn rotate_backups(path: &Path, backups: u32) -> std::io::Result<()> {
+    for index in (1..backups).rev() {
+        let older = backup_path(path, index);
+        if older.is_file() {
+            fs::rename(&older, backup_path(path, index + 1))?;
+        }
+    }
+    fs::copy(path, backup_path(path, 1)).map(|_| ())
+}
+
+fn backup_path(path: &Path, index: u32) -> PathBuf {
+    let mut name = path
+        .file_name()
+        .map(|name| name.to_os_string())
+        .unwrap_or_default();
+    name.push(format!(".{index}"));
+    path.with_file_name(name)
+}
+
+/// Returns the absolute directory containing the project file `path`.
+fn absolute_parent(path: &Path) -> Result<PathBuf> {
+    let absolute = std::path::absolute(path).map_err(|source| EngineError::ProjectIo {
+        context: "resolve project file path",
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(absolute
+        .parent()
+        .map_or_else(|| absolute.clone(), Path::to_path_buf))
+}
+
+/// Returns `path` relative to `dir` with `/` separators, when it lies under
+/// `dir`, so the file opens on other platforms too.
+fn portable_relative_path(path: &Path, dir: &Path) -> Option<PathBuf> {
+    let relative = path.strip_prefix(dir).ok()?;
+    let mut parts = Vec::new();
+    for component in relative.components() {
+        match component {
+            std::path::Component::Normal(part) => parts.push(part.to_str()?),
+            _ => return None,
         }
     }
+    (!parts.is_empty()).then(|| PathBuf::from(parts.join("/")))
 }
 
 fn validate_segment_ranges(asset: &MediaAsset, segment: &Segment) -> Result<()> {
@@ -557,6 +2203,39 @@ fn validate_audio_segment_range(asset: &MediaAsset, segment: &Segment) -> Result
     }
 }
 
+impl From<&MediaAsset> for MediaAssetSummary {
+    fn from(asset: &MediaAsset) -> Self {
+        Self {
+            id: asset.id,
+            path: asset.path.clone(),
+            has_video: asset.video.is_some(),
+            has_audio: asset.audio.is_some(),
+            duration_tl: asset.duration_tl,
+            source_in_tl: asset.source_marks.in_tl,
+            source_out_tl: asset.source_marks.out_tl,
+            offline: asset.offline,
+            frame_grid: asset.frame_grid(),
+            start_timecode: asset.video.and_then(|video| video.start_timecode),
+        }
+    }
+}
+
+impl From<&Segment> for SegmentSummary {
+    fn from(segment: &Segment) -> Self {
+        Self {
+            id: segment.id,
+            asset_id: segment.asset_id,
+            timeline_start: segment.timeline_start,
+            timeline_duration: segment.timeline_duration,
+            src_in_video: segment.src_in_video,
+            src_out_video: segment.src_out_video,
+            src_in_audio: segment.src_in_audio,
+            src_out_audio: segment.src_out_audio,
+            enabled: segment.enabled,
+        }
+    }
+}
+
 impl From<ProbedVideoStream> for VideoStreamInfo {
     fn from(value: ProbedVideoStream) -> Self {
         Self {
@@ -564,6 +2243,8 @@ impl From<ProbedVideoStream> for VideoStreamInfo {
             frame_rate: value.frame_rate,
             width: value.width,
             height: value.height,
+            start_pts: value.src_in,
+            start_timecode: value.start_timecode,
         }
     }
 }
@@ -574,6 +2255,7 @@ impl From<ProbedAudioStream> for AudioStreamInfo {
             time_base: value.time_base,
             sample_rate: value.sample_rate,
             channels: value.channels,
+            start_pts: value.src_in,
         }
     }
 }
@@ -595,21 +2277,29 @@ pub(crate) fn ensure_non_empty_duration(path: &Path, duration_tl: Option<i64>) -
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
     use std::fs;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
     use std::time::{SystemTime, UNIX_EPOCH};
 
     use super::{
-        AudioStreamInfo, MediaAsset, Project, ProjectExportSettings, ProjectSettings,
-        VideoStreamInfo, normalize_playhead,
+        AudioStreamInfo, MediaAsset, MediaPathStyle, PROJECT_BACKUP_COUNT, Project,
+        ProjectExportSettings, ProjectSettings, TimelineMarks, VideoStreamInfo, normalize_playhead,
     };
+    use crate::api::{EditPointDirection, PasteMode, ReplaceAlignment};
     use crate::error::EngineError;
-    use crate::time::Rational;
+    use crate::time::{FrameGrid, Rational, Timecode};
     use crate::timeline::{Segment, Timeline};
 
     #[test]
     fn project_persistence_roundtrip_restores_assets_segments_and_settings() {
-        let project = sample_project();
+        let mut project = sample_project();
+        project
+            .set_source_in_point(1, 250_000)
+            .expect("source in should succeed");
+        let media_path = temp_file_path("project-roundtrip-media", "mp4");
+        fs::write(&media_path, b"media").expect("write media file");
+        project.assets[0].path = media_path.clone();
         let path = temp_file_path("project-roundtrip", "json");
 
         project.save_to_file(&path).expect("save should succeed");
@@ -617,6 +2307,233 @@ mod tests {
 
         assert_eq!(loaded, project);
         fs::remove_file(path).expect("cleanup persisted file");
+        fs::remove_file(media_path).expect("cleanup media file");
+    }
+
+    #[test]
+    fn media_under_project_dir_is_saved_relative_and_survives_a_move() {
+        let root = temp_file_path("project-relative", "d");
+        let project_dir = root.join("show");
+        fs::create_dir_all(project_dir.join("media")).expect("create media dir");
+        let media_path = project_dir.join("media/clip.mp4");
+        fs::write(&media_path, b"media").expect("write media file");
+        let mut project = sample_project();
+        project.assets[0].path = media_path.clone();
+        let path = project_dir.join("cut.nle.json");
+
+        project.save_to_file(&path).expect("save should succeed");
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).expect("read project"))
+                .expect("valid json");
+        assert_eq!(json["assets"][0]["path"], "media/clip.mp4");
+        assert_eq!(
+            json["assets"][0]["absolute_path"],
+            media_path.to_str().expect("utf-8 path")
+        );
+
+        let moved_dir = root.join("moved");
+        fs::rename(&project_dir, &moved_dir).expect("move project folder");
+        let loaded =
+            Project::load_from_file(moved_dir.join("cut.nle.json")).expect("load should succeed");
+        assert_eq!(loaded.assets[0].path, moved_dir.join("media/clip.mp4"));
+        assert!(loaded.offline_asset_ids().is_empty());
+
+        fs::rename(&moved_dir, &project_dir).expect("restore project folder");
+        let copied_path = root.join("cut-copy.nle.json");
+        fs::copy(&path, &copied_path).expect("copy project file only");
+        let loaded = Project::load_from_file(&copied_path).expect("load should succeed");
+        assert_eq!(loaded.assets[0].path, media_path);
+        assert!(loaded.offline_asset_ids().is_empty());
+        fs::remove_dir_all(root).expect("cleanup project dirs");
+    }
+
+    #[test]
+    fn save_as_rewrites_paths_for_the_new_location_or_as_absolute() {
+        let root = temp_file_path("project-save-as", "d");
+        fs::create_dir_all(root.join("media")).expect("create media dir");
+        let media_path = root.join("media/clip.mp4");
+        fs::write(&media_path, b"media").expect("write media file");
+        let mut project = sample_project();
+        project.assets[0].path = media_path.clone();
+        let stored_path = |path: &Path| {
+            let json: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(path).expect("read project"))
+                    .expect("valid json");
+            (
+                json["assets"][0]["path"].clone(),
+                json["assets"][0].get("absolute_path").cloned(),
+            )
+        };
+
+        let nested = root.join("media/cut.nle.json");
+        project.save_to_file(&nested).expect("save should succeed");
+        assert_eq!(stored_path(&nested).0, "clip.mp4");
+
+        let absolute = root.join("cut.nle.json");
+        project
+            .save_to_file_with(&absolute, MediaPathStyle::Absolute)
+            .expect("save should succeed");
+        assert_eq!(
+            stored_path(&absolute),
+            (
+                serde_json::Value::from(media_path.to_str().expect("utf-8 path")),
+                None
+            )
+        );
+        let loaded = Project::load_from_file(&absolute).expect("load should succeed");
+        assert_eq!(loaded.assets[0].path, media_path);
+        fs::remove_dir_all(root).expect("cleanup project dirs");
+    }
+
+    #[test]
+    fn saves_keep_rotating_backups_of_previous_versions() {
+        let root = temp_file_path("project-backups", "d");
+        fs::create_dir_all(&root).expect("create project dir");
+        let path = root.join("cut.nle.json");
+        let backup = |index: u32| root.join(format!("cut.nle.json.{index}"));
+        let mut project = sample_project();
+
+        project
+            .save_to_file(&path)
+            .expect("first save should succeed");
+        assert!(!backup(1).exists());
+        for duration in 1..=PROJECT_BACKUP_COUNT + 1 {
+            let previous = fs::read_to_string(&path).expect("read project");
+            project.timeline.segments[0].timeline_duration = i64::from(duration) * 100_000;
+            project.save_to_file(&path).expect("save should succeed");
+            assert_eq!(
+                fs::read_to_string(backup(1)).expect("read newest backup"),
+                previous
+            );
+        }
+
+        assert!(backup(PROJECT_BACKUP_COUNT).exists());
+        assert!(!backup(PROJECT_BACKUP_COUNT + 1).exists());
+        let entries = fs::read_dir(&root).expect("list project dir").count();
+        assert_eq!(
+            entries,
+            1 + PROJECT_BACKUP_COUNT as usize,
+            "no temp files left"
+        );
+        fs::remove_dir_all(root).expect("cleanup project dir");
+    }
+
+    #[test]
+    fn newest_valid_backup_skips_damaged_backups() {
+        let root = temp_file_path("project-backup-fallback", "d");
+        fs::create_dir_all(&root).expect("create project dir");
+        let path = root.join("cut.nle.json");
+        let project = sample_project();
+        project.save_to_file(&path).expect("save should succeed");
+        project.save_to_file(&path).expect("save should succeed");
+        project.save_to_file(&path).expect("save should succeed");
+        assert_eq!(
+            Project::newest_valid_backup(&path),
+            Some(root.join("cut.nle.json.1"))
+        );
+
+        fs::write(
+            root.join("cut.nle.json.1"),
+            b"{ \"schema_version\": 2, \"assets\"",
+        )
+        .expect("truncate newest backup");
+        fs::write(&path, b"").expect("damage project file");
+        assert!(matches!(
+            Project::load_from_file(&path),
+            Err(EngineError::ProjectSerialization { .. })
+        ));
+        assert_eq!(
+            Project::newest_valid_backup(&path),
+            Some(root.join("cut.nle.json.2"))
+        );
+        fs::remove_dir_all(root).expect("cleanup project dir");
+    }
+
+    #[test]
+    fn load_reports_missing_media_as_offline_instead_of_failing() {
+        let project = sample_project();
+        let path = temp_file_path("project-offline", "json");
+
+        project.save_to_file(&path).expect("save should succeed");
+        let loaded = Project::load_from_file(&path).expect("load should succeed");
+
+        assert_eq!(loaded.offline_asset_ids(), vec![1]);
+        assert!(matches!(
+            loaded.preview_request_at(0),
+            Err(EngineError::AssetOffline { asset_id: 1, .. })
+        ));
+        assert!(matches!(
+            crate::export::build_video_export_plan(&loaded, PathBuf::from("out.mp4")),
+            Err(EngineError::AssetOffline { asset_id: 1, .. })
+        ));
+        fs::remove_file(path).expect("cleanup persisted file");
+    }
+
+    #[test]
+    fn relink_accepts_only_media_with_matching_streams() {
+        let mut project = sample_project();
+        project.assets[0].offline = true;
+        let moved = MediaAsset {
+            path: PathBuf::from("moved/demo.mp4"),
+            file_size: Some(42),
+            offline: false,
+            ..project.assets[0].clone()
+        };
+
+        let mut resized = moved.clone();
+        resized.video.as_mut().expect("video").width = 1280;
+        assert!(matches!(
+            project.relink_asset(1, resized),
+            Err(EngineError::IncompatibleMedia { asset_id: 1, .. })
+        ));
+        let mut retimed = moved.clone();
+        retimed.audio.as_mut().expect("audio").time_base =
+            Rational::new(1, 44_100).expect("valid rational");
+        assert!(project.relink_asset(1, retimed).is_err());
+        let mut shorter = moved.clone();
+        shorter.duration_tl -= 1;
+        assert!(project.relink_asset(1, shorter).is_err());
+        assert_eq!(project.offline_asset_ids(), vec![1]);
+
+        project
+            .relink_asset(1, moved)
+            .expect("matching media should relink");
+        assert_eq!(project.assets[0].path, PathBuf::from("moved/demo.mp4"));
+        assert_eq!(project.assets[0].file_size, Some(42));
+        assert!(project.offline_asset_ids().is_empty());
+    }
+
+    #[test]
+    fn relink_candidates_match_file_name_and_recorded_size() {
+        let dir = temp_file_path("relink-search", "d");
+        fs::create_dir_all(dir.join("a")).expect("create dir a");
+        fs::create_dir_all(dir.join("b/nested")).expect("create dir b");
+        fs::write(dir.join("a/demo.mp4"), b"abc").expect("write wrong size");
+        fs::write(dir.join("b/nested/demo.mp4"), b"abcd").expect("write match");
+        fs::write(dir.join("b/other.mp4"), b"abcd").expect("write other name");
+        let mut project = sample_project();
+        project.assets[0].file_size = Some(4);
+
+        assert!(
+            project
+                .find_relink_candidates(&dir)
+                .expect("search should succeed")
+                .is_empty(),
+            "online assets are not relinked"
+        );
+        project.assets[0].offline = true;
+        assert_eq!(
+            project
+                .find_relink_candidates(&dir)
+                .expect("search should succeed"),
+            vec![(1, dir.join("b/nested/demo.mp4"))]
+        );
+        assert!(
+            project
+                .find_relink_candidates(&dir.join("missing"))
+                .is_err()
+        );
+        fs::remove_dir_all(dir).expect("cleanup search dir");
     }
 
     #[test]
@@ -643,6 +2560,33 @@ mod tests {
         fs::remove_file(path).expect("cleanup persisted file");
     }
 
+    #[test]
+    fn project_persistence_keeps_disabled_segments_and_defaults_to_enabled() {
+        let mut project = sample_project();
+        project
+            .set_segment_enabled(1, false)
+            .expect("disable should succeed");
+        let path = temp_file_path("project-disabled-segment", "json");
+
+        project.save_to_file(&path).expect("save should succeed");
+        let loaded = Project::load_from_file(&path).expect("load should succeed");
+        assert!(!loaded.timeline.segments[0].enabled);
+        assert_eq!(loaded.disabled_frame_size_at(10), Some((1920, 1080)));
+
+        let text = fs::read_to_string(&path).expect("persisted json must be readable");
+        let mut json: serde_json::Value =
+            serde_json::from_str(&text).expect("json should be valid");
+        json["segments"][0]
+            .as_object_mut()
+            .expect("segment is an object")
+            .remove("enabled");
+        fs::write(&path, json.to_string()).expect("rewrite project without flag");
+        let loaded = Project::load_from_file(&path).expect("load should succeed");
+        assert!(loaded.timeline.segments[0].enabled);
+        assert_eq!(loaded.disabled_frame_size_at(10), None);
+        fs::remove_file(path).expect("cleanup persisted file");
+    }
+
     #[test]
     fn normalize_playhead_returns_zero_for_empty_project_duration() {
         assert_eq!(normalize_playhead(10, 0), 0);
@@ -713,6 +2657,7 @@ mod tests {
             src_out_audio: Some(105_600),
             timeline_start: 1_500_000,
             timeline_duration: 200_000,
+            enabled: true,
         });
         let path = temp_file_path("project-gap-segments", "json");
 
@@ -736,6 +2681,7 @@ mod tests {
             src_out_audio: Some(105_601),
             timeline_start: 1_200_000,
             timeline_duration: 1,
+            enabled: true,
         });
 
         let result = project.save_to_file(temp_file_path("duplicate-segment-id", "json"));
@@ -745,6 +2691,70 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn v1_fixtures_load_through_migration() {
+        let load = |text: &str| {
+            let path = temp_file_path("project-v1-fixture", "json");
+            fs::write(&path, text).expect("write v1 fixture");
+            let (project, migrations) =
+                Project::load_from_file_with_migrations(&path).expect("v1 should load");
+            assert_eq!(
+                migrations
+                    .iter()
+                    .map(|step| (step.from_version, step.to_version))
+                    .collect::<Vec<_>>(),
+                vec![(1, 2)]
+            );
+
+            project.save_to_file(&path).expect("resave should succeed");
+            let (resaved, migrations) =
+                Project::load_from_file_with_migrations(&path).expect("v2 should load");
+            assert!(migrations.is_empty());
+            assert_eq!(resaved, project);
+            fs::remove_file(path).expect("cleanup persisted file");
+            project
+        };
+
+        let first_release = load(include_str!("../tests/fixtures/project_v1.json"));
+        assert_eq!(first_release.assets.len(), 2);
+        assert_eq!(first_release.offline_asset_ids(), vec![1, 2]);
+        assert_eq!(first_release.assets[0].video.expect("video").start_pts, 0);
+        assert!(
+            first_release
+                .timeline
+                .segments
+                .iter()
+                .all(|segment| segment.enabled)
+        );
+        assert_eq!(first_release.marks, TimelineMarks::default());
+
+        let late = load(include_str!("../tests/fixtures/project_v1_late.json"));
+        assert_eq!(late.assets[0].video.expect("video").start_pts, 9_000);
+        assert_eq!(late.assets[0].source_marks.in_tl, Some(200_000));
+        assert_eq!(late.assets[0].file_size, Some(1_048_576));
+        assert!(!late.timeline.segments[1].enabled);
+        assert_eq!(late.marks.in_tl, Some(100_000));
+    }
+
+    #[test]
+    fn load_project_refuses_files_from_a_newer_build() {
+        let path = temp_file_path("project-too-new", "json");
+        fs::write(
+            &path,
+            r#"{ "schema_version": 99, "assets": [], "segments": [] }"#,
+        )
+        .expect("write newer project");
+
+        assert!(matches!(
+            Project::load_from_file(&path),
+            Err(EngineError::ProjectFileTooNew {
+                version: 99,
+                supported: 2
+            })
+        ));
+        fs::remove_file(path).expect("cleanup persisted file");
+    }
+
     #[test]
     fn load_project_rejects_invalid_rational_in_json() {
         let path = temp_file_path("invalid-rational-project", "json");
@@ -799,6 +2809,775 @@ mod tests {
         fs::remove_file(path).expect("cleanup persisted file");
     }
 
+    #[test]
+    fn step_frames_follows_asset_frame_rate_without_drift() {
+        let project = sample_project();
+
+        assert_eq!(project.step_frames(0, 1), 33_367);
+        assert_eq!(project.step_frames(0, 30), 1_001_000);
+        assert_eq!(project.step_frames(1_001_000, -30), 0);
+        assert_eq!(project.step_frames(40_000, -1), 33_367);
+        assert_eq!(project.step_frames(1_190_000, 5), 1_199_999);
+    }
+
+    #[test]
+    fn source_and_record_timecode_follow_the_start_timecode_and_its_count() {
+        let mut project = sample_project();
+        assert_eq!(project.timecode_at(1_001_000).to_string(), "00:00:01:00");
+
+        project.assets[0]
+            .video
+            .as_mut()
+            .expect("video")
+            .start_timecode = Some(Timecode::parse("00:59:59;28").expect("valid"));
+        let asset = &project.assets[0];
+        assert_eq!(
+            asset
+                .source_timecode_at(0)
+                .expect("valid start")
+                .to_string(),
+            "00:59:59;28"
+        );
+        assert_eq!(
+            asset
+                .source_timecode_at(66_734)
+                .expect("valid start")
+                .to_string(),
+            "01:00:00;00"
+        );
+        assert_eq!(
+            asset
+                .source_tl_at_timecode(Timecode::parse("01:00:00;00").expect("valid"))
+                .expect("valid timecode"),
+            66_734
+        );
+        assert!(project.timecode_format().1);
+        assert_eq!(project.timecode_at(1_001_000).to_string(), "00:00:01;00");
+
+        let json = serde_json::to_string(asset).expect("serialize asset");
+        assert!(json.contains(r#""start_timecode":"00:59:59;28""#));
+        let restored: MediaAsset = serde_json::from_str(&json).expect("deserialize asset");
+        assert_eq!(restored, *asset);
+    }
+
+    #[test]
+    fn frame_snapping_lands_edit_points_on_the_project_grid() {
+        let mut project = sample_project();
+        assert_eq!(project.snap_tl(610_001), 610_001);
+        project.settings.snap_to_frames = true;
+
+        project.split(500_000, 2).expect("split should succeed");
+        assert_eq!(project.timeline.segments[1].timeline_start, 500_500);
+        assert_eq!(project.timeline.segments[0].src_out_video, Some(135_045));
+        assert_eq!(project.timeline.segments[1].src_in_video, Some(135_045));
+
+        project
+            .trim_segment_end(1, 400_000)
+            .expect("trim end should succeed");
+        assert_eq!(project.timeline.segments[0].timeline_duration, 400_400);
+        project
+            .trim_segment_start(2, 520_000)
+            .expect("trim start should succeed");
+        assert_eq!(project.timeline.segments[1].timeline_start, 533_867);
+        project
+            .move_segment(2, 610_000)
+            .expect("move should succeed");
+        assert_eq!(project.timeline.segments[1].timeline_start, 600_600);
+
+        project.settings.frame_rate = Some(Rational::new(25, 1).expect("valid rational"));
+        assert_eq!(
+            project.timecode_format(),
+            (
+                FrameGrid::from_frame_rate(Rational::new(25, 1).expect("valid rational")),
+                false
+            )
+        );
+        project.selection = BTreeSet::from([2]);
+        assert_eq!(project.move_selection(7_000).expect("move selection"), -600);
+        assert_eq!(project.timeline.segments[1].timeline_start, 600_000);
+
+        let json = serde_json::to_string(&project.settings).expect("serialize settings");
+        assert!(json.contains(r#""frame_rate":{"num":25,"den":1}"#));
+        assert!(json.contains(r#""snap_to_frames":true"#));
+        let restored: ProjectSettings = serde_json::from_str(&json).expect("deserialize settings");
+        assert_eq!(restored, project.settings);
+    }
+
+    #[test]
+    fn frame_snapping_keeps_range_deletes_pastes_and_marks_on_the_grid() {
+        let spans = |project: &Project| -> Vec<(i64, i64)> {
+            project
+                .timeline
+                .segments
+                .iter()
+                .map(|segment| (segment.timeline_start, segment.timeline_duration))
+                .collect()
+        };
+        let mut project = sample_project();
+        project.settings.frame_rate = Some(Rational::new(25, 1).expect("valid rational"));
+        project.settings.snap_to_frames = true;
+        let copied = vec![Segment {
+            timeline_start: 0,
+            ..project.timeline.segments[0].clone()
+        }];
+        let mut next_id = 10;
+        let mut allocate = || {
+            next_id += 1;
+            next_id
+        };
+
+        let mut rippled = project.clone();
+        rippled
+            .delete_range(410_000, 810_000, true, &mut allocate)
+            .expect("delete should succeed");
+        assert_eq!(spans(&rippled), vec![(0, 400_000), (400_000, 400_000)]);
+
+        let mut pasted = project.clone();
+        let end_tl = pasted
+            .paste_segments(430_000, &copied, PasteMode::Insert, &mut allocate)
+            .expect("paste should succeed");
+        assert_eq!(end_tl, 1_640_000);
+        assert_eq!(
+            spans(&pasted),
+            vec![(0, 440_000), (440_000, 1_200_000), (1_640_000, 760_000)]
+        );
+
+        project.set_in_point(430_000);
+        project.set_out_point(790_000);
+        assert_eq!(project.in_out_range(), Some((440_000, 800_000)));
+    }
+
+    #[test]
+    fn step_frames_stops_at_segment_boundaries_and_uses_project_grid_in_gaps() {
+        let mut project = sample_project();
+        project.split(500_000, 2).expect("split should succeed");
+        project.cut(600_000).expect("cut should succeed");
+        project.timeline.segments.push(Segment {
+            id: 3,
+            asset_id: 1,
+            src_in_video: Some(90_000),
+            src_out_video: Some(99_000),
+            src_in_audio: Some(48_000),
+            src_out_audio: Some(52_800),
+            timeline_start: 900_000,
+            timeline_duration: 100_000,
+            enabled: true,
+        });
+
+        assert_eq!(project.step_frames(480_000, 1), 500_000);
+        assert_eq!(project.step_frames(500_000, -1), 467_134);
+        assert_eq!(project.step_frames(500_000, 1), 500_500);
+        assert_eq!(project.step_frames(520_000, 1), 533_867);
+        assert_eq!(project.step_frames(880_000, 1), 900_000);
+        assert_eq!(project.step_frames(900_000, -1), 867_534);
+    }
+
+    #[test]
+    fn edit_point_finds_neighbouring_segment_boundaries() {
+        let mut project = sample_project();
+        project.split(400_000, 2).expect("split should succeed");
+        project.split(800_000, 3).expect("split should succeed");
+
+        assert_eq!(
+            project.edit_point(500_000, EditPointDirection::Previous),
+            Some(400_000)
+        );
+        assert_eq!(
+            project.edit_point(400_000, EditPointDirection::Next),
+            Some(800_000)
+        );
+        assert_eq!(
+            project.edit_point(900_000, EditPointDirection::Next),
+            Some(1_199_999)
+        );
+        assert_eq!(project.edit_point(0, EditPointDirection::Previous), None);
+        assert_eq!(
+            project.edit_point(1_199_999, EditPointDirection::Next),
+            None
+        );
+    }
+
+    #[test]
+    fn in_out_marks_clamp_and_clear_conflicting_mark() {
+        let mut project = sample_project();
+        assert_eq!(project.in_out_range(), None);
+
+        project.set_in_point(-10);
+        assert_eq!(project.in_out_range(), Some((0, 1_200_000)));
+        project.set_out_point(2_000_000);
+        assert_eq!(project.marks.out_tl, Some(1_200_000));
+        project.set_in_point(300_000);
+        project.set_out_point(700_000);
+        assert_eq!(project.in_out_range(), Some((300_000, 700_000)));
+
+        project.set_in_point(800_000);
+        assert_eq!(
+            project.marks,
+            TimelineMarks {
+                in_tl: Some(800_000),
+                out_tl: None,
+            }
+        );
+        project.clear_in_out();
+        assert_eq!(project.in_out_range(), None);
+    }
+
+    #[test]
+    fn delete_range_lifts_or_ripples_across_segment_edges() {
+        let mut lifted = sample_project();
+        lifted.split(600_000, 2).expect("split should succeed");
+        lifted.set_in_point(400_000);
+        lifted.set_out_point(800_000);
+        let mut rippled = lifted.clone();
+        let mut next_id = 10;
+
+        lifted
+            .delete_range(400_000, 800_000, false, || {
+                next_id += 1;
+                next_id
+            })
+            .expect("lift should succeed");
+        let spans: Vec<_> = lifted
+            .timeline
+            .segments
+            .iter()
+            .map(|segment| (segment.timeline_start, segment.timeline_duration))
+            .collect();
+        assert_eq!(spans, vec![(0, 400_000), (800_000, 400_000)]);
+        assert_eq!(lifted.marks.in_tl, Some(400_000));
+
+        rippled
+            .delete_range(400_000, 800_000, true, || {
+                next_id += 1;
+                next_id
+            })
+            .expect("ripple delete should succeed");
+        let spans: Vec<_> = rippled
+            .timeline
+            .segments
+            .iter()
+            .map(|segment| (segment.timeline_start, segment.timeline_duration))
+            .collect();
+        assert_eq!(spans, vec![(0, 400_000), (400_000, 400_000)]);
+        assert_eq!(rippled.duration_tl(), 800_000);
+        assert!(rippled.marks.is_empty());
+        assert_eq!(rippled.timeline.segments[1].src_in_video, Some(162_000));
+    }
+
+    #[test]
+    fn select_range_replaces_or_extends_overlapping_segments() {
+        let mut project = sample_project();
+        project.split(300_000, 2).expect("split should succeed");
+        project.split(600_000, 3).expect("split should succeed");
+        project.split(900_000, 4).expect("split should succeed");
+
+        project.select_range(650_000, 250_000, false);
+        assert_eq!(project.selection, BTreeSet::from([1, 2, 3]));
+
+        project.select_range(300_000, 600_000, false);
+        assert_eq!(project.selection, BTreeSet::from([2]));
+        project.select_range(1_000_000, 1_100_000, true);
+        assert_eq!(project.selection, BTreeSet::from([2, 4]));
+
+        assert!(matches!(
+            project.select(&[4, 99], false),
+            Err(EngineError::SegmentIdNotFound { segment_id: 99 })
+        ));
+        assert_eq!(project.selection, BTreeSet::from([2, 4]));
+    }
+
+    #[test]
+    fn toggle_selection_adds_or_removes_one_segment() {
+        let mut project = sample_project();
+        project.split(300_000, 2).expect("split should succeed");
+
+        project.toggle_selection(2).expect("toggle on");
+        project.toggle_selection(1).expect("toggle on");
+        assert_eq!(project.selection, BTreeSet::from([1, 2]));
+        project.toggle_selection(2).expect("toggle off");
+        assert_eq!(project.selection, BTreeSet::from([1]));
+
+        assert!(matches!(
+            project.toggle_selection(99),
+            Err(EngineError::SegmentIdNotFound { segment_id: 99 })
+        ));
+        assert_eq!(project.selection, BTreeSet::from([1]));
+    }
+
+    #[test]
+    fn delete_selection_lifts_or_ripples_every_selected_segment() {
+        let mut lifted = sample_project();
+        lifted.split(300_000, 2).expect("split should succeed");
+        lifted.split(600_000, 3).expect("split should succeed");
+        lifted.split(900_000, 4).expect("split should succeed");
+        lifted
+            .select(&[1, 3], false)
+            .expect("select should succeed");
+        let mut rippled = lifted.clone();
+
+        lifted
+            .delete_selection(false)
+            .expect("delete should succeed");
+        let spans: Vec<_> = lifted
+            .timeline
+            .segments
+            .iter()
+            .map(|segment| (segment.id, segment.timeline_start))
+            .collect();
+        assert_eq!(spans, vec![(2, 300_000), (4, 900_000)]);
+        assert!(lifted.selection.is_empty());
+
+        rippled
+            .delete_selection(true)
+            .expect("ripple delete should succeed");
+        let spans: Vec<_> = rippled
+            .timeline
+            .segments
+            .iter()
+            .map(|segment| (segment.id, segment.timeline_start))
+            .collect();
+        assert_eq!(spans, vec![(2, 0), (4, 300_000)]);
+        assert_eq!(rippled.duration_tl(), 600_000);
+        assert!(matches!(
+            rippled.delete_selection(true),
+            Err(EngineError::SelectionEmpty)
+        ));
+    }
+
+    #[test]
+    fn copy_selection_rebases_segments_and_keeps_their_spacing() {
+        let mut project = sample_project();
+        project.split(300_000, 2).expect("split should succeed");
+        project.split(600_000, 3).expect("split should succeed");
+        project
+            .select(&[1, 3], false)
+            .expect("select should succeed");
+        project.timeline.segments[2].enabled = false;
+
+        let copied = project.copy_selection().expect("copy should succeed");
+
+        let spans: Vec<_> = copied
+            .iter()
+            .map(|segment| (segment.id, segment.timeline_start, segment.enabled))
+            .collect();
+        assert_eq!(spans, vec![(1, 0, true), (3, 600_000, false)]);
+        assert_eq!(
+            copied[1].src_in_video,
+            project.timeline.segments[2].src_in_video
+        );
+
+        project.selection.clear();
+        assert!(matches!(
+            project.copy_selection(),
+            Err(EngineError::SelectionEmpty)
+        ));
+    }
+
+    #[test]
+    fn paste_inserts_or_overwrites_with_fresh_ids() {
+        let mut project = sample_project();
+        project.split(600_000, 2).expect("split should succeed");
+        project.select(&[2], false).expect("select should succeed");
+        let copied = project.copy_selection().expect("copy should succeed");
+        let mut overwritten = project.clone();
+        let mut next_id = 2;
+        let mut allocate = || {
+            next_id += 1;
+            next_id
+        };
+
+        let end_tl = project
+            .paste_segments(300_000, &copied, PasteMode::Insert, &mut allocate)
+            .expect("insert paste should succeed");
+        let spans: Vec<_> = project
+            .timeline
+            .segments
+            .iter()
+            .map(|segment| {
+                (
+                    segment.id,
+                    segment.timeline_start,
+                    segment.timeline_duration,
+                )
+            })
+            .collect();
+        assert_eq!(end_tl, 900_000);
+        assert_eq!(
+            spans,
+            vec![
+                (1, 0, 300_000),
+                (4, 300_000, 600_000),
+                (3, 900_000, 300_000),
+                (2, 1_200_000, 600_000),
+            ]
+        );
+        assert_eq!(project.selection, BTreeSet::from([4]));
+
+        let end_tl = overwritten
+            .paste_segments(300_000, &copied, PasteMode::Overwrite, &mut allocate)
+            .expect("overwrite paste should succeed");
+        let spans: Vec<_> = overwritten
+            .timeline
+            .segments
+            .iter()
+            .map(|segment| {
+                (
+                    segment.id,
+                    segment.timeline_start,
+                    segment.timeline_duration,
+                )
+            })
+            .collect();
+        assert_eq!(end_tl, 900_000);
+        assert_eq!(
+            spans,
+            vec![
+                (1, 0, 300_000),
+                (7, 300_000, 600_000),
+                (6, 900_000, 300_000)
+            ]
+        );
+        assert_eq!(overwritten.duration_tl(), 1_200_000);
+        assert!(matches!(
+            overwritten.paste_segments(0, &[], PasteMode::Insert, &mut allocate),
+            Err(EngineError::ClipboardEmpty)
+        ));
+    }
+
+    #[test]
+    fn source_marks_clamp_and_resolve_to_stream_ranges() {
+        let mut project = sample_project();
+        project
+            .set_source_out_point(1, 5_000_000)
+            .expect("source out should succeed");
+        project
+            .set_source_in_point(1, 1_200_000)
+            .expect("source in should succeed");
+        let asset = &project.assets[0];
+        assert_eq!(asset.source_marks.in_tl, Some(1_200_000));
+        assert_eq!(asset.source_marks.out_tl, None);
+        assert_eq!(asset.source_range(), (1_199_999, 1_200_000));
+
+        project
+            .set_source_in_point(1, 100_000)
+            .expect("source in should succeed");
+        project
+            .set_source_out_point(1, 400_000)
+            .expect("source out should succeed");
+        let segment = project.assets[0].source_segment(9);
+        assert_eq!(segment.src_in_video, Some(99_000));
+        assert_eq!(segment.src_out_video, Some(126_000));
+        assert_eq!(segment.src_in_audio, Some(52_800));
+        assert_eq!(segment.src_out_audio, Some(67_200));
+        assert_eq!(segment.timeline_duration, 300_000);
+
+        project
+            .clear_source_in_out(1)
+            .expect("clear should succeed");
+        assert!(project.assets[0].source_marks.is_empty());
+        assert!(matches!(
+            project.set_source_in_point(2, 0),
+            Err(EngineError::MissingAsset { asset_id: 2 })
+        ));
+    }
+
+    #[test]
+    fn match_frame_maps_timeline_ticks_back_to_asset_source_position() {
+        let mut project = sample_project();
+        project
+            .trim_segment_start(1, 300_000)
+            .expect("trim should succeed");
+        project
+            .move_segment(1, 500_000)
+            .expect("move should succeed");
+
+        assert_eq!(
+            project.match_frame(600_000).expect("match frame"),
+            (1, 400_000)
+        );
+        assert!(matches!(
+            project.match_frame(100_000),
+            Err(EngineError::SegmentNotFound { at_tl: 100_000 })
+        ));
+    }
+
+    #[test]
+    fn replace_segment_media_keeps_timing_and_remaps_source_in_point() {
+        let mut project = sample_project();
+        project
+            .trim_segment_start(1, 300_000)
+            .expect("trim should succeed");
+        let mut graded = project.assets[0].clone();
+        graded.id = 2;
+        graded.duration_tl = 1_500_000;
+        graded.video.as_mut().expect("video").start_pts = 0;
+        graded.audio.as_mut().expect("audio").start_pts = 0;
+        project.add_asset(graded.clone());
+        graded.id = 3;
+        graded.duration_tl = 800_000;
+        project.add_asset(graded);
+        let before = project.timeline.segments[0].clone();
+        let src_in = |project: &Project| {
+            let segment = &project.timeline.segments[0];
+            (segment.asset_id, segment.src_in_video, segment.src_in_audio)
+        };
+
+        let mut by_timecode = project.clone();
+        by_timecode
+            .replace_segment_media(1, 2, ReplaceAlignment::SourceTimecode, 0, 0)
+            .expect("timecode replace should succeed");
+        let replaced = &by_timecode.timeline.segments[0];
+        assert_eq!(src_in(&by_timecode), (2, Some(27_000), Some(14_400)));
+        assert_eq!(replaced.src_out_video, Some(108_000));
+        assert_eq!(
+            (replaced.timeline_start, replaced.timeline_duration),
+            (before.timeline_start, before.timeline_duration)
+        );
+
+        let mut from_start = project.clone();
+        from_start
+            .replace_segment_media(1, 2, ReplaceAlignment::StartOfFile, 0, 0)
+            .expect("start of file replace should succeed");
+        assert_eq!(src_in(&from_start), (2, Some(0), Some(0)));
+
+        let mut by_playhead = project.clone();
+        by_playhead
+            .replace_segment_media(
+                1,
+                2,
+                ReplaceAlignment::Playhead,
+                before.timeline_start + 200_000,
+                700_000,
+            )
+            .expect("playhead replace should succeed");
+        assert_eq!(src_in(&by_playhead), (2, Some(45_000), Some(24_000)));
+
+        assert!(matches!(
+            project.replace_segment_media(
+                1,
+                2,
+                ReplaceAlignment::Playhead,
+                before.timeline_start + 200_000,
+                100_000,
+            ),
+            Err(EngineError::InsufficientMedia {
+                asset_id: 2,
+                start_tl: -100_000,
+                ..
+            })
+        ));
+        assert!(matches!(
+            project.replace_segment_media(1, 3, ReplaceAlignment::StartOfFile, 0, 0),
+            Err(EngineError::InsufficientMedia {
+                asset_id: 3,
+                duration_tl: 800_000,
+                ..
+            })
+        ));
+        assert_eq!(project.timeline.segments[0], before);
+    }
+
+    #[test]
+    fn replace_by_source_timecode_lines_up_assets_with_different_start_timecodes() {
+        let mut project = sample_project();
+        project
+            .trim_segment_start(1, 300_000)
+            .expect("trim should succeed");
+        project.assets[0]
+            .video
+            .as_mut()
+            .expect("video")
+            .start_timecode = Some(Timecode::parse("01:00:00;00").expect("valid"));
+        let mut graded = project.assets[0].clone();
+        graded.id = 2;
+        graded.video.as_mut().expect("video").start_timecode =
+            Some(Timecode::parse("01:00:00;05").expect("valid"));
+        project.add_asset(graded);
+        let before = project.timeline.segments[0].clone();
+        let timeline_start = before.timeline_start;
+        let (_, in_src) = project.match_frame(timeline_start).expect("segment");
+        let timecode = project.assets[0]
+            .source_timecode_at(in_src)
+            .expect("valid start");
+
+        project
+            .replace_segment_media(1, 2, ReplaceAlignment::SourceTimecode, 0, 0)
+            .expect("timecode replace should succeed");
+
+        let (asset_id, in_src) = project.match_frame(timeline_start).expect("segment");
+        assert_eq!(asset_id, 2);
+        assert_eq!(timecode, Timecode::parse("01:00:00;08").expect("valid"));
+        assert_eq!(
+            project.assets[1]
+                .source_timecode_at(in_src)
+                .expect("valid start"),
+            timecode
+        );
+        // Five 29.97 fps frames earlier in the source, in 1/90000 ticks.
+        assert_eq!(
+            project.timeline.segments[0].src_in_video,
+            before.src_in_video.map(|src_in| src_in - 5 * 3_003)
+        );
+    }
+
+    #[test]
+    fn edit_from_asset_inserts_or_overwrites_marked_range() {
+        let mut project = sample_project();
+        project
+            .set_source_in_point(1, 100_000)
+            .expect("source in should succeed");
+        project
+            .set_source_out_point(1, 400_000)
+            .expect("source out should succeed");
+        let mut overwritten = project.clone();
+        let mut next_id = 1;
+        let mut allocate = || {
+            next_id += 1;
+            next_id
+        };
+
+        let end_tl = project
+            .edit_from_asset(1, 500_000, PasteMode::Insert, &mut allocate)
+            .expect("insert should succeed");
+        let spans: Vec<_> = project
+            .timeline
+            .segments
+            .iter()
+            .map(|segment| (segment.id, segment.timeline_start))
+            .collect();
+        assert_eq!(end_tl, 800_000);
+        assert_eq!(spans, vec![(1, 0), (3, 500_000), (2, 800_000)]);
+        assert_eq!(project.duration_tl(), 1_500_000);
+
+        let end_tl = overwritten
+            .edit_from_asset(1, 500_000, PasteMode::Overwrite, &mut allocate)
+            .expect("overwrite should succeed");
+        let spans: Vec<_> = overwritten
+            .timeline
+            .segments
+            .iter()
+            .map(|segment| (segment.id, segment.timeline_start))
+            .collect();
+        assert_eq!(end_tl, 800_000);
+        assert_eq!(spans, vec![(1, 0), (6, 500_000), (5, 800_000)]);
+        assert_eq!(overwritten.duration_tl(), 1_200_000);
+        assert_eq!(overwritten.selection, BTreeSet::from([6]));
+    }
+
+    #[test]
+    fn duplicate_segment_inserts_copy_after_original() {
+        let mut project = sample_project();
+        project.split(400_000, 2).expect("split should succeed");
+
+        project
+            .duplicate_segment(1, 3)
+            .expect("duplicate should succeed");
+
+        let spans: Vec<_> = project
+            .timeline
+            .segments
+            .iter()
+            .map(|segment| (segment.id, segment.timeline_start, segment.src_in_video))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                (1, 0, Some(90_000)),
+                (3, 400_000, Some(90_000)),
+                (2, 800_000, Some(126_000)),
+            ]
+        );
+        assert_eq!(project.selection, BTreeSet::from([3]));
+        assert!(matches!(
+            project.duplicate_segment(9, 4),
+            Err(EngineError::SegmentIdNotFound { segment_id: 9 })
+        ));
+    }
+
+    #[test]
+    fn move_selection_clamps_delta_against_unselected_neighbours() {
+        let mut project = sample_project();
+        project.split(300_000, 2).expect("split should succeed");
+        project.split(600_000, 3).expect("split should succeed");
+        project.split(900_000, 4).expect("split should succeed");
+        project
+            .select(&[1, 3], false)
+            .expect("select should succeed");
+        project
+            .delete_selection(false)
+            .expect("delete should succeed");
+        project.select(&[2], false).expect("select should succeed");
+
+        assert_eq!(
+            project
+                .move_selection(-500_000)
+                .expect("move should succeed"),
+            -300_000
+        );
+        assert_eq!(project.timeline.segments[0].timeline_start, 0);
+        assert_eq!(
+            project
+                .move_selection(800_000)
+                .expect("move should succeed"),
+            600_000
+        );
+        assert_eq!(project.timeline.segments[0].timeline_start, 600_000);
+
+        project.select(&[4], true).expect("select should succeed");
+        assert_eq!(
+            project
+                .move_selection(100_000)
+                .expect("move should succeed"),
+            100_000
+        );
+        let starts: Vec<_> = project
+            .timeline
+            .segments
+            .iter()
+            .map(|segment| segment.timeline_start)
+            .collect();
+        assert_eq!(starts, vec![700_000, 1_000_000]);
+    }
+
+    #[test]
+    fn set_selection_enabled_only_touches_selected_segments() {
+        let mut project = sample_project();
+        project.split(600_000, 2).expect("split should succeed");
+        project.select(&[2], false).expect("select should succeed");
+
+        project
+            .set_selection_enabled(false)
+            .expect("disable should succeed");
+
+        let enabled: Vec<_> = project
+            .timeline
+            .segments
+            .iter()
+            .map(|segment| segment.enabled)
+            .collect();
+        assert_eq!(enabled, vec![true, false]);
+    }
+
+    #[test]
+    fn clipped_to_range_rebases_timeline_and_trims_sources() {
+        let project = sample_project();
+
+        let clipped = project
+            .clipped_to_range(300_000, 900_000)
+            .expect("clip should succeed");
+
+        assert_eq!(clipped.timeline.segments.len(), 1);
+        let segment = &clipped.timeline.segments[0];
+        assert_eq!(segment.timeline_start, 0);
+        assert_eq!(segment.timeline_duration, 600_000);
+        assert_eq!(segment.src_in_video, Some(117_000));
+        assert_eq!(segment.src_out_video, Some(171_000));
+        assert!(matches!(
+            project.clipped_to_range(500_000, 500_000),
+            Err(EngineError::EmptyRange { .. })
+        ));
+    }
+
     fn sample_project() -> Project {
         Project {
             assets: vec![MediaAsset {
@@ -811,13 +3590,19 @@ mod tests {
                     frame_rate: Some(Rational::new(30_000, 1_001).expect("valid rational")),
                     width: 1920,
                     height: 1080,
+                    start_pts: 90_000,
+                    start_timecode: None,
                 }),
                 audio: Some(AudioStreamInfo {
                     time_base: Rational::new(1, 48_000).expect("valid rational"),
                     sample_rate: 48_000,
                     channels: 2,
+                    start_pts: 48_000,
                 }),
                 duration_tl: 1_200_000,
+                source_marks: TimelineMarks::default(),
+                file_size: None,
+                offline: false,
             }],
             timeline: Timeline {
                 segments: vec![Segment {
@@ -829,6 +3614,7 @@ mod tests {
                     src_out_audio: Some(105_600),
                     timeline_start: 0,
                     timeline_duration: 1_200_000,
+                    enabled: true,
                 }],
             },
             settings: ProjectSettings {
@@ -837,7 +3623,12 @@ mod tests {
                     video_codec: String::from("h264"),
                     audio_codec: String::from("aac"),
                 }),
+                ..ProjectSettings::default()
             },
+            marks: TimelineMarks::default(),
+            selection: BTreeSet::new(),
+            loop_playback: false,
+            changes: Default::default(),
         }
     }
 
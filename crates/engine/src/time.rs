@@ -1,5 +1,7 @@
+use std::fmt::{Display, Formatter};
+
 use crate::error::{EngineError, Result};
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// FFmpeg-like rational number used as a time base.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
@@ -67,6 +69,316 @@ pub fn rescale(ts: i64, from: Rational, to: Rational) -> i64 {
     rounded.clamp(i128::from(i64::MIN), i128::from(i64::MAX)) as i64
 }
 
+/// Converts timeline ticks to seconds, the unit media backends seek in.
+pub(crate) fn ticks_to_seconds(t_tl: i64) -> f64 {
+    t_tl as f64 / TIMELINE_TIME_BASE.den as f64
+}
+
+/// Frame boundaries on the timeline for a constant frame rate.
+///
+/// Frame `n` starts at the first timeline tick at or after `n / fps` seconds,
+/// computed from the exact rational rate so long runs of frames do not drift.
+///
+/// # Example
+/// ```
+/// use engine::{FrameGrid, Rational};
+///
+/// let grid = FrameGrid::from_frame_rate(Rational::new(30_000, 1_001).expect("valid"));
+/// assert_eq!(grid.frame_start_tl(1), 33_367);
+/// assert_eq!(grid.frame_start_tl(30_000), 1_001_000_000);
+/// assert_eq!(grid.frame_at(33_366), 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameGrid {
+    frame_rate: Rational,
+}
+
+impl FrameGrid {
+    /// Fallback grid used when no asset provides a frame rate (30 fps).
+    pub const DEFAULT: Self = Self {
+        frame_rate: Rational { num: 30, den: 1 },
+    };
+
+    /// Builds a grid from frames per second.
+    pub fn from_frame_rate(frame_rate: Rational) -> Self {
+        Self { frame_rate }
+    }
+
+    /// Builds a grid with one frame per tick of `time_base`.
+    pub fn from_time_base(time_base: Rational) -> Self {
+        Self {
+            frame_rate: Rational {
+                num: time_base.den,
+                den: time_base.num,
+            },
+        }
+    }
+
+    /// Returns the frame rate in frames per second.
+    pub fn frame_rate(&self) -> Rational {
+        self.frame_rate
+    }
+
+    /// Returns the index of the frame displayed at `t_tl`.
+    pub fn frame_at(&self, t_tl: i64) -> i64 {
+        let numerator = i128::from(t_tl) * i128::from(self.frame_rate.num);
+        let denominator = i128::from(TIMELINE_TIME_BASE.den) * i128::from(self.frame_rate.den);
+        numerator
+            .div_euclid(denominator)
+            .clamp(i128::from(i64::MIN), i128::from(i64::MAX)) as i64
+    }
+
+    /// Returns the frame nearest to `ticks` in `time_base`.
+    pub fn nearest_frame(&self, ticks: i64, time_base: Rational) -> i64 {
+        let frame_duration = Rational {
+            num: self.frame_rate.den,
+            den: self.frame_rate.num,
+        };
+        rescale(ticks, time_base, frame_duration)
+    }
+
+    /// Returns the first timeline tick of frame `frame`.
+    pub fn frame_start_tl(&self, frame: i64) -> i64 {
+        let numerator = i128::from(frame)
+            * i128::from(TIMELINE_TIME_BASE.den)
+            * i128::from(self.frame_rate.den);
+        let denominator = i128::from(self.frame_rate.num);
+        let ceil = -((-numerator).div_euclid(denominator));
+        ceil.clamp(i128::from(i64::MIN), i128::from(i64::MAX)) as i64
+    }
+
+    /// Returns the start of the frame boundary nearest to `t_tl`.
+    pub fn nearest_frame_start_tl(&self, t_tl: i64) -> i64 {
+        self.frame_start_tl(self.nearest_frame(t_tl, TIMELINE_TIME_BASE))
+    }
+
+    /// Returns whole frames per timecode second: the rate rounded up, so
+    /// 29.97 fps counts 30.
+    pub fn nominal_fps(&self) -> i64 {
+        let rate = self.frame_rate;
+        (i64::from(rate.num) + i64::from(rate.den) - 1) / i64::from(rate.den)
+    }
+
+    /// Returns `true` for the 29.97 and 59.94 fps rates that have a SMPTE
+    /// drop-frame count.
+    pub fn supports_drop_frame(&self) -> bool {
+        self.frame_rate.den == 1001 && matches!(self.nominal_fps(), 30 | 60)
+    }
+}
+
+/// SMPTE `HH:MM:SS:FF` timecode.
+///
+/// Drop-frame timecode (29.97 and 59.94 fps only) skips the first two (four at
+/// 59.94) frame labels of every minute except each tenth minute, so the count
+/// stays in step with the clock; it is written with `;` before the frames.
+/// Frame counts wrap at 24 hours.
+///
+/// # Example
+/// ```
+/// use engine::{FrameGrid, Rational, Timecode};
+///
+/// let grid = FrameGrid::from_frame_rate(Rational::new(30_000, 1_001).expect("valid"));
+/// let timecode = Timecode::from_frame(1_800, grid, true);
+/// assert_eq!(timecode.to_string(), "00:01:00;02");
+/// assert_eq!(Timecode::parse("00:01:00;02").expect("valid").frame(grid).expect("valid"), 1_800);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Timecode {
+    pub hours: u32,
+    pub minutes: u32,
+    pub seconds: u32,
+    pub frames: u32,
+    pub drop_frame: bool,
+}
+
+impl Timecode {
+    /// Timecode `00:00:00:00`.
+    pub const ZERO: Self = Self {
+        hours: 0,
+        minutes: 0,
+        seconds: 0,
+        frames: 0,
+        drop_frame: false,
+    };
+
+    /// Parses `HH:MM:SS:FF`; `;`, `.` or `,` before the frames marks
+    /// drop-frame. The frame field is checked against a rate by [`Self::frame`].
+    pub fn parse(text: &str) -> Result<Self> {
+        let invalid = |reason: &str| EngineError::InvalidTimecode {
+            value: text.to_owned(),
+            reason: reason.to_owned(),
+        };
+        let trimmed = text.trim();
+        let separator = trimmed
+            .char_indices()
+            .rev()
+            .find(|(_, ch)| !ch.is_ascii_digit())
+            .ok_or_else(|| invalid("expected HH:MM:SS:FF"))?;
+        let drop_frame = match separator.1 {
+            ':' => false,
+            ';' | '.' | ',' => true,
+            _ => return Err(invalid("expected HH:MM:SS:FF")),
+        };
+        let (clock, frames) = (&trimmed[..separator.0], &trimmed[separator.0 + 1..]);
+        let mut fields = clock
+            .split(':')
+            .chain(std::iter::once(frames))
+            .map(|field| {
+                (field.len() == 2 && field.bytes().all(|byte| byte.is_ascii_digit()))
+                    .then(|| field.parse::<u32>().ok())
+                    .flatten()
+            });
+        let mut next = || fields.next().flatten();
+        let timecode = Self {
+            hours: next().ok_or_else(|| invalid("expected HH:MM:SS:FF"))?,
+            minutes: next().ok_or_else(|| invalid("expected HH:MM:SS:FF"))?,
+            seconds: next().ok_or_else(|| invalid("expected HH:MM:SS:FF"))?,
+            frames: next().ok_or_else(|| invalid("expected HH:MM:SS:FF"))?,
+            drop_frame,
+        };
+        if fields.next().is_some() {
+            return Err(invalid("expected HH:MM:SS:FF"));
+        }
+        if timecode.hours >= 24 || timecode.minutes >= 60 || timecode.seconds >= 60 {
+            return Err(invalid("hours, minutes or seconds out of range"));
+        }
+        Ok(timecode)
+    }
+
+    /// Builds the timecode labelling frame `frame` (counted from
+    /// `00:00:00:00`) at `grid`'s rate. `drop_frame` is ignored for rates
+    /// without a drop-frame count.
+    pub fn from_frame(frame: i64, grid: FrameGrid, drop_frame: bool) -> Self {
+        let fps = grid.nominal_fps();
+        let drop_frame = drop_frame && grid.supports_drop_frame();
+        let mut label = frame.rem_euclid(Self::frames_per_day(grid, drop_frame));
+        if drop_frame {
+            let dropped = fps / 15;
+            let per_ten_minutes = fps * 600 - dropped * 9;
+            let per_minute = fps * 60 - dropped;
+            let tens = label / per_ten_minutes;
+            let rest = label % per_ten_minutes;
+            label += dropped * 9 * tens;
+            if rest > dropped {
+                label += dropped * ((rest - dropped) / per_minute);
+            }
+        }
+        let seconds = label / fps;
+        Self {
+            hours: (seconds / 3600) as u32,
+            minutes: (seconds / 60 % 60) as u32,
+            seconds: (seconds % 60) as u32,
+            frames: (label % fps) as u32,
+            drop_frame,
+        }
+    }
+
+    /// Returns the frame this timecode labels at `grid`'s rate, counted from
+    /// `00:00:00:00`.
+    ///
+    /// Fails when the frame field is out of range for the rate, the rate has no
+    /// drop-frame count, or the label is one drop-frame skips.
+    pub fn frame(self, grid: FrameGrid) -> Result<i64> {
+        let invalid = |reason: String| EngineError::InvalidTimecode {
+            value: self.to_string(),
+            reason,
+        };
+        let fps = grid.nominal_fps();
+        if i64::from(self.frames) >= fps {
+            return Err(invalid(format!(
+                "frame {} is out of range at {fps} fps",
+                self.frames
+            )));
+        }
+        let total_minutes = i64::from(self.hours) * 60 + i64::from(self.minutes);
+        let mut frame =
+            (total_minutes * 60 + i64::from(self.seconds)) * fps + i64::from(self.frames);
+        if self.drop_frame {
+            if !grid.supports_drop_frame() {
+                return Err(invalid(String::from(
+                    "drop-frame timecode needs a 29.97 or 59.94 fps rate",
+                )));
+            }
+            let dropped = fps / 15;
+            if self.seconds == 0
+                && !self.minutes.is_multiple_of(10)
+                && i64::from(self.frames) < dropped
+            {
+                return Err(invalid(String::from("label is skipped by drop-frame")));
+            }
+            frame -= dropped * (total_minutes - total_minutes / 10);
+        }
+        Ok(frame)
+    }
+
+    /// Returns the timecode of the frame displayed at `t_tl`.
+    pub fn from_tl(t_tl: i64, grid: FrameGrid, drop_frame: bool) -> Self {
+        Self::from_frame(grid.frame_at(t_tl), grid, drop_frame)
+    }
+
+    /// Returns the first timeline tick of the frame this timecode labels.
+    pub fn to_tl(self, grid: FrameGrid) -> Result<i64> {
+        Ok(grid.frame_start_tl(self.frame(grid)?))
+    }
+
+    /// Returns the timecode of the frame displayed `t_tl` ticks after this
+    /// one, in the same count.
+    ///
+    /// Fails like [`Self::frame`] when this timecode does not fit `grid`.
+    pub fn offset_tl(self, t_tl: i64, grid: FrameGrid) -> Result<Self> {
+        let start_frame = self.frame(grid)?;
+        Ok(Self::from_frame(
+            start_frame + grid.frame_at(t_tl),
+            grid,
+            self.drop_frame,
+        ))
+    }
+
+    /// Returns the number of frames in 24 hours of timecode at `grid`'s rate.
+    pub fn frames_per_day(grid: FrameGrid, drop_frame: bool) -> i64 {
+        let fps = grid.nominal_fps();
+        let dropped = if drop_frame && grid.supports_drop_frame() {
+            fps / 15
+        } else {
+            0
+        };
+        24 * 6 * (fps * 600 - dropped * 9)
+    }
+}
+
+impl Display for Timecode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{}{:02}",
+            self.hours,
+            self.minutes,
+            self.seconds,
+            if self.drop_frame { ';' } else { ':' },
+            self.frames
+        )
+    }
+}
+
+impl Serialize for Timecode {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timecode {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let text = String::deserialize(deserializer)?;
+        Timecode::parse(&text).map_err(serde::de::Error::custom)
+    }
+}
+
 fn div_round_nearest(num: i128, den: i128) -> i128 {
     debug_assert!(den > 0);
 
@@ -100,7 +412,32 @@ impl From<Rational> for media_ffmpeg::Rational {
 
 #[cfg(test)]
 mod tests {
-    use super::Rational;
+    use super::{FrameGrid, Rational, Timecode, ticks_to_seconds};
+
+    #[test]
+    fn ticks_to_seconds_divides_by_the_timeline_time_base() {
+        assert_eq!(ticks_to_seconds(1_500_000), 1.5);
+        assert_eq!(ticks_to_seconds(0), 0.0);
+    }
+
+    #[test]
+    fn frame_grid_start_and_index_round_trip_for_ntsc_rate() {
+        let grid = FrameGrid::from_frame_rate(Rational::new(30_000, 1_001).expect("valid"));
+
+        for frame in [0, 1, 2, 29, 30, 1_799, 107_892] {
+            let start = grid.frame_start_tl(frame);
+            assert_eq!(grid.frame_at(start), frame);
+            assert_eq!(grid.frame_at(start - 1), frame - 1);
+        }
+    }
+
+    #[test]
+    fn frame_grid_from_time_base_counts_time_base_ticks() {
+        let grid = FrameGrid::from_time_base(Rational::new(1, 1_000).expect("valid"));
+
+        assert_eq!(grid.frame_at(2_999), 2);
+        assert_eq!(grid.frame_start_tl(3), 3_000);
+    }
 
     #[test]
     fn rational_new_rejects_negative_numerator() {
@@ -112,4 +449,79 @@ mod tests {
         let deserialized = serde_json::from_str::<Rational>(r#"{"num":1,"den":0}"#);
         assert!(deserialized.is_err());
     }
+
+    #[test]
+    fn drop_frame_timecode_skips_labels_except_every_tenth_minute() {
+        let ntsc = FrameGrid::from_frame_rate(Rational::new(30_000, 1_001).expect("valid"));
+
+        for (frame, label) in [
+            (0, "00:00:00;00"),
+            (1_799, "00:00:59;29"),
+            (1_800, "00:01:00;02"),
+            (17_981, "00:09:59;29"),
+            (17_982, "00:10:00;00"),
+            (107_892, "01:00:00;00"),
+        ] {
+            let timecode = Timecode::from_frame(frame, ntsc, true);
+            assert_eq!(timecode.to_string(), label);
+            assert_eq!(Timecode::parse(label).expect("valid"), timecode);
+            assert_eq!(timecode.frame(ntsc).expect("valid"), frame);
+        }
+
+        let fifty_nine = FrameGrid::from_frame_rate(Rational::new(60_000, 1_001).expect("valid"));
+        assert_eq!(
+            Timecode::from_frame(3_600, fifty_nine, true).to_string(),
+            "00:01:00;04"
+        );
+        assert!(
+            Timecode::parse("00:01:00;01")
+                .expect("valid syntax")
+                .frame(ntsc)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn non_drop_timecode_counts_nominal_frames_and_converts_ticks() {
+        let pal = FrameGrid::from_frame_rate(Rational::new(25, 1).expect("valid"));
+        let timecode = Timecode::parse("01:00:00:12").expect("valid");
+
+        assert_eq!(timecode.frame(pal).expect("valid"), 90_012);
+        assert_eq!(timecode.to_tl(pal).expect("valid"), 3_600_480_000);
+        assert_eq!(
+            Timecode::from_tl(3_600_480_000 + 39_999, pal, false),
+            timecode
+        );
+        // Drop-frame is ignored at rates without a drop-frame count.
+        assert_eq!(
+            Timecode::from_frame(90_012, pal, true).to_string(),
+            "01:00:00:12"
+        );
+        assert!(
+            Timecode::parse("00:00:00:25")
+                .expect("valid")
+                .frame(pal)
+                .is_err()
+        );
+        assert!(
+            Timecode::parse("00:00:00;10")
+                .expect("valid")
+                .frame(pal)
+                .is_err()
+        );
+        for text in [
+            "00:00:00",
+            "0:00:00:00",
+            "00:60:00:00",
+            "00:00:00-00",
+            "00:00:00:00:00",
+        ] {
+            assert!(Timecode::parse(text).is_err(), "{text} should be rejected");
+        }
+        // Counts wrap at 24 hours.
+        assert_eq!(
+            Timecode::from_frame(-1, pal, false).to_string(),
+            "23:59:59:24"
+        );
+    }
 }
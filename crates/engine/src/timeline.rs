@@ -9,7 +9,7 @@ pub type SegmentId = u64;
 pub type AssetId = u64;
 
 /// Single-track timeline used in the MVP.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Timeline {
     pub segments: Vec<Segment>,
 }
@@ -25,6 +25,13 @@ pub struct Segment {
     pub src_out_audio: Option<i64>,
     pub timeline_start: i64,
     pub timeline_duration: i64,
+    /// Disabled segments keep their place on the timeline.
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+}
+
+fn enabled_by_default() -> bool {
+    true
 }
 
 impl Timeline {
@@ -63,6 +70,7 @@ impl Timeline {
     ///         src_out_audio: None,
     ///         timeline_start: 0,
     ///         timeline_duration: 1_000_000,
+    ///         enabled: true,
     ///     }],
     /// };
     ///
@@ -164,6 +172,7 @@ impl Timeline {
     ///             src_out_audio: None,
     ///             timeline_start: 0,
     ///             timeline_duration: 1_000_000,
+    ///             enabled: true,
     ///         },
     ///         Segment {
     ///             id: 2,
@@ -174,6 +183,7 @@ impl Timeline {
     ///             src_out_audio: None,
     ///             timeline_start: 1_000_000,
     ///             timeline_duration: 1_000_000,
+    ///             enabled: true,
     ///         },
     ///     ],
     /// };
@@ -251,6 +261,7 @@ mod tests {
                 src_out_audio: None,
                 timeline_start: 0,
                 timeline_duration: 1_000,
+                enabled: true,
             }],
         };
 
@@ -274,6 +285,7 @@ mod tests {
                     src_out_audio: None,
                     timeline_start: 0,
                     timeline_duration: 100,
+                    enabled: true,
                 },
                 Segment {
                     id: 2,
@@ -284,6 +296,7 @@ mod tests {
                     src_out_audio: None,
                     timeline_start: 100,
                     timeline_duration: 100,
+                    enabled: true,
                 },
             ],
         };
@@ -308,6 +321,7 @@ mod tests {
                     src_out_audio: None,
                     timeline_start: 0,
                     timeline_duration: 100,
+                    enabled: true,
                 },
                 Segment {
                     id: 2,
@@ -318,6 +332,7 @@ mod tests {
                     src_out_audio: None,
                     timeline_start: 100,
                     timeline_duration: 100,
+                    enabled: true,
                 },
                 Segment {
                     id: 3,
@@ -328,6 +343,7 @@ mod tests {
                     src_out_audio: None,
                     timeline_start: 200,
                     timeline_duration: 100,
+                    enabled: true,
                 },
             ],
         };
@@ -0,0 +1,431 @@
+//! Background preview decoding.
+//!
+//! The engine submits preview decodes to a small pool of worker threads so a
+//! slow decode never blocks command handling. Every job carries the preview
+//! generation that was current when it was submitted; queued jobs from older
+//! generations are dropped as soon as a newer request arrives. The timeline
+//! and the source monitor count generations separately, so scrubbing one
+//! never drops the other's jobs. Draft jobs are taken before full-quality
+//! jobs so a drag keeps getting fast frames. An optional waker runs after
+//! every finished decode so a host loop can block instead of polling.
+
+use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::api::CommandId;
+use crate::error::Result;
+use crate::preview::{MediaBackend, PreviewFrame, PreviewQuality};
+use crate::time::ticks_to_seconds;
+use crate::timeline::AssetId;
+use tracing::{debug, warn};
+
+/// Why a preview frame was requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PreviewJobKind {
+    /// The frame under the playhead; its result is reported to the UI.
+    Playhead,
+    /// A neighbouring frame decoded only to warm the cache.
+    Prefetch,
+    /// The frame under the source monitor playhead of a bin asset.
+    Source { asset_id: AssetId },
+}
+
+impl PreviewJobKind {
+    fn is_source(self) -> bool {
+        matches!(self, Self::Source { .. })
+    }
+}
+
+/// One preview decode request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PreviewJob {
+    pub generation: u64,
+    pub kind: PreviewJobKind,
+    pub quality: PreviewQuality,
+    /// Tagged command that requested the frame, echoed on its event.
+    pub command_id: Option<CommandId>,
+    /// Timeline tick of the request; source jobs carry the source position.
+    pub t_tl: i64,
+    pub path: PathBuf,
+    pub source_tl: i64,
+}
+
+/// Decode outcome returned by a worker.
+#[derive(Debug)]
+pub(crate) struct PreviewResult {
+    pub job: PreviewJob,
+    pub frame: Result<PreviewFrame>,
+}
+
+#[derive(Debug, Default)]
+struct JobQueue {
+    jobs: VecDeque<PreviewJob>,
+    shutdown: bool,
+}
+
+/// Callback run on a worker thread after each finished decode.
+pub(crate) type PreviewWaker = Arc<dyn Fn() + Send + Sync>;
+
+#[derive(Default)]
+struct Shared {
+    queue: Mutex<JobQueue>,
+    job_ready: Condvar,
+    waker: Mutex<Option<PreviewWaker>>,
+}
+
+/// Fixed-size pool of preview decode threads.
+pub(crate) struct PreviewWorkerPool {
+    shared: Arc<Shared>,
+    results_rx: Receiver<PreviewResult>,
+    outstanding: usize,
+}
+
+impl PreviewWorkerPool {
+    /// Spawns `worker_count` threads decoding through `media`.
+    pub fn spawn<M>(media: Arc<M>, worker_count: usize) -> Self
+    where
+        M: MediaBackend + 'static,
+    {
+        let shared = Arc::new(Shared::default());
+        let (results_tx, results_rx) = mpsc::channel();
+        for index in 0..worker_count.max(1) {
+            let media = Arc::clone(&media);
+            let shared = Arc::clone(&shared);
+            let results_tx = results_tx.clone();
+            let spawned = thread::Builder::new()
+                .name(format!("preview-decode-{index}"))
+                .spawn(move || run_worker(media.as_ref(), &shared, &results_tx));
+            if let Err(error) = spawned {
+                warn!(%error, "failed to spawn preview worker");
+            }
+        }
+        Self {
+            shared,
+            results_rx,
+            outstanding: 0,
+        }
+    }
+
+    /// Queues `job` after dropping queued jobs of the same monitor from
+    /// older generations.
+    pub fn submit(&mut self, job: PreviewJob) {
+        if job.kind.is_source() {
+            self.cancel_source_before(job.generation);
+        } else {
+            self.cancel_before(job.generation);
+        }
+        let mut queue = lock_queue(&self.shared);
+        queue.jobs.push_back(job);
+        self.outstanding += 1;
+        self.shared.job_ready.notify_one();
+    }
+
+    /// Drops queued timeline jobs older than `generation`; jobs already
+    /// decoding finish.
+    pub fn cancel_before(&mut self, generation: u64) {
+        self.drop_queued(generation, |job| !job.kind.is_source());
+    }
+
+    /// Drops queued source monitor jobs older than `generation`.
+    pub fn cancel_source_before(&mut self, generation: u64) {
+        self.drop_queued(generation, |job| job.kind.is_source());
+    }
+
+    fn drop_queued(&mut self, generation: u64, applies: impl Fn(&PreviewJob) -> bool) {
+        let mut queue = lock_queue(&self.shared);
+        let queued = queue.jobs.len();
+        queue
+            .jobs
+            .retain(|job| !applies(job) || job.generation >= generation);
+        let dropped = queued - queue.jobs.len();
+        if dropped > 0 {
+            debug!(dropped, generation, "stale preview jobs dropped");
+            self.outstanding -= dropped;
+        }
+    }
+
+    /// Installs `waker`, replacing any previous one; it runs after each result
+    /// is sent, so a woken caller always finds it in [`Self::try_collect`].
+    pub fn set_waker(&self, waker: PreviewWaker) {
+        *self
+            .shared
+            .waker
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(waker);
+    }
+
+    /// Returns whether any submitted job has not been collected yet.
+    pub fn has_outstanding(&self) -> bool {
+        self.outstanding > 0
+    }
+
+    /// Returns one finished job without blocking.
+    pub fn try_collect(&mut self) -> Option<PreviewResult> {
+        let result = self.results_rx.try_recv().ok()?;
+        self.outstanding -= 1;
+        Some(result)
+    }
+
+    /// Waits up to `timeout` for one finished job.
+    pub fn collect_timeout(&mut self, timeout: Duration) -> Option<PreviewResult> {
+        if self.outstanding == 0 {
+            return None;
+        }
+        match self.results_rx.recv_timeout(timeout) {
+            Ok(result) => {
+                self.outstanding -= 1;
+                Some(result)
+            }
+            Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => None,
+        }
+    }
+}
+
+impl Debug for PreviewWorkerPool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PreviewWorkerPool")
+            .field("outstanding", &self.outstanding)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for PreviewWorkerPool {
+    fn drop(&mut self) {
+        // Workers exit after their current decode; they are not joined so a
+        // slow decode cannot stall engine shutdown.
+        lock_queue(&self.shared).shutdown = true;
+        self.shared.job_ready.notify_all();
+    }
+}
+
+fn run_worker<M>(media: &M, shared: &Shared, results_tx: &Sender<PreviewResult>)
+where
+    M: MediaBackend,
+{
+    loop {
+        let job = {
+            let mut queue = lock_queue(shared);
+            loop {
+                if queue.shutdown {
+                    return;
+                }
+                let next = queue
+                    .jobs
+                    .iter()
+                    .position(|job| job.quality == PreviewQuality::Draft)
+                    .or_else(|| (!queue.jobs.is_empty()).then_some(0));
+                if let Some(job) = next.and_then(|index| queue.jobs.remove(index)) {
+                    break job;
+                }
+                queue = shared
+                    .job_ready
+                    .wait(queue)
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+            }
+        };
+
+        let at_seconds = ticks_to_seconds(job.source_tl);
+        let frame = match job.quality {
+            PreviewQuality::Draft => media.decode_preview_draft_frame(&job.path, at_seconds),
+            PreviewQuality::Full => media.decode_preview_frame(&job.path, at_seconds),
+        };
+        if results_tx.send(PreviewResult { job, frame }).is_err() {
+            return;
+        }
+        let waker = shared
+            .waker
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        if let Some(waker) = waker {
+            waker();
+        }
+    }
+}
+
+fn lock_queue(shared: &Shared) -> std::sync::MutexGuard<'_, JobQueue> {
+    shared
+        .queue
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex, mpsc};
+    use std::time::Duration;
+
+    use super::{PreviewJob, PreviewJobKind, PreviewWorkerPool};
+    use crate::export::ExportVideoPlan;
+    use crate::playback::{PcmChunk, PcmFormat};
+    use crate::preview::{
+        MediaBackend, PreviewFrame, PreviewPixelFormat, PreviewQuality, ProbedMedia,
+    };
+
+    const WAIT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn newer_job_drops_queued_jobs_but_lets_running_decode_finish() {
+        let (release_tx, release_rx) = mpsc::channel();
+        let (started_tx, started_rx) = mpsc::channel();
+        let backend = GatedBackend {
+            release_rx: Mutex::new(release_rx),
+            started_tx: Mutex::new(started_tx),
+        };
+        let mut pool = PreviewWorkerPool::spawn(Arc::new(backend), 1);
+
+        pool.submit(job(1, 1_000_000));
+        started_rx.recv_timeout(WAIT).expect("first decode started");
+        pool.submit(job(2, 2_000_000));
+        pool.submit(job(3, 3_000_000));
+        release_tx.send(()).expect("release first decode");
+        release_tx.send(()).expect("release second decode");
+
+        let first = pool.collect_timeout(WAIT).expect("first result");
+        let second = pool.collect_timeout(WAIT).expect("second result");
+        assert_eq!(first.job.generation, 1);
+        assert_eq!(second.job.generation, 3);
+        assert!(!pool.has_outstanding());
+    }
+
+    #[test]
+    fn draft_jobs_are_decoded_before_queued_full_jobs() {
+        let (release_tx, release_rx) = mpsc::channel();
+        let (started_tx, started_rx) = mpsc::channel();
+        let backend = GatedBackend {
+            release_rx: Mutex::new(release_rx),
+            started_tx: Mutex::new(started_tx),
+        };
+        let mut pool = PreviewWorkerPool::spawn(Arc::new(backend), 1);
+
+        pool.submit(job(1, 1_000_000));
+        started_rx.recv_timeout(WAIT).expect("first decode started");
+        pool.submit(job(1, 2_000_000));
+        pool.submit(PreviewJob {
+            quality: PreviewQuality::Draft,
+            ..job(1, 3_000_000)
+        });
+        for _ in 0..3 {
+            release_tx.send(()).expect("release decode");
+        }
+
+        let order: Vec<_> = (0..3)
+            .map(|_| pool.collect_timeout(WAIT).expect("result").job.source_tl)
+            .collect();
+        assert_eq!(order, vec![1_000_000, 3_000_000, 2_000_000]);
+    }
+
+    #[test]
+    fn timeline_and_source_jobs_are_cancelled_independently() {
+        let (release_tx, release_rx) = mpsc::channel();
+        let (started_tx, started_rx) = mpsc::channel();
+        let backend = GatedBackend {
+            release_rx: Mutex::new(release_rx),
+            started_tx: Mutex::new(started_tx),
+        };
+        let mut pool = PreviewWorkerPool::spawn(Arc::new(backend), 1);
+
+        pool.submit(job(1, 1_000_000));
+        started_rx.recv_timeout(WAIT).expect("first decode started");
+        pool.submit(PreviewJob {
+            kind: PreviewJobKind::Source { asset_id: 2 },
+            ..job(1, 2_000_000)
+        });
+        pool.submit(job(2, 3_000_000));
+        pool.cancel_before(3);
+        for _ in 0..2 {
+            release_tx.send(()).expect("release decode");
+        }
+
+        let order: Vec<_> = (0..2)
+            .map(|_| pool.collect_timeout(WAIT).expect("result").job.source_tl)
+            .collect();
+        assert_eq!(order, vec![1_000_000, 2_000_000]);
+        assert!(!pool.has_outstanding());
+    }
+
+    #[test]
+    fn waker_runs_once_the_result_can_be_collected() {
+        let (release_tx, release_rx) = mpsc::channel();
+        let (started_tx, _started_rx) = mpsc::channel();
+        let backend = GatedBackend {
+            release_rx: Mutex::new(release_rx),
+            started_tx: Mutex::new(started_tx),
+        };
+        let mut pool = PreviewWorkerPool::spawn(Arc::new(backend), 1);
+        let (woken_tx, woken_rx) = mpsc::channel();
+        pool.set_waker(Arc::new(move || {
+            let _ = woken_tx.send(());
+        }));
+
+        pool.submit(job(1, 1_000_000));
+        release_tx.send(()).expect("release decode");
+        woken_rx.recv_timeout(WAIT).expect("waker ran");
+
+        let result = pool.try_collect().expect("result ready when woken");
+        assert_eq!(result.job.source_tl, 1_000_000);
+    }
+
+    fn job(generation: u64, source_tl: i64) -> PreviewJob {
+        PreviewJob {
+            generation,
+            kind: PreviewJobKind::Playhead,
+            quality: PreviewQuality::Full,
+            command_id: None,
+            t_tl: source_tl,
+            path: PathBuf::from("demo.mp4"),
+            source_tl,
+        }
+    }
+
+    /// Backend whose preview decodes block until the test releases them.
+    struct GatedBackend {
+        release_rx: Mutex<mpsc::Receiver<()>>,
+        started_tx: Mutex<mpsc::Sender<()>>,
+    }
+
+    impl MediaBackend for GatedBackend {
+        fn probe(&self, _path: &Path) -> crate::Result<ProbedMedia> {
+            unreachable!("probe is not used by preview workers")
+        }
+
+        fn decode_preview_frame(
+            &self,
+            _path: &Path,
+            _at_seconds: f64,
+        ) -> crate::Result<PreviewFrame> {
+            let _ = self.started_tx.lock().expect("lock started").send(());
+            self.release_rx
+                .lock()
+                .expect("lock release")
+                .recv_timeout(WAIT)
+                .expect("decode released");
+            Ok(PreviewFrame {
+                width: 1,
+                height: 1,
+                format: PreviewPixelFormat::Rgba8,
+                bytes: Arc::from(vec![0_u8; 4]),
+            })
+        }
+
+        fn decode_audio(
+            &self,
+            _path: &Path,
+            _at_seconds: f64,
+            _duration_seconds: f64,
+            format: PcmFormat,
+        ) -> crate::Result<PcmChunk> {
+            Ok(PcmChunk::silence(format, 0))
+        }
+
+        fn export_video(&self, _plan: &ExportVideoPlan) -> crate::Result<()> {
+            unreachable!("export is not used by preview workers")
+        }
+    }
+}
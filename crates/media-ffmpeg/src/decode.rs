@@ -15,6 +15,22 @@ pub struct DecodedVideoFrame {
     pub time_base: Rational,
 }
 
+/// A downscaled RGBA frame decoded from the nearest keyframe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DraftVideoFrame {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+/// Interleaved `f32` PCM samples decoded from the first audio stream.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedAudio {
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub samples: Vec<f32>,
+}
+
 /// Decodes a single video frame at-or-after the requested timestamp.
 ///
 /// This function resolves the target timestamp in the input stream time base
@@ -77,6 +93,172 @@ pub fn decode_video_frame_near_seconds(
     })
 }
 
+/// Decodes the keyframe at-or-before `at_seconds`, scaled to `max_width`.
+///
+/// Unlike [`decode_video_frame_near_seconds`] this seeks the input directly
+/// and decodes keyframes only, so it stays fast on long-GOP footage at the
+/// cost of frame accuracy. Frames narrower than `max_width` keep their size.
+///
+/// # Example
+/// ```no_run
+/// use media_ffmpeg::decode_video_keyframe_near_seconds;
+///
+/// let frame = decode_video_keyframe_near_seconds("sample.mp4", 12.5, 480)
+///     .expect("decode should succeed");
+/// assert!(frame.width <= 480);
+/// ```
+pub fn decode_video_keyframe_near_seconds(
+    path: impl AsRef<Path>,
+    at_seconds: f64,
+    max_width: u32,
+) -> Result<DraftVideoFrame> {
+    if !at_seconds.is_finite() || at_seconds < 0.0 {
+        return Err(MediaFfmpegError::InvalidTimestampSeconds(at_seconds));
+    }
+
+    let path = path.as_ref();
+    let media = probe_media(path)?;
+    let video = media
+        .first_video()
+        .ok_or_else(|| MediaFfmpegError::MissingVideoStream(path.to_path_buf()))?;
+    let (Some(source_width), Some(source_height)) = (video.width, video.height) else {
+        return Err(MediaFfmpegError::MissingVideoDimensions(path.to_path_buf()));
+    };
+    let (width, height) = draft_dimensions(source_width, source_height, max_width);
+
+    let filter = format!("scale={width}:{height},format=rgba");
+    let output = Command::new("ffmpeg")
+        .arg("-hide_banner")
+        .arg("-v")
+        .arg("error")
+        .args(["-skip_frame", "nokey", "-noaccurate_seek", "-ss"])
+        .arg(format!("{at_seconds:.6}"))
+        .arg("-i")
+        .arg(path)
+        .args(["-map", "0:v:0", "-vf"])
+        .arg(&filter)
+        .args(["-frames:v", "1", "-f", "rawvideo", "-pix_fmt", "rgba", "-"])
+        .output()
+        .map_err(|source| MediaFfmpegError::Io {
+            context: "run ffmpeg decode keyframe",
+            source,
+        })?;
+
+    if !output.status.success() {
+        return Err(MediaFfmpegError::CommandFailed {
+            command: format!("ffmpeg decode keyframe {}", path.display()),
+            status: output.status,
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        });
+    }
+
+    let expected_size = width as usize * height as usize * 4;
+    if output.stdout.len() != expected_size {
+        return Err(MediaFfmpegError::Parse {
+            context: "decoded keyframe rgba size",
+            value: format!(
+                "expected {expected_size} bytes, got {}",
+                output.stdout.len()
+            ),
+        });
+    }
+
+    Ok(DraftVideoFrame {
+        width,
+        height,
+        rgba: output.stdout,
+    })
+}
+
+/// Decodes `duration_seconds` of audio starting at `at_seconds`.
+///
+/// The first audio stream is resampled to `sample_rate` / `channels` and
+/// returned as interleaved little-endian `f32` samples. The returned buffer
+/// can be shorter than requested near the end of the stream.
+///
+/// # Example
+/// ```no_run
+/// use media_ffmpeg::decode_audio_pcm_seconds;
+///
+/// let audio = decode_audio_pcm_seconds("sample.mp4", 0.5, 0.1, 48_000, 2)
+///     .expect("decode should succeed");
+/// assert_eq!(audio.channels, 2);
+/// ```
+pub fn decode_audio_pcm_seconds(
+    path: impl AsRef<Path>,
+    at_seconds: f64,
+    duration_seconds: f64,
+    sample_rate: u32,
+    channels: u16,
+) -> Result<DecodedAudio> {
+    if !at_seconds.is_finite() || at_seconds < 0.0 {
+        return Err(MediaFfmpegError::InvalidTimestampSeconds(at_seconds));
+    }
+    if !duration_seconds.is_finite() || duration_seconds <= 0.0 {
+        return Err(MediaFfmpegError::InvalidTimestampSeconds(duration_seconds));
+    }
+    if sample_rate == 0 || channels == 0 {
+        return Err(MediaFfmpegError::InvalidAudioFormat {
+            sample_rate,
+            channels,
+        });
+    }
+
+    let path = path.as_ref();
+    let output = Command::new("ffmpeg")
+        .arg("-hide_banner")
+        .arg("-v")
+        .arg("error")
+        .arg("-ss")
+        .arg(format!("{at_seconds:.6}"))
+        .arg("-i")
+        .arg(path)
+        .arg("-t")
+        .arg(format!("{duration_seconds:.6}"))
+        .args(["-map", "0:a:0", "-ac"])
+        .arg(channels.to_string())
+        .arg("-ar")
+        .arg(sample_rate.to_string())
+        .args(["-f", "f32le", "-"])
+        .output()
+        .map_err(|source| MediaFfmpegError::Io {
+            context: "run ffmpeg decode audio",
+            source,
+        })?;
+
+    if !output.status.success() {
+        return Err(MediaFfmpegError::CommandFailed {
+            command: format!("ffmpeg decode audio {}", path.display()),
+            status: output.status,
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        });
+    }
+
+    Ok(DecodedAudio {
+        sample_rate,
+        channels,
+        samples: f32_samples_from_le_bytes(&output.stdout),
+    })
+}
+
+/// Scales `width`x`height` down to `max_width`, keeping the aspect ratio and
+/// an even height.
+fn draft_dimensions(width: u32, height: u32, max_width: u32) -> (u32, u32) {
+    if max_width == 0 || width <= max_width {
+        return (width, height);
+    }
+    let scaled = u64::from(height) * u64::from(max_width) / u64::from(width);
+    let even = (scaled + scaled % 2).max(2);
+    (max_width, u32::try_from(even).unwrap_or(height))
+}
+
+fn f32_samples_from_le_bytes(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
 fn read_video_best_effort_timestamps(path: &Path) -> Result<Vec<i64>> {
     let output = Command::new("ffprobe")
         .args([
@@ -164,3 +346,25 @@ fn decode_rgba_frame_at_or_after(path: &Path, timestamp: i64) -> Result<Vec<u8>>
 
     Ok(output.stdout)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{draft_dimensions, f32_samples_from_le_bytes};
+
+    #[test]
+    fn draft_dimensions_keep_aspect_ratio_with_even_height() {
+        assert_eq!(draft_dimensions(1920, 1080, 480), (480, 270));
+        assert_eq!(draft_dimensions(1000, 333, 500), (500, 166));
+        assert_eq!(draft_dimensions(320, 240, 480), (320, 240));
+    }
+
+    #[test]
+    fn f32_samples_ignore_trailing_partial_sample() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&0.5_f32.to_le_bytes());
+        bytes.extend_from_slice(&(-1.0_f32).to_le_bytes());
+        bytes.extend_from_slice(&[0, 1]);
+
+        assert_eq!(f32_samples_from_le_bytes(&bytes), vec![0.5, -1.0]);
+    }
+}
@@ -12,6 +12,10 @@ pub enum MediaFfmpegError {
         den: i32,
     },
     InvalidTimestampSeconds(f64),
+    InvalidAudioFormat {
+        sample_rate: u32,
+        channels: u16,
+    },
     MissingVideoStream(PathBuf),
     MissingVideoDimensions(PathBuf),
     InvalidExportRequest {
@@ -42,6 +46,15 @@ impl Display for MediaFfmpegError {
             Self::InvalidTimestampSeconds(value) => {
                 write!(f, "invalid timestamp seconds: {value}")
             }
+            Self::InvalidAudioFormat {
+                sample_rate,
+                channels,
+            } => {
+                write!(
+                    f,
+                    "invalid audio format: {sample_rate} Hz, {channels} channels"
+                )
+            }
             Self::MissingVideoStream(path) => {
                 write!(f, "video stream not found: {}", path.display())
             }
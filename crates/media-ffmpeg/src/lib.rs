@@ -1,11 +1,16 @@
 mod decode;
 mod error;
 mod mux;
+mod playback;
 mod probe;
 mod time;
 
-pub use decode::{DecodedVideoFrame, decode_video_frame_near_seconds};
+pub use decode::{
+    DecodedAudio, DecodedVideoFrame, DraftVideoFrame, decode_audio_pcm_seconds,
+    decode_video_frame_near_seconds, decode_video_keyframe_near_seconds,
+};
 pub use error::{MediaFfmpegError, Result};
 pub use mux::{AudioExportSettings, VideoExportRequest, VideoExportSegment, export_video_mp4};
+pub use playback::PcmPlayback;
 pub use probe::{MediaInfo, StreamInfo, StreamKind, probe_media};
 pub use time::{Rational, rescale};
@@ -30,6 +30,9 @@ pub struct VideoExportSegment {
     pub src_in_audio: Option<i64>,
     pub src_out_audio: Option<i64>,
     pub src_audio_time_base: Option<Rational>,
+    /// Disabled segments keep their trimmed length but are painted black and
+    /// muted.
+    pub enabled: bool,
 }
 
 /// Exports timeline segments into an MP4 by decode -> trim -> re-encode.
@@ -98,13 +101,19 @@ fn build_filter_complex(request: &VideoExportRequest) -> String {
     let has_audio = request.audio.is_some();
     let mut chains = Vec::<String>::with_capacity(request.segments.len() * 2 + 1);
     for (index, segment) in request.segments.iter().enumerate() {
+        let (blank_video, blank_audio) = if segment.enabled {
+            ("", "")
+        } else {
+            (",drawbox=color=black:t=fill", ",volume=0")
+        };
         let video_chain = format!(
-            "[{}:v:0]settb={}/{},trim=start_pts={}:end_pts={},setpts=PTS-STARTPTS[v{}]",
+            "[{}:v:0]settb={}/{},trim=start_pts={}:end_pts={},setpts=PTS-STARTPTS{}[v{}]",
             segment.input_index,
             segment.src_video_time_base.num,
             segment.src_video_time_base.den,
             segment.src_in_video,
             segment.src_out_video,
+            blank_video,
             index
         );
         chains.push(video_chain);
@@ -119,7 +128,7 @@ fn build_filter_complex(request: &VideoExportRequest) -> String {
                 .src_audio_time_base
                 .expect("audio time base must exist when audio export is enabled");
             let audio_chain = format!(
-                "[{}:a:0]asettb={}/{},atrim=start_pts={}:end_pts={},asetpts=PTS-STARTPTS,aresample={}:async=1:first_pts=0,aformat=sample_rates={}:channel_layouts={}[a{}]",
+                "[{}:a:0]asettb={}/{},atrim=start_pts={}:end_pts={},asetpts=PTS-STARTPTS{},aresample={}:async=1:first_pts=0,aformat=sample_rates={}:channel_layouts={}[a{}]",
                 segment.input_index,
                 audio_tb.num,
                 audio_tb.den,
@@ -129,6 +138,7 @@ fn build_filter_complex(request: &VideoExportRequest) -> String {
                 segment
                     .src_out_audio
                     .expect("audio range end must exist when audio export is enabled"),
+                blank_audio,
                 output_audio.sample_rate,
                 output_audio.sample_rate,
                 output_channel_layout,
@@ -235,7 +245,7 @@ fn command_for_display(request: &VideoExportRequest) -> String {
     format!("ffmpeg export {}", request.output_path.display())
 }
 
-fn channel_layout_for_channels(channels: u16) -> Option<&'static str> {
+pub(crate) fn channel_layout_for_channels(channels: u16) -> Option<&'static str> {
     match channels {
         1 => Some("mono"),
         2 => Some("stereo"),
@@ -271,6 +281,7 @@ mod tests {
                     src_in_audio: None,
                     src_out_audio: None,
                     src_audio_time_base: None,
+                    enabled: true,
                 },
                 VideoExportSegment {
                     input_index: 0,
@@ -280,6 +291,7 @@ mod tests {
                     src_in_audio: None,
                     src_out_audio: None,
                     src_audio_time_base: None,
+                    enabled: true,
                 },
             ],
             audio: None,
@@ -308,6 +320,7 @@ mod tests {
                     src_in_audio: Some(48_000),
                     src_out_audio: Some(64_000),
                     src_audio_time_base: Some(Rational::new(1, 48_000).expect("valid")),
+                    enabled: true,
                 },
                 VideoExportSegment {
                     input_index: 0,
@@ -317,6 +330,7 @@ mod tests {
                     src_in_audio: Some(64_000),
                     src_out_audio: Some(105_600),
                     src_audio_time_base: Some(Rational::new(1, 48_000).expect("valid")),
+                    enabled: true,
                 },
             ],
             audio: Some(AudioExportSettings {
@@ -337,6 +351,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn build_filter_complex_blanks_disabled_segments() {
+        let request = VideoExportRequest {
+            inputs: vec![PathBuf::from("in.mp4")],
+            segments: vec![VideoExportSegment {
+                input_index: 0,
+                src_in_video: 90_000,
+                src_out_video: 120_000,
+                src_video_time_base: Rational::new(1, 90_000).expect("valid"),
+                src_in_audio: Some(48_000),
+                src_out_audio: Some(64_000),
+                src_audio_time_base: Some(Rational::new(1, 48_000).expect("valid")),
+                enabled: false,
+            }],
+            audio: Some(AudioExportSettings {
+                sample_rate: 48_000,
+                channels: 2,
+            }),
+            output_path: PathBuf::from("out.mp4"),
+        };
+
+        let filter = build_filter_complex(&request);
+        assert_eq!(
+            filter,
+            "[0:v:0]settb=1/90000,trim=start_pts=90000:end_pts=120000,setpts=PTS-STARTPTS,drawbox=color=black:t=fill[v0];\
+[0:a:0]asettb=1/48000,atrim=start_pts=48000:end_pts=64000,asetpts=PTS-STARTPTS,volume=0,aresample=48000:async=1:first_pts=0,aformat=sample_rates=48000:channel_layouts=stereo[a0]"
+        );
+    }
+
     #[test]
     fn validate_request_rejects_unsupported_audio_channel_layout() {
         let request = VideoExportRequest {
@@ -349,6 +392,7 @@ mod tests {
                 src_in_audio: Some(48_000),
                 src_out_audio: Some(64_000),
                 src_audio_time_base: Some(Rational::new(1, 48_000).expect("valid")),
+                enabled: true,
             }],
             audio: Some(AudioExportSettings {
                 sample_rate: 48_000,
@@ -0,0 +1,102 @@
+use std::io::Write;
+use std::process::{Child, ChildStdin, Command, Stdio};
+
+use crate::error::{MediaFfmpegError, Result};
+use crate::mux::channel_layout_for_channels;
+
+/// Raw PCM playback through an `ffplay` child process.
+///
+/// Samples are written as interleaved little-endian `f32` to the process
+/// stdin. Dropping the value stops playback immediately.
+///
+/// # Example
+/// ```no_run
+/// use media_ffmpeg::PcmPlayback;
+///
+/// let mut playback = PcmPlayback::spawn(48_000, 2).expect("ffplay should start");
+/// playback
+///     .write_samples(&vec![0.0; 48_000 * 2])
+///     .expect("write one second of silence");
+/// ```
+#[derive(Debug)]
+pub struct PcmPlayback {
+    child: Child,
+    stdin: ChildStdin,
+    sample_rate: u32,
+    channels: u16,
+}
+
+impl PcmPlayback {
+    /// Starts an `ffplay` process that reads PCM from stdin.
+    pub fn spawn(sample_rate: u32, channels: u16) -> Result<Self> {
+        let layout =
+            channel_layout_for_channels(channels).ok_or(MediaFfmpegError::InvalidAudioFormat {
+                sample_rate,
+                channels,
+            })?;
+        if sample_rate == 0 {
+            return Err(MediaFfmpegError::InvalidAudioFormat {
+                sample_rate,
+                channels,
+            });
+        }
+
+        let mut child = Command::new("ffplay")
+            .args(["-hide_banner", "-v", "error", "-nodisp", "-autoexit"])
+            .args(["-f", "f32le", "-ar"])
+            .arg(sample_rate.to_string())
+            .arg("-ch_layout")
+            .arg(layout)
+            .arg("-")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|source| MediaFfmpegError::Io {
+                context: "spawn ffplay pcm playback",
+                source,
+            })?;
+        let stdin = child.stdin.take().ok_or(MediaFfmpegError::Io {
+            context: "open ffplay stdin",
+            source: std::io::Error::from(std::io::ErrorKind::BrokenPipe),
+        })?;
+
+        Ok(Self {
+            child,
+            stdin,
+            sample_rate,
+            channels,
+        })
+    }
+
+    /// Returns the output sample rate.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Returns the output channel count.
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    /// Queues interleaved samples for playback.
+    pub fn write_samples(&mut self, samples: &[f32]) -> Result<()> {
+        let mut bytes = Vec::with_capacity(samples.len() * 4);
+        for sample in samples {
+            bytes.extend_from_slice(&sample.to_le_bytes());
+        }
+        self.stdin
+            .write_all(&bytes)
+            .map_err(|source| MediaFfmpegError::Io {
+                context: "write pcm to ffplay",
+                source,
+            })
+    }
+}
+
+impl Drop for PcmPlayback {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
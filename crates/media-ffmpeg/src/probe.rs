@@ -28,6 +28,8 @@ pub struct StreamInfo {
     pub channel_layout: Option<String>,
     pub start_pts: Option<i64>,
     pub duration_ts: Option<i64>,
+    /// `timecode` tag, such as `01:00:00:00` or `00:59:59;28`.
+    pub timecode: Option<String>,
 }
 
 /// Media probe result.
@@ -68,6 +70,27 @@ impl MediaInfo {
             .iter()
             .find(|stream| stream.kind == StreamKind::Audio)
     }
+
+    /// Returns the source start timecode: the first video stream's `timecode`
+    /// tag, else the first tagged stream's (QuickTime keeps it on a `tmcd`
+    /// data stream).
+    ///
+    /// # Example
+    /// ```no_run
+    /// use media_ffmpeg::probe_media;
+    ///
+    /// let info = probe_media("sample.mov").expect("probe should succeed");
+    /// println!("{:?}", info.start_timecode());
+    /// ```
+    pub fn start_timecode(&self) -> Option<&str> {
+        self.first_video()
+            .and_then(|stream| stream.timecode.as_deref())
+            .or_else(|| {
+                self.streams
+                    .iter()
+                    .find_map(|stream| stream.timecode.as_deref())
+            })
+    }
 }
 
 /// Probes a media file via `ffprobe`.
@@ -87,7 +110,7 @@ pub fn probe_media(path: impl AsRef<Path>) -> Result<MediaInfo> {
             "-v",
             "error",
             "-show_entries",
-            "stream=index,codec_type,codec_name,time_base,width,height,r_frame_rate,pix_fmt,sample_rate,channels,channel_layout,start_pts,duration_ts",
+            "stream=index,codec_type,codec_name,time_base,width,height,r_frame_rate,pix_fmt,sample_rate,channels,channel_layout,start_pts,duration_ts:stream_tags=timecode",
             "-of",
             "compact=p=0:nk=0",
         ])
@@ -181,6 +204,10 @@ fn parse_stream_line(line: &str) -> Result<StreamInfo> {
             .map(|value| value.to_string()),
         start_pts: parse_optional_i64(map.get("start_pts").copied(), "start_pts")?,
         duration_ts: parse_optional_i64(map.get("duration_ts").copied(), "duration_ts")?,
+        timecode: map
+            .get("tag:timecode")
+            .filter(|value| !value.is_empty())
+            .map(|value| value.to_string()),
     })
 }
 
@@ -273,3 +300,23 @@ fn unquote(value: &str) -> &str {
 fn command_for_display(context: &str, path: &Path) -> String {
     format!("{context}: ffprobe {}", path.display())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{StreamKind, parse_stream_line};
+
+    #[test]
+    fn stream_line_reads_the_timecode_tag() {
+        let stream = parse_stream_line(
+            "index=0|codec_name=prores|codec_type=video|time_base=1/30000|width=1920|height=1080|r_frame_rate=30000/1001|start_pts=0|duration_ts=30030|tag:timecode=00:59:59;28",
+        )
+        .expect("stream line should parse");
+
+        assert_eq!(stream.kind, StreamKind::Video);
+        assert_eq!(stream.timecode.as_deref(), Some("00:59:59;28"));
+
+        let untagged = parse_stream_line("index=1|codec_type=audio|time_base=1/48000")
+            .expect("stream line should parse");
+        assert_eq!(untagged.timecode, None);
+    }
+}
@@ -115,6 +115,7 @@ fn export_video_mp4_trims_and_concatenates_av_segments() {
                 src_in_audio: Some(seg0_audio_start),
                 src_out_audio: Some(seg0_audio_end),
                 src_audio_time_base: Some(audio_tb),
+                enabled: true,
             },
             VideoExportSegment {
                 input_index: 0,
@@ -124,6 +125,7 @@ fn export_video_mp4_trims_and_concatenates_av_segments() {
                 src_in_audio: Some(seg1_audio_start),
                 src_out_audio: Some(seg1_audio_end),
                 src_audio_time_base: Some(audio_tb),
+                enabled: true,
             },
         ],
         audio: Some(AudioExportSettings {
@@ -186,6 +188,7 @@ fn export_video_mp4_handles_input_with_non_zero_start_pts() {
             src_in_audio: None,
             src_out_audio: None,
             src_audio_time_base: None,
+            enabled: true,
         }],
         audio: None,
         output_path: output.clone(),
@@ -1,7 +1,9 @@
 use std::path::PathBuf;
 use std::process::Command;
 
-use media_ffmpeg::{Rational, decode_video_frame_near_seconds, probe_media, rescale};
+use media_ffmpeg::{
+    Rational, decode_audio_pcm_seconds, decode_video_frame_near_seconds, probe_media, rescale,
+};
 
 fn make_sample_video() -> PathBuf {
     let output = std::env::temp_dir().join(format!(
@@ -99,3 +101,20 @@ fn rescale_preserves_non_integer_rate_precision() {
     );
     assert_eq!(micros, 33_367);
 }
+
+#[test]
+fn decode_audio_pcm_resamples_requested_window_to_interleaved_f32() {
+    let sample = make_sample_video();
+
+    let audio = decode_audio_pcm_seconds(&sample, 0.5, 0.1, 44_100, 2)
+        .expect("audio decode should succeed");
+
+    assert_eq!(audio.sample_rate, 44_100);
+    assert_eq!(audio.channels, 2);
+    let frames = audio.samples.len() / 2;
+    assert!(
+        (4_300..=4_500).contains(&frames),
+        "expected about 0.1s of audio, got {frames} frames"
+    );
+    assert!(audio.samples.iter().any(|sample| sample.abs() > 0.01));
+}
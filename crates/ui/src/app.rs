@@ -1,52 +1,185 @@
 use std::path::PathBuf;
 use std::{cmp, sync::mpsc::TrySendError};
 
-use engine::{Command, EngineErrorKind, Event, ExportSettings, ProjectSnapshot};
+use engine::api::MediaAssetSummary;
+use engine::{
+    Command, CommandId, EditPointDirection, EngineErrorEvent, EngineErrorKind, Event, ExportRange,
+    ExportSettings, FrameGrid, MediaPathStyle, PasteMode, PreviewQuality, ProjectSnapshot,
+    ReplaceAlignment, TaggedCommand, TaggedEvent, Timecode,
+};
+use iced::keyboard::{self, Key, Modifiers, key::Named};
 use iced::widget::canvas;
-use iced::widget::{button, column, container, row, text, text_input};
+use iced::widget::{Column, button, column, container, row, slider, text, text_input};
 use iced::{Element, Length, Subscription, Task};
 
 use crate::bridge::{BridgeEvent, EngineCommandSender, engine_subscription};
 use crate::widgets::{preview, timeline};
 
-const IDLE_WARM_MAX_ROUNDS: u16 = 120;
+const SHIFT_STEP_FRAMES: i64 = 10;
 
 /// UI messages handled by the iced app update loop.
 #[derive(Debug, Clone)]
 pub enum Message {
     ImportPathChanged(String),
     ImportPressed,
+    OpenProjectPressed,
+    RestoreRecoveryPressed,
+    DiscardRecoveryPressed,
+    /// Saves the project to the path input.
+    SaveProjectPressed(MediaPathStyle),
+    AddToBinPressed,
+    /// Relinks a bin asset to the file in the path input.
+    RelinkPressed(u64),
+    /// Searches the directory in the path input for offline assets.
+    RelinkFolderPressed,
+    /// Edits a bin asset's marked source range in at the playhead.
+    BinEditPressed {
+        asset_id: u64,
+        mode: PasteMode,
+    },
+    /// Loads a bin asset into the source monitor.
+    SourceAssetOpened(u64),
+    SourceScrubbed(i64),
+    SourceMarkInPressed,
+    SourceMarkOutPressed,
+    /// Edits the source monitor asset in at the playhead.
+    SourceEditPressed(PasteMode),
+    /// Points the single selected segment at the source monitor asset.
+    ReplaceSegmentPressed(ReplaceAlignment),
+    MatchFramePressed,
     ExportPathChanged(String),
     ExportPressed,
+    /// Writes the timeline as a CMX3600 EDL to the export path.
+    ExportEdlPressed,
+    /// Rebuilds the timeline from the EDL in the path input.
+    ImportEdlPressed,
+    /// Writes the timeline as OpenTimelineIO to the export path.
+    ExportOtioPressed,
+    /// Rebuilds the timeline from the `.otio` file in the path input.
+    ImportOtioPressed,
+    /// Writes the timeline as Final Cut Pro 7 XML to the export path.
+    ExportFcpXmlPressed,
+    /// Rebuilds the timeline from the CSV/JSON cut list in the path input.
+    ImportCutListPressed,
     SplitPressed,
     CutPressed,
+    PlayPausePressed,
+    ShuttleReverse,
+    ShuttlePause,
+    ShuttleForward,
+    StepFrame(i64),
+    JumpToEditPoint(EditPointDirection),
+    MarkInPressed,
+    MarkOutPressed,
+    ClearInOutPressed,
+    LoopPlaybackToggled,
+    SnapToFramesToggled,
+    LiftPressed,
+    ExtractPressed,
+    ExportInOutPressed,
+    SelectAllPressed,
+    ClearSelectionPressed,
+    DeleteSelectionPressed {
+        ripple: bool,
+    },
+    SetSelectionEnabledPressed(bool),
+    ToggleSelectionEnabledPressed,
+    CopyPressed,
+    PastePressed(PasteMode),
+    DuplicatePressed,
     TimelineScrubbed(i64),
     TimelineSplitRequested(i64),
     TimelineCutRequested(i64),
-    TimelineSegmentMoveRequested { segment_id: u64, new_start_tl: i64 },
-    TimelineSegmentTrimStartRequested { segment_id: u64, new_start_tl: i64 },
-    TimelineSegmentTrimEndRequested { segment_id: u64, new_end_tl: i64 },
+    TimelineSegmentMoveRequested {
+        segment_id: u64,
+        new_start_tl: i64,
+    },
+    TimelineSegmentTrimStartRequested {
+        segment_id: u64,
+        new_start_tl: i64,
+    },
+    TimelineSegmentTrimEndRequested {
+        segment_id: u64,
+        new_end_tl: i64,
+    },
+    TimelineSegmentSelected {
+        segment_id: u64,
+        mode: timeline::SelectMode,
+    },
+    TimelineRangeSelected {
+        start_tl: i64,
+        end_tl: i64,
+    },
     Bridge(BridgeEvent),
 }
 
+/// Split or cut awaiting its `CommandCompleted`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PendingEdit {
+    Split { at_tl: i64 },
+    Cut { at_tl: i64 },
+}
+
+impl PendingEdit {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Split { .. } => "split",
+            Self::Cut { .. } => "cut",
+        }
+    }
+
+    fn at_tl(self) -> i64 {
+        match self {
+            Self::Split { at_tl } | Self::Cut { at_tl } => at_tl,
+        }
+    }
+
+    /// Expected rejections, such as splitting on a boundary, read as skipped.
+    fn failure_status(self, error: &EngineErrorEvent) -> String {
+        let skipped = match self {
+            Self::Split { .. } => matches!(
+                error.kind,
+                EngineErrorKind::SplitPointAtBoundary | EngineErrorKind::SegmentNotFound
+            ),
+            Self::Cut { .. } => matches!(error.kind, EngineErrorKind::SegmentNotFound),
+        };
+        format!(
+            "{} {} at {}: {}",
+            self.name(),
+            if skipped { "skipped" } else { "failed" },
+            self.at_tl(),
+            error.message
+        )
+    }
+}
+
 /// Root UI state for Step 6 bootstrap.
 pub struct AppState {
     engine_tx: Option<EngineCommandSender>,
     project: Option<ProjectSnapshot>,
     preview_image: Option<preview::PreviewImage>,
+    /// Bin asset shown in the source monitor.
+    source_asset: Option<u64>,
+    /// Source monitor position, in timeline ticks from the asset start.
+    source_t_src: i64,
+    source_preview_image: Option<preview::PreviewImage>,
+    /// Newest source monitor request; source events of older commands are stale.
+    latest_source_seek_id: Option<CommandId>,
     import_path: String,
     export_path: String,
     playhead_tl: i64,
-    pending_playhead_tl: Option<i64>,
-    latest_requested_playhead_tl: Option<i64>,
-    playhead_request_in_flight: bool,
-    idle_warm_target_tl: Option<i64>,
-    idle_warm_rounds: u16,
+    playing: bool,
+    next_command_id: u64,
+    /// Newest user seek; playhead events of older commands are stale.
+    latest_seek_id: Option<CommandId>,
+    /// Whether the newest user seek has not completed yet.
+    seek_awaiting_completion: bool,
     loaded_preview_ranges_tl: Vec<(i64, i64)>,
-    pending_split_tl: Option<i64>,
-    pending_cut_tl: Option<i64>,
+    pending_edit: Option<(CommandId, PendingEdit)>,
     last_split_tl: Option<i64>,
     timeline_cache: canvas::Cache,
+    /// Recovery copy the engine offered at startup or on open.
+    recovery_offer: Option<PathBuf>,
     status: String,
 }
 
@@ -58,19 +191,22 @@ impl AppState {
                 engine_tx: None,
                 project: None,
                 preview_image: None,
+                source_asset: None,
+                source_t_src: 0,
+                source_preview_image: None,
+                latest_source_seek_id: None,
                 import_path: String::new(),
                 export_path: String::new(),
                 playhead_tl: 0,
-                pending_playhead_tl: None,
-                latest_requested_playhead_tl: None,
-                playhead_request_in_flight: false,
-                idle_warm_target_tl: None,
-                idle_warm_rounds: 0,
+                playing: false,
+                next_command_id: 1,
+                latest_seek_id: None,
+                seek_awaiting_completion: false,
                 loaded_preview_ranges_tl: Vec::new(),
-                pending_split_tl: None,
-                pending_cut_tl: None,
+                pending_edit: None,
                 last_split_tl: None,
                 timeline_cache: canvas::Cache::new(),
+                recovery_offer: None,
                 status: String::from("starting engine bridge"),
             },
             Task::none(),
@@ -84,60 +220,331 @@ impl AppState {
                 self.import_path = path;
             }
             Message::ImportPressed => {
-                let path = self.import_path.trim().to_owned();
-                if path.is_empty() {
-                    self.status = String::from("import path is empty");
-                } else if self.send_command(Command::Import {
-                    path: PathBuf::from(&path),
-                }) {
-                    self.pending_split_tl = None;
-                    self.pending_cut_tl = None;
+                if let Some(path) = self.send_path_command(|path| Command::Import { path }) {
+                    self.pending_edit = None;
                     self.last_split_tl = None;
                     self.status = format!("importing {}", path);
                 }
             }
+            Message::OpenProjectPressed => {
+                if let Some(path) = self.send_path_command(|path| Command::OpenProject { path }) {
+                    self.pending_edit = None;
+                    self.last_split_tl = None;
+                    self.status = format!("opening {}", path);
+                }
+            }
+            Message::SaveProjectPressed(paths) => {
+                if let Some(path) =
+                    self.send_path_command(|path| Command::SaveProject { path, paths })
+                {
+                    self.status = format!("saving {}", path);
+                }
+            }
+            Message::AddToBinPressed => {
+                if let Some(path) = self.send_path_command(|path| Command::AddAsset { path }) {
+                    self.status = format!("adding {} to bin", path);
+                }
+            }
+            Message::RelinkPressed(asset_id) => {
+                if let Some(path) =
+                    self.send_path_command(|new_path| Command::RelinkAsset { asset_id, new_path })
+                {
+                    self.status = format!("relinking asset {asset_id} to {}", path);
+                }
+            }
+            Message::RelinkFolderPressed => {
+                if let Some(path) =
+                    self.send_path_command(|dir| Command::RelinkFromDirectory { dir })
+                {
+                    self.status = format!("searching {} for offline media", path);
+                }
+            }
+            Message::BinEditPressed { asset_id, mode } => {
+                let at_tl = self.clamp_playhead(self.playhead_tl);
+                let command = match mode {
+                    PasteMode::Insert => Command::InsertFromAsset { asset_id, at_tl },
+                    PasteMode::Overwrite => Command::OverwriteFromAsset { asset_id, at_tl },
+                };
+                if self.send_command(command).is_some() {
+                    self.status = match mode {
+                        PasteMode::Insert => format!("insert of asset {asset_id} at {at_tl}"),
+                        PasteMode::Overwrite => {
+                            format!("overwrite with asset {asset_id} at {at_tl}")
+                        }
+                    };
+                }
+            }
+            Message::SourceAssetOpened(asset_id) => {
+                let t_src = self
+                    .project
+                    .as_ref()
+                    .and_then(|snapshot| snapshot.assets.iter().find(|asset| asset.id == asset_id))
+                    .and_then(|asset| asset.source_in_tl)
+                    .unwrap_or(0);
+                if self.source_asset != Some(asset_id) {
+                    self.source_asset = Some(asset_id);
+                    self.source_preview_image = None;
+                }
+                self.source_t_src = t_src;
+                self.send_source_command(Command::SetSourcePlayhead { asset_id, t_src });
+            }
+            Message::SourceScrubbed(t_src) => {
+                if let Some(asset_id) = self.source_asset {
+                    self.source_t_src = t_src;
+                    self.send_source_command(Command::SetSourcePlayhead { asset_id, t_src });
+                }
+            }
+            Message::SourceMarkInPressed => {
+                if let Some(asset_id) = self.source_asset {
+                    let at_src = self.source_t_src;
+                    if self
+                        .send_command(Command::SetSourceInPoint { asset_id, at_src })
+                        .is_some()
+                    {
+                        self.status = format!("source in requested at {at_src}");
+                    }
+                } else {
+                    self.status = String::from("no asset in the source monitor");
+                }
+            }
+            Message::SourceMarkOutPressed => {
+                if let Some(asset_id) = self.source_asset {
+                    let at_src = self.source_t_src;
+                    if self
+                        .send_command(Command::SetSourceOutPoint { asset_id, at_src })
+                        .is_some()
+                    {
+                        self.status = format!("source out requested at {at_src}");
+                    }
+                } else {
+                    self.status = String::from("no asset in the source monitor");
+                }
+            }
+            Message::SourceEditPressed(mode) => {
+                if let Some(asset_id) = self.source_asset {
+                    return self.update(Message::BinEditPressed { asset_id, mode });
+                }
+                self.status = String::from("no asset in the source monitor");
+            }
+            Message::ReplaceSegmentPressed(align) => match (self.source_asset, self.selection()) {
+                (Some(asset_id), &[segment_id]) => {
+                    if self
+                        .send_command(Command::ReplaceSegmentMedia {
+                            segment_id,
+                            asset_id,
+                            align,
+                        })
+                        .is_some()
+                    {
+                        self.status =
+                            format!("replacing segment {segment_id} media with asset {asset_id}");
+                    }
+                }
+                (None, _) => self.status = String::from("no asset in the source monitor"),
+                _ => self.status = String::from("select one segment to replace"),
+            },
+            Message::MatchFramePressed => {
+                if self.send_source_command(Command::MatchFrame) {
+                    self.status = format!("match frame at {}", self.playhead_tl);
+                }
+            }
             Message::ExportPathChanged(path) => {
                 self.export_path = path;
             }
             Message::ExportPressed => {
-                let path = self.export_path.trim().to_owned();
-                if path.is_empty() {
-                    self.status = String::from("export path is empty");
-                } else if self.send_command(Command::Export {
-                    path: PathBuf::from(&path),
-                    settings: ExportSettings::default(),
-                }) {
-                    self.status = format!("export requested: {}", path);
+                self.request_export(ExportRange::Timeline);
+            }
+            Message::ExportInOutPressed => {
+                self.request_export(ExportRange::InOut);
+            }
+            Message::ExportEdlPressed => {
+                self.request_export_to(|path| Command::ExportEdl { path });
+            }
+            Message::ImportEdlPressed => {
+                if let Some(path) = self.send_path_command(|path| Command::ImportEdl { path }) {
+                    self.pending_edit = None;
+                    self.status = format!("importing EDL {}", path);
+                }
+            }
+            Message::ExportOtioPressed => {
+                self.request_export_to(|path| Command::ExportOtio { path });
+            }
+            Message::ImportOtioPressed => {
+                if let Some(path) = self.send_path_command(|path| Command::ImportOtio { path }) {
+                    self.pending_edit = None;
+                    self.status = format!("importing OTIO {}", path);
+                }
+            }
+            Message::ExportFcpXmlPressed => {
+                self.request_export_to(|path| Command::ExportFcpXml { path });
+            }
+            Message::ImportCutListPressed => {
+                if let Some(path) = self.send_path_command(|path| Command::ImportCutList { path }) {
+                    self.pending_edit = None;
+                    self.status = format!("importing cut list {}", path);
                 }
             }
             Message::SplitPressed => {
                 let clamped = self.clamp_playhead(self.playhead_tl);
                 self.playhead_tl = clamped;
                 self.request_split(clamped);
-                self.queue_playhead_from_user(clamped);
+                self.send_navigation_command(Command::SetPlayhead { t_tl: clamped });
             }
             Message::CutPressed => {
                 let clamped = self.clamp_playhead(self.playhead_tl);
                 self.playhead_tl = clamped;
                 self.request_cut(clamped);
-                self.queue_playhead_from_user(clamped);
+                self.send_navigation_command(Command::SetPlayhead { t_tl: clamped });
+            }
+            Message::PlayPausePressed => {
+                let command = if self.playing {
+                    Command::Pause
+                } else {
+                    Command::Play
+                };
+                if self.send_command(command).is_some() {
+                    self.status = if self.playing {
+                        String::from("pause requested")
+                    } else {
+                        String::from("play requested")
+                    };
+                }
+            }
+            Message::ShuttleReverse => {
+                let _ = self.send_command(Command::ShuttleReverse);
+            }
+            Message::ShuttlePause => {
+                let _ = self.send_command(Command::Pause);
+            }
+            Message::ShuttleForward => {
+                let _ = self.send_command(Command::ShuttleForward);
+            }
+            Message::StepFrame(delta) => {
+                self.send_navigation_command(Command::StepFrame { delta });
+            }
+            Message::JumpToEditPoint(direction) => {
+                self.send_navigation_command(Command::JumpToEditPoint { direction });
+            }
+            Message::MarkInPressed => {
+                let at_tl = self.clamp_playhead(self.playhead_tl);
+                if self.send_command(Command::SetInPoint { at_tl }).is_some() {
+                    self.status = format!("in point requested at {at_tl}");
+                }
+            }
+            Message::MarkOutPressed => {
+                let at_tl = self.clamp_playhead(self.playhead_tl);
+                if self.send_command(Command::SetOutPoint { at_tl }).is_some() {
+                    self.status = format!("out point requested at {at_tl}");
+                }
+            }
+            Message::ClearInOutPressed => {
+                let _ = self.send_command(Command::ClearInOut);
+            }
+            Message::LoopPlaybackToggled => {
+                let enabled = !self.loop_playback();
+                let _ = self.send_command(Command::SetLoopPlayback { enabled });
+            }
+            Message::SnapToFramesToggled => {
+                let enabled = !self.snap_to_frames();
+                let _ = self.send_command(Command::SetSnapToFrames { enabled });
+            }
+            Message::LiftPressed => {
+                if self
+                    .send_command(Command::DeleteInOutRange { ripple: false })
+                    .is_some()
+                {
+                    self.status = String::from("lift requested");
+                }
+            }
+            Message::ExtractPressed => {
+                if self
+                    .send_command(Command::DeleteInOutRange { ripple: true })
+                    .is_some()
+                {
+                    self.status = String::from("extract requested");
+                }
+            }
+            Message::SelectAllPressed => {
+                let _ = self.send_command(Command::SelectAll);
+            }
+            Message::ClearSelectionPressed => {
+                let _ = self.send_command(Command::ClearSelection);
+            }
+            Message::DeleteSelectionPressed { ripple } => {
+                if self
+                    .send_command(Command::DeleteSelection { ripple })
+                    .is_some()
+                {
+                    self.status = if ripple {
+                        String::from("ripple delete requested")
+                    } else {
+                        String::from("delete requested")
+                    };
+                }
+            }
+            Message::SetSelectionEnabledPressed(enabled) => {
+                if self
+                    .send_command(Command::SetSelectionEnabled { enabled })
+                    .is_some()
+                {
+                    self.status = if enabled {
+                        String::from("enable requested")
+                    } else {
+                        String::from("disable requested")
+                    };
+                }
+            }
+            Message::ToggleSelectionEnabledPressed => {
+                // Any enabled segment in the selection means "disable".
+                let enable = self.project.as_ref().is_some_and(|snapshot| {
+                    !snapshot
+                        .segments
+                        .iter()
+                        .any(|segment| segment.enabled && snapshot.selection.contains(&segment.id))
+                });
+                return self.update(Message::SetSelectionEnabledPressed(enable));
+            }
+            Message::CopyPressed => {
+                if self.send_command(Command::CopySelection).is_some() {
+                    self.status = format!("copying {} segment(s)", self.selection().len());
+                }
+            }
+            Message::PastePressed(mode) => {
+                if self.send_command(Command::Paste { mode }).is_some() {
+                    self.status = match mode {
+                        PasteMode::Insert => String::from("paste requested"),
+                        PasteMode::Overwrite => String::from("overwrite paste requested"),
+                    };
+                }
+            }
+            Message::DuplicatePressed => {
+                if let &[segment_id] = self.selection() {
+                    if self
+                        .send_command(Command::DuplicateSegment { segment_id })
+                        .is_some()
+                    {
+                        self.status = format!("duplicate of segment {segment_id} requested");
+                    }
+                } else {
+                    self.status = String::from("select one segment to duplicate");
+                }
             }
             Message::TimelineScrubbed(t_tl) => {
                 let clamped = self.clamp_playhead(t_tl);
                 self.playhead_tl = clamped;
-                self.queue_playhead_from_user(clamped);
+                self.send_navigation_command(Command::SetPlayhead { t_tl: clamped });
             }
             Message::TimelineSplitRequested(at_tl) => {
                 let clamped = self.clamp_playhead(at_tl);
                 self.playhead_tl = clamped;
                 self.request_split(clamped);
-                self.queue_playhead_from_user(clamped);
+                self.send_navigation_command(Command::SetPlayhead { t_tl: clamped });
             }
             Message::TimelineCutRequested(at_tl) => {
                 let clamped = self.clamp_playhead(at_tl);
                 self.playhead_tl = clamped;
                 self.request_cut(clamped);
-                self.queue_playhead_from_user(clamped);
+                self.send_navigation_command(Command::SetPlayhead { t_tl: clamped });
             }
             Message::TimelineSegmentMoveRequested {
                 segment_id,
@@ -157,25 +564,43 @@ impl AppState {
             } => {
                 self.request_trim_segment_end(segment_id, new_end_tl);
             }
+            Message::TimelineSegmentSelected { segment_id, mode } => {
+                self.request_select_segment(segment_id, mode);
+            }
+            Message::TimelineRangeSelected { start_tl, end_tl } => {
+                let _ = self.send_command(Command::SelectRange {
+                    start_tl,
+                    end_tl,
+                    extend: true,
+                });
+            }
             Message::Bridge(BridgeEvent::Ready(sender)) => {
                 self.engine_tx = Some(sender);
                 self.status = String::from("engine ready");
-                self.flush_playhead_request();
+                let _ = self.send_command(Command::CheckForRecovery);
+            }
+            Message::RestoreRecoveryPressed => {
+                if self.send_command(Command::RestoreRecovery).is_some() {
+                    self.recovery_offer = None;
+                    self.status = String::from("restoring recovery copy");
+                }
+            }
+            Message::DiscardRecoveryPressed => {
+                if self.send_command(Command::DiscardRecovery).is_some() {
+                    self.recovery_offer = None;
+                    self.status = String::from("recovery copy discarded");
+                }
             }
             Message::Bridge(BridgeEvent::Event(event)) => {
-                self.apply_engine_event(event);
+                self.apply_engine_event(*event);
             }
             Message::Bridge(BridgeEvent::Disconnected) => {
                 self.status = String::from("engine event channel closed");
                 self.engine_tx = None;
-                self.pending_playhead_tl = None;
-                self.latest_requested_playhead_tl = None;
-                self.playhead_request_in_flight = false;
-                self.idle_warm_target_tl = None;
-                self.idle_warm_rounds = 0;
+                self.playing = false;
+                self.seek_awaiting_completion = false;
                 self.loaded_preview_ranges_tl.clear();
-                self.pending_split_tl = None;
-                self.pending_cut_tl = None;
+                self.pending_edit = None;
                 self.last_split_tl = None;
             }
         }
@@ -183,189 +608,305 @@ impl AppState {
         Task::none()
     }
 
-    fn send_command(&mut self, command: Command) -> bool {
+    /// Wraps `command` with a fresh id for correlating its events.
+    fn tag_command(&mut self, command: Command) -> TaggedCommand {
+        let id = CommandId(self.next_command_id);
+        self.next_command_id += 1;
+        TaggedCommand { id, command }
+    }
+
+    fn send_command(&mut self, command: Command) -> Option<CommandId> {
+        let tagged = self.tag_command(command);
+        let id = tagged.id;
         if let Some(sender) = &self.engine_tx {
-            match sender.try_send(command) {
-                Ok(()) => true,
+            match sender.try_send(tagged) {
+                Ok(()) => Some(id),
                 Err(TrySendError::Full(_)) => {
                     self.status = String::from("engine command queue is full");
-                    false
+                    None
                 }
                 Err(TrySendError::Disconnected(_)) => {
                     self.status = String::from("engine command channel closed");
                     self.engine_tx = None;
-                    self.playhead_request_in_flight = false;
-                    false
+                    None
                 }
             }
         } else {
             self.status = String::from("engine is not ready");
-            false
+            None
         }
     }
 
-    fn queue_playhead(&mut self, t_tl: i64, update_latest: bool) {
-        self.pending_playhead_tl = Some(t_tl);
-        if update_latest {
-            self.latest_requested_playhead_tl = Some(t_tl);
+    /// Sends an engine-side playhead move; it supersedes earlier seeks.
+    fn send_navigation_command(&mut self, command: Command) {
+        if let Some(id) = self.send_command(command) {
+            self.latest_seek_id = Some(id);
+            self.seek_awaiting_completion = true;
         }
-        self.flush_playhead_request();
     }
 
-    fn queue_playhead_from_user(&mut self, t_tl: i64) {
-        self.idle_warm_target_tl = None;
-        self.idle_warm_rounds = 0;
-        self.queue_playhead(t_tl, true);
+    /// Sends a source monitor move; it supersedes earlier source requests.
+    ///
+    /// Returns whether the command was sent.
+    fn send_source_command(&mut self, command: Command) -> bool {
+        let Some(id) = self.send_command(command) else {
+            return false;
+        };
+        self.latest_source_seek_id = Some(id);
+        true
     }
 
-    fn queue_playhead_for_idle_warm(&mut self, t_tl: i64) {
-        self.queue_playhead(t_tl, false);
+    fn source_asset_summary(&self) -> Option<&MediaAssetSummary> {
+        let asset_id = self.source_asset?;
+        self.project
+            .as_ref()?
+            .assets
+            .iter()
+            .find(|asset| asset.id == asset_id)
     }
 
-    fn request_split(&mut self, at_tl: i64) {
-        if self.pending_split_tl.is_some() {
-            self.status = String::from("split request is already pending");
-            return;
-        }
-        if self.pending_cut_tl.is_some() {
-            self.status = String::from("cut request is already pending");
-            return;
+    /// Record timecode at the playhead; 30 fps non-drop without a project.
+    fn playhead_timecode(&self) -> Timecode {
+        match &self.project {
+            Some(snapshot) => snapshot.timecode_at(self.playhead_tl),
+            None => Timecode::from_tl(self.playhead_tl, FrameGrid::DEFAULT, false),
         }
+    }
+
+    fn request_export(&mut self, range: ExportRange) {
+        self.request_export_to(|path| Command::Export {
+            path,
+            settings: ExportSettings { range },
+        });
+    }
 
-        if self.send_command(Command::Split { at_tl }) {
-            self.pending_split_tl = Some(at_tl);
-            self.status = format!("split requested at {}", at_tl);
+    /// Sends the command built from the trimmed export path input.
+    fn request_export_to(&mut self, command: impl FnOnce(PathBuf) -> Command) {
+        let path = self.export_path.trim().to_owned();
+        if path.is_empty() {
+            self.status = String::from("export path is empty");
+        } else if self.send_command(command(PathBuf::from(&path))).is_some() {
+            self.status = format!("export requested: {}", path);
         }
     }
 
+    fn loop_playback(&self) -> bool {
+        self.project
+            .as_ref()
+            .is_some_and(|snapshot| snapshot.loop_playback)
+    }
+
+    fn snap_to_frames(&self) -> bool {
+        self.project
+            .as_ref()
+            .is_some_and(|snapshot| snapshot.snap_to_frames)
+    }
+
+    fn request_split(&mut self, at_tl: i64) {
+        self.request_edit(PendingEdit::Split { at_tl }, Command::Split { at_tl });
+    }
+
     fn request_cut(&mut self, at_tl: i64) {
-        if self.pending_cut_tl.is_some() {
-            self.status = String::from("cut request is already pending");
+        self.request_edit(PendingEdit::Cut { at_tl }, Command::Cut { at_tl });
+    }
+
+    /// Sends a split or cut unless another one still awaits completion.
+    fn request_edit(&mut self, edit: PendingEdit, command: Command) {
+        if let Some((_, pending)) = self.pending_edit {
+            self.status = format!("{} request is already pending", pending.name());
             return;
         }
-        if self.pending_split_tl.is_some() {
-            self.status = String::from("split request is already pending");
-            return;
+
+        if let Some(id) = self.send_command(command) {
+            self.pending_edit = Some((id, edit));
+            self.status = format!("{} requested at {}", edit.name(), edit.at_tl());
         }
+    }
 
-        if self.send_command(Command::Cut { at_tl }) {
-            self.pending_cut_tl = Some(at_tl);
-            self.status = format!("cut requested at {}", at_tl);
+    /// Sends the command built from the trimmed path input.
+    ///
+    /// Returns the path on success; an empty input only updates the status.
+    fn send_path_command(&mut self, command: impl FnOnce(PathBuf) -> Command) -> Option<String> {
+        let path = self.import_path.trim().to_owned();
+        if path.is_empty() {
+            self.status = String::from("import path is empty");
+            return None;
         }
+        self.send_command(command(PathBuf::from(&path)))?;
+        Some(path)
+    }
+
+    fn selection(&self) -> &[u64] {
+        self.project
+            .as_ref()
+            .map_or(&[], |snapshot| snapshot.selection.as_slice())
+    }
+
+    fn request_select_segment(&mut self, segment_id: u64, mode: timeline::SelectMode) {
+        let command = match mode {
+            timeline::SelectMode::Replace => Command::Select {
+                segment_ids: vec![segment_id],
+            },
+            timeline::SelectMode::Add => Command::AddToSelection {
+                segment_ids: vec![segment_id],
+            },
+            timeline::SelectMode::Toggle => Command::ToggleSelection { segment_id },
+        };
+        let _ = self.send_command(command);
     }
 
+    /// Dragging one segment of a multi-segment selection moves all of them.
     fn request_move_segment(&mut self, segment_id: u64, new_start_tl: i64) {
-        if self.send_command(Command::MoveSegment {
-            segment_id,
-            new_start_tl,
-        }) {
+        let selection = self.selection();
+        let grouped_start = if selection.len() > 1 && selection.contains(&segment_id) {
+            self.project
+                .as_ref()
+                .and_then(|snapshot| {
+                    snapshot
+                        .segments
+                        .iter()
+                        .find(|segment| segment.id == segment_id)
+                })
+                .map(|segment| segment.timeline_start)
+        } else {
+            None
+        };
+        if let Some(start_tl) = grouped_start {
+            let delta_tl = new_start_tl - start_tl;
+            if self
+                .send_command(Command::MoveSelection { delta_tl })
+                .is_some()
+            {
+                self.status = format!("selection moved by {delta_tl}");
+            }
+            return;
+        }
+
+        if self
+            .send_command(Command::MoveSegment {
+                segment_id,
+                new_start_tl,
+            })
+            .is_some()
+        {
             self.status = format!("segment {} moved to {}", segment_id, new_start_tl);
         }
     }
 
     fn request_trim_segment_start(&mut self, segment_id: u64, new_start_tl: i64) {
-        if self.send_command(Command::TrimSegmentStart {
-            segment_id,
-            new_start_tl,
-        }) {
+        if self
+            .send_command(Command::TrimSegmentStart {
+                segment_id,
+                new_start_tl,
+            })
+            .is_some()
+        {
             self.status = format!("segment {} trim-start to {}", segment_id, new_start_tl);
         }
     }
 
     fn request_trim_segment_end(&mut self, segment_id: u64, new_end_tl: i64) {
-        if self.send_command(Command::TrimSegmentEnd {
-            segment_id,
-            new_end_tl,
-        }) {
+        if self
+            .send_command(Command::TrimSegmentEnd {
+                segment_id,
+                new_end_tl,
+            })
+            .is_some()
+        {
             self.status = format!("segment {} trim-end to {}", segment_id, new_end_tl);
         }
     }
 
-    fn flush_playhead_request(&mut self) {
-        if self.playhead_request_in_flight {
-            return;
-        }
-
-        let Some(t_tl) = self.pending_playhead_tl.take() else {
-            return;
-        };
-
-        if let Some(sender) = &self.engine_tx {
-            match sender.try_send(Command::SetPlayhead { t_tl }) {
-                Ok(()) => {
-                    self.playhead_request_in_flight = true;
-                }
-                Err(TrySendError::Full(_)) => {
-                    self.pending_playhead_tl = Some(t_tl);
-                    self.status = String::from("engine command queue is full");
-                }
-                Err(TrySendError::Disconnected(_)) => {
-                    self.status = String::from("engine command channel closed");
-                    self.engine_tx = None;
-                    self.playhead_request_in_flight = false;
-                }
-            }
-        } else {
-            self.pending_playhead_tl = Some(t_tl);
-            self.status = String::from("engine is not ready");
-        }
-    }
-
-    fn apply_engine_event(&mut self, event: Event) {
+    fn apply_engine_event(&mut self, tagged: TaggedEvent) {
+        let TaggedEvent { id, event } = tagged;
         match event {
             Event::ProjectChanged(snapshot) => {
+                // Mark and loop changes keep the timeline, so the preview and
+                // loaded ranges stay valid; any other snapshot resets them.
+                let invalidates_preview = self.project.as_ref().is_none_or(|previous| {
+                    previous.segments != snapshot.segments
+                        || previous.assets != snapshot.assets
+                        || (previous.in_point_tl == snapshot.in_point_tl
+                            && previous.out_point_tl == snapshot.out_point_tl
+                            && previous.loop_playback == snapshot.loop_playback)
+                });
+                let loop_toggled = self
+                    .project
+                    .as_ref()
+                    .is_some_and(|previous| previous.loop_playback != snapshot.loop_playback);
                 self.project = Some(snapshot);
-                self.playhead_tl = self.clamp_playhead(self.playhead_tl);
-                self.preview_image = None;
-                self.timeline_cache.clear();
-                self.loaded_preview_ranges_tl.clear();
-                self.pending_playhead_tl = None;
-                self.latest_requested_playhead_tl = None;
-                self.playhead_request_in_flight = false;
-                self.idle_warm_target_tl = None;
-                self.idle_warm_rounds = 0;
-                self.last_split_tl = None;
-                let pending_split_tl = self.pending_split_tl.take();
-                let pending_cut_tl = self.pending_cut_tl.take();
-                if let Some(split_tl) = pending_split_tl {
-                    self.last_split_tl = Some(split_tl);
-                    self.status = format!("split applied at {}", split_tl);
-                } else if let Some(cut_tl) = pending_cut_tl {
-                    self.status = format!("cut applied at {}", cut_tl);
-                } else {
-                    self.status = String::from("project loaded");
+                self.apply_project_change(id, invalidates_preview, loop_toggled);
+            }
+            Event::ProjectUpdated(delta) => {
+                let Some(project) = self.project.as_mut() else {
+                    self.request_project_snapshot();
+                    return;
+                };
+                let loop_toggled = project.loop_playback != delta.loop_playback;
+                let snap_toggled = project.snap_to_frames != delta.snap_to_frames;
+                let marks_changed = project.in_point_tl != delta.in_point_tl
+                    || project.out_point_tl != delta.out_point_tl;
+                if !project.apply_delta(&delta) {
+                    self.request_project_snapshot();
+                    return;
+                }
+                self.apply_project_change(id, delta.changes_timeline(), loop_toggled);
+                let selection_only = delta.selection.is_some()
+                    && !delta.changes_timeline()
+                    && !marks_changed
+                    && !loop_toggled;
+                if selection_only {
+                    self.status = format!("{} segment(s) selected", self.selection().len());
+                } else if snap_toggled {
+                    self.status = if delta.snap_to_frames {
+                        String::from("frame snapping on")
+                    } else {
+                        String::from("frame snapping off")
+                    };
                 }
             }
             Event::PlayheadChanged { t_tl } => {
-                if !self.is_stale_playhead_event(t_tl) {
+                if !self.is_superseded_playhead_event(id) {
                     self.playhead_tl = self.clamp_playhead(t_tl);
                 }
-                self.playhead_request_in_flight = false;
-                self.flush_playhead_request();
             }
-            Event::PreviewFrameReady { t_tl, frame } => {
-                if !self.is_stale_playhead_event(t_tl) {
+            Event::PreviewFrameReady {
+                t_tl,
+                quality,
+                frame,
+                ..
+            } => {
+                let full = quality == PreviewQuality::Full;
+                if !self.is_superseded_playhead_event(id) {
                     self.playhead_tl = self.clamp_playhead(t_tl);
                     self.preview_image = preview::PreviewImage::from_frame(&frame);
-                    self.record_loaded_preview_at(self.playhead_tl);
-                    self.status = if self.preview_image.is_some() {
-                        format!("preview ready at {}", self.playhead_tl)
-                    } else {
-                        String::from(
+                    if full {
+                        self.record_loaded_preview_at(self.playhead_tl);
+                    }
+                    self.status = match (&self.preview_image, full) {
+                        (Some(_), true) => format!("preview ready at {}", self.playhead_tl),
+                        (Some(_), false) => format!("draft preview at {}", self.playhead_tl),
+                        (None, _) => String::from(
                             "preview frame dropped: unsupported format or invalid frame data",
-                        )
+                        ),
                     };
                 }
-                self.playhead_request_in_flight = false;
-                self.flush_playhead_request();
-                if self.preview_image.is_some() && self.should_queue_idle_warm(t_tl) {
-                    if self.idle_warm_target_tl != Some(t_tl) {
-                        self.idle_warm_target_tl = Some(t_tl);
-                        self.idle_warm_rounds = 0;
-                    }
-                    self.idle_warm_rounds = self.idle_warm_rounds.saturating_add(1);
-                    self.record_idle_warm_loaded_hint(t_tl, self.idle_warm_rounds);
-                    self.queue_playhead_for_idle_warm(t_tl);
+            }
+            Event::SourcePlayheadChanged { asset_id, t_src } => {
+                if self.is_superseded_source_event(id) {
+                    return;
+                }
+                if self.source_asset != Some(asset_id) {
+                    self.source_asset = Some(asset_id);
+                    self.source_preview_image = None;
+                }
+                self.source_t_src = t_src;
+            }
+            Event::SourcePreviewFrameReady {
+                asset_id, frame, ..
+            } => {
+                if self.source_asset == Some(asset_id) && !self.is_superseded_source_event(id) {
+                    self.source_preview_image = preview::PreviewImage::from_frame(&frame);
                 }
             }
             Event::ExportProgress { done, total } => {
@@ -374,43 +915,144 @@ impl AppState {
             Event::ExportFinished { path } => {
                 self.status = format!("export finished: {}", path.display());
             }
+            Event::ProjectSaved { path } => {
+                self.status = format!("project saved: {}", path.display());
+            }
+            Event::RecoveryAvailable { recovery_path, .. } => {
+                self.status = format!(
+                    "unsaved work found in {}: restore or discard it",
+                    recovery_path.display()
+                );
+                self.recovery_offer = Some(recovery_path);
+            }
+            Event::BackupAvailable {
+                backup_path,
+                project_path,
+                reason,
+            } => {
+                self.status = format!(
+                    "{} could not be opened ({reason}): restore backup {} or discard",
+                    project_path.display(),
+                    backup_path.display()
+                );
+                self.recovery_offer = Some(backup_path);
+            }
+            Event::RecoverySaved { path } => {
+                self.status = format!("autosaved to {}", path.display());
+            }
+            Event::LoopPlaybackChanged { enabled } => {
+                if let Some(project) = self.project.as_mut() {
+                    project.loop_playback = enabled;
+                }
+                self.status = if enabled {
+                    String::from("loop playback on")
+                } else {
+                    String::from("loop playback off")
+                };
+            }
+            Event::PlaybackStateChanged { playing, rate } => {
+                self.playing = playing;
+                self.status = if playing {
+                    format!("playing at {rate}x")
+                } else {
+                    format!("paused at {}", self.playhead_tl)
+                };
+            }
             Event::Error(error) => {
-                if let Some(split_tl) = self.pending_split_tl.take() {
-                    self.last_split_tl = None;
-                    if self.is_split_error(&error.kind) {
-                        self.status = format!("split skipped at {}: {}", split_tl, error.message);
-                    } else {
-                        self.status = format!("split failed at {}: {}", split_tl, error.message);
-                    }
-                } else if let Some(cut_tl) = self.pending_cut_tl.take() {
-                    if self.is_cut_error(&error.kind) {
-                        self.status = format!("cut skipped at {}: {}", cut_tl, error.message);
+                self.status = format!("error: {}", error.message);
+            }
+            Event::CommandCompleted {
+                id: completed,
+                result,
+            } => {
+                if self.latest_seek_id == Some(completed) {
+                    self.seek_awaiting_completion = false;
+                }
+                let edit = self.take_pending_edit(Some(completed));
+                if let Err(error) = result {
+                    if let Some(edit) = edit {
+                        if matches!(edit, PendingEdit::Split { .. }) {
+                            self.last_split_tl = None;
+                        }
+                        self.status = edit.failure_status(&error);
                     } else {
-                        self.status = format!("cut failed at {}: {}", cut_tl, error.message);
+                        self.status = format!("error: {}", error.message);
                     }
-                } else {
-                    self.status = format!("error: {}", error.message);
                 }
-                self.playhead_request_in_flight = false;
-                self.flush_playhead_request();
             }
         }
     }
 
-    fn is_stale_playhead_event(&self, event_t_tl: i64) -> bool {
-        self.latest_requested_playhead_tl
-            .is_some_and(|latest_t_tl| latest_t_tl != event_t_tl)
+    /// Refreshes derived state after `project` changed.
+    fn apply_project_change(
+        &mut self,
+        id: Option<CommandId>,
+        invalidates_preview: bool,
+        loop_toggled: bool,
+    ) {
+        self.playhead_tl = self.clamp_playhead(self.playhead_tl);
+        self.timeline_cache.clear();
+        if self.source_asset.is_some() && self.source_asset_summary().is_none() {
+            self.source_asset = None;
+            self.source_t_src = 0;
+            self.source_preview_image = None;
+        }
+        if invalidates_preview {
+            self.preview_image = None;
+            self.loaded_preview_ranges_tl.clear();
+        }
+        self.last_split_tl = None;
+        if let Some(edit) = self.take_pending_edit(id) {
+            if let PendingEdit::Split { at_tl } = edit {
+                self.last_split_tl = Some(at_tl);
+            }
+            self.status = format!("{} applied at {}", edit.name(), edit.at_tl());
+        } else if loop_toggled {
+            self.status = if self.loop_playback() {
+                String::from("loop playback on")
+            } else {
+                String::from("loop playback off")
+            };
+        } else if !invalidates_preview {
+            self.status = self
+                .project
+                .as_ref()
+                .map_or_else(String::new, in_out_status);
+        } else {
+            self.status = String::from("project loaded");
+        }
+    }
+
+    /// Asks for a full snapshot after missing or out-of-order deltas.
+    fn request_project_snapshot(&mut self) {
+        if self.send_command(Command::RequestProjectSnapshot).is_some() {
+            self.status = String::from("resynchronizing project");
+        }
     }
 
-    fn is_split_error(&self, kind: &EngineErrorKind) -> bool {
-        matches!(
-            kind,
-            EngineErrorKind::SplitPointAtBoundary | EngineErrorKind::SegmentNotFound
-        )
+    fn take_pending_edit(&mut self, id: Option<CommandId>) -> Option<PendingEdit> {
+        let (pending_id, _) = self.pending_edit?;
+        if id != Some(pending_id) {
+            return None;
+        }
+        self.pending_edit.take().map(|(_, edit)| edit)
+    }
+
+    /// Returns whether a playhead or preview event lost against a newer seek.
+    ///
+    /// Events of commands sent before the newest seek are stale; the engine
+    /// drops their queued decodes, but results already in flight still land.
+    /// Untagged playback positions are stale until the newest seek completed.
+    fn is_superseded_playhead_event(&self, id: Option<CommandId>) -> bool {
+        match id {
+            Some(id) => self.latest_seek_id.is_some_and(|latest| id < latest),
+            None => self.seek_awaiting_completion,
+        }
     }
 
-    fn is_cut_error(&self, kind: &EngineErrorKind) -> bool {
-        matches!(kind, EngineErrorKind::SegmentNotFound)
+    /// Returns whether a source monitor event lost against a newer source request.
+    fn is_superseded_source_event(&self, id: Option<CommandId>) -> bool {
+        id.is_some_and(|id| self.latest_source_seek_id.is_some_and(|latest| id < latest))
     }
 
     fn clamp_playhead(&self, t_tl: i64) -> i64 {
@@ -489,41 +1131,134 @@ impl AppState {
             .unwrap_or(engine::DEFAULT_PREVIEW_CACHE_BUCKET_TL)
     }
 
-    fn record_idle_warm_loaded_hint(&mut self, t_tl: i64, warm_round: u16) {
-        let bucket_tl = self.preview_bucket_tl();
-        let step = i64::from(warm_round.div_ceil(2));
-        let direction = if warm_round % 2 == 1 { 1 } else { -1 };
-        let offset = bucket_tl.saturating_mul(step).saturating_mul(direction);
-        self.add_loaded_bucket_for_tick(t_tl.saturating_add(offset));
-    }
-
-    fn should_queue_idle_warm(&self, t_tl: i64) -> bool {
-        self.project.is_some()
-            && self.latest_requested_playhead_tl == Some(t_tl)
-            && self.pending_playhead_tl.is_none()
-            && !self.playhead_request_in_flight
-            && self.idle_warm_target_tl.is_none_or(|target| target == t_tl)
-            && self.idle_warm_rounds < IDLE_WARM_MAX_ROUNDS
-    }
-
     /// Renders the UI tree.
     pub fn view(&self) -> Element<'_, Message> {
         let import_row = row![
             text_input("media path", &self.import_path).on_input(Message::ImportPathChanged),
             button("Import").on_press(Message::ImportPressed),
+            button("Open Project").on_press(Message::OpenProjectPressed),
+            button("Save Project").on_press(Message::SaveProjectPressed(MediaPathStyle::Relative)),
+            button("Save (Absolute Paths)")
+                .on_press(Message::SaveProjectPressed(MediaPathStyle::Absolute)),
+            button("Add to Bin").on_press(Message::AddToBinPressed),
+            button("Relink Folder").on_press(Message::RelinkFolderPressed),
             button("Split").on_press(Message::SplitPressed),
             button("Cut").on_press(Message::CutPressed),
+            button(if self.playing { "Pause" } else { "Play" }).on_press(Message::PlayPausePressed),
         ]
         .spacing(12);
         let export_row = row![
             text_input("export path", &self.export_path).on_input(Message::ExportPathChanged),
             button("Export").on_press(Message::ExportPressed),
+            button("Export In/Out").on_press(Message::ExportInOutPressed),
+            button("Export EDL").on_press(Message::ExportEdlPressed),
+            button("Import EDL").on_press(Message::ImportEdlPressed),
+            button("Export OTIO").on_press(Message::ExportOtioPressed),
+            button("Import OTIO").on_press(Message::ImportOtioPressed),
+            button("Export FCP XML").on_press(Message::ExportFcpXmlPressed),
+            button("Import Cut List").on_press(Message::ImportCutListPressed),
+        ]
+        .spacing(12);
+        let marks_row = row![
+            button("Mark In").on_press(Message::MarkInPressed),
+            button("Mark Out").on_press(Message::MarkOutPressed),
+            button("Clear In/Out").on_press(Message::ClearInOutPressed),
+            button(if self.loop_playback() {
+                "Loop: On"
+            } else {
+                "Loop: Off"
+            })
+            .on_press(Message::LoopPlaybackToggled),
+            button(if self.snap_to_frames() {
+                "Snap: On"
+            } else {
+                "Snap: Off"
+            })
+            .on_press(Message::SnapToFramesToggled),
+            button("Lift").on_press(Message::LiftPressed),
+            button("Extract").on_press(Message::ExtractPressed),
+        ]
+        .spacing(12);
+        let selection_row = row![
+            button("Select All").on_press(Message::SelectAllPressed),
+            button("Clear Selection").on_press(Message::ClearSelectionPressed),
+            button("Delete").on_press(Message::DeleteSelectionPressed { ripple: false }),
+            button("Ripple Delete").on_press(Message::DeleteSelectionPressed { ripple: true }),
+            button("Enable").on_press(Message::SetSelectionEnabledPressed(true)),
+            button("Disable").on_press(Message::SetSelectionEnabledPressed(false)),
+            button("Copy").on_press(Message::CopyPressed),
+            button("Paste").on_press(Message::PastePressed(PasteMode::Insert)),
+            button("Paste Over").on_press(Message::PastePressed(PasteMode::Overwrite)),
+            button("Duplicate").on_press(Message::DuplicatePressed),
         ]
         .spacing(12);
 
+        let bin_assets = self
+            .project
+            .iter()
+            .flat_map(|snapshot| snapshot.assets.iter());
+        let bin_list = Column::with_children(bin_assets.map(|asset| {
+            row![
+                text(bin_entry_label(asset)).width(Length::Fill),
+                button("View").on_press(Message::SourceAssetOpened(asset.id)),
+                button("Insert").on_press(Message::BinEditPressed {
+                    asset_id: asset.id,
+                    mode: PasteMode::Insert,
+                }),
+                button("Overwrite").on_press(Message::BinEditPressed {
+                    asset_id: asset.id,
+                    mode: PasteMode::Overwrite,
+                }),
+            ]
+            .push_maybe(
+                asset
+                    .offline
+                    .then(|| button("Relink").on_press(Message::RelinkPressed(asset.id))),
+            )
+            .spacing(12)
+            .into()
+        }))
+        .spacing(4);
+
         let preview_widget = container(preview::view(self.preview_image.as_ref()))
             .width(Length::Fill)
             .height(Length::Fixed(240.0));
+        let source_duration_tl = self
+            .source_asset_summary()
+            .map_or(1, |asset| asset.duration_tl.max(1));
+        let source_monitor = column![
+            container(preview::view(self.source_preview_image.as_ref()))
+                .width(Length::Fill)
+                .height(Length::Fixed(240.0)),
+            slider(
+                0.0..=(source_duration_tl - 1) as f64,
+                self.source_t_src as f64,
+                |t_src| Message::SourceScrubbed(t_src.round() as i64),
+            ),
+            row![
+                button("Source In").on_press(Message::SourceMarkInPressed),
+                button("Source Out").on_press(Message::SourceMarkOutPressed),
+                button("Insert").on_press(Message::SourceEditPressed(PasteMode::Insert)),
+                button("Overwrite").on_press(Message::SourceEditPressed(PasteMode::Overwrite)),
+                button("Replace").on_press(Message::ReplaceSegmentPressed(
+                    ReplaceAlignment::SourceTimecode
+                )),
+                button("Replace @ Playhead")
+                    .on_press(Message::ReplaceSegmentPressed(ReplaceAlignment::Playhead)),
+                button("Match Frame").on_press(Message::MatchFramePressed),
+            ]
+            .spacing(12),
+            text(match self.source_asset_summary() {
+                Some(asset) => match asset.source_timecode_at(self.source_t_src) {
+                    Ok(timecode) => format!("Source: {} @ {timecode}", bin_entry_label(asset)),
+                    Err(_) => format!("Source: {} @ invalid timecode", bin_entry_label(asset)),
+                },
+                None => String::from("Source: none"),
+            }),
+        ]
+        .spacing(8)
+        .width(Length::Fill);
+        let monitors = row![source_monitor, preview_widget].spacing(12);
 
         let timeline_widget = timeline::view(
             self.project.as_ref(),
@@ -549,33 +1284,63 @@ impl AppState {
                     segment_id,
                     new_end_tl,
                 },
+                on_select: |segment_id, mode| Message::TimelineSegmentSelected { segment_id, mode },
+                on_select_range: |start_tl, end_tl| Message::TimelineRangeSelected {
+                    start_tl,
+                    end_tl,
+                },
             },
         );
 
-        let controls = column![
-            import_row,
-            export_row,
-            preview_widget,
-            timeline_widget,
-            text(format!("Playhead: {}", self.playhead_tl)),
-            text(format!(
-                "Segments: {}",
-                self.project
-                    .as_ref()
-                    .map(|snapshot| snapshot.segments.len())
-                    .unwrap_or(0)
-            )),
-            text(format!("Status: {}", self.status)),
-        ]
-        .spacing(12)
-        .padding(16);
+        let recovery_row = self.recovery_offer.as_ref().map(|path| {
+            row![
+                text(format!("Recovery available: {}", path.display())),
+                button("Restore").on_press(Message::RestoreRecoveryPressed),
+                button("Discard").on_press(Message::DiscardRecoveryPressed),
+            ]
+            .spacing(12)
+        });
+
+        let controls = column![]
+            .push_maybe(recovery_row)
+            .push(
+                column![
+                    import_row,
+                    export_row,
+                    marks_row,
+                    selection_row,
+                    bin_list,
+                    monitors,
+                    timeline_widget,
+                    text(format!(
+                        "Playhead: {} ({} tl)",
+                        self.playhead_timecode(),
+                        self.playhead_tl
+                    )),
+                    text(format!(
+                        "Segments: {}",
+                        self.project
+                            .as_ref()
+                            .map(|snapshot| snapshot.segments.len())
+                            .unwrap_or(0)
+                    )),
+                    text(format!("Selected: {}", self.selection().len())),
+                    text(format!("Status: {}", self.status)),
+                ]
+                .spacing(12),
+            )
+            .spacing(12)
+            .padding(16);
 
         controls.into()
     }
 
-    /// Subscribes to bridge events emitted by the engine worker thread.
+    /// Subscribes to bridge events and transport keyboard shortcuts.
     pub fn subscription(&self) -> Subscription<Message> {
-        engine_subscription().map(Message::Bridge)
+        Subscription::batch([
+            engine_subscription().map(Message::Bridge),
+            keyboard::on_key_press(shortcut_message),
+        ])
     }
 
     #[cfg(test)]
@@ -584,36 +1349,134 @@ impl AppState {
             engine_tx: Some(engine_tx),
             project: None,
             preview_image: None,
+            source_asset: None,
+            source_t_src: 0,
+            source_preview_image: None,
+            latest_source_seek_id: None,
             import_path: String::new(),
             export_path: String::new(),
             playhead_tl: 0,
-            pending_playhead_tl: None,
-            latest_requested_playhead_tl: None,
-            playhead_request_in_flight: false,
-            idle_warm_target_tl: None,
-            idle_warm_rounds: 0,
+            playing: false,
+            next_command_id: 1,
+            latest_seek_id: None,
+            seek_awaiting_completion: false,
             loaded_preview_ranges_tl: Vec::new(),
-            pending_split_tl: None,
-            pending_cut_tl: None,
+            pending_edit: None,
             last_split_tl: None,
             timeline_cache: canvas::Cache::new(),
+            recovery_offer: None,
             status: String::from("idle"),
         }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use std::path::PathBuf;
-    use std::sync::mpsc;
-    use std::sync::mpsc::TryRecvError;
-    use std::time::Duration;
-
-    use engine::{Command, Event, ProjectSnapshot};
+fn in_out_status(snapshot: &ProjectSnapshot) -> String {
+    let mark = |mark_tl: Option<i64>, fallback: &str| {
+        mark_tl.map_or_else(|| fallback.to_owned(), |t_tl| t_tl.to_string())
+    };
+    match (snapshot.in_point_tl, snapshot.out_point_tl) {
+        (None, None) => String::from("in/out cleared"),
+        (in_tl, out_tl) => format!(
+            "in/out marked: {}..{}",
+            mark(in_tl, "start"),
+            mark(out_tl, "end")
+        ),
+    }
+}
 
-    use crate::bridge::BridgeEvent;
+/// Maps standard NLE transport keys to messages.
+///
+/// `J`/`K`/`L` shuttle, space toggles playback, left/right step one frame
+/// (ten with shift) and up/down jump to the previous/next edit point. `I`/`O`
+/// mark in/out at the playhead and `Alt+X` clears both marks. `Ctrl+A` selects
+/// every segment, escape clears the selection and delete/backspace delete it
+/// (rippling with shift). `Ctrl+C` copies the selection, `Ctrl+V` pastes it at
+/// the playhead (overwriting with shift), `Ctrl+D` duplicates the selected
+/// segment and `Shift+E` toggles whether the selection is enabled. Keys typed into a focused text input never reach this function.
+/// Describes one bin asset as its file name, duration and source marks.
+fn bin_entry_label(asset: &MediaAssetSummary) -> String {
+    let name = asset.path.file_name().map_or_else(
+        || asset.path.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    );
+    let mut label = format!("{} #{} ({} tl)", name, asset.id, asset.duration_tl);
+    if asset.source_in_tl.is_some() || asset.source_out_tl.is_some() {
+        let mark = |mark: Option<i64>| mark.map_or_else(|| String::from("-"), |t| t.to_string());
+        label.push_str(&format!(
+            " src {}..{}",
+            mark(asset.source_in_tl),
+            mark(asset.source_out_tl)
+        ));
+    }
+    if asset.offline {
+        label.push_str(" [offline]");
+    }
+    label
+}
+
+fn shortcut_message(key: Key, modifiers: Modifiers) -> Option<Message> {
+    let step = if modifiers.shift() {
+        SHIFT_STEP_FRAMES
+    } else {
+        1
+    };
+    match key.as_ref() {
+        Key::Character("j" | "J") => Some(Message::ShuttleReverse),
+        Key::Character("k" | "K") => Some(Message::ShuttlePause),
+        Key::Character("l" | "L") => Some(Message::ShuttleForward),
+        Key::Character("i" | "I") if modifiers.shift() => Some(Message::SourceMarkInPressed),
+        Key::Character("o" | "O") if modifiers.shift() => Some(Message::SourceMarkOutPressed),
+        Key::Character("i" | "I") => Some(Message::MarkInPressed),
+        Key::Character("o" | "O") => Some(Message::MarkOutPressed),
+        Key::Character(",") => Some(Message::SourceEditPressed(PasteMode::Insert)),
+        Key::Character(".") => Some(Message::SourceEditPressed(PasteMode::Overwrite)),
+        Key::Character("f" | "F") => Some(Message::MatchFramePressed),
+        Key::Character("x" | "X") if modifiers.alt() => Some(Message::ClearInOutPressed),
+        Key::Character("a" | "A") if modifiers.command() => Some(Message::SelectAllPressed),
+        Key::Character("c" | "C") if modifiers.command() => Some(Message::CopyPressed),
+        Key::Character("v" | "V") if modifiers.command() => {
+            Some(Message::PastePressed(if modifiers.shift() {
+                PasteMode::Overwrite
+            } else {
+                PasteMode::Insert
+            }))
+        }
+        Key::Character("d" | "D") if modifiers.command() => Some(Message::DuplicatePressed),
+        Key::Character("e" | "E") if modifiers.shift() => {
+            Some(Message::ToggleSelectionEnabledPressed)
+        }
+        Key::Named(Named::Escape) => Some(Message::ClearSelectionPressed),
+        Key::Named(Named::Delete | Named::Backspace) => Some(Message::DeleteSelectionPressed {
+            ripple: modifiers.shift(),
+        }),
+        Key::Named(Named::Space) => Some(Message::PlayPausePressed),
+        Key::Named(Named::ArrowLeft) => Some(Message::StepFrame(-step)),
+        Key::Named(Named::ArrowRight) => Some(Message::StepFrame(step)),
+        Key::Named(Named::ArrowUp) => Some(Message::JumpToEditPoint(EditPointDirection::Previous)),
+        Key::Named(Named::ArrowDown) => Some(Message::JumpToEditPoint(EditPointDirection::Next)),
+        _ => None,
+    }
+}
 
-    use super::{AppState, Message};
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::mpsc;
+    use std::sync::mpsc::TryRecvError;
+    use std::time::Duration;
+
+    use engine::api::{MediaAssetSummary, SegmentSummary};
+    use engine::{
+        Command, CommandId, Event, FrameGrid, MediaPathStyle, PasteMode, ProjectDelta,
+        ProjectSnapshot, ReplaceAlignment, TaggedEvent,
+    };
+
+    use crate::bridge::BridgeEvent;
+
+    use engine::EditPointDirection;
+    use iced::keyboard::{Key, Modifiers, key::Named};
+
+    use super::{AppState, Message, PendingEdit, bin_entry_label, shortcut_message, timeline};
 
     #[test]
     fn import_button_dispatches_import_command() {
@@ -623,7 +1486,7 @@ mod tests {
         let _ = app.update(Message::ImportPathChanged("demo.mp4".to_owned()));
         let _ = app.update(Message::ImportPressed);
 
-        let command = command_rx.recv().expect("import command");
+        let command = command_rx.recv().expect("import command").command;
         assert_eq!(
             command,
             Command::Import {
@@ -632,6 +1495,324 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bin_buttons_add_assets_and_edit_them_in_at_the_playhead() {
+        let (command_tx, command_rx) = mpsc::sync_channel(8);
+        let mut app = AppState::from_sender_for_test(command_tx);
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(1_000_000))));
+        app.playhead_tl = 400_000;
+
+        let _ = app.update(Message::ImportPathChanged("broll.mp4".to_owned()));
+        let _ = app.update(Message::AddToBinPressed);
+        let _ = app.update(Message::BinEditPressed {
+            asset_id: 2,
+            mode: PasteMode::Insert,
+        });
+        let _ = app.update(Message::BinEditPressed {
+            asset_id: 2,
+            mode: PasteMode::Overwrite,
+        });
+
+        let commands: Vec<_> = command_rx.try_iter().map(|tagged| tagged.command).collect();
+        assert_eq!(
+            commands,
+            vec![
+                Command::AddAsset {
+                    path: PathBuf::from("broll.mp4")
+                },
+                Command::InsertFromAsset {
+                    asset_id: 2,
+                    at_tl: 400_000
+                },
+                Command::OverwriteFromAsset {
+                    asset_id: 2,
+                    at_tl: 400_000
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn source_monitor_sends_every_scrub_and_ignores_stale_frames() {
+        let (command_tx, command_rx) = mpsc::sync_channel(8);
+        let mut app = AppState::from_sender_for_test(command_tx);
+        let mut snapshot = empty_snapshot(1_000_000);
+        snapshot.assets.push(MediaAssetSummary {
+            id: 2,
+            path: PathBuf::from("broll.mp4"),
+            has_video: true,
+            has_audio: false,
+            duration_tl: 5_000_000,
+            source_in_tl: Some(1_000_000),
+            source_out_tl: None,
+            offline: false,
+            frame_grid: None,
+            start_timecode: None,
+        });
+        let _ = app.update(untagged(Event::ProjectChanged(snapshot)));
+
+        let _ = app.update(Message::SourceAssetOpened(2));
+        let _ = app.update(Message::SourceScrubbed(1_500_000));
+        let sent: Vec<_> = command_rx.try_iter().collect();
+        assert_eq!(
+            sent.iter()
+                .map(|tagged| &tagged.command)
+                .collect::<Vec<_>>(),
+            vec![
+                &Command::SetSourcePlayhead {
+                    asset_id: 2,
+                    t_src: 1_000_000
+                },
+                &Command::SetSourcePlayhead {
+                    asset_id: 2,
+                    t_src: 1_500_000
+                },
+            ]
+        );
+        let source_frame = |t_src, generation| Event::SourcePreviewFrameReady {
+            asset_id: 2,
+            t_src,
+            generation,
+            quality: engine::PreviewQuality::Full,
+            frame: engine::PreviewFrame {
+                width: 2,
+                height: 1,
+                format: engine::PreviewPixelFormat::Rgba8,
+                bytes: std::sync::Arc::from(vec![0_u8; 8]),
+            },
+        };
+
+        let _ = app.update(reply(
+            sent[0].id,
+            Event::SourcePlayheadChanged {
+                asset_id: 2,
+                t_src: 1_000_000,
+            },
+        ));
+        let _ = app.update(reply(sent[0].id, source_frame(1_000_000, 1)));
+        assert!(app.source_preview_image.is_none());
+        assert_eq!(app.source_t_src, 1_500_000);
+
+        let _ = app.update(reply(
+            sent[1].id,
+            Event::SourcePlayheadChanged {
+                asset_id: 2,
+                t_src: 1_500_000,
+            },
+        ));
+        let _ = app.update(reply(sent[1].id, source_frame(1_500_000, 2)));
+        assert!(app.source_preview_image.is_some());
+        assert_eq!(app.source_t_src, 1_500_000);
+
+        let _ = app.update(Message::SourceMarkOutPressed);
+        let _ = app.update(Message::SourceEditPressed(PasteMode::Overwrite));
+        let _ = app.update(Message::MatchFramePressed);
+        let commands: Vec<_> = command_rx.try_iter().map(|tagged| tagged.command).collect();
+        assert_eq!(
+            commands,
+            vec![
+                Command::SetSourceOutPoint {
+                    asset_id: 2,
+                    at_src: 1_500_000
+                },
+                Command::OverwriteFromAsset {
+                    asset_id: 2,
+                    at_tl: 0
+                },
+                Command::MatchFrame,
+            ]
+        );
+    }
+
+    #[test]
+    fn replace_button_targets_single_selected_segment_with_source_asset() {
+        let (command_tx, command_rx) = mpsc::sync_channel(8);
+        let mut app = AppState::from_sender_for_test(command_tx);
+        let mut snapshot = empty_snapshot(100);
+        snapshot.segments = vec![segment(1, 0, 50), segment(2, 50, 50)];
+        snapshot.selection = vec![1, 2];
+        snapshot.assets.push(MediaAssetSummary {
+            id: 2,
+            path: PathBuf::from("graded.mp4"),
+            has_video: true,
+            has_audio: false,
+            duration_tl: 500,
+            source_in_tl: None,
+            source_out_tl: None,
+            offline: false,
+            frame_grid: None,
+            start_timecode: None,
+        });
+        let _ = app.update(untagged(Event::ProjectChanged(snapshot.clone())));
+
+        let _ = app.update(Message::ReplaceSegmentPressed(ReplaceAlignment::Playhead));
+        assert_eq!(app.status, "no asset in the source monitor");
+        let _ = app.update(Message::SourceAssetOpened(2));
+        let _ = command_rx.try_recv().expect("source playhead command");
+        let _ = app.update(Message::ReplaceSegmentPressed(ReplaceAlignment::Playhead));
+        assert_eq!(app.status, "select one segment to replace");
+        assert_eq!(command_rx.try_recv(), Err(TryRecvError::Empty));
+
+        let mut delta = delta_on(&snapshot);
+        delta.selection = Some(vec![2]);
+        let _ = app.update(untagged(Event::ProjectUpdated(delta)));
+        let _ = app.update(Message::ReplaceSegmentPressed(
+            ReplaceAlignment::SourceTimecode,
+        ));
+        assert_eq!(
+            command_rx.recv().expect("replace command").command,
+            Command::ReplaceSegmentMedia {
+                segment_id: 2,
+                asset_id: 2,
+                align: ReplaceAlignment::SourceTimecode,
+            }
+        );
+        assert_eq!(app.status, "replacing segment 2 media with asset 2");
+    }
+
+    #[test]
+    fn source_shortcuts_map_to_source_monitor_messages() {
+        let key = |c: &str| Key::Character(c.into());
+
+        assert!(matches!(
+            shortcut_message(key("I"), Modifiers::SHIFT),
+            Some(Message::SourceMarkInPressed)
+        ));
+        assert!(matches!(
+            shortcut_message(key("O"), Modifiers::SHIFT),
+            Some(Message::SourceMarkOutPressed)
+        ));
+        assert!(matches!(
+            shortcut_message(key(","), Modifiers::default()),
+            Some(Message::SourceEditPressed(PasteMode::Insert))
+        ));
+        assert!(matches!(
+            shortcut_message(key("."), Modifiers::default()),
+            Some(Message::SourceEditPressed(PasteMode::Overwrite))
+        ));
+        assert!(matches!(
+            shortcut_message(key("f"), Modifiers::default()),
+            Some(Message::MatchFramePressed)
+        ));
+    }
+
+    #[test]
+    fn bin_entry_label_shows_file_name_and_source_marks() {
+        let mut asset = MediaAssetSummary {
+            id: 2,
+            path: PathBuf::from("media/broll.mp4"),
+            has_video: true,
+            has_audio: true,
+            duration_tl: 5_000_000,
+            source_in_tl: None,
+            source_out_tl: None,
+            offline: false,
+            frame_grid: None,
+            start_timecode: None,
+        };
+        assert_eq!(bin_entry_label(&asset), "broll.mp4 #2 (5000000 tl)");
+
+        asset.source_out_tl = Some(2_000_000);
+        assert_eq!(
+            bin_entry_label(&asset),
+            "broll.mp4 #2 (5000000 tl) src -..2000000"
+        );
+
+        asset.offline = true;
+        assert_eq!(
+            bin_entry_label(&asset),
+            "broll.mp4 #2 (5000000 tl) src -..2000000 [offline]"
+        );
+    }
+
+    #[test]
+    fn recovery_offer_is_shown_until_restored_or_discarded() {
+        let (command_tx, command_rx) = mpsc::sync_channel(8);
+        let mut app = AppState::from_sender_for_test(command_tx);
+        let offer = || {
+            untagged(Event::RecoveryAvailable {
+                recovery_path: PathBuf::from("cut.nle.json.recovery"),
+                project_path: Some(PathBuf::from("cut.nle.json")),
+            })
+        };
+
+        let _ = app.update(offer());
+        assert_eq!(
+            app.recovery_offer,
+            Some(PathBuf::from("cut.nle.json.recovery"))
+        );
+        let _ = app.update(Message::RestoreRecoveryPressed);
+        assert_eq!(app.recovery_offer, None);
+        assert_eq!(app.status, "restoring recovery copy");
+
+        let _ = app.update(untagged(Event::BackupAvailable {
+            backup_path: PathBuf::from("cut.nle.json.1"),
+            project_path: PathBuf::from("cut.nle.json"),
+            reason: String::from("truncated"),
+        }));
+        assert_eq!(app.recovery_offer, Some(PathBuf::from("cut.nle.json.1")));
+        assert_eq!(
+            app.status,
+            "cut.nle.json could not be opened (truncated): restore backup cut.nle.json.1 or discard"
+        );
+        let _ = app.update(Message::DiscardRecoveryPressed);
+        assert_eq!(app.recovery_offer, None);
+        let _ = app.update(untagged(Event::RecoverySaved {
+            path: PathBuf::from("cut.nle.json.recovery"),
+        }));
+        assert_eq!(app.status, "autosaved to cut.nle.json.recovery");
+
+        let commands: Vec<_> = command_rx.try_iter().map(|tagged| tagged.command).collect();
+        assert_eq!(
+            commands,
+            vec![Command::RestoreRecovery, Command::DiscardRecovery]
+        );
+    }
+
+    #[test]
+    fn open_and_relink_buttons_send_the_typed_path() {
+        let (command_tx, command_rx) = mpsc::sync_channel(8);
+        let mut app = AppState::from_sender_for_test(command_tx);
+
+        let _ = app.update(Message::RelinkFolderPressed);
+        assert_eq!(app.status, "import path is empty");
+        let _ = app.update(Message::ImportPathChanged(" cut.nle.json ".to_owned()));
+        let _ = app.update(Message::OpenProjectPressed);
+        assert_eq!(app.status, "opening cut.nle.json");
+        let _ = app.update(Message::SaveProjectPressed(MediaPathStyle::Absolute));
+        assert_eq!(app.status, "saving cut.nle.json");
+        let _ = app.update(untagged(Event::ProjectSaved {
+            path: PathBuf::from("cut.nle.json"),
+        }));
+        assert_eq!(app.status, "project saved: cut.nle.json");
+        let _ = app.update(Message::ImportPathChanged("/media/moved".to_owned()));
+        let _ = app.update(Message::RelinkFolderPressed);
+        let _ = app.update(Message::ImportPathChanged("/media/moved/a.mov".to_owned()));
+        let _ = app.update(Message::RelinkPressed(3));
+        assert_eq!(app.status, "relinking asset 3 to /media/moved/a.mov");
+
+        let commands: Vec<_> = command_rx.try_iter().map(|tagged| tagged.command).collect();
+        assert_eq!(
+            commands,
+            vec![
+                Command::OpenProject {
+                    path: PathBuf::from("cut.nle.json")
+                },
+                Command::SaveProject {
+                    path: PathBuf::from("cut.nle.json"),
+                    paths: MediaPathStyle::Absolute,
+                },
+                Command::RelinkFromDirectory {
+                    dir: PathBuf::from("/media/moved")
+                },
+                Command::RelinkAsset {
+                    asset_id: 3,
+                    new_path: PathBuf::from("/media/moved/a.mov")
+                },
+            ]
+        );
+    }
+
     #[test]
     fn export_button_dispatches_export_command() {
         let (command_tx, command_rx) = mpsc::sync_channel(8);
@@ -640,7 +1821,7 @@ mod tests {
         let _ = app.update(Message::ExportPathChanged("out.mp4".to_owned()));
         let _ = app.update(Message::ExportPressed);
 
-        let command = command_rx.recv().expect("export command");
+        let command = command_rx.recv().expect("export command").command;
         assert_eq!(
             command,
             Command::Export {
@@ -650,6 +1831,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn interchange_buttons_export_to_the_export_path_and_import_from_the_path_input() {
+        let (command_tx, command_rx) = mpsc::sync_channel(8);
+        let mut app = AppState::from_sender_for_test(command_tx);
+
+        let _ = app.update(Message::ExportPathChanged(" cut.edl ".to_owned()));
+        let _ = app.update(Message::ExportEdlPressed);
+        assert_eq!(app.status, "export requested: cut.edl");
+        let _ = app.update(Message::ImportPathChanged("handoff.edl".to_owned()));
+        let _ = app.update(Message::ImportEdlPressed);
+        assert_eq!(app.status, "importing EDL handoff.edl");
+        let _ = app.update(Message::ExportOtioPressed);
+        let _ = app.update(Message::ImportPathChanged("handoff.otio".to_owned()));
+        let _ = app.update(Message::ImportOtioPressed);
+        assert_eq!(app.status, "importing OTIO handoff.otio");
+        let _ = app.update(Message::ExportPathChanged("cut.xml".to_owned()));
+        let _ = app.update(Message::ExportFcpXmlPressed);
+        assert_eq!(app.status, "export requested: cut.xml");
+        let _ = app.update(Message::ImportPathChanged("cuts.csv".to_owned()));
+        let _ = app.update(Message::ImportCutListPressed);
+        assert_eq!(app.status, "importing cut list cuts.csv");
+
+        let commands: Vec<_> = command_rx.try_iter().map(|tagged| tagged.command).collect();
+        assert_eq!(
+            commands,
+            vec![
+                Command::ExportEdl {
+                    path: PathBuf::from("cut.edl")
+                },
+                Command::ImportEdl {
+                    path: PathBuf::from("handoff.edl")
+                },
+                Command::ExportOtio {
+                    path: PathBuf::from("cut.edl")
+                },
+                Command::ImportOtio {
+                    path: PathBuf::from("handoff.otio")
+                },
+                Command::ExportFcpXml {
+                    path: PathBuf::from("cut.xml")
+                },
+                Command::ImportCutList {
+                    path: PathBuf::from("cuts.csv")
+                },
+            ]
+        );
+    }
+
     #[test]
     fn export_button_rejects_empty_path() {
         let (command_tx, command_rx) = mpsc::sync_channel(8);
@@ -669,7 +1898,7 @@ mod tests {
 
         let _ = app.update(Message::TimelineScrubbed(42));
 
-        let command = command_rx.recv().expect("set playhead command");
+        let command = command_rx.recv().expect("set playhead command").command;
         assert_eq!(command, Command::SetPlayhead { t_tl: 42 });
     }
 
@@ -677,18 +1906,11 @@ mod tests {
     fn timeline_scrub_clamps_to_last_timeline_tick() {
         let (command_tx, command_rx) = mpsc::sync_channel(8);
         let mut app = AppState::from_sender_for_test(command_tx);
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(100))));
 
         let _ = app.update(Message::TimelineScrubbed(100));
 
-        let command = command_rx.recv().expect("set playhead command");
+        let command = command_rx.recv().expect("set playhead command").command;
         assert_eq!(command, Command::SetPlayhead { t_tl: 99 });
     }
 
@@ -701,7 +1923,7 @@ mod tests {
 
         let _ = app.update(Message::SplitPressed);
 
-        let command = command_rx.recv().expect("split command");
+        let command = command_rx.recv().expect("split command").command;
         assert_eq!(command, Command::Split { at_tl: 250_000 });
     }
 
@@ -714,73 +1936,57 @@ mod tests {
 
         let _ = app.update(Message::CutPressed);
 
-        let command = command_rx.recv().expect("cut command");
+        let command = command_rx.recv().expect("cut command").command;
         assert_eq!(command, Command::Cut { at_tl: 250_000 });
     }
 
     #[test]
-    fn split_button_queues_playhead_refresh_after_in_flight_preview() {
+    fn split_button_refreshes_the_playhead_after_the_split() {
         let (command_tx, command_rx) = mpsc::sync_channel(8);
         let mut app = AppState::from_sender_for_test(command_tx);
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(100))));
 
         let _ = app.update(Message::TimelineScrubbed(40));
         let first = command_rx
             .recv_timeout(Duration::from_millis(100))
             .expect("first set playhead command");
-        assert_eq!(first, Command::SetPlayhead { t_tl: 40 });
+        assert_eq!(first.command, Command::SetPlayhead { t_tl: 40 });
 
         let _ = app.update(Message::SplitPressed);
-        let split = command_rx.recv().expect("split command");
-        assert_eq!(split, Command::Split { at_tl: 40 });
-        assert!(matches!(command_rx.try_recv(), Err(TryRecvError::Empty)));
 
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(
-            Event::PreviewFrameReady {
-                t_tl: 40,
-                frame: engine::PreviewFrame {
-                    width: 1,
-                    height: 1,
-                    format: engine::PreviewPixelFormat::Rgba8,
-                    bytes: std::sync::Arc::from(vec![0_u8; 4]),
-                },
-            },
-        )));
-
-        let refreshed = command_rx.recv().expect("refreshed set playhead command");
-        assert_eq!(refreshed, Command::SetPlayhead { t_tl: 40 });
+        assert_eq!(
+            command_rx
+                .try_iter()
+                .map(|tagged| tagged.command)
+                .collect::<Vec<_>>(),
+            vec![
+                Command::Split { at_tl: 40 },
+                Command::SetPlayhead { t_tl: 40 }
+            ]
+        );
     }
 
     #[test]
     fn split_requests_are_not_sent_while_another_split_is_pending() {
         let (command_tx, command_rx) = mpsc::sync_channel(8);
         let mut app = AppState::from_sender_for_test(command_tx);
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(100))));
 
         let _ = app.update(Message::TimelineScrubbed(40));
         let _ = command_rx.recv().expect("first set playhead command");
 
         let _ = app.update(Message::SplitPressed);
-        let first_split = command_rx.recv().expect("first split command");
+        let first_split = command_rx.recv().expect("first split command").command;
         assert_eq!(first_split, Command::Split { at_tl: 40 });
+        let _ = command_rx.recv().expect("playhead refresh command");
 
         let _ = app.update(Message::SplitPressed);
         assert_eq!(app.status, "split request is already pending");
-        assert!(matches!(command_rx.try_recv(), Err(TryRecvError::Empty)));
+        assert!(
+            command_rx
+                .try_iter()
+                .all(|tagged| !matches!(tagged.command, Command::Split { .. }))
+        );
     }
 
     #[test]
@@ -788,37 +1994,36 @@ mod tests {
         let (command_tx, _command_rx) = mpsc::sync_channel(8);
         let mut app = AppState::from_sender_for_test(command_tx);
 
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(
-            Event::PlayheadChanged { t_tl: 1234 },
-        )));
+        let _ = app.update(untagged(Event::PlayheadChanged { t_tl: 1234 }));
 
         assert_eq!(app.playhead_tl, 1234);
     }
 
     #[test]
-    fn timeline_scrub_coalesces_pending_playhead_updates() {
+    fn timeline_scrub_sends_every_playhead_and_shows_only_the_newest() {
         let (command_tx, command_rx) = mpsc::sync_channel(8);
         let mut app = AppState::from_sender_for_test(command_tx);
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(100))));
 
         let _ = app.update(Message::TimelineScrubbed(10));
         let _ = app.update(Message::TimelineScrubbed(20));
         let _ = app.update(Message::TimelineScrubbed(30));
 
-        let first = command_rx.recv().expect("first set playhead command");
-        assert_eq!(first, Command::SetPlayhead { t_tl: 10 });
-        assert!(matches!(command_rx.try_recv(), Err(TryRecvError::Empty)));
+        let sent = command_rx.try_iter().collect::<Vec<_>>();
+        assert_eq!(
+            sent.iter()
+                .map(|tagged| tagged.command.clone())
+                .collect::<Vec<_>>(),
+            [10, 20, 30].map(|t_tl| Command::SetPlayhead { t_tl })
+        );
+        assert_eq!(app.latest_seek_id, Some(sent[2].id));
 
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(
+        let _ = app.update(reply(
+            sent[0].id,
             Event::PreviewFrameReady {
                 t_tl: 10,
+                generation: 1,
+                quality: engine::PreviewQuality::Full,
                 frame: engine::PreviewFrame {
                     width: 1,
                     height: 1,
@@ -826,71 +2031,50 @@ mod tests {
                     bytes: std::sync::Arc::from(vec![0_u8; 4]),
                 },
             },
-        )));
+        ));
         assert_eq!(app.playhead_tl, 30);
-
-        let second = command_rx.recv().expect("second set playhead command");
-        assert_eq!(second, Command::SetPlayhead { t_tl: 30 });
+        assert!(app.preview_image.is_none());
     }
 
     #[test]
     fn stale_playhead_changed_event_does_not_override_latest_scrubbed_playhead() {
         let (command_tx, command_rx) = mpsc::sync_channel(8);
         let mut app = AppState::from_sender_for_test(command_tx);
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(100))));
 
         let _ = app.update(Message::TimelineScrubbed(10));
-        let _ = command_rx.recv().expect("first set playhead command");
+        let first = command_rx.recv().expect("first set playhead command");
         let _ = app.update(Message::TimelineScrubbed(80));
+        let second = command_rx.try_recv().expect("second set playhead command");
+        assert_eq!(second.command, Command::SetPlayhead { t_tl: 80 });
 
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(
-            Event::PlayheadChanged { t_tl: 10 },
-        )));
+        let _ = app.update(reply(first.id, Event::PlayheadChanged { t_tl: 10 }));
+        let _ = app.update(completed(first.id));
 
         assert_eq!(app.playhead_tl, 80);
-
-        let second = command_rx
-            .recv_timeout(Duration::from_millis(100))
-            .expect("second set playhead command");
-        assert_eq!(second, Command::SetPlayhead { t_tl: 80 });
     }
 
     #[test]
     fn stale_preview_frame_ready_event_does_not_override_latest_scrubbed_playhead() {
         let (command_tx, command_rx) = mpsc::sync_channel(8);
         let mut app = AppState::from_sender_for_test(command_tx);
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(100))));
 
         let _ = app.update(Message::TimelineScrubbed(10));
         let first = command_rx.recv().expect("first set playhead command");
-        assert_eq!(first, Command::SetPlayhead { t_tl: 10 });
-
+        assert_eq!(first.command, Command::SetPlayhead { t_tl: 10 });
         let _ = app.update(Message::TimelineScrubbed(80));
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(
-            Event::PlayheadChanged { t_tl: 10 },
-        )));
-        let second = command_rx
-            .recv_timeout(Duration::from_millis(100))
-            .expect("second set playhead command");
-        assert_eq!(second, Command::SetPlayhead { t_tl: 80 });
+        let second = command_rx.try_recv().expect("second set playhead command");
+        assert_eq!(second.command, Command::SetPlayhead { t_tl: 80 });
 
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(
+        let _ = app.update(reply(first.id, Event::PlayheadChanged { t_tl: 10 }));
+        let _ = app.update(completed(first.id));
+        let _ = app.update(reply(
+            first.id,
             Event::PreviewFrameReady {
                 t_tl: 10,
+                generation: 1,
+                quality: engine::PreviewQuality::Full,
                 frame: engine::PreviewFrame {
                     width: 1,
                     height: 1,
@@ -898,71 +2082,70 @@ mod tests {
                     bytes: std::sync::Arc::from(vec![0_u8; 4]),
                 },
             },
-        )));
+        ));
 
         assert_eq!(app.playhead_tl, 80);
         assert!(app.preview_image.is_none());
-    }
 
-    #[test]
-    fn preview_frame_ready_updates_loaded_ranges_and_project_changed_clears_them() {
-        let (command_tx, _command_rx) = mpsc::sync_channel(8);
-        let mut app = AppState::from_sender_for_test(command_tx);
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 200_000,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
-
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(
+        let _ = app.update(reply(
+            second.id,
             Event::PreviewFrameReady {
-                t_tl: 40_000,
+                t_tl: 80,
+                generation: 2,
+                quality: engine::PreviewQuality::Full,
                 frame: engine::PreviewFrame {
-                    width: 2,
+                    width: 1,
                     height: 1,
                     format: engine::PreviewPixelFormat::Rgba8,
-                    bytes: std::sync::Arc::from(vec![0_u8; 8]),
+                    bytes: std::sync::Arc::from(vec![0_u8; 4]),
                 },
             },
-        )));
+        ));
+        assert_eq!(app.playhead_tl, 80);
+        assert!(app.preview_image.is_some());
+    }
+
+    #[test]
+    fn preview_frame_ready_updates_loaded_ranges_and_project_changed_clears_them() {
+        let (command_tx, _command_rx) = mpsc::sync_channel(8);
+        let mut app = AppState::from_sender_for_test(command_tx);
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(200_000))));
+
+        let _ = app.update(untagged(Event::PreviewFrameReady {
+            t_tl: 40_000,
+            generation: 1,
+            quality: engine::PreviewQuality::Full,
+            frame: engine::PreviewFrame {
+                width: 2,
+                height: 1,
+                format: engine::PreviewPixelFormat::Rgba8,
+                bytes: std::sync::Arc::from(vec![0_u8; 8]),
+            },
+        }));
 
         assert!(range_contains_tick(&app.loaded_preview_ranges_tl, 40_000));
         assert!(!range_contains_tick(&app.loaded_preview_ranges_tl, 73_333));
 
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 200_000,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(200_000))));
         assert!(app.loaded_preview_ranges_tl.is_empty());
     }
 
     #[test]
-    fn preview_frame_ready_when_idle_queues_additional_playhead_for_progressive_warm_prefetch() {
+    fn draft_preview_frame_is_shown_without_further_commands() {
         let (command_tx, command_rx) = mpsc::sync_channel(8);
         let mut app = AppState::from_sender_for_test(command_tx);
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(100))));
 
         let _ = app.update(Message::TimelineScrubbed(40));
         let first = command_rx.recv().expect("first set playhead command");
-        assert_eq!(first, Command::SetPlayhead { t_tl: 40 });
+        assert_eq!(first.command, Command::SetPlayhead { t_tl: 40 });
 
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(
+        let _ = app.update(reply(
+            first.id,
             Event::PreviewFrameReady {
                 t_tl: 40,
+                generation: 1,
+                quality: engine::PreviewQuality::Draft,
                 frame: engine::PreviewFrame {
                     width: 1,
                     height: 1,
@@ -970,82 +2153,30 @@ mod tests {
                     bytes: std::sync::Arc::from(vec![0_u8; 4]),
                 },
             },
-        )));
-        assert!(app.preview_image.is_some());
-        assert_eq!(app.latest_requested_playhead_tl, Some(40));
-        assert!(app.playhead_request_in_flight);
-        assert!(app.pending_playhead_tl.is_none());
-        assert_eq!(app.idle_warm_target_tl, Some(40));
-
-        let second = command_rx
-            .recv_timeout(Duration::from_millis(100))
-            .expect("idle warm set playhead command");
-        assert_eq!(second, Command::SetPlayhead { t_tl: 40 });
+        ));
 
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(
-            Event::PreviewFrameReady {
-                t_tl: 40,
-                frame: engine::PreviewFrame {
-                    width: 1,
-                    height: 1,
-                    format: engine::PreviewPixelFormat::Rgba8,
-                    bytes: std::sync::Arc::from(vec![0_u8; 4]),
-                },
-            },
-        )));
-        let third = command_rx
-            .recv_timeout(Duration::from_millis(100))
-            .expect("second idle warm set playhead command");
-        assert_eq!(third, Command::SetPlayhead { t_tl: 40 });
+        assert!(app.preview_image.is_some());
+        assert_eq!(app.status, "draft preview at 40");
+        let _ = app.update(completed(first.id));
+        assert!(matches!(command_rx.try_recv(), Err(TryRecvError::Empty)));
     }
 
     #[test]
-    fn scrub_after_idle_warm_in_flight_still_updates_preview() {
+    fn full_preview_frame_leaves_neighbor_warming_to_the_engine() {
         let (command_tx, command_rx) = mpsc::sync_channel(8);
         let mut app = AppState::from_sender_for_test(command_tx);
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 200,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(100))));
 
         let _ = app.update(Message::TimelineScrubbed(40));
         let first = command_rx.recv().expect("first set playhead command");
-        assert_eq!(first, Command::SetPlayhead { t_tl: 40 });
-
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(
-            Event::PreviewFrameReady {
-                t_tl: 40,
-                frame: engine::PreviewFrame {
-                    width: 1,
-                    height: 1,
-                    format: engine::PreviewPixelFormat::Rgba8,
-                    bytes: std::sync::Arc::from(vec![0_u8; 4]),
-                },
-            },
-        )));
-        let warm = command_rx
-            .recv_timeout(Duration::from_millis(100))
-            .expect("idle warm set playhead command");
-        assert_eq!(warm, Command::SetPlayhead { t_tl: 40 });
-
-        let _ = app.update(Message::TimelineScrubbed(80));
-        assert!(matches!(command_rx.try_recv(), Err(TryRecvError::Empty)));
-
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(
-            Event::PlayheadChanged { t_tl: 40 },
-        )));
-        let second = command_rx
-            .recv_timeout(Duration::from_millis(100))
-            .expect("second set playhead command");
-        assert_eq!(second, Command::SetPlayhead { t_tl: 80 });
+        assert_eq!(first.command, Command::SetPlayhead { t_tl: 40 });
 
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(
+        let _ = app.update(reply(
+            first.id,
             Event::PreviewFrameReady {
                 t_tl: 40,
+                generation: 1,
+                quality: engine::PreviewQuality::Full,
                 frame: engine::PreviewFrame {
                     width: 1,
                     height: 1,
@@ -1053,23 +2184,12 @@ mod tests {
                     bytes: std::sync::Arc::from(vec![0_u8; 4]),
                 },
             },
-        )));
-        assert_eq!(app.playhead_tl, 80);
-
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(
-            Event::PreviewFrameReady {
-                t_tl: 80,
-                frame: engine::PreviewFrame {
-                    width: 1,
-                    height: 1,
-                    format: engine::PreviewPixelFormat::Rgba8,
-                    bytes: std::sync::Arc::from(vec![1_u8; 4]),
-                },
-            },
-        )));
+        ));
+        let _ = app.update(completed(first.id));
 
-        assert_eq!(app.playhead_tl, 80);
         assert!(app.preview_image.is_some());
+        assert_eq!(app.status, "preview ready at 40");
+        assert!(matches!(command_rx.try_recv(), Err(TryRecvError::Empty)));
     }
 
     #[test]
@@ -1077,17 +2197,17 @@ mod tests {
         let (command_tx, _command_rx) = mpsc::sync_channel(8);
         let mut app = AppState::from_sender_for_test(command_tx);
 
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(
-            Event::PreviewFrameReady {
-                t_tl: 12,
-                frame: engine::PreviewFrame {
-                    width: 2,
-                    height: 1,
-                    format: engine::PreviewPixelFormat::Rgba8,
-                    bytes: std::sync::Arc::from(vec![0_u8; 8]),
-                },
+        let _ = app.update(untagged(Event::PreviewFrameReady {
+            t_tl: 12,
+            generation: 1,
+            quality: engine::PreviewQuality::Full,
+            frame: engine::PreviewFrame {
+                width: 2,
+                height: 1,
+                format: engine::PreviewPixelFormat::Rgba8,
+                bytes: std::sync::Arc::from(vec![0_u8; 8]),
             },
-        )));
+        }));
 
         assert!(app.preview_image.is_some());
     }
@@ -1097,17 +2217,17 @@ mod tests {
         let (command_tx, _command_rx) = mpsc::sync_channel(8);
         let mut app = AppState::from_sender_for_test(command_tx);
 
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(
-            Event::PreviewFrameReady {
-                t_tl: 12,
-                frame: engine::PreviewFrame {
-                    width: 2,
-                    height: 1,
-                    format: engine::PreviewPixelFormat::Rgba8,
-                    bytes: std::sync::Arc::from(vec![0_u8; 3]),
-                },
+        let _ = app.update(untagged(Event::PreviewFrameReady {
+            t_tl: 12,
+            generation: 1,
+            quality: engine::PreviewQuality::Full,
+            frame: engine::PreviewFrame {
+                width: 2,
+                height: 1,
+                format: engine::PreviewPixelFormat::Rgba8,
+                bytes: std::sync::Arc::from(vec![0_u8; 3]),
             },
-        )));
+        }));
 
         assert_eq!(
             app.status,
@@ -1119,21 +2239,14 @@ mod tests {
     fn timeline_split_requested_dispatches_split_command() {
         let (command_tx, command_rx) = mpsc::sync_channel(8);
         let mut app = AppState::from_sender_for_test(command_tx);
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(100))));
 
         let _ = app.update(Message::TimelineSplitRequested(100));
 
-        let split = command_rx.recv().expect("split command");
+        let split = command_rx.recv().expect("split command").command;
         assert_eq!(split, Command::Split { at_tl: 99 });
 
-        let set_playhead = command_rx.recv().expect("set playhead command");
+        let set_playhead = command_rx.recv().expect("set playhead command").command;
         assert_eq!(set_playhead, Command::SetPlayhead { t_tl: 99 });
     }
 
@@ -1141,21 +2254,14 @@ mod tests {
     fn timeline_cut_requested_dispatches_cut_command() {
         let (command_tx, command_rx) = mpsc::sync_channel(8);
         let mut app = AppState::from_sender_for_test(command_tx);
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(100))));
 
         let _ = app.update(Message::TimelineCutRequested(100));
 
-        let cut = command_rx.recv().expect("cut command");
+        let cut = command_rx.recv().expect("cut command").command;
         assert_eq!(cut, Command::Cut { at_tl: 99 });
 
-        let set_playhead = command_rx.recv().expect("set playhead command");
+        let set_playhead = command_rx.recv().expect("set playhead command").command;
         assert_eq!(set_playhead, Command::SetPlayhead { t_tl: 99 });
     }
 
@@ -1169,7 +2275,7 @@ mod tests {
             new_start_tl: 345_000,
         });
 
-        let command = command_rx.recv().expect("move segment command");
+        let command = command_rx.recv().expect("move segment command").command;
         assert_eq!(
             command,
             Command::MoveSegment {
@@ -1189,7 +2295,7 @@ mod tests {
             new_start_tl: 123_000,
         });
 
-        let command = command_rx.recv().expect("trim start command");
+        let command = command_rx.recv().expect("trim start command").command;
         assert_eq!(
             command,
             Command::TrimSegmentStart {
@@ -1209,7 +2315,7 @@ mod tests {
             new_end_tl: 456_000,
         });
 
-        let command = command_rx.recv().expect("trim end command");
+        let command = command_rx.recv().expect("trim end command").command;
         assert_eq!(
             command,
             Command::TrimSegmentEnd {
@@ -1219,61 +2325,27 @@ mod tests {
         );
     }
 
-    #[test]
-    fn project_changed_resets_in_flight_state_and_allows_new_scrub_dispatch() {
-        let (command_tx, command_rx) = mpsc::sync_channel(8);
-        let mut app = AppState::from_sender_for_test(command_tx);
-
-        let _ = app.update(Message::TimelineScrubbed(0));
-        let first = command_rx.recv().expect("first set playhead command");
-        assert_eq!(first, Command::SetPlayhead { t_tl: 0 });
-
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
-        let _ = app.update(Message::TimelineScrubbed(60));
-
-        let second = command_rx
-            .recv_timeout(Duration::from_millis(100))
-            .expect("second set playhead command");
-        assert_eq!(second, Command::SetPlayhead { t_tl: 60 });
-    }
-
     #[test]
     fn split_success_updates_status_and_keeps_split_feedback_tick() {
         let (command_tx, command_rx) = mpsc::sync_channel(8);
         let mut app = AppState::from_sender_for_test(command_tx);
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(100))));
 
         let _ = app.update(Message::TimelineScrubbed(30));
         let _ = command_rx.recv().expect("set playhead command");
         let _ = app.update(Message::SplitPressed);
-        let _ = command_rx.recv().expect("split command");
-        assert_eq!(app.pending_split_tl, Some(30));
-
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let split = command_rx.recv().expect("split command");
+        assert_eq!(
+            app.pending_edit,
+            Some((split.id, PendingEdit::Split { at_tl: 30 }))
+        );
+
+        let mut delta = delta_on(&empty_snapshot(100));
+        delta.segments_added.push(segment(2, 30, 70));
+        let _ = app.update(reply(split.id, Event::ProjectUpdated(delta)));
 
         assert_eq!(app.status, "split applied at 30");
-        assert_eq!(app.pending_split_tl, None);
+        assert_eq!(app.pending_edit, None);
         assert_eq!(app.last_split_tl, Some(30));
     }
 
@@ -1281,33 +2353,28 @@ mod tests {
     fn split_failure_updates_status_with_context_and_clears_pending_feedback() {
         let (command_tx, command_rx) = mpsc::sync_channel(8);
         let mut app = AppState::from_sender_for_test(command_tx);
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(100))));
 
         let _ = app.update(Message::TimelineScrubbed(99));
         let _ = command_rx.recv().expect("set playhead command");
         let _ = app.update(Message::SplitPressed);
-        let _ = command_rx.recv().expect("split command");
-        assert_eq!(app.pending_split_tl, Some(99));
+        let split = command_rx.recv().expect("split command");
+        assert_eq!(
+            app.pending_edit,
+            Some((split.id, PendingEdit::Split { at_tl: 99 }))
+        );
 
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::Error(
-            engine::EngineErrorEvent {
-                kind: engine::EngineErrorKind::SplitPointAtBoundary,
-                message: "cannot split at segment boundary: 99".to_owned(),
-            },
-        ))));
+        let _ = app.update(failed(
+            split.id,
+            engine::EngineErrorKind::SplitPointAtBoundary,
+            "cannot split at segment boundary: 99",
+        ));
 
         assert_eq!(
             app.status,
             "split skipped at 99: cannot split at segment boundary: 99"
         );
-        assert_eq!(app.pending_split_tl, None);
+        assert_eq!(app.pending_edit, None);
         assert_eq!(app.last_split_tl, None);
     }
 
@@ -1315,39 +2382,30 @@ mod tests {
     fn split_failure_clears_previous_split_marker() {
         let (command_tx, command_rx) = mpsc::sync_channel(8);
         let mut app = AppState::from_sender_for_test(command_tx);
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(100))));
 
         let _ = app.update(Message::TimelineScrubbed(30));
-        let _ = command_rx.recv().expect("set playhead command");
+        let seek = command_rx.recv().expect("set playhead command");
+        let _ = app.update(completed(seek.id));
         let _ = app.update(Message::SplitPressed);
-        let _ = command_rx.recv().expect("split command");
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let first_split = command_rx.recv().expect("split command");
+        let _ = command_rx.recv().expect("playhead refresh command");
+        let _ = app.update(reply(
+            first_split.id,
+            Event::ProjectChanged(empty_snapshot(100)),
+        ));
         assert_eq!(app.last_split_tl, Some(30));
 
         let _ = app.update(Message::TimelineScrubbed(99));
         let _ = command_rx.recv().expect("set playhead command");
         let _ = app.update(Message::SplitPressed);
-        let _ = command_rx.recv().expect("split command");
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::Error(
-            engine::EngineErrorEvent {
-                kind: engine::EngineErrorKind::SplitPointAtBoundary,
-                message: "cannot split at segment boundary: 99".to_owned(),
-            },
-        ))));
+        let second_split = command_rx.recv().expect("split command");
+        let _ = command_rx.recv().expect("playhead refresh command");
+        let _ = app.update(failed(
+            second_split.id,
+            engine::EngineErrorKind::SplitPointAtBoundary,
+            "cannot split at segment boundary: 99",
+        ));
 
         assert_eq!(app.last_split_tl, None);
     }
@@ -1356,43 +2414,28 @@ mod tests {
     fn non_split_error_clears_pending_split_feedback() {
         let (command_tx, command_rx) = mpsc::sync_channel(8);
         let mut app = AppState::from_sender_for_test(command_tx);
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(100))));
 
         let _ = app.update(Message::TimelineScrubbed(42));
         let _ = command_rx.recv().expect("set playhead command");
         let _ = app.update(Message::SplitPressed);
-        let _ = command_rx.recv().expect("split command");
+        let split = command_rx.recv().expect("split command");
 
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::Error(
-            engine::EngineErrorEvent {
-                kind: engine::EngineErrorKind::Other,
-                message: "media backend error: decode failed".to_owned(),
-            },
-        ))));
+        let _ = app.update(failed(
+            split.id,
+            engine::EngineErrorKind::Other,
+            "media backend error: decode failed",
+        ));
 
         assert_eq!(
             app.status,
             "split failed at 42: media backend error: decode failed"
         );
-        assert_eq!(app.pending_split_tl, None);
+        assert_eq!(app.pending_edit, None);
         assert_eq!(app.last_split_tl, None);
 
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
-        assert_eq!(app.pending_split_tl, None);
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(100))));
+        assert_eq!(app.pending_edit, None);
         assert_eq!(app.last_split_tl, None);
     }
 
@@ -1400,32 +2443,24 @@ mod tests {
     fn split_like_error_message_still_clears_pending_when_kind_is_other() {
         let (command_tx, command_rx) = mpsc::sync_channel(8);
         let mut app = AppState::from_sender_for_test(command_tx);
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(100))));
 
         let _ = app.update(Message::TimelineScrubbed(50));
         let _ = command_rx.recv().expect("set playhead command");
         let _ = app.update(Message::SplitPressed);
-        let _ = command_rx.recv().expect("split command");
+        let split = command_rx.recv().expect("split command");
 
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::Error(
-            engine::EngineErrorEvent {
-                kind: engine::EngineErrorKind::Other,
-                message: "cannot split at segment boundary: 50".to_owned(),
-            },
-        ))));
+        let _ = app.update(failed(
+            split.id,
+            engine::EngineErrorKind::Other,
+            "cannot split at segment boundary: 50",
+        ));
 
         assert_eq!(
             app.status,
             "split failed at 50: cannot split at segment boundary: 50"
         );
-        assert_eq!(app.pending_split_tl, None);
+        assert_eq!(app.pending_edit, None);
         assert_eq!(app.last_split_tl, None);
     }
 
@@ -1433,57 +2468,34 @@ mod tests {
     fn non_cut_error_clears_pending_cut_feedback() {
         let (command_tx, command_rx) = mpsc::sync_channel(8);
         let mut app = AppState::from_sender_for_test(command_tx);
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(100))));
 
         let _ = app.update(Message::TimelineScrubbed(40));
         let _ = command_rx.recv().expect("set playhead command");
         let _ = app.update(Message::CutPressed);
-        let _ = command_rx.recv().expect("cut command");
-        assert_eq!(app.pending_cut_tl, Some(40));
+        let cut = command_rx.recv().expect("cut command");
+        assert_eq!(
+            app.pending_edit,
+            Some((cut.id, PendingEdit::Cut { at_tl: 40 }))
+        );
 
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::Error(
-            engine::EngineErrorEvent {
-                kind: engine::EngineErrorKind::Other,
-                message: "mux failed".to_owned(),
-            },
-        ))));
+        let _ = app.update(failed(cut.id, engine::EngineErrorKind::Other, "mux failed"));
 
         assert_eq!(app.status, "cut failed at 40: mux failed");
-        assert_eq!(app.pending_cut_tl, None);
+        assert_eq!(app.pending_edit, None);
     }
 
     #[test]
     fn bridge_disconnected_clears_split_feedback() {
         let (command_tx, command_rx) = mpsc::sync_channel(8);
         let mut app = AppState::from_sender_for_test(command_tx);
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(100))));
 
         let _ = app.update(Message::TimelineScrubbed(30));
         let _ = command_rx.recv().expect("set playhead command");
         let _ = app.update(Message::SplitPressed);
-        let _ = command_rx.recv().expect("split command");
-        let _ = app.update(Message::Bridge(BridgeEvent::Event(Event::ProjectChanged(
-            ProjectSnapshot {
-                assets: vec![],
-                segments: vec![],
-                duration_tl: 100,
-                preview_bucket_tl: 33_333,
-            },
-        ))));
+        let split = command_rx.recv().expect("split command");
+        let _ = app.update(reply(split.id, Event::ProjectChanged(empty_snapshot(100))));
         assert_eq!(app.last_split_tl, Some(30));
 
         let _ = app.update(Message::Bridge(BridgeEvent::Disconnected));
@@ -1491,6 +2503,516 @@ mod tests {
         assert_eq!(app.last_split_tl, None);
     }
 
+    #[test]
+    fn play_pause_button_toggles_with_engine_playback_state() {
+        let (command_tx, command_rx) = mpsc::sync_channel(8);
+        let mut app = AppState::from_sender_for_test(command_tx);
+
+        let _ = app.update(Message::PlayPausePressed);
+        assert_eq!(
+            command_rx.recv().expect("play command").command,
+            Command::Play
+        );
+
+        let _ = app.update(untagged(Event::PlaybackStateChanged {
+            playing: true,
+            rate: 1,
+        }));
+        let _ = app.update(Message::PlayPausePressed);
+        assert_eq!(
+            command_rx.recv().expect("pause command").command,
+            Command::Pause
+        );
+    }
+
+    #[test]
+    fn playback_playhead_events_advance_playhead_after_scrub_is_acknowledged() {
+        let (command_tx, command_rx) = mpsc::sync_channel(8);
+        let mut app = AppState::from_sender_for_test(command_tx);
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(1_000_000))));
+        let _ = app.update(untagged(Event::PlaybackStateChanged {
+            playing: true,
+            rate: 1,
+        }));
+
+        let _ = app.update(Message::TimelineScrubbed(500_000));
+        let seek = command_rx.recv().expect("set playhead command");
+        let _ = app.update(untagged(Event::PlayheadChanged { t_tl: 100_000 }));
+        assert_eq!(app.playhead_tl, 500_000);
+
+        let _ = app.update(reply(seek.id, Event::PlayheadChanged { t_tl: 500_000 }));
+        let _ = app.update(completed(seek.id));
+        let _ = app.update(untagged(Event::PlayheadChanged { t_tl: 530_000 }));
+        assert_eq!(app.playhead_tl, 530_000);
+    }
+
+    #[test]
+    fn transport_shortcuts_map_jkl_arrows_and_space() {
+        let key = |c: &str| Key::Character(c.into());
+
+        assert!(matches!(
+            shortcut_message(key("j"), Modifiers::default()),
+            Some(Message::ShuttleReverse)
+        ));
+        assert!(matches!(
+            shortcut_message(key("k"), Modifiers::default()),
+            Some(Message::ShuttlePause)
+        ));
+        assert!(matches!(
+            shortcut_message(key("l"), Modifiers::default()),
+            Some(Message::ShuttleForward)
+        ));
+        assert!(matches!(
+            shortcut_message(Key::Named(Named::Space), Modifiers::default()),
+            Some(Message::PlayPausePressed)
+        ));
+        assert!(matches!(
+            shortcut_message(Key::Named(Named::ArrowLeft), Modifiers::SHIFT),
+            Some(Message::StepFrame(-10))
+        ));
+        assert!(matches!(
+            shortcut_message(Key::Named(Named::ArrowRight), Modifiers::default()),
+            Some(Message::StepFrame(1))
+        ));
+        assert!(matches!(
+            shortcut_message(Key::Named(Named::ArrowDown), Modifiers::default()),
+            Some(Message::JumpToEditPoint(EditPointDirection::Next))
+        ));
+        assert!(shortcut_message(key("x"), Modifiers::default()).is_none());
+    }
+
+    #[test]
+    fn mark_shortcuts_map_i_o_and_alt_x() {
+        let key = |c: &str| Key::Character(c.into());
+
+        assert!(matches!(
+            shortcut_message(key("i"), Modifiers::default()),
+            Some(Message::MarkInPressed)
+        ));
+        assert!(matches!(
+            shortcut_message(key("o"), Modifiers::default()),
+            Some(Message::MarkOutPressed)
+        ));
+        assert!(matches!(
+            shortcut_message(key("x"), Modifiers::ALT),
+            Some(Message::ClearInOutPressed)
+        ));
+    }
+
+    #[test]
+    fn mark_buttons_dispatch_marks_at_playhead_and_range_commands() {
+        let (command_tx, command_rx) = mpsc::sync_channel(8);
+        let mut app = AppState::from_sender_for_test(command_tx);
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(1_000_000))));
+        app.playhead_tl = 250_000;
+
+        let _ = app.update(Message::MarkInPressed);
+        let _ = app.update(Message::MarkOutPressed);
+        let _ = app.update(Message::LoopPlaybackToggled);
+        let _ = app.update(Message::ExtractPressed);
+        let _ = app.update(Message::ExportPathChanged("range.mp4".to_owned()));
+        let _ = app.update(Message::ExportInOutPressed);
+
+        let commands: Vec<_> = command_rx.try_iter().map(|tagged| tagged.command).collect();
+        assert_eq!(
+            commands,
+            vec![
+                Command::SetInPoint { at_tl: 250_000 },
+                Command::SetOutPoint { at_tl: 250_000 },
+                Command::SetLoopPlayback { enabled: true },
+                Command::DeleteInOutRange { ripple: true },
+                Command::Export {
+                    path: PathBuf::from("range.mp4"),
+                    settings: engine::ExportSettings {
+                        range: engine::ExportRange::InOut,
+                    },
+                },
+            ]
+        );
+
+        let _ = app.update(untagged(Event::LoopPlaybackChanged { enabled: true }));
+        assert_eq!(app.status, "loop playback on");
+        let _ = app.update(Message::LoopPlaybackToggled);
+        let toggled = command_rx.try_recv().expect("loop toggle command");
+        assert_eq!(toggled.command, Command::SetLoopPlayback { enabled: false });
+    }
+
+    #[test]
+    fn marks_only_project_change_keeps_loaded_preview_ranges() {
+        let (command_tx, _command_rx) = mpsc::sync_channel(8);
+        let mut app = AppState::from_sender_for_test(command_tx);
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(1_000_000))));
+        app.loaded_preview_ranges_tl.push((0, 33_333));
+
+        let mut marked = empty_snapshot(1_000_000);
+        marked.in_point_tl = Some(100_000);
+        let _ = app.update(untagged(Event::ProjectChanged(marked)));
+
+        assert_eq!(app.loaded_preview_ranges_tl, vec![(0, 33_333)]);
+        assert_eq!(app.status, "in/out marked: 100000..end");
+    }
+
+    #[test]
+    fn project_delta_patches_segments_and_resets_preview() {
+        let (command_tx, command_rx) = mpsc::sync_channel(8);
+        let mut app = AppState::from_sender_for_test(command_tx);
+        let mut snapshot = empty_snapshot(100);
+        snapshot.segments = vec![segment(1, 0, 50), segment(2, 50, 50)];
+        let mut delta = delta_on(&snapshot);
+        let _ = app.update(untagged(Event::ProjectChanged(snapshot)));
+        app.loaded_preview_ranges_tl.push((0, 33));
+
+        delta.segments_updated.push(segment(1, 0, 20));
+        delta.segments_added.push(segment(3, 20, 30));
+        delta.segments_removed.push(2);
+        delta.duration_tl = 50;
+        let _ = app.update(untagged(Event::ProjectUpdated(delta)));
+
+        let project = app.project.as_ref().expect("project");
+        assert_eq!(project.revision, 2);
+        assert_eq!(
+            project.segments,
+            vec![segment(1, 0, 20), segment(3, 20, 30)]
+        );
+        assert_eq!(project.duration_tl, 50);
+        assert!(app.loaded_preview_ranges_tl.is_empty());
+        assert_eq!(command_rx.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    #[test]
+    fn project_delta_for_other_revision_requests_full_snapshot() {
+        let (command_tx, command_rx) = mpsc::sync_channel(8);
+        let mut app = AppState::from_sender_for_test(command_tx);
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(100))));
+
+        let mut delta = delta_on(&empty_snapshot(100));
+        delta.base_revision = 4;
+        delta.revision = 5;
+        delta.duration_tl = 40;
+        let _ = app.update(untagged(Event::ProjectUpdated(delta)));
+
+        assert_eq!(app.project, Some(empty_snapshot(100)));
+        assert_eq!(
+            command_rx.recv().expect("snapshot request").command,
+            Command::RequestProjectSnapshot
+        );
+        assert_eq!(app.status, "resynchronizing project");
+    }
+
+    #[test]
+    fn selection_shortcuts_map_to_selection_messages() {
+        let key = |c: &str| Key::Character(c.into());
+
+        assert!(matches!(
+            shortcut_message(key("a"), Modifiers::COMMAND),
+            Some(Message::SelectAllPressed)
+        ));
+        assert!(shortcut_message(key("a"), Modifiers::default()).is_none());
+        assert!(matches!(
+            shortcut_message(Key::Named(Named::Escape), Modifiers::default()),
+            Some(Message::ClearSelectionPressed)
+        ));
+        assert!(matches!(
+            shortcut_message(Key::Named(Named::Delete), Modifiers::default()),
+            Some(Message::DeleteSelectionPressed { ripple: false })
+        ));
+        assert!(matches!(
+            shortcut_message(Key::Named(Named::Backspace), Modifiers::SHIFT),
+            Some(Message::DeleteSelectionPressed { ripple: true })
+        ));
+    }
+
+    #[test]
+    fn clipboard_shortcuts_and_duplicate_dispatch_commands() {
+        let key = |c: &str| Key::Character(c.into());
+        assert!(matches!(
+            shortcut_message(key("c"), Modifiers::COMMAND),
+            Some(Message::CopyPressed)
+        ));
+        assert!(matches!(
+            shortcut_message(key("v"), Modifiers::COMMAND),
+            Some(Message::PastePressed(PasteMode::Insert))
+        ));
+        assert!(matches!(
+            shortcut_message(key("V"), Modifiers::COMMAND | Modifiers::SHIFT),
+            Some(Message::PastePressed(PasteMode::Overwrite))
+        ));
+
+        let (command_tx, command_rx) = mpsc::sync_channel(8);
+        let mut app = AppState::from_sender_for_test(command_tx);
+        let mut snapshot = empty_snapshot(100);
+        snapshot.segments = vec![segment(1, 0, 50), segment(2, 50, 50)];
+        snapshot.selection = vec![1, 2];
+        let _ = app.update(untagged(Event::ProjectChanged(snapshot.clone())));
+
+        let _ = app.update(Message::DuplicatePressed);
+        assert_eq!(app.status, "select one segment to duplicate");
+        assert_eq!(command_rx.try_recv(), Err(TryRecvError::Empty));
+
+        let mut delta = delta_on(&snapshot);
+        delta.selection = Some(vec![2]);
+        let _ = app.update(untagged(Event::ProjectUpdated(delta)));
+        let _ = app.update(Message::DuplicatePressed);
+        assert_eq!(
+            command_rx.recv().expect("duplicate command").command,
+            Command::DuplicateSegment { segment_id: 2 }
+        );
+
+        let _ = app.update(Message::PastePressed(PasteMode::Overwrite));
+        assert_eq!(
+            command_rx.recv().expect("paste command").command,
+            Command::Paste {
+                mode: PasteMode::Overwrite
+            }
+        );
+    }
+
+    #[test]
+    fn toggling_selection_enabled_disables_unless_all_are_disabled() {
+        assert!(matches!(
+            shortcut_message(Key::Character("E".into()), Modifiers::SHIFT),
+            Some(Message::ToggleSelectionEnabledPressed)
+        ));
+        let (command_tx, command_rx) = mpsc::sync_channel(8);
+        let mut app = AppState::from_sender_for_test(command_tx);
+        let mut snapshot = empty_snapshot(100);
+        let mut disabled = segment(2, 50, 50);
+        disabled.enabled = false;
+        snapshot.segments = vec![segment(1, 0, 50), disabled];
+        snapshot.selection = vec![1, 2];
+        let _ = app.update(untagged(Event::ProjectChanged(snapshot.clone())));
+
+        let _ = app.update(Message::ToggleSelectionEnabledPressed);
+        assert_eq!(
+            command_rx.recv().expect("disable command").command,
+            Command::SetSelectionEnabled { enabled: false }
+        );
+
+        let mut delta = delta_on(&snapshot);
+        delta.selection = Some(vec![2]);
+        let _ = app.update(untagged(Event::ProjectUpdated(delta)));
+        let _ = app.update(Message::ToggleSelectionEnabledPressed);
+        assert_eq!(
+            command_rx.recv().expect("enable command").command,
+            Command::SetSelectionEnabled { enabled: true }
+        );
+    }
+
+    #[test]
+    fn timeline_selection_clicks_replace_add_or_toggle_segments() {
+        let (command_tx, command_rx) = mpsc::sync_channel(8);
+        let mut app = AppState::from_sender_for_test(command_tx);
+        let mut snapshot = empty_snapshot(100);
+        snapshot.segments = vec![segment(1, 0, 50), segment(2, 50, 50)];
+        snapshot.selection = vec![1, 2];
+        let _ = app.update(untagged(Event::ProjectChanged(snapshot)));
+
+        let _ = app.update(Message::TimelineSegmentSelected {
+            segment_id: 2,
+            mode: timeline::SelectMode::Toggle,
+        });
+        assert_eq!(
+            command_rx.recv().expect("toggle command").command,
+            Command::ToggleSelection { segment_id: 2 }
+        );
+
+        let _ = app.update(Message::TimelineSegmentSelected {
+            segment_id: 2,
+            mode: timeline::SelectMode::Replace,
+        });
+        assert_eq!(
+            command_rx.recv().expect("replace command").command,
+            Command::Select {
+                segment_ids: vec![2]
+            }
+        );
+
+        let _ = app.update(Message::TimelineSegmentSelected {
+            segment_id: 2,
+            mode: timeline::SelectMode::Add,
+        });
+        assert_eq!(
+            command_rx.recv().expect("add command").command,
+            Command::AddToSelection {
+                segment_ids: vec![2]
+            }
+        );
+
+        let _ = app.update(Message::TimelineRangeSelected {
+            start_tl: 10,
+            end_tl: 60,
+        });
+        assert_eq!(
+            command_rx.recv().expect("range command").command,
+            Command::SelectRange {
+                start_tl: 10,
+                end_tl: 60,
+                extend: true,
+            }
+        );
+    }
+
+    #[test]
+    fn dragging_a_selected_segment_moves_the_whole_selection() {
+        let (command_tx, command_rx) = mpsc::sync_channel(8);
+        let mut app = AppState::from_sender_for_test(command_tx);
+        let mut snapshot = empty_snapshot(100);
+        snapshot.segments = vec![segment(1, 0, 30), segment(2, 40, 30)];
+        snapshot.selection = vec![1, 2];
+        let _ = app.update(untagged(Event::ProjectChanged(snapshot)));
+
+        let _ = app.update(Message::TimelineSegmentMoveRequested {
+            segment_id: 2,
+            new_start_tl: 55,
+        });
+
+        assert_eq!(
+            command_rx.recv().expect("move selection command").command,
+            Command::MoveSelection { delta_tl: 15 }
+        );
+        assert_eq!(app.status, "selection moved by 15");
+    }
+
+    #[test]
+    fn selection_delta_keeps_preview_and_reports_selection() {
+        let (command_tx, _command_rx) = mpsc::sync_channel(8);
+        let mut app = AppState::from_sender_for_test(command_tx);
+        let mut snapshot = empty_snapshot(100);
+        snapshot.segments = vec![segment(1, 0, 50), segment(2, 50, 50)];
+        let mut delta = delta_on(&snapshot);
+        let _ = app.update(untagged(Event::ProjectChanged(snapshot)));
+        app.loaded_preview_ranges_tl.push((0, 33));
+
+        delta.selection = Some(vec![1, 2]);
+        let _ = app.update(untagged(Event::ProjectUpdated(delta)));
+
+        assert_eq!(app.selection(), &[1, 2]);
+        assert_eq!(app.loaded_preview_ranges_tl, vec![(0, 33)]);
+        assert_eq!(app.status, "2 segment(s) selected");
+    }
+
+    #[test]
+    fn snap_toggle_sends_the_flipped_setting_and_reports_it() {
+        let (command_tx, command_rx) = mpsc::sync_channel(8);
+        let mut app = AppState::from_sender_for_test(command_tx);
+        let snapshot = empty_snapshot(100);
+        let mut delta = delta_on(&snapshot);
+        let _ = app.update(untagged(Event::ProjectChanged(snapshot)));
+        app.loaded_preview_ranges_tl.push((0, 33));
+
+        let _ = app.update(Message::SnapToFramesToggled);
+        let toggle = command_rx.try_recv().expect("snap command");
+        assert_eq!(toggle.command, Command::SetSnapToFrames { enabled: true });
+
+        delta.snap_to_frames = true;
+        let _ = app.update(reply(toggle.id, Event::ProjectUpdated(delta)));
+        assert!(app.snap_to_frames());
+        assert_eq!(app.loaded_preview_ranges_tl, vec![(0, 33)]);
+        assert_eq!(app.status, "frame snapping on");
+    }
+
+    #[test]
+    fn step_frame_result_is_not_treated_as_stale_scrub_response() {
+        let (command_tx, command_rx) = mpsc::sync_channel(8);
+        let mut app = AppState::from_sender_for_test(command_tx);
+        let _ = app.update(untagged(Event::ProjectChanged(empty_snapshot(1_000_000))));
+        let _ = app.update(Message::TimelineScrubbed(100_000));
+        let _ = command_rx.recv().expect("set playhead command");
+
+        let _ = app.update(Message::StepFrame(1));
+        let step = command_rx.recv().expect("step command");
+        assert_eq!(step.command, Command::StepFrame { delta: 1 });
+        let _ = app.update(reply(step.id, Event::PlayheadChanged { t_tl: 133_367 }));
+
+        assert_eq!(app.playhead_tl, 133_367);
+    }
+
+    /// Bridge message for an event not caused by a UI command.
+    fn untagged(event: Event) -> Message {
+        Message::Bridge(BridgeEvent::Event(Box::new(TaggedEvent {
+            id: None,
+            event,
+        })))
+    }
+
+    /// Bridge message for an event caused by command `id`.
+    fn reply(id: CommandId, event: Event) -> Message {
+        Message::Bridge(BridgeEvent::Event(Box::new(TaggedEvent {
+            id: Some(id),
+            event,
+        })))
+    }
+
+    fn completed(id: CommandId) -> Message {
+        reply(id, Event::CommandCompleted { id, result: Ok(()) })
+    }
+
+    fn failed(id: CommandId, kind: engine::EngineErrorKind, message: &str) -> Message {
+        reply(
+            id,
+            Event::CommandCompleted {
+                id,
+                result: Err(engine::EngineErrorEvent {
+                    kind,
+                    message: message.to_owned(),
+                }),
+            },
+        )
+    }
+
+    fn empty_snapshot(duration_tl: i64) -> ProjectSnapshot {
+        ProjectSnapshot {
+            revision: 1,
+            assets: vec![],
+            segments: vec![],
+            duration_tl,
+            preview_bucket_tl: 33_333,
+            in_point_tl: None,
+            out_point_tl: None,
+            loop_playback: false,
+            frame_grid: FrameGrid::DEFAULT,
+            drop_frame: false,
+            snap_to_frames: false,
+            selection: vec![],
+        }
+    }
+
+    /// Delta on top of `snapshot` that changes nothing yet.
+    fn delta_on(snapshot: &ProjectSnapshot) -> ProjectDelta {
+        ProjectDelta {
+            base_revision: snapshot.revision,
+            revision: snapshot.revision + 1,
+            assets_added: vec![],
+            assets_updated: vec![],
+            segments_added: vec![],
+            segments_updated: vec![],
+            segments_removed: vec![],
+            duration_tl: snapshot.duration_tl,
+            preview_bucket_tl: snapshot.preview_bucket_tl,
+            in_point_tl: snapshot.in_point_tl,
+            out_point_tl: snapshot.out_point_tl,
+            loop_playback: snapshot.loop_playback,
+            frame_grid: snapshot.frame_grid,
+            drop_frame: snapshot.drop_frame,
+            snap_to_frames: snapshot.snap_to_frames,
+            selection: None,
+        }
+    }
+
+    fn segment(id: u64, timeline_start: i64, timeline_duration: i64) -> SegmentSummary {
+        SegmentSummary {
+            id,
+            asset_id: 1,
+            timeline_start,
+            timeline_duration,
+            src_in_video: Some(timeline_start),
+            src_out_video: Some(timeline_start + timeline_duration),
+            src_in_audio: None,
+            src_out_audio: None,
+            enabled: true,
+        }
+    }
+
     fn range_contains_tick(ranges: &[(i64, i64)], tick: i64) -> bool {
         ranges
             .iter()
@@ -1,28 +1,43 @@
 use std::sync::mpsc;
 use std::thread;
+use std::time::{Duration, Instant};
 
-use engine::{Command, Engine, EngineErrorEvent, Event, MediaBackend};
+use engine::{
+    Engine, EngineErrorEvent, Event, MediaBackend, Result as EngineResult, TaggedCommand,
+    TaggedEvent,
+};
 use iced::futures::{SinkExt, StreamExt, channel::mpsc as futures_mpsc, executor};
 use iced::{Subscription, stream};
 
 const COMMAND_CHANNEL_CAPACITY: usize = 32;
 const EVENT_CHANNEL_CAPACITY: usize = 8;
 const SUBSCRIPTION_CHANNEL_CAPACITY: usize = 32;
+/// Interval between playback clock ticks while the engine is playing.
+const PLAYBACK_TICK_INTERVAL: Duration = Duration::from_millis(15);
 
 /// Sender used by the UI thread to dispatch commands to the engine thread.
-pub type EngineCommandSender = mpsc::SyncSender<Command>;
+pub type EngineCommandSender = mpsc::SyncSender<TaggedCommand>;
 
 /// Receiver used by the UI thread to read events emitted by the engine thread.
-pub type EngineEventReceiver = mpsc::Receiver<Event>;
+pub type EngineEventReceiver = mpsc::Receiver<TaggedEvent>;
 
 /// Messages emitted by the engine bridge subscription.
 #[derive(Debug, Clone)]
 pub enum BridgeEvent {
     Ready(EngineCommandSender),
-    Event(Event),
+    /// Boxed so project deltas do not inflate every bridge message.
+    Event(Box<TaggedEvent>),
     Disconnected,
 }
 
+/// Wake-ups of the engine thread: client commands and finished background
+/// preview decodes share one channel so the thread never polls.
+enum BridgeInput {
+    Command(TaggedCommand),
+    PreviewReady,
+    CommandsClosed,
+}
+
 /// Builds a subscription that starts the engine bridge and forwards events.
 pub fn engine_subscription() -> Subscription<BridgeEvent> {
     Subscription::run(bridge_worker_stream)
@@ -47,7 +62,9 @@ fn bridge_worker_stream_with(
             thread::spawn(move || {
                 let mut forward_tx = forward_tx;
                 while let Ok(event) = engine_rx.recv() {
-                    if executor::block_on(forward_tx.send(BridgeEvent::Event(event))).is_err() {
+                    if executor::block_on(forward_tx.send(BridgeEvent::Event(Box::new(event))))
+                        .is_err()
+                    {
                         return;
                     }
                 }
@@ -71,29 +88,65 @@ pub fn spawn_ffmpeg_bridge() -> (EngineCommandSender, EngineEventReceiver) {
 /// Spawns a bridge around any engine backend.
 pub fn spawn_engine_bridge<M>(mut engine: Engine<M>) -> (EngineCommandSender, EngineEventReceiver)
 where
-    M: MediaBackend + Send + 'static,
+    M: MediaBackend + 'static,
 {
-    let (command_tx, command_rx) = mpsc::sync_channel::<Command>(COMMAND_CHANNEL_CAPACITY);
-    let (event_tx, event_rx) = mpsc::sync_channel::<Event>(EVENT_CHANNEL_CAPACITY);
+    let (command_tx, command_rx) = mpsc::sync_channel::<TaggedCommand>(COMMAND_CHANNEL_CAPACITY);
+    let (event_tx, event_rx) = mpsc::sync_channel::<TaggedEvent>(EVENT_CHANNEL_CAPACITY);
+    let (input_tx, input_rx) = mpsc::channel::<BridgeInput>();
 
+    let preview_tx = input_tx.clone();
+    engine.set_preview_waker(move || {
+        let _ = preview_tx.send(BridgeInput::PreviewReady);
+    });
     thread::spawn(move || {
         while let Ok(command) = command_rx.recv() {
-            match engine.handle_command(command) {
-                Ok(events) => {
-                    for event in events {
-                        if event_tx.send(event).is_err() {
-                            return;
-                        }
-                    }
-                }
-                Err(error) => {
-                    if event_tx
-                        .send(Event::Error(EngineErrorEvent::from_error(&error)))
-                        .is_err()
+            if input_tx.send(BridgeInput::Command(command)).is_err() {
+                return;
+            }
+        }
+        let _ = input_tx.send(BridgeInput::CommandsClosed);
+    });
+
+    thread::spawn(move || {
+        let mut next_tick = Instant::now();
+        loop {
+            let playback_due = engine.is_playing().then_some(next_tick);
+            let deadline = match (playback_due, engine.autosave_due()) {
+                (Some(tick), Some(autosave)) => Some(tick.min(autosave)),
+                (tick, autosave) => tick.or(autosave),
+            };
+            let input = match deadline {
+                Some(deadline) => {
+                    match input_rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
                     {
-                        return;
+                        Ok(input) => Some(input),
+                        Err(mpsc::RecvTimeoutError::Timeout) => None,
+                        Err(mpsc::RecvTimeoutError::Disconnected) => return,
                     }
                 }
+                None => match input_rx.recv() {
+                    Ok(input) => Some(input),
+                    Err(_) => return,
+                },
+            };
+            let mut events = match input {
+                Some(BridgeInput::Command(command)) => engine.handle_tagged_command(command),
+                Some(BridgeInput::CommandsClosed) => return,
+                Some(BridgeInput::PreviewReady) | None => Vec::new(),
+            };
+            // Ticks run whenever they are due so a steady command stream
+            // cannot stall playback and audio.
+            let now = Instant::now();
+            if engine.is_playing() && now >= next_tick {
+                events.extend(untagged(engine.tick_playback()));
+                next_tick = now + PLAYBACK_TICK_INTERVAL;
+            }
+            events.extend(engine.poll_tagged_events());
+            events.extend(untagged(engine.tick_autosave()));
+            for event in events {
+                if event_tx.send(event).is_err() {
+                    return;
+                }
             }
         }
     });
@@ -101,6 +154,17 @@ where
     (command_tx, event_rx)
 }
 
+/// Wraps events not caused by a client command, such as playback ticks and
+/// autosaves.
+fn untagged(result: EngineResult<Vec<Event>>) -> Vec<TaggedEvent> {
+    let events =
+        result.unwrap_or_else(|error| vec![Event::Error(EngineErrorEvent::from_error(&error))]);
+    events
+        .into_iter()
+        .map(|event| TaggedEvent { id: None, event })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::{Path, PathBuf};
@@ -111,12 +175,11 @@ mod tests {
 
     use iced::futures::{StreamExt, executor, pin_mut};
 
-    use engine::Rational;
     use engine::preview::{PreviewFrame, PreviewPixelFormat, ProbedMedia, ProbedVideoStream};
+    use engine::{Command, CommandId, PcmChunk, PcmFormat, Rational, TaggedCommand, TaggedEvent};
 
     use super::{
-        BridgeEvent, Command, Engine, Event, MediaBackend, bridge_worker_stream_with,
-        spawn_engine_bridge,
+        BridgeEvent, Engine, Event, MediaBackend, bridge_worker_stream_with, spawn_engine_bridge,
     };
 
     #[test]
@@ -124,9 +187,12 @@ mod tests {
         let (command_tx, event_rx) = spawn_engine_bridge(Engine::new(MockBackend));
 
         command_tx
-            .send(Command::Import {
-                path: PathBuf::from("demo.mp4"),
-            })
+            .send(tagged(
+                1,
+                Command::Import {
+                    path: PathBuf::from("demo.mp4"),
+                },
+            ))
             .expect("send import command");
 
         let first = event_rx
@@ -136,8 +202,25 @@ mod tests {
             .recv_timeout(Duration::from_secs(1))
             .expect("second event");
 
-        assert!(matches!(first, Event::ProjectChanged(_)));
-        assert_eq!(second, Event::PlayheadChanged { t_tl: 0 });
+        assert_eq!(first.id, Some(CommandId(1)));
+        assert!(matches!(first.event, Event::ProjectChanged(_)));
+        assert_eq!(
+            second,
+            TaggedEvent {
+                id: Some(CommandId(1)),
+                event: Event::PlayheadChanged { t_tl: 0 },
+            }
+        );
+        let third = event_rx
+            .recv_timeout(Duration::from_secs(1))
+            .expect("completion event");
+        assert_eq!(
+            third.event,
+            Event::CommandCompleted {
+                id: CommandId(1),
+                result: Ok(()),
+            }
+        );
     }
 
     #[test]
@@ -145,15 +228,19 @@ mod tests {
         let (command_tx, event_rx) = spawn_engine_bridge(Engine::new(MockBackend));
 
         command_tx
-            .send(Command::SetPlayhead { t_tl: 10 })
+            .send(tagged(1, Command::SetPlayhead { t_tl: 10 }))
             .expect("send set playhead command");
 
         let event = event_rx
             .recv_timeout(Duration::from_secs(1))
             .expect("error event");
 
-        let Event::Error(error) = event else {
-            panic!("expected Event::Error");
+        let Event::CommandCompleted {
+            id: CommandId(1),
+            result: Err(error),
+        } = event.event
+        else {
+            panic!("expected a failed CommandCompleted");
         };
         assert_eq!(error.kind, engine::EngineErrorKind::Other);
         assert!(error.message.contains("project is not loaded"));
@@ -167,7 +254,7 @@ mod tests {
             let stream = bridge_worker_stream_with(spawn_mock_bridge);
             executor::block_on(async move {
                 pin_mut!(stream);
-                for _ in 0..4 {
+                for _ in 0..5 {
                     let Some(event) = stream.next().await else {
                         break;
                     };
@@ -186,9 +273,12 @@ mod tests {
         };
 
         command_tx
-            .send(Command::Import {
-                path: PathBuf::from("demo.mp4"),
-            })
+            .send(tagged(
+                1,
+                Command::Import {
+                    path: PathBuf::from("demo.mp4"),
+                },
+            ))
             .expect("send import command");
 
         let first = bridge_rx
@@ -196,7 +286,7 @@ mod tests {
             .expect("first forwarded event");
         assert!(matches!(
             first,
-            BridgeEvent::Event(Event::ProjectChanged(_))
+            BridgeEvent::Event(tagged) if matches!(tagged.event, Event::ProjectChanged(_))
         ));
 
         let second = bridge_rx
@@ -204,7 +294,15 @@ mod tests {
             .expect("second forwarded event");
         assert!(matches!(
             second,
-            BridgeEvent::Event(Event::PlayheadChanged { t_tl: 0 })
+            BridgeEvent::Event(tagged) if matches!(tagged.event, Event::PlayheadChanged { t_tl: 0 })
+        ));
+
+        let third = bridge_rx
+            .recv_timeout(Duration::from_secs(1))
+            .expect("completion event");
+        assert!(matches!(
+            third,
+            BridgeEvent::Event(tagged) if matches!(tagged.event, Event::CommandCompleted { .. })
         ));
 
         drop(command_tx);
@@ -215,6 +313,111 @@ mod tests {
         assert!(matches!(disconnected, BridgeEvent::Disconnected));
     }
 
+    #[test]
+    fn bridge_ticks_playback_until_timeline_end() {
+        let (command_tx, event_rx) = spawn_engine_bridge(Engine::new(MockBackend));
+        command_tx
+            .send(tagged(
+                1,
+                Command::Import {
+                    path: PathBuf::from("demo.mp4"),
+                },
+            ))
+            .expect("send import command");
+        command_tx
+            .send(tagged(2, Command::Play))
+            .expect("send play command");
+
+        let mut saw_end = false;
+        while let Ok(event) = event_rx.recv_timeout(Duration::from_secs(1)) {
+            if matches!(
+                event.event,
+                Event::PlaybackStateChanged { playing: false, .. }
+            ) {
+                saw_end = true;
+                break;
+            }
+        }
+
+        assert!(saw_end, "playback should stop at the timeline end");
+    }
+
+    #[test]
+    fn bridge_keeps_ticking_playback_under_a_steady_command_stream() {
+        let (command_tx, event_rx) = spawn_engine_bridge(Engine::new(MockBackend));
+        command_tx
+            .send(tagged(
+                1,
+                Command::Import {
+                    path: PathBuf::from("demo.mp4"),
+                },
+            ))
+            .expect("send import command");
+        command_tx
+            .send(tagged(2, Command::Play))
+            .expect("send play command");
+        let chatter_tx = command_tx.clone();
+        thread::spawn(move || {
+            for id in 3..2_000 {
+                let command = tagged(id, Command::SetLoopPlayback { enabled: false });
+                if chatter_tx.send(command).is_err() {
+                    return;
+                }
+                thread::sleep(Duration::from_millis(2));
+            }
+        });
+
+        let mut saw_end = false;
+        while let Ok(event) = event_rx.recv_timeout(Duration::from_secs(1)) {
+            if event.id.is_none()
+                && matches!(
+                    event.event,
+                    Event::PlaybackStateChanged { playing: false, .. }
+                )
+            {
+                saw_end = true;
+                break;
+            }
+        }
+
+        assert!(saw_end, "commands every 2 ms must not stall playback");
+    }
+
+    #[test]
+    fn bridge_forwards_preview_frames_decoded_in_background() {
+        let (command_tx, event_rx) = spawn_engine_bridge(Engine::new(MockBackend));
+        command_tx
+            .send(tagged(
+                1,
+                Command::Import {
+                    path: PathBuf::from("demo.mp4"),
+                },
+            ))
+            .expect("send import command");
+        command_tx
+            .send(tagged(2, Command::SetPlayhead { t_tl: 500_000 }))
+            .expect("send set playhead command");
+
+        let mut saw_preview = false;
+        while let Ok(event) = event_rx.recv_timeout(Duration::from_secs(1)) {
+            if event.id == Some(CommandId(2))
+                && matches!(event.event, Event::PreviewFrameReady { t_tl: 500_000, .. })
+            {
+                saw_preview = true;
+                break;
+            }
+        }
+
+        assert!(saw_preview, "background preview should reach the UI");
+    }
+
+    fn tagged(id: u64, command: Command) -> TaggedCommand {
+        TaggedCommand {
+            id: CommandId(id),
+            command,
+        }
+    }
+
     fn spawn_mock_bridge() -> (super::EngineCommandSender, super::EngineEventReceiver) {
         spawn_engine_bridge(Engine::new(MockBackend))
     }
@@ -235,6 +438,7 @@ mod tests {
                     src_out: 90_000,
                     width: 160,
                     height: 90,
+                    start_timecode: None,
                 }),
                 audio: None,
             })
@@ -253,6 +457,17 @@ mod tests {
             })
         }
 
+        fn decode_audio(
+            &self,
+            _path: &Path,
+            _at_seconds: f64,
+            duration_seconds: f64,
+            format: PcmFormat,
+        ) -> engine::Result<PcmChunk> {
+            let frames = (duration_seconds * f64::from(format.sample_rate)).round() as usize;
+            Ok(PcmChunk::silence(format, frames))
+        }
+
         fn export_video(&self, _plan: &engine::export::ExportVideoPlan) -> engine::Result<()> {
             Ok(())
         }
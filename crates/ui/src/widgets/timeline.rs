@@ -1,8 +1,8 @@
-use engine::ProjectSnapshot;
 use engine::api::SegmentSummary;
+use engine::{FrameGrid, ProjectSnapshot, Timecode};
 use iced::widget::canvas::{self, Path, Stroke};
 use iced::widget::container;
-use iced::{Color, Element, Length, Point, Rectangle, Size, Theme, mouse};
+use iced::{Color, Element, Length, Point, Rectangle, Size, Theme, keyboard, mouse};
 
 /// Converts an x coordinate in timeline widget space to a timeline tick.
 ///
@@ -29,10 +29,22 @@ pub fn tick_from_x(x: f32, width: f32, duration_tl: i64) -> i64 {
     tick.clamp(0, duration_tl - 1)
 }
 
+/// How a click changes the segment selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectMode {
+    /// Plain click: selects only the segment.
+    Replace,
+    /// Shift-click: adds the segment to the selection.
+    Add,
+    /// Ctrl/Cmd-click: adds the segment, or removes it when already selected.
+    Toggle,
+}
+
 #[derive(Debug, Default)]
 struct TimelineState {
     drag_mode: Option<DragMode>,
     drag_start_x: Option<f32>,
+    modifiers: keyboard::Modifiers,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -48,11 +60,19 @@ enum DragMode {
     TrimEnd {
         segment_id: u64,
     },
+    /// Modifier-drag rubber band adding to the selection; a click without
+    /// dragging selects `clicked` or scrubs when the press hit no segment.
+    RubberBand {
+        clicked: Option<(u64, SelectMode)>,
+    },
 }
 
 const DRAG_START_THRESHOLD_PX: f32 = 4.0;
 const SEGMENT_VERTICAL_PADDING_PX: f32 = 12.0;
 const EDGE_HIT_THRESHOLD_PX: f32 = 6.0;
+const RULER_LABEL_SPACING_PX: f32 = 96.0;
+/// Ruler steps in timecode seconds, from finest to coarsest.
+const RULER_STEPS_SECONDS: [u32; 13] = [1, 2, 5, 10, 15, 30, 60, 120, 300, 600, 900, 1800, 3600];
 
 #[derive(Debug)]
 struct TimelineProgram<'a, Message> {
@@ -60,7 +80,11 @@ struct TimelineProgram<'a, Message> {
     playhead_tl: i64,
     split_feedback_tl: Option<i64>,
     loaded_preview_ranges_tl: &'a [(i64, i64)],
+    in_out_tl: (Option<i64>, Option<i64>),
+    /// Record timecode rate and drop-frame count used by the ruler.
+    timecode_format: (FrameGrid, bool),
     segments: &'a [SegmentSummary],
+    selection: &'a [u64],
     cache: &'a canvas::Cache,
     on_scrub: fn(i64) -> Message,
     on_split: fn(i64) -> Message,
@@ -68,6 +92,8 @@ struct TimelineProgram<'a, Message> {
     on_move: fn(u64, i64) -> Message,
     on_trim_start: fn(u64, i64) -> Message,
     on_trim_end: fn(u64, i64) -> Message,
+    on_select: fn(u64, SelectMode) -> Message,
+    on_select_range: fn(i64, i64) -> Message,
 }
 
 /// Message callbacks emitted from timeline interactions.
@@ -79,6 +105,11 @@ pub struct TimelineActions<Message> {
     pub on_move: fn(u64, i64) -> Message,
     pub on_trim_start: fn(u64, i64) -> Message,
     pub on_trim_end: fn(u64, i64) -> Message,
+    /// Click on a segment; modifiers pick the [`SelectMode`].
+    pub on_select: fn(u64, SelectMode) -> Message,
+    /// Modifier rubber band over `start_tl..end_tl`, added to the current
+    /// selection.
+    pub on_select_range: fn(i64, i64) -> Message,
 }
 
 fn playhead_x_from_tick(playhead_tl: i64, duration_tl: i64, width: f32) -> f32 {
@@ -95,6 +126,62 @@ fn playhead_x_from_tick(playhead_tl: i64, duration_tl: i64, width: f32) -> f32 {
     (clamped_tick as f32 / duration_tl as f32) * width
 }
 
+/// Returns the ruler marks as `(tick, label)` pairs.
+///
+/// Marks fall on whole timecode seconds, using the finest step that keeps
+/// labels at least `RULER_LABEL_SPACING_PX` apart. A drop-frame mark whose
+/// `;00` label is skipped moves to the first label of that minute.
+///
+/// # Example
+///
+/// ```ignore
+/// let marks = ruler_marks(3_000_000, 400.0, (FrameGrid::DEFAULT, false));
+/// assert_eq!(marks[1], (1_000_000, String::from("00:00:01:00")));
+/// ```
+fn ruler_marks(
+    duration_tl: i64,
+    width: f32,
+    timecode_format: (FrameGrid, bool),
+) -> Vec<(i64, String)> {
+    if duration_tl <= 0 || width <= 0.0 {
+        return Vec::new();
+    }
+
+    let (grid, drop_frame) = timecode_format;
+    let px_per_second = 1_000_000.0 / duration_tl as f32 * width;
+    let step = RULER_STEPS_SECONDS
+        .into_iter()
+        .find(|step| *step as f32 * px_per_second >= RULER_LABEL_SPACING_PX)
+        .unwrap_or(RULER_STEPS_SECONDS[RULER_STEPS_SECONDS.len() - 1]);
+
+    let mut marks = Vec::new();
+    for second in (0..24 * 3600).step_by(step as usize) {
+        let mut timecode = Timecode {
+            hours: second / 3600,
+            minutes: second / 60 % 60,
+            seconds: second % 60,
+            frames: 0,
+            drop_frame,
+        };
+        let t_tl = match timecode.to_tl(grid) {
+            Ok(t_tl) => t_tl,
+            Err(_) => {
+                timecode.frames = (grid.nominal_fps() / 15) as u32;
+                match timecode.to_tl(grid) {
+                    Ok(t_tl) => t_tl,
+                    Err(_) => break,
+                }
+            }
+        };
+        if t_tl >= duration_tl {
+            break;
+        }
+        marks.push((t_tl, timecode.to_string()));
+    }
+
+    marks
+}
+
 fn split_boundary_ticks(segments: &[SegmentSummary], duration_tl: i64) -> Vec<i64> {
     if duration_tl <= 0 {
         return Vec::new();
@@ -130,6 +217,27 @@ fn edge_x_from_tl(t_tl: i64, duration_tl: i64, width: f32) -> f32 {
     (clamped_tl as f32 / duration_tl as f32) * width
 }
 
+/// Returns the x span of the in/out range; unset marks fall back to the
+/// timeline edges.
+fn marked_range_x(
+    in_out_tl: (Option<i64>, Option<i64>),
+    duration_tl: i64,
+    width: f32,
+) -> Option<(f32, f32)> {
+    if duration_tl <= 0 || (in_out_tl.0.is_none() && in_out_tl.1.is_none()) {
+        return None;
+    }
+    let start_tl = in_out_tl.0.unwrap_or(0);
+    let end_tl = in_out_tl.1.unwrap_or(duration_tl);
+    if start_tl >= end_tl {
+        return None;
+    }
+    Some((
+        edge_x_from_tl(start_tl, duration_tl, width),
+        edge_x_from_tl(end_tl, duration_tl, width),
+    ))
+}
+
 fn is_over_segment_layer(y: Option<f32>, height: f32) -> bool {
     let Some(y) = y else {
         return false;
@@ -140,6 +248,23 @@ fn is_over_segment_layer(y: Option<f32>, height: f32) -> bool {
     top <= y && y <= bottom
 }
 
+/// Disabled segments are drawn dimmed so they stay visible but read as muted.
+fn segment_fill_color(selected: bool, enabled: bool) -> Color {
+    match (selected, enabled) {
+        (true, true) => Color::from_rgb8(92, 156, 235),
+        (false, true) => Color::from_rgb8(55, 110, 188),
+        (true, false) => Color::from_rgb8(84, 96, 118),
+        (false, false) => Color::from_rgb8(48, 54, 66),
+    }
+}
+
+/// Returns the tick range covered by a rubber band between two x positions.
+fn rubber_band_range_tl(start_x: f32, end_x: f32, width: f32, duration_tl: i64) -> (i64, i64) {
+    let start_tl = tick_from_x(start_x.min(end_x), width, duration_tl);
+    let end_tl = tick_from_x(start_x.max(end_x), width, duration_tl);
+    (start_tl, end_tl + 1)
+}
+
 impl<Message> canvas::Program<Message> for TimelineProgram<'_, Message> {
     type State = TimelineState;
 
@@ -150,6 +275,10 @@ impl<Message> canvas::Program<Message> for TimelineProgram<'_, Message> {
         bounds: Rectangle,
         cursor: mouse::Cursor,
     ) -> (canvas::event::Status, Option<Message>) {
+        if let canvas::Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) = event {
+            state.modifiers = modifiers;
+            return (canvas::event::Status::Ignored, None);
+        }
         if self.duration_tl <= 0 {
             return (canvas::event::Status::Ignored, None);
         }
@@ -174,6 +303,19 @@ impl<Message> canvas::Program<Message> for TimelineProgram<'_, Message> {
                 } else {
                     None
                 };
+                let select_mode = if state.modifiers.command() {
+                    Some(SelectMode::Toggle)
+                } else if state.modifiers.shift() {
+                    Some(SelectMode::Add)
+                } else {
+                    None
+                };
+                if let Some(mode) = select_mode.filter(|_| is_over_segment_layer) {
+                    state.drag_mode = Some(DragMode::RubberBand {
+                        clicked: segment.map(|segment| (segment.id, mode)),
+                    });
+                    return (canvas::event::Status::Captured, None);
+                }
                 if let Some(segment) = segment {
                     let start_x =
                         edge_x_from_tl(segment.timeline_start, self.duration_tl, bounds.width);
@@ -220,11 +362,8 @@ impl<Message> canvas::Program<Message> for TimelineProgram<'_, Message> {
                     return (canvas::event::Status::Captured, None);
                 };
                 let tick = tick_from_x(x, bounds.width, self.duration_tl);
-                let drag_distance = state
-                    .drag_start_x
-                    .take()
-                    .map(|start_x| (x - start_x).abs())
-                    .unwrap_or(0.0);
+                let start_x = state.drag_start_x.take().unwrap_or(x);
+                let drag_distance = (x - start_x).abs();
                 let message = match drag_mode {
                     DragMode::Scrub => None,
                     DragMode::Move {
@@ -232,26 +371,37 @@ impl<Message> canvas::Program<Message> for TimelineProgram<'_, Message> {
                         grab_offset_tl,
                     } => {
                         if drag_distance < DRAG_START_THRESHOLD_PX {
-                            Some((self.on_scrub)(tick))
+                            Some((self.on_select)(segment_id, SelectMode::Replace))
                         } else {
                             Some((self.on_move)(segment_id, tick - grab_offset_tl))
                         }
                     }
                     DragMode::TrimStart { segment_id } => {
                         if drag_distance < DRAG_START_THRESHOLD_PX {
-                            Some((self.on_scrub)(tick))
+                            Some((self.on_select)(segment_id, SelectMode::Replace))
                         } else {
                             Some((self.on_trim_start)(segment_id, tick))
                         }
                     }
                     DragMode::TrimEnd { segment_id } => {
                         if drag_distance < DRAG_START_THRESHOLD_PX {
-                            Some((self.on_scrub)(tick))
+                            Some((self.on_select)(segment_id, SelectMode::Replace))
                         } else {
                             let end_tl = (tick + 1).clamp(1, self.duration_tl);
                             Some((self.on_trim_end)(segment_id, end_tl))
                         }
                     }
+                    DragMode::RubberBand { clicked } => {
+                        if drag_distance >= DRAG_START_THRESHOLD_PX {
+                            let (start_tl, end_tl) =
+                                rubber_band_range_tl(start_x, x, bounds.width, self.duration_tl);
+                            Some((self.on_select_range)(start_tl, end_tl))
+                        } else if let Some((segment_id, mode)) = clicked {
+                            Some((self.on_select)(segment_id, mode))
+                        } else {
+                            Some((self.on_scrub)(tick))
+                        }
+                    }
                 };
                 (canvas::event::Status::Captured, message)
             }
@@ -270,6 +420,12 @@ impl<Message> canvas::Program<Message> for TimelineProgram<'_, Message> {
                 let tick = tick_from_x(x, bounds.width, self.duration_tl);
                 (canvas::event::Status::Captured, Some((self.on_scrub)(tick)))
             }
+            // Keeps redrawing the rubber band while it follows the cursor.
+            canvas::Event::Mouse(mouse::Event::CursorMoved { .. })
+                if matches!(state.drag_mode, Some(DragMode::RubberBand { .. })) =>
+            {
+                (canvas::event::Status::Captured, None)
+            }
             canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) => {
                 if !cursor.is_over(bounds) {
                     return (canvas::event::Status::Ignored, None);
@@ -296,11 +452,11 @@ impl<Message> canvas::Program<Message> for TimelineProgram<'_, Message> {
 
     fn draw(
         &self,
-        _state: &Self::State,
+        state: &Self::State,
         renderer: &iced::Renderer,
         _theme: &Theme,
         bounds: Rectangle,
-        _cursor: mouse::Cursor,
+        cursor: mouse::Cursor,
     ) -> Vec<canvas::Geometry> {
         let segments = self.cache.draw(renderer, bounds.size(), |frame| {
             let background = Path::rectangle(Point::ORIGIN, frame.size());
@@ -324,6 +480,32 @@ impl<Message> canvas::Program<Message> for TimelineProgram<'_, Message> {
                 frame.fill(&rect, Color::from_rgba(0.35, 0.92, 0.53, 0.85));
             }
 
+            for (mark_tl, label) in
+                ruler_marks(self.duration_tl, bounds.width, self.timecode_format)
+            {
+                let mark_x = playhead_x_from_tick(mark_tl, self.duration_tl, bounds.width);
+                let tick_line = Path::line(
+                    Point::new(mark_x, bounds.height - SEGMENT_VERTICAL_PADDING_PX),
+                    Point::new(mark_x, bounds.height),
+                );
+                frame.stroke(
+                    &tick_line,
+                    Stroke::default()
+                        .with_width(1.0)
+                        .with_color(Color::from_rgb8(120, 128, 140)),
+                );
+                frame.fill_text(canvas::Text {
+                    content: label,
+                    position: Point::new(
+                        mark_x + 3.0,
+                        bounds.height - SEGMENT_VERTICAL_PADDING_PX + 1.0,
+                    ),
+                    color: Color::from_rgb8(160, 168, 180),
+                    size: 9.0.into(),
+                    ..canvas::Text::default()
+                });
+            }
+
             for segment in self.segments {
                 let x =
                     (segment.timeline_start.max(0) as f32 / self.duration_tl as f32) * bounds.width;
@@ -336,7 +518,39 @@ impl<Message> canvas::Program<Message> for TimelineProgram<'_, Message> {
                         (bounds.height - SEGMENT_VERTICAL_PADDING_PX * 2.0).max(1.0),
                     ),
                 );
-                frame.fill(&rect, Color::from_rgb8(55, 110, 188));
+                let selected = self.selection.contains(&segment.id);
+                frame.fill(&rect, segment_fill_color(selected, segment.enabled));
+                if selected {
+                    frame.stroke(
+                        &rect,
+                        Stroke::default()
+                            .with_width(2.0)
+                            .with_color(Color::from_rgb8(232, 240, 255)),
+                    );
+                }
+            }
+
+            if let Some((start_x, end_x)) =
+                marked_range_x(self.in_out_tl, self.duration_tl, bounds.width)
+            {
+                let range = Path::rectangle(
+                    Point::new(start_x, 0.0),
+                    Size::new((end_x - start_x).max(1.0), bounds.height),
+                );
+                frame.fill(&range, Color::from_rgba(1.0, 0.84, 0.35, 0.18));
+                for (mark_tl, mark_x) in [(self.in_out_tl.0, start_x), (self.in_out_tl.1, end_x)] {
+                    if mark_tl.is_none() {
+                        continue;
+                    }
+                    let mark_line =
+                        Path::line(Point::new(mark_x, 0.0), Point::new(mark_x, bounds.height));
+                    frame.stroke(
+                        &mark_line,
+                        Stroke::default()
+                            .with_width(2.0)
+                            .with_color(Color::from_rgb8(255, 214, 89)),
+                    );
+                }
             }
 
             for split_tl in split_boundary_ticks(self.segments, self.duration_tl) {
@@ -360,6 +574,29 @@ impl<Message> canvas::Program<Message> for TimelineProgram<'_, Message> {
 
         let mut playhead_frame = canvas::Frame::new(renderer, bounds.size());
         if self.duration_tl > 0 {
+            if let (Some(DragMode::RubberBand { .. }), Some(start_x), Some(position)) = (
+                state.drag_mode,
+                state.drag_start_x,
+                cursor.position_in(bounds),
+            ) {
+                let left = start_x.min(position.x).max(0.0);
+                let right = start_x.max(position.x).min(bounds.width);
+                let band = Path::rectangle(
+                    Point::new(left, SEGMENT_VERTICAL_PADDING_PX),
+                    Size::new(
+                        (right - left).max(1.0),
+                        (bounds.height - SEGMENT_VERTICAL_PADDING_PX * 2.0).max(1.0),
+                    ),
+                );
+                playhead_frame.fill(&band, Color::from_rgba(0.75, 0.85, 1.0, 0.18));
+                playhead_frame.stroke(
+                    &band,
+                    Stroke::default()
+                        .with_width(1.0)
+                        .with_color(Color::from_rgba(0.75, 0.85, 1.0, 0.8)),
+                );
+            }
+
             if let Some(split_tl) = self.split_feedback_tl {
                 let split_x = playhead_x_from_tick(split_tl, self.duration_tl, bounds.width);
                 let split_line = Path::line(
@@ -413,10 +650,21 @@ pub fn view<'a, Message>(
 where
     Message: 'a,
 {
-    let (segments, duration_tl): (&'a [SegmentSummary], i64) = match snapshot {
-        Some(project) => (project.segments.as_slice(), project.duration_tl),
-        None => (&[], 0),
+    let (segments, selection, duration_tl): (&'a [SegmentSummary], &'a [u64], i64) = match snapshot
+    {
+        Some(project) => (
+            project.segments.as_slice(),
+            project.selection.as_slice(),
+            project.duration_tl,
+        ),
+        None => (&[], &[], 0),
     };
+    let in_out_tl = snapshot
+        .map(|project| (project.in_point_tl, project.out_point_tl))
+        .unwrap_or_default();
+    let timecode_format = snapshot
+        .map(ProjectSnapshot::timecode_format)
+        .unwrap_or((FrameGrid::DEFAULT, false));
 
     container(
         canvas::Canvas::new(TimelineProgram {
@@ -424,7 +672,10 @@ where
             playhead_tl,
             split_feedback_tl,
             loaded_preview_ranges_tl,
+            in_out_tl,
+            timecode_format,
             segments,
+            selection,
             cache,
             on_scrub: actions.on_scrub,
             on_split: actions.on_split,
@@ -432,6 +683,8 @@ where
             on_move: actions.on_move,
             on_trim_start: actions.on_trim_start,
             on_trim_end: actions.on_trim_end,
+            on_select: actions.on_select,
+            on_select_range: actions.on_select_range,
         })
         .width(Length::Fill)
         .height(Length::Fixed(56.0)),
@@ -443,12 +696,15 @@ where
 #[cfg(test)]
 mod tests {
     use engine::api::SegmentSummary;
+    use engine::{FrameGrid, Rational};
     use iced::widget::canvas;
     use iced::widget::canvas::Program;
-    use iced::{Point, Rectangle, mouse};
+    use iced::{Point, Rectangle, keyboard, mouse};
 
-    use super::{DragMode, TimelineProgram, TimelineState};
-    use super::{playhead_x_from_tick, split_boundary_ticks, tick_from_x};
+    use super::{DragMode, SelectMode, TimelineProgram, TimelineState};
+    use super::{
+        marked_range_x, playhead_x_from_tick, ruler_marks, split_boundary_ticks, tick_from_x,
+    };
 
     fn sample_segment(id: u64, timeline_start: i64, timeline_duration: i64) -> SegmentSummary {
         SegmentSummary {
@@ -460,9 +716,24 @@ mod tests {
             src_out_video: None,
             src_in_audio: None,
             src_out_audio: None,
+            enabled: true,
         }
     }
 
+    #[test]
+    fn marked_range_falls_back_to_timeline_edges_for_unset_marks() {
+        assert_eq!(marked_range_x((None, None), 1_000, 200.0), None);
+        assert_eq!(
+            marked_range_x((Some(250), None), 1_000, 200.0),
+            Some((50.0, 200.0))
+        );
+        assert_eq!(
+            marked_range_x((None, Some(500)), 1_000, 200.0),
+            Some((0.0, 100.0))
+        );
+        assert_eq!(marked_range_x((Some(500), Some(500)), 1_000, 200.0), None);
+    }
+
     #[test]
     fn maps_left_edge_to_zero() {
         assert_eq!(tick_from_x(0.0, 200.0, 1_000), 0);
@@ -523,6 +794,32 @@ mod tests {
         assert_eq!(split_boundary_ticks(&segments, 400), vec![100]);
     }
 
+    #[test]
+    fn ruler_marks_fall_on_timecode_seconds_and_skip_dropped_labels() {
+        let non_drop = ruler_marks(3_000_000, 400.0, (FrameGrid::DEFAULT, false));
+        assert_eq!(
+            non_drop,
+            vec![
+                (0, String::from("00:00:00:00")),
+                (1_000_000, String::from("00:00:01:00")),
+                (2_000_000, String::from("00:00:02:00")),
+            ]
+        );
+
+        let ntsc = FrameGrid::from_frame_rate(Rational::new(30_000, 1_001).expect("valid"));
+        let drop_frame = ruler_marks(130_000_000, 260.0, (ntsc, true));
+        assert_eq!(
+            drop_frame,
+            vec![
+                (0, String::from("00:00:00;00")),
+                (60_060_000, String::from("00:01:00;02")),
+                (120_053_267, String::from("00:02:00;02")),
+            ]
+        );
+
+        assert!(ruler_marks(0, 400.0, (FrameGrid::DEFAULT, false)).is_empty());
+    }
+
     #[test]
     fn mouse_interaction_is_none_when_timeline_is_empty() {
         let cache = iced::widget::canvas::Cache::new();
@@ -531,7 +828,10 @@ mod tests {
             playhead_tl: 0,
             split_feedback_tl: None,
             loaded_preview_ranges_tl: &[],
+            in_out_tl: (None, None),
+            timecode_format: (FrameGrid::DEFAULT, false),
             segments: &[],
+            selection: &[],
             cache: &cache,
             on_scrub: |_| (),
             on_split: |_| (),
@@ -539,6 +839,8 @@ mod tests {
             on_move: |_, _| (),
             on_trim_start: |_, _| (),
             on_trim_end: |_, _| (),
+            on_select: |_, _| (),
+            on_select_range: |_, _| (),
         };
         let interaction = program.mouse_interaction(
             &TimelineState::default(),
@@ -562,7 +864,10 @@ mod tests {
             playhead_tl: 0,
             split_feedback_tl: None,
             loaded_preview_ranges_tl: &[],
+            in_out_tl: (None, None),
+            timecode_format: (FrameGrid::DEFAULT, false),
             segments: &[],
+            selection: &[],
             cache: &cache,
             on_scrub: |_| (),
             on_split: |_| (),
@@ -570,6 +875,8 @@ mod tests {
             on_move: |_, _| (),
             on_trim_start: |_, _| (),
             on_trim_end: |_, _| (),
+            on_select: |_, _| (),
+            on_select_range: |_, _| (),
         };
         let interaction = program.mouse_interaction(
             &TimelineState::default(),
@@ -593,7 +900,10 @@ mod tests {
             playhead_tl: 0,
             split_feedback_tl: None,
             loaded_preview_ranges_tl: &[],
+            in_out_tl: (None, None),
+            timecode_format: (FrameGrid::DEFAULT, false),
             segments: &[],
+            selection: &[],
             cache: &cache,
             on_scrub: |tick| tick,
             on_split: |_| -1,
@@ -601,6 +911,8 @@ mod tests {
             on_move: |_, _| -3,
             on_trim_start: |_, _| -4,
             on_trim_end: |_, _| -5,
+            on_select: |_, _| -7,
+            on_select_range: |_, _| -8,
         };
         let bounds = Rectangle {
             x: 0.0,
@@ -641,7 +953,10 @@ mod tests {
             playhead_tl: 0,
             split_feedback_tl: None,
             loaded_preview_ranges_tl: &[],
+            in_out_tl: (None, None),
+            timecode_format: (FrameGrid::DEFAULT, false),
             segments: &[],
+            selection: &[],
             cache: &cache,
             on_scrub: |tick| tick,
             on_split: |_| -1,
@@ -649,6 +964,8 @@ mod tests {
             on_move: |_, _| -3,
             on_trim_start: |_, _| -4,
             on_trim_end: |_, _| -5,
+            on_select: |_, _| -7,
+            on_select_range: |_, _| -8,
         };
         let bounds = Rectangle {
             x: 0.0,
@@ -678,7 +995,10 @@ mod tests {
             playhead_tl: 0,
             split_feedback_tl: None,
             loaded_preview_ranges_tl: &[],
+            in_out_tl: (None, None),
+            timecode_format: (FrameGrid::DEFAULT, false),
             segments: &[],
+            selection: &[],
             cache: &cache,
             on_scrub: |_| -1,
             on_split: |tick| tick,
@@ -686,6 +1006,8 @@ mod tests {
             on_move: |_, _| -3,
             on_trim_start: |_, _| -4,
             on_trim_end: |_, _| -5,
+            on_select: |_, _| -7,
+            on_select_range: |_, _| -8,
         };
         let bounds = Rectangle {
             x: 0.0,
@@ -714,7 +1036,10 @@ mod tests {
             playhead_tl: 0,
             split_feedback_tl: None,
             loaded_preview_ranges_tl: &[],
+            in_out_tl: (None, None),
+            timecode_format: (FrameGrid::DEFAULT, false),
             segments: &[],
+            selection: &[],
             cache: &cache,
             on_scrub: |_| -1,
             on_split: |_| -2,
@@ -722,6 +1047,8 @@ mod tests {
             on_move: |_, _| -3,
             on_trim_start: |_, _| -4,
             on_trim_end: |_, _| -5,
+            on_select: |_, _| -7,
+            on_select_range: |_, _| -8,
         };
         let bounds = Rectangle {
             x: 0.0,
@@ -750,7 +1077,10 @@ mod tests {
             playhead_tl: 0,
             split_feedback_tl: None,
             loaded_preview_ranges_tl: &[],
+            in_out_tl: (None, None),
+            timecode_format: (FrameGrid::DEFAULT, false),
             segments: &[],
+            selection: &[],
             cache: &cache,
             on_scrub: |_| -1,
             on_split: |_| -2,
@@ -758,6 +1088,8 @@ mod tests {
             on_move: |_, _| -3,
             on_trim_start: |_, _| -4,
             on_trim_end: |_, _| -5,
+            on_select: |_, _| -7,
+            on_select_range: |_, _| -8,
         };
         let bounds = Rectangle {
             x: 0.0,
@@ -787,7 +1119,10 @@ mod tests {
             playhead_tl: 0,
             split_feedback_tl: None,
             loaded_preview_ranges_tl: &[],
+            in_out_tl: (None, None),
+            timecode_format: (FrameGrid::DEFAULT, false),
             segments: &segments,
+            selection: &[],
             cache: &cache,
             on_scrub: |_| -1,
             on_split: |_| -2,
@@ -795,6 +1130,8 @@ mod tests {
             on_move: |segment_id, start_tl| segment_id as i64 * 1_000 + start_tl,
             on_trim_start: |_, _| -4,
             on_trim_end: |_, _| -5,
+            on_select: |_, _| -7,
+            on_select_range: |_, _| -8,
         };
         let bounds = Rectangle {
             x: 0.0,
@@ -831,7 +1168,10 @@ mod tests {
             playhead_tl: 0,
             split_feedback_tl: None,
             loaded_preview_ranges_tl: &[],
+            in_out_tl: (None, None),
+            timecode_format: (FrameGrid::DEFAULT, false),
             segments: &segments,
+            selection: &[],
             cache: &cache,
             on_scrub: |_| -1,
             on_split: |_| -2,
@@ -839,6 +1179,8 @@ mod tests {
             on_move: |segment_id, start_tl| segment_id as i64 * 1_000 + start_tl,
             on_trim_start: |_, _| -4,
             on_trim_end: |_, _| -5,
+            on_select: |_, _| -7,
+            on_select_range: |_, _| -8,
         };
         let bounds = Rectangle {
             x: 0.0,
@@ -867,7 +1209,7 @@ mod tests {
     }
 
     #[test]
-    fn click_segment_body_dispatches_scrub_instead_of_move() {
+    fn click_segment_body_selects_it_instead_of_moving() {
         let cache = iced::widget::canvas::Cache::new();
         let segments = vec![sample_segment(7, 20, 40)];
         let program = TimelineProgram {
@@ -875,7 +1217,10 @@ mod tests {
             playhead_tl: 0,
             split_feedback_tl: None,
             loaded_preview_ranges_tl: &[],
+            in_out_tl: (None, None),
+            timecode_format: (FrameGrid::DEFAULT, false),
             segments: &segments,
+            selection: &[],
             cache: &cache,
             on_scrub: |tick| tick,
             on_split: |_| -2,
@@ -883,6 +1228,8 @@ mod tests {
             on_move: |_, _| -4,
             on_trim_start: |_, _| -5,
             on_trim_end: |_, _| -6,
+            on_select: |_, _| -7,
+            on_select_range: |_, _| -8,
         };
         let bounds = Rectangle {
             x: 0.0,
@@ -907,7 +1254,7 @@ mod tests {
             mouse::Cursor::Available(Point::new(30.0, 20.0)),
         );
         assert_eq!(status, canvas::event::Status::Captured);
-        assert_eq!(released, Some(30));
+        assert_eq!(released, Some(-7));
     }
 
     #[test]
@@ -919,7 +1266,10 @@ mod tests {
             playhead_tl: 0,
             split_feedback_tl: None,
             loaded_preview_ranges_tl: &[],
+            in_out_tl: (None, None),
+            timecode_format: (FrameGrid::DEFAULT, false),
             segments: &segments,
+            selection: &[],
             cache: &cache,
             on_scrub: |tick| tick,
             on_split: |_| -2,
@@ -927,6 +1277,8 @@ mod tests {
             on_move: |_, _| -4,
             on_trim_start: |_, _| -5,
             on_trim_end: |_, _| -6,
+            on_select: |_, _| -7,
+            on_select_range: |_, _| -8,
         };
         let bounds = Rectangle {
             x: 0.0,
@@ -957,7 +1309,10 @@ mod tests {
             playhead_tl: 0,
             split_feedback_tl: None,
             loaded_preview_ranges_tl: &[],
+            in_out_tl: (None, None),
+            timecode_format: (FrameGrid::DEFAULT, false),
             segments: &segments,
+            selection: &[],
             cache: &cache,
             on_scrub: |tick| tick,
             on_split: |_| -2,
@@ -965,6 +1320,8 @@ mod tests {
             on_move: |_, _| -4,
             on_trim_start: |_, _| -5,
             on_trim_end: |_, _| -6,
+            on_select: |_, _| -7,
+            on_select_range: |_, _| -8,
         };
         let bounds = Rectangle {
             x: 0.0,
@@ -995,7 +1352,10 @@ mod tests {
             playhead_tl: 0,
             split_feedback_tl: None,
             loaded_preview_ranges_tl: &[],
+            in_out_tl: (None, None),
+            timecode_format: (FrameGrid::DEFAULT, false),
             segments: &segments,
+            selection: &[],
             cache: &cache,
             on_scrub: |tick| tick,
             on_split: |_| -2,
@@ -1003,6 +1363,8 @@ mod tests {
             on_move: |_, _| -4,
             on_trim_start: |_, _| -5,
             on_trim_end: |_, _| -6,
+            on_select: |_, _| -7,
+            on_select_range: |_, _| -8,
         };
         let bounds = Rectangle {
             x: 0.0,
@@ -1033,7 +1395,10 @@ mod tests {
             playhead_tl: 0,
             split_feedback_tl: None,
             loaded_preview_ranges_tl: &[],
+            in_out_tl: (None, None),
+            timecode_format: (FrameGrid::DEFAULT, false),
             segments: &segments,
+            selection: &[],
             cache: &cache,
             on_scrub: |tick| tick,
             on_split: |_| -2,
@@ -1041,6 +1406,8 @@ mod tests {
             on_move: |_, _| -4,
             on_trim_start: |_, _| -5,
             on_trim_end: |_, _| -6,
+            on_select: |_, _| -7,
+            on_select_range: |_, _| -8,
         };
         let bounds = Rectangle {
             x: 0.0,
@@ -1071,7 +1438,10 @@ mod tests {
             playhead_tl: 0,
             split_feedback_tl: None,
             loaded_preview_ranges_tl: &[],
+            in_out_tl: (None, None),
+            timecode_format: (FrameGrid::DEFAULT, false),
             segments: &segments,
+            selection: &[],
             cache: &cache,
             on_scrub: |_| -1,
             on_split: |_| -2,
@@ -1079,6 +1449,8 @@ mod tests {
             on_move: |_, _| -4,
             on_trim_start: |segment_id, start_tl| segment_id as i64 * 1_000 + start_tl,
             on_trim_end: |_, _| -5,
+            on_select: |_, _| -7,
+            on_select_range: |_, _| -8,
         };
         let bounds = Rectangle {
             x: 0.0,
@@ -1115,7 +1487,10 @@ mod tests {
             playhead_tl: 0,
             split_feedback_tl: None,
             loaded_preview_ranges_tl: &[],
+            in_out_tl: (None, None),
+            timecode_format: (FrameGrid::DEFAULT, false),
             segments: &segments,
+            selection: &[],
             cache: &cache,
             on_scrub: |_| -1,
             on_split: |_| -2,
@@ -1123,6 +1498,8 @@ mod tests {
             on_move: |_, _| -4,
             on_trim_start: |segment_id, start_tl| segment_id as i64 * 1_000 + start_tl,
             on_trim_end: |_, _| -5,
+            on_select: |_, _| -7,
+            on_select_range: |_, _| -8,
         };
         let bounds = Rectangle {
             x: 0.0,
@@ -1151,7 +1528,7 @@ mod tests {
     }
 
     #[test]
-    fn click_segment_start_edge_dispatches_scrub_instead_of_trim() {
+    fn click_segment_start_edge_selects_it_instead_of_trimming() {
         let cache = iced::widget::canvas::Cache::new();
         let segments = vec![sample_segment(7, 20, 40)];
         let program = TimelineProgram {
@@ -1159,7 +1536,10 @@ mod tests {
             playhead_tl: 0,
             split_feedback_tl: None,
             loaded_preview_ranges_tl: &[],
+            in_out_tl: (None, None),
+            timecode_format: (FrameGrid::DEFAULT, false),
             segments: &segments,
+            selection: &[],
             cache: &cache,
             on_scrub: |tick| tick,
             on_split: |_| -2,
@@ -1167,6 +1547,8 @@ mod tests {
             on_move: |_, _| -4,
             on_trim_start: |_, _| -5,
             on_trim_end: |_, _| -6,
+            on_select: |_, _| -7,
+            on_select_range: |_, _| -8,
         };
         let bounds = Rectangle {
             x: 0.0,
@@ -1191,7 +1573,7 @@ mod tests {
             mouse::Cursor::Available(Point::new(20.0, 20.0)),
         );
         assert_eq!(status, canvas::event::Status::Captured);
-        assert_eq!(released, Some(20));
+        assert_eq!(released, Some(-7));
     }
 
     #[test]
@@ -1203,7 +1585,10 @@ mod tests {
             playhead_tl: 0,
             split_feedback_tl: None,
             loaded_preview_ranges_tl: &[],
+            in_out_tl: (None, None),
+            timecode_format: (FrameGrid::DEFAULT, false),
             segments: &segments,
+            selection: &[],
             cache: &cache,
             on_scrub: |_| -1,
             on_split: |_| -2,
@@ -1211,6 +1596,8 @@ mod tests {
             on_move: |_, _| -4,
             on_trim_start: |_, _| -5,
             on_trim_end: |segment_id, end_tl| segment_id as i64 * 1_000 + end_tl,
+            on_select: |_, _| -7,
+            on_select_range: |_, _| -8,
         };
         let bounds = Rectangle {
             x: 0.0,
@@ -1237,4 +1624,171 @@ mod tests {
         assert_eq!(status, canvas::event::Status::Captured);
         assert_eq!(released, Some(7_070));
     }
+
+    fn selecting_program<'a>(
+        segments: &'a [SegmentSummary],
+        cache: &'a canvas::Cache,
+    ) -> TimelineProgram<'a, String> {
+        TimelineProgram {
+            duration_tl: 100,
+            playhead_tl: 0,
+            split_feedback_tl: None,
+            loaded_preview_ranges_tl: &[],
+            in_out_tl: (None, None),
+            timecode_format: (FrameGrid::DEFAULT, false),
+            segments,
+            selection: &[],
+            cache,
+            on_scrub: |tick| format!("scrub {tick}"),
+            on_split: |_| String::from("split"),
+            on_cut: |_| String::from("cut"),
+            on_move: |segment_id, start_tl| format!("move {segment_id} {start_tl}"),
+            on_trim_start: |_, _| String::from("trim start"),
+            on_trim_end: |_, _| String::from("trim end"),
+            on_select: |segment_id, mode| format!("select {segment_id} {mode:?}"),
+            on_select_range: |start_tl, end_tl| format!("range {start_tl}..{end_tl}"),
+        }
+    }
+
+    fn press_and_release(
+        program: &TimelineProgram<'_, String>,
+        state: &mut TimelineState,
+        press_x: f32,
+        release_x: f32,
+    ) -> Option<String> {
+        let bounds = Rectangle {
+            x: 0.0,
+            y: 0.0,
+            width: 100.0,
+            height: 40.0,
+        };
+        let (_, pressed) = program.update(
+            state,
+            canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)),
+            bounds,
+            mouse::Cursor::Available(Point::new(press_x, 20.0)),
+        );
+        assert_eq!(pressed, None);
+        let (status, released) = program.update(
+            state,
+            canvas::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)),
+            bounds,
+            mouse::Cursor::Available(Point::new(release_x, 20.0)),
+        );
+        assert_eq!(status, canvas::event::Status::Captured);
+        released
+    }
+
+    fn hold_modifiers(
+        program: &TimelineProgram<'_, String>,
+        state: &mut TimelineState,
+        modifiers: keyboard::Modifiers,
+    ) {
+        let (status, message) = program.update(
+            state,
+            canvas::Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)),
+            Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width: 100.0,
+                height: 40.0,
+            },
+            mouse::Cursor::Unavailable,
+        );
+        assert_eq!(status, canvas::event::Status::Ignored);
+        assert_eq!(message, None);
+    }
+
+    #[test]
+    fn shift_and_ctrl_click_select_the_segment_instead_of_scrubbing() {
+        let cache = canvas::Cache::new();
+        let segments = vec![sample_segment(7, 20, 40)];
+        let program = selecting_program(&segments, &cache);
+        let mut state = TimelineState::default();
+
+        hold_modifiers(&program, &mut state, keyboard::Modifiers::SHIFT);
+        assert_eq!(
+            press_and_release(&program, &mut state, 30.0, 30.0),
+            Some(format!("select 7 {:?}", SelectMode::Add))
+        );
+
+        hold_modifiers(&program, &mut state, keyboard::Modifiers::COMMAND);
+        assert_eq!(
+            press_and_release(&program, &mut state, 20.0, 21.0),
+            Some(format!("select 7 {:?}", SelectMode::Toggle))
+        );
+    }
+
+    #[test]
+    fn modifier_drag_extends_selection_with_rubber_band() {
+        let cache = canvas::Cache::new();
+        let segments = vec![sample_segment(1, 0, 40), sample_segment(2, 40, 60)];
+        let program = selecting_program(&segments, &cache);
+        let mut state = TimelineState::default();
+
+        hold_modifiers(&program, &mut state, keyboard::Modifiers::SHIFT);
+        assert_eq!(
+            press_and_release(&program, &mut state, 50.0, 30.0),
+            Some(String::from("range 30..51"))
+        );
+        assert!(state.drag_mode.is_none());
+    }
+
+    #[test]
+    fn plain_click_selects_only_the_segment() {
+        let cache = canvas::Cache::new();
+        let segments = vec![sample_segment(7, 20, 40)];
+        let program = selecting_program(&segments, &cache);
+        let mut state = TimelineState::default();
+
+        assert_eq!(
+            press_and_release(&program, &mut state, 40.0, 41.0),
+            Some(format!("select 7 {:?}", SelectMode::Replace))
+        );
+    }
+
+    #[test]
+    fn press_on_gap_scrubs_at_once_and_drag_keeps_scrubbing() {
+        let cache = canvas::Cache::new();
+        let segments = vec![sample_segment(1, 0, 20), sample_segment(2, 60, 40)];
+        let program = selecting_program(&segments, &cache);
+        let mut state = TimelineState::default();
+        let bounds = Rectangle {
+            x: 0.0,
+            y: 0.0,
+            width: 100.0,
+            height: 40.0,
+        };
+
+        let (_, pressed) = program.update(
+            &mut state,
+            canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)),
+            bounds,
+            mouse::Cursor::Available(Point::new(40.0, 20.0)),
+        );
+        assert_eq!(pressed, Some(String::from("scrub 40")));
+        assert!(matches!(state.drag_mode, Some(DragMode::Scrub)));
+
+        let (_, moved) = program.update(
+            &mut state,
+            canvas::Event::Mouse(mouse::Event::CursorMoved {
+                position: Point::new(50.0, 20.0),
+            }),
+            bounds,
+            mouse::Cursor::Available(Point::new(50.0, 20.0)),
+        );
+        assert_eq!(moved, Some(String::from("scrub 50")));
+    }
+
+    #[test]
+    fn rubber_band_release_outside_timeline_cancels_selection() {
+        let cache = canvas::Cache::new();
+        let segments = vec![sample_segment(1, 0, 20)];
+        let program = selecting_program(&segments, &cache);
+        let mut state = TimelineState::default();
+
+        hold_modifiers(&program, &mut state, keyboard::Modifiers::SHIFT);
+        assert_eq!(press_and_release(&program, &mut state, 40.0, 140.0), None);
+        assert!(state.drag_mode.is_none());
+    }
 }